@@ -3,7 +3,7 @@
 
 use hello_ockam::Echoer;
 use ockam::{node, Context, Result};
-use ockam_transport_udp::UdpTransportExtension;
+use ockam_transport_udp::{UdpListenerOptions, UdpTransportExtension};
 
 #[ockam::node]
 async fn main(ctx: Context) -> Result<()> {
@@ -13,12 +13,17 @@ async fn main(ctx: Context) -> Result<()> {
     // Initialize the UDP Transport
     let udp = node.create_udp_transport().await?;
 
-    // Create a UDP listener and wait for incoming datagrams.
-    udp.listen("127.0.0.1:4000").await?;
-
     // Create an echoer worker
     node.start_worker("echoer", Echoer).await?;
 
+    // Create a UDP listener and wait for incoming datagrams.
+    let listener_options = UdpListenerOptions::new();
+
+    // Allow access to the Echoer via the datagrams received by the UDP listener
+    node.flow_controls()
+        .add_consumer("echoer", &listener_options.flow_control_id());
+    udp.listen("127.0.0.1:4000", listener_options).await?;
+
     // Don't call node.stop() here so this node runs forever.
     Ok(())
 }
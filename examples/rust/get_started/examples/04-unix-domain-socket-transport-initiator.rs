@@ -1,7 +1,7 @@
 // This node routes a message, to a worker on a different node, over the tcp transport.
 
 use ockam::{node, route, Context, Result};
-use ockam_transport_uds::{UdsTransportExtension, UDS};
+use ockam_transport_uds::{UdsConnectionOptions, UdsTransportExtension, UDS};
 
 #[ockam::node]
 async fn main(ctx: Context) -> Result<()> {
@@ -11,17 +11,17 @@ async fn main(ctx: Context) -> Result<()> {
     // Initialize the UDS Transport
     let uds = node.create_uds_transport().await?;
 
-    let connection = uds.connect("/tmp/ockam-example-echoer").await;
+    let connection = uds
+        .connect("/tmp/ockam-example-echoer", UdsConnectionOptions::new())
+        .await;
 
     if let Err(e) = connection {
         println!("Error connecting to echoer {e}");
     }
     // Send a message to the "echoer" worker, on a different node, over a uds transport.
-    let r = route![(UDS, "/tmp/ockam-example-echoer"), "echoer"];
-    node.send(r, "Hello Ockam!".to_string()).await?;
-
     // Wait to receive a reply and print it.
-    let reply = node.receive::<String>().await?;
+    let r = route![(UDS, "/tmp/ockam-example-echoer"), "echoer"];
+    let reply = node.send_and_receive::<String>(r, "Hello Ockam!".to_string()).await?;
     println!("App Received: {}", reply); // should print "Hello Ockam!"
 
     // Stop all workers, stop the node, cleanup and return.
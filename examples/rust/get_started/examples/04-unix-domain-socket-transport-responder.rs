@@ -3,7 +3,7 @@
 
 use hello_ockam::Echoer;
 use ockam::{node, Context, Result};
-use ockam_transport_uds::UdsTransportExtension;
+use ockam_transport_uds::{UdsListenerOptions, UdsTransportExtension};
 
 #[ockam::node]
 async fn main(ctx: Context) -> Result<()> {
@@ -13,12 +13,17 @@ async fn main(ctx: Context) -> Result<()> {
     // Initialize the UDS Transport
     let uds = node.create_uds_transport().await?;
 
-    // Create a Uds listener and wait for incoming connections.
-    uds.listen("/tmp/ockam-example-echoer").await?;
-
     // Create an echoer worker
     node.start_worker("echoer", Echoer).await?;
 
+    // Create a Uds listener and wait for incoming connections.
+    let listener_options = UdsListenerOptions::new();
+
+    // Allow access to the Echoer via UDS connections from the UDS listener
+    node.flow_controls()
+        .add_consumer("echoer", &listener_options.spawner_flow_control_id());
+    uds.listen("/tmp/ockam-example-echoer", listener_options).await?;
+
     // Don't call node.stop() here so this node runs forever.
     Ok(())
 }
@@ -20,6 +20,7 @@ pub enum OckamError {
     UnknownForwarderDestinationAddress,
     UnknownForwarderNextHopAddress,
     InvalidHex,
+    TooManyStreams,
 }
 
 impl ockam_core::compat::error::Error for OckamError {}
@@ -36,6 +37,7 @@ impl From<OckamError> for Error {
         // TODO: improve this mapping
         let kind = match err {
             SystemAddressNotBound | SystemInvalidConfiguration | InvalidParameter => Kind::Misuse,
+            TooManyStreams => Kind::ResourceExhausted,
             _ => Kind::Protocol,
         };
 
@@ -70,12 +70,19 @@ mod error;
 mod forwarding_service;
 mod metadata;
 mod monotonic;
+mod stream_service;
 mod system;
 mod unique;
 
 pub use error::OckamError;
 pub use forwarding_service::{ForwardingService, ForwardingServiceOptions};
 pub use metadata::OckamMessage;
+#[cfg(feature = "std")]
+pub use stream_service::FileStreamStorage;
+pub use stream_service::{
+    InMemoryStreamStorage, StreamIndexService, StreamService, StreamServiceOptions, StreamStorage,
+    StreamsData, DEFAULT_MAX_STREAMS,
+};
 pub use system::{SystemBuilder, SystemHandler, WorkerSystem};
 pub use unique::unique_with_prefix;
 
@@ -1,9 +1,9 @@
 //! Stream protocol request payloads
 
-use crate::protocols::ProtocolPayload;
-use crate::Message;
-use ockam_core::compat::{string::String, vec::Vec};
-use ockam_core::Uint;
+use crate::protocols::{ProtocolParser, ProtocolPayload};
+use crate::{Message, OckamError, Result};
+use ockam_core::compat::{collections::BTreeSet, string::String, vec::Vec};
+use ockam_core::{Decodable, Uint};
 use serde::{Deserialize, Serialize};
 
 /// Request a new mailbox to be created
@@ -140,3 +140,43 @@ impl IndexRequest {
         )
     }
 }
+
+/// A convenience enum to wrap all possible request types
+///
+/// In your stream service you will want to match this enum, given to
+/// you via the `ProtocolParser` abstraction.
+#[derive(Serialize, Deserialize, Message)]
+pub enum Request {
+    /// Wraps a [`CreateStreamRequest`], see its documentation for more info.
+    CreateStream(CreateStreamRequest),
+    /// Wraps a [`PushRequest`], see its documentation for more info.
+    Push(PushRequest),
+    /// Wraps a [`PullRequest`], see its documentation for more info.
+    Pull(PullRequest),
+    /// Wraps an [`IndexRequest`], see its documentation for more info.
+    Index(IndexRequest),
+}
+
+impl ProtocolParser for Request {
+    fn check_id(id: &str) -> bool {
+        vec![
+            "stream_create",
+            "stream_push",
+            "stream_pull",
+            "stream_index",
+        ]
+        .into_iter()
+        .collect::<BTreeSet<_>>()
+        .contains(id)
+    }
+
+    fn parse(ProtocolPayload { protocol, data }: ProtocolPayload) -> Result<Self> {
+        Ok(match protocol.as_str() {
+            "stream_create" => Request::CreateStream(CreateStreamRequest::decode(&data)?),
+            "stream_push" => Request::Push(PushRequest::decode(&data)?),
+            "stream_pull" => Request::Pull(PullRequest::decode(&data)?),
+            "stream_index" => Request::Index(IndexRequest::decode(&data)?),
+            _ => return Err(OckamError::NoSuchProtocol.into()),
+        })
+    }
+}
@@ -0,0 +1,85 @@
+use crate::protocols::stream::requests::{IndexRequest, Request};
+use crate::protocols::stream::responses::IndexResponse;
+use crate::protocols::{ProtocolParser, ProtocolPayload};
+use crate::stream_service::{StreamServiceOptions, StreamStorage};
+use crate::{Context, OckamError};
+use ockam_core::compat::boxed::Box;
+use ockam_core::compat::sync::Arc;
+use ockam_core::{Address, Any, Decodable, Result, Routed, Worker};
+use ockam_node::WorkerBuilder;
+
+/// Local implementation of the stream index service used by the
+/// [`Stream`](crate::stream::Stream) client.
+///
+/// It stores the index of the next message to be read by each consumer of
+/// a stream, so that a consumer resumes where it left off after a restart.
+pub struct StreamIndexService {
+    storage: Arc<dyn StreamStorage>,
+}
+
+impl StreamIndexService {
+    /// Start a stream index service
+    pub async fn create(
+        ctx: &Context,
+        address: impl Into<Address>,
+        storage: Arc<dyn StreamStorage>,
+        options: StreamServiceOptions,
+    ) -> Result<()> {
+        let address = address.into();
+
+        options.setup_flow_control(ctx.flow_controls(), &address);
+
+        WorkerBuilder::new(Self { storage })
+            .with_address(address)
+            .with_incoming_access_control_arc(options.incoming_access_control)
+            .start(ctx)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[crate::worker]
+impl Worker for StreamIndexService {
+    type Context = Context;
+    type Message = Any;
+
+    async fn handle_message(
+        &mut self,
+        ctx: &mut Self::Context,
+        msg: Routed<Self::Message>,
+    ) -> Result<()> {
+        let pp = ProtocolPayload::decode(msg.payload())?;
+
+        match Request::parse(pp)? {
+            Request::Index(IndexRequest::Get {
+                client_id,
+                stream_name,
+            }) => {
+                let index = self.storage.get_index(&stream_name, &client_id).await?;
+                ctx.send(
+                    msg.return_route(),
+                    IndexResponse::new(client_id, stream_name, index),
+                )
+                .await
+            }
+            // Saving an index is not acknowledged
+            Request::Index(IndexRequest::Save {
+                client_id,
+                stream_name,
+                index,
+            }) => {
+                self.storage
+                    .save_index(&stream_name, &client_id, index.u64())
+                    .await
+            }
+            _ => {
+                warn!(
+                    "Stream index service {} only handles indices",
+                    ctx.address()
+                );
+                Err(OckamError::NoSuchProtocol.into())
+            }
+        }
+    }
+}
@@ -0,0 +1,11 @@
+mod index_service;
+mod options;
+mod storage;
+#[allow(clippy::module_inception)]
+mod stream_service;
+mod stream_worker;
+
+pub use index_service::*;
+pub use options::*;
+pub use storage::*;
+pub use stream_service::*;
@@ -0,0 +1,69 @@
+use ockam_core::compat::sync::Arc;
+use ockam_core::compat::vec::Vec;
+use ockam_core::flow_control::{FlowControlId, FlowControls};
+use ockam_core::{Address, AllowAll, IncomingAccessControl};
+
+/// Default maximum number of streams served by a Stream service
+pub const DEFAULT_MAX_STREAMS: usize = 256;
+
+/// Trust Options for a Stream service and a Stream index service
+#[derive(Clone)]
+pub struct StreamServiceOptions {
+    pub(super) incoming_access_control: Arc<dyn IncomingAccessControl>,
+    pub(super) consumer: Vec<FlowControlId>,
+    pub(super) max_streams: usize,
+}
+
+impl StreamServiceOptions {
+    /// Default constructor without Access Control
+    pub fn new() -> Self {
+        Self {
+            incoming_access_control: Arc::new(AllowAll),
+            consumer: vec![],
+            max_streams: DEFAULT_MAX_STREAMS,
+        }
+    }
+
+    /// Mark that this service and its streams are Consumers for to the given [`FlowControlId`]
+    pub fn as_consumer(mut self, id: &FlowControlId) -> Self {
+        self.consumer.push(id.clone());
+
+        self
+    }
+
+    /// Set the maximum number of streams, each served by its own worker
+    pub fn with_max_streams(mut self, max_streams: usize) -> Self {
+        self.max_streams = max_streams;
+        self
+    }
+
+    /// Set the Incoming Access Control of the service and its streams
+    pub fn with_incoming_access_control_impl(
+        mut self,
+        access_control: impl IncomingAccessControl,
+    ) -> Self {
+        self.incoming_access_control = Arc::new(access_control);
+        self
+    }
+
+    /// Set the Incoming Access Control of the service and its streams
+    pub fn with_incoming_access_control(
+        mut self,
+        access_control: Arc<dyn IncomingAccessControl>,
+    ) -> Self {
+        self.incoming_access_control = access_control;
+        self
+    }
+
+    pub(super) fn setup_flow_control(&self, flow_controls: &FlowControls, address: &Address) {
+        for id in &self.consumer {
+            flow_controls.add_consumer(address.clone(), id);
+        }
+    }
+}
+
+impl Default for StreamServiceOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
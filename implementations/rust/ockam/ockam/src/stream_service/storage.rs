@@ -0,0 +1,224 @@
+use crate::protocols::stream::responses::StreamMessage;
+use ockam_core::compat::collections::{BTreeMap, VecDeque};
+use ockam_core::compat::sync::RwLock;
+use ockam_core::compat::{boxed::Box, string::String, vec::Vec};
+use ockam_core::{async_trait, Result};
+use serde::{Deserialize, Serialize};
+
+/// Storage of the messages and consumer indices of the streams served by a
+/// [`StreamService`](super::StreamService) and a
+/// [`StreamIndexService`](super::StreamIndexService)
+///
+/// The index of a message is its position in its stream, starting at 0.
+#[async_trait]
+pub trait StreamStorage: Send + Sync + 'static {
+    /// Append a message to a stream and return its index
+    async fn push(&self, stream_name: &str, data: Vec<u8>) -> Result<u64>;
+
+    /// Return the messages of a stream starting at `index`
+    ///
+    /// At most `limit` messages are returned, zero meaning all the messages.
+    async fn pull(&self, stream_name: &str, index: u64, limit: u64) -> Result<Vec<StreamMessage>>;
+
+    /// Return the index saved by a consumer of a stream
+    async fn get_index(&self, stream_name: &str, client_id: &str) -> Result<Option<u64>>;
+
+    /// Save the index of the next message to be read by a consumer of a stream
+    ///
+    /// The messages below the lowest index saved for a stream may be dropped.
+    async fn save_index(&self, stream_name: &str, client_id: &str, index: u64) -> Result<()>;
+}
+
+/// Stored messages and indices of all the streams
+///
+/// The messages which were read by all the consumers of a stream, below the
+/// lowest index they saved, are dropped when an index is saved.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StreamsData {
+    streams: BTreeMap<String, StoredStream>,
+    /// Index saved by each consumer, per stream
+    indices: BTreeMap<String, BTreeMap<String, u64>>,
+}
+
+/// Messages of a stream which were not read by all its consumers yet
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct StoredStream {
+    /// Index of the first stored message
+    first_index: u64,
+    messages: VecDeque<StoredMessage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredMessage {
+    #[serde(with = "ockam_core::hex_encoding")]
+    data: Vec<u8>,
+}
+
+impl StreamsData {
+    fn push(&mut self, stream_name: &str, data: Vec<u8>) -> u64 {
+        let stream = self.streams.entry(stream_name.into()).or_default();
+        stream.messages.push_back(StoredMessage { data });
+        stream.first_index + (stream.messages.len() - 1) as u64
+    }
+
+    fn pull(&self, stream_name: &str, index: u64, limit: u64) -> Vec<StreamMessage> {
+        let stream = match self.streams.get(stream_name) {
+            Some(stream) => stream,
+            None => return Vec::new(),
+        };
+        let limit = if limit == 0 {
+            usize::MAX
+        } else {
+            limit as usize
+        };
+        // The messages below the first stored index were already dropped
+        let skipped = index.saturating_sub(stream.first_index) as usize;
+        stream
+            .messages
+            .iter()
+            .zip(stream.first_index..)
+            .skip(skipped)
+            .take(limit)
+            .map(|(message, index)| StreamMessage {
+                index: index.into(),
+                data: message.data.clone(),
+            })
+            .collect()
+    }
+
+    fn get_index(&self, stream_name: &str, client_id: &str) -> Option<u64> {
+        self.indices
+            .get(stream_name)
+            .and_then(|indices| indices.get(client_id))
+            .copied()
+    }
+
+    fn save_index(&mut self, stream_name: &str, client_id: &str, index: u64) {
+        let indices = self.indices.entry(stream_name.into()).or_default();
+        indices.insert(client_id.into(), index);
+        let min_index = indices.values().min().copied().unwrap_or_default();
+        self.compact(stream_name, min_index);
+    }
+
+    /// Drop the messages of a stream below `min_index`
+    fn compact(&mut self, stream_name: &str, min_index: u64) {
+        let stream = match self.streams.get_mut(stream_name) {
+            Some(stream) => stream,
+            None => return,
+        };
+        while stream.first_index < min_index && stream.messages.pop_front().is_some() {
+            stream.first_index += 1;
+        }
+    }
+}
+
+/// Stream storage keeping the messages in memory
+#[derive(Default)]
+pub struct InMemoryStreamStorage {
+    data: RwLock<StreamsData>,
+}
+
+impl InMemoryStreamStorage {
+    /// Create an empty in-memory stream storage
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl StreamStorage for InMemoryStreamStorage {
+    async fn push(&self, stream_name: &str, data: Vec<u8>) -> Result<u64> {
+        Ok(self.data.write().unwrap().push(stream_name, data))
+    }
+
+    async fn pull(&self, stream_name: &str, index: u64, limit: u64) -> Result<Vec<StreamMessage>> {
+        Ok(self.data.read().unwrap().pull(stream_name, index, limit))
+    }
+
+    async fn get_index(&self, stream_name: &str, client_id: &str) -> Result<Option<u64>> {
+        Ok(self.data.read().unwrap().get_index(stream_name, client_id))
+    }
+
+    async fn save_index(&self, stream_name: &str, client_id: &str, index: u64) -> Result<()> {
+        self.data
+            .write()
+            .unwrap()
+            .save_index(stream_name, client_id, index);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+pub use file_stream_storage::*;
+
+#[cfg(feature = "std")]
+mod file_stream_storage {
+    use super::*;
+    use ockam_core::compat::string::ToString;
+    use ockam_node::{FileValueStorage, ValueStorage};
+    use std::path::Path;
+
+    /// Stream storage persisting the messages to a file, so that they survive
+    /// a restart of the node
+    ///
+    /// A message is only confirmed to its producer once it has been written to
+    /// the file. The whole file is rewritten on each update, which makes this
+    /// storage suitable for buffering a moderate number of messages. The
+    /// messages read by all the consumers of a stream are removed from the
+    /// file when they save their index.
+    pub struct FileStreamStorage {
+        storage: FileValueStorage<StreamsData>,
+    }
+
+    impl FileStreamStorage {
+        /// Create a stream storage persisted to the file at `path`,
+        /// loading the messages already stored in that file
+        pub async fn create(path: &Path) -> Result<Self> {
+            Ok(Self {
+                storage: FileValueStorage::create(path).await?,
+            })
+        }
+    }
+
+    #[async_trait]
+    impl StreamStorage for FileStreamStorage {
+        async fn push(&self, stream_name: &str, data: Vec<u8>) -> Result<u64> {
+            let stream_name = stream_name.to_string();
+            self.storage
+                .modify_value(move |mut streams| {
+                    let index = streams.push(&stream_name, data.clone());
+                    Ok((streams, index))
+                })
+                .await
+        }
+
+        async fn pull(
+            &self,
+            stream_name: &str,
+            index: u64,
+            limit: u64,
+        ) -> Result<Vec<StreamMessage>> {
+            let stream_name = stream_name.to_string();
+            self.storage
+                .read_value(move |streams| Ok(streams.pull(&stream_name, index, limit)))
+                .await
+        }
+
+        async fn get_index(&self, stream_name: &str, client_id: &str) -> Result<Option<u64>> {
+            let (stream_name, client_id) = (stream_name.to_string(), client_id.to_string());
+            self.storage
+                .read_value(move |streams| Ok(streams.get_index(&stream_name, &client_id)))
+                .await
+        }
+
+        async fn save_index(&self, stream_name: &str, client_id: &str, index: u64) -> Result<()> {
+            let (stream_name, client_id) = (stream_name.to_string(), client_id.to_string());
+            self.storage
+                .update_value(move |mut streams| {
+                    streams.save_index(&stream_name, &client_id, index);
+                    Ok(streams)
+                })
+                .await
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use crate::protocols::stream::requests::{CreateStreamRequest, Request};
+use crate::protocols::{ProtocolParser, ProtocolPayload};
+use crate::stream_service::stream_worker::StreamWorker;
+use crate::stream_service::{StreamServiceOptions, StreamStorage};
+use crate::{Context, OckamError};
+use ockam_core::compat::collections::BTreeMap;
+use ockam_core::compat::rand::{self, Rng};
+use ockam_core::compat::sync::Arc;
+use ockam_core::compat::{boxed::Box, string::String};
+use ockam_core::{Address, Any, Decodable, Result, Routed, Worker};
+use ockam_node::WorkerBuilder;
+
+/// Local implementation of the stream service used by the
+/// [`Stream`](crate::stream::Stream) client.
+///
+/// Each stream is served by its own worker, created when the stream is
+/// first requested, up to the maximum number of streams set in the
+/// [`StreamServiceOptions`]. The producers push messages to that worker and the
+/// consumers pull them, starting at the index saved in a
+/// [`StreamIndexService`](super::StreamIndexService), so that messages
+/// can be buffered for a peer while it is offline.
+///
+/// Messages are delivered at least once: a consumer saves its index
+/// after forwarding the pulled messages, and pulls them again if it
+/// restarts before that.
+pub struct StreamService {
+    storage: Arc<dyn StreamStorage>,
+    options: StreamServiceOptions,
+    /// Address of the worker of each created stream
+    streams: BTreeMap<String, Address>,
+}
+
+impl StreamService {
+    /// Start a stream service
+    pub async fn create(
+        ctx: &Context,
+        address: impl Into<Address>,
+        storage: Arc<dyn StreamStorage>,
+        options: StreamServiceOptions,
+    ) -> Result<()> {
+        let address = address.into();
+
+        options.setup_flow_control(ctx.flow_controls(), &address);
+
+        let incoming_access_control = options.incoming_access_control.clone();
+
+        let s = Self {
+            storage,
+            options,
+            streams: BTreeMap::new(),
+        };
+
+        WorkerBuilder::new(s)
+            .with_address(address)
+            .with_incoming_access_control_arc(incoming_access_control)
+            .start(ctx)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Return the address of the worker of a stream, starting it if needed
+    async fn stream_worker(&mut self, ctx: &Context, stream_name: String) -> Result<Address> {
+        if let Some(address) = self.streams.get(&stream_name) {
+            return Ok(address.clone());
+        }
+
+        if self.streams.len() >= self.options.max_streams {
+            warn!(
+                "Stream service {} can't create stream '{}', it already serves {} streams",
+                ctx.address(),
+                stream_name,
+                self.streams.len()
+            );
+            return Err(OckamError::TooManyStreams.into());
+        }
+
+        let address = Address::random_tagged("StreamWorker");
+        self.options
+            .setup_flow_control(ctx.flow_controls(), &address);
+
+        StreamWorker::create(
+            ctx,
+            address.clone(),
+            stream_name.clone(),
+            self.storage.clone(),
+            self.options.incoming_access_control.clone(),
+        )
+        .await?;
+
+        self.streams.insert(stream_name, address.clone());
+        Ok(address)
+    }
+}
+
+#[crate::worker]
+impl Worker for StreamService {
+    type Context = Context;
+    type Message = Any;
+
+    async fn handle_message(
+        &mut self,
+        ctx: &mut Self::Context,
+        msg: Routed<Self::Message>,
+    ) -> Result<()> {
+        let pp = ProtocolPayload::decode(msg.payload())?;
+        let stream_name = match Request::parse(pp)? {
+            Request::CreateStream(CreateStreamRequest { stream_name }) => stream_name
+                .unwrap_or_else(|| {
+                    let random: [u8; 8] = rand::thread_rng().gen();
+                    hex::encode(random)
+                }),
+            _ => {
+                warn!("Stream service {} only creates streams", ctx.address());
+                return Err(OckamError::NoSuchProtocol.into());
+            }
+        };
+
+        let address = self.stream_worker(ctx, stream_name).await?;
+
+        // The stream worker answers the request, so that its address is
+        // the return route used by the client for the next requests
+        let mut local_msg = msg.into_local_message();
+        local_msg
+            .transport_mut()
+            .onward_route
+            .modify()
+            .pop_front()
+            .prepend(address);
+
+        ctx.forward(local_msg).await
+    }
+}
@@ -0,0 +1,95 @@
+use crate::protocols::stream::requests::{PullRequest, PushRequest, Request};
+use crate::protocols::stream::responses::{InitResponse, PullResponse, PushConfirm};
+use crate::protocols::{ProtocolParser, ProtocolPayload};
+use crate::stream_service::StreamStorage;
+use crate::{Context, OckamError};
+use ockam_core::compat::sync::Arc;
+use ockam_core::compat::{boxed::Box, string::String};
+use ockam_core::{Address, Any, Decodable, IncomingAccessControl, Result, Routed, Worker};
+use ockam_node::WorkerBuilder;
+
+/// Worker serving the requests of the producers and consumers of a single stream
+pub(super) struct StreamWorker {
+    stream_name: String,
+    storage: Arc<dyn StreamStorage>,
+}
+
+impl StreamWorker {
+    pub(super) async fn create(
+        ctx: &Context,
+        address: Address,
+        stream_name: String,
+        storage: Arc<dyn StreamStorage>,
+        incoming_access_control: Arc<dyn IncomingAccessControl>,
+    ) -> Result<()> {
+        info!("Created stream '{}' at {}", stream_name, address);
+
+        let worker = Self {
+            stream_name,
+            storage,
+        };
+
+        WorkerBuilder::new(worker)
+            .with_address(address)
+            .with_incoming_access_control_arc(incoming_access_control)
+            .start(ctx)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[crate::worker]
+impl Worker for StreamWorker {
+    type Context = Context;
+    type Message = Any;
+
+    async fn handle_message(
+        &mut self,
+        ctx: &mut Self::Context,
+        msg: Routed<Self::Message>,
+    ) -> Result<()> {
+        let return_route = msg.return_route();
+        let pp = ProtocolPayload::decode(msg.payload())?;
+
+        let response = match Request::parse(pp)? {
+            Request::CreateStream(_) => InitResponse::new(self.stream_name.clone()),
+            Request::Push(PushRequest { request_id, data }) => {
+                // The message is only confirmed once it is stored
+                match self.storage.push(&self.stream_name, data).await {
+                    Ok(index) => {
+                        trace!("Pushed message {} to stream '{}'", index, self.stream_name);
+                        PushConfirm::new(request_id.u64(), true, index)
+                    }
+                    Err(e) => {
+                        error!(
+                            "Failed to store a message of stream '{}': {}",
+                            self.stream_name, e
+                        );
+                        PushConfirm::new(request_id.u64(), false, 0)
+                    }
+                }
+            }
+            Request::Pull(PullRequest {
+                request_id,
+                index,
+                limit,
+            }) => {
+                let messages = self
+                    .storage
+                    .pull(&self.stream_name, index.u64(), limit.u64())
+                    .await?;
+                PullResponse::new(request_id.u64(), messages)
+            }
+            Request::Index(_) => {
+                warn!(
+                    "Index requests must be sent to a stream index service, not to stream '{}'",
+                    self.stream_name
+                );
+                return Err(OckamError::NoSuchProtocol.into());
+            }
+        };
+
+        ctx.send(return_route, response).await
+    }
+}
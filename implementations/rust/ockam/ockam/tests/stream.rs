@@ -0,0 +1,123 @@
+use ockam::protocols::stream::requests::CreateStreamRequest;
+use ockam::protocols::ProtocolPayload;
+use ockam::stream::Stream;
+use ockam::{
+    FileStreamStorage, InMemoryStreamStorage, StreamIndexService, StreamService,
+    StreamServiceOptions, StreamStorage,
+};
+use ockam_core::compat::sync::Arc;
+use ockam_core::{route, Result};
+use ockam_node::{Context, MessageReceiveOptions};
+use std::time::Duration;
+
+// A message sent to a stream is received by the consumer of that stream
+#[ockam_macros::test]
+async fn local_stream_service(ctx: &mut Context) -> Result<()> {
+    let storage = Arc::new(InMemoryStreamStorage::new());
+    StreamService::create(ctx, "stream", storage.clone(), StreamServiceOptions::new()).await?;
+    StreamIndexService::create(ctx, "stream_index", storage, StreamServiceOptions::new()).await?;
+
+    let (sender, mut receiver) = Stream::new(ctx)
+        .await?
+        .with_interval(Duration::from_millis(50))
+        .connect(route![], "test-stream", "test-stream")
+        .await?;
+
+    ctx.send(sender.to_route(), "Hello".to_string()).await?;
+
+    let msg = receiver.next::<String>().await?;
+    assert_eq!(msg.body(), "Hello");
+
+    ctx.stop().await
+}
+
+// Messages and consumer indices survive a restart of the file storage
+#[ockam_macros::test]
+async fn file_stream_storage(ctx: &mut Context) -> Result<()> {
+    let file = tempfile::NamedTempFile::new().unwrap();
+
+    let storage = FileStreamStorage::create(file.path()).await?;
+    assert_eq!(storage.push("s", b"first".to_vec()).await?, 0);
+    assert_eq!(storage.push("s", b"second".to_vec()).await?, 1);
+    storage.save_index("s", "client", 1).await?;
+    drop(storage);
+
+    let storage = FileStreamStorage::create(file.path()).await?;
+    let messages = storage.pull("s", 1, 0).await?;
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].index.u64(), 1);
+    assert_eq!(messages[0].data, b"second".to_vec());
+    assert_eq!(storage.get_index("s", "client").await?, Some(1));
+    assert_eq!(storage.get_index("s", "other").await?, None);
+
+    ctx.stop().await
+}
+
+// The messages read by all the consumers of a stream are dropped
+#[ockam_macros::test]
+async fn stream_storage_compaction(ctx: &mut Context) -> Result<()> {
+    let file = tempfile::NamedTempFile::new().unwrap();
+
+    let storage = FileStreamStorage::create(file.path()).await?;
+    for data in [b"0", b"1", b"2"] {
+        storage.push("s", data.to_vec()).await?;
+    }
+    storage.save_index("s", "client1", 1).await?;
+    storage.save_index("s", "client2", 2).await?;
+    drop(storage);
+
+    // The first message was read by both consumers
+    let storage = FileStreamStorage::create(file.path()).await?;
+    let messages = storage.pull("s", 0, 0).await?;
+    let indices: Vec<u64> = messages.iter().map(|m| m.index.u64()).collect();
+    assert_eq!(indices, vec![1, 2]);
+    assert_eq!(messages[0].data, b"1".to_vec());
+
+    // The indices keep increasing after a compaction
+    storage.save_index("s", "client1", 3).await?;
+    assert_eq!(storage.push("s", b"3".to_vec()).await?, 3);
+    let messages = storage.pull("s", 0, 0).await?;
+    let indices: Vec<u64> = messages.iter().map(|m| m.index.u64()).collect();
+    assert_eq!(indices, vec![2, 3]);
+
+    ctx.stop().await
+}
+
+// A stream service doesn't create more streams than its maximum
+#[ockam_macros::test]
+async fn stream_service_max_streams(ctx: &mut Context) -> Result<()> {
+    let storage = Arc::new(InMemoryStreamStorage::new());
+    StreamService::create(
+        ctx,
+        "stream",
+        storage,
+        StreamServiceOptions::new().with_max_streams(1),
+    )
+    .await?;
+
+    let options = || MessageReceiveOptions::new().with_timeout(Duration::from_millis(500));
+
+    ctx.send(route!["stream"], CreateStreamRequest::new("a".to_string()))
+        .await?;
+    assert!(ctx
+        .receive_extended::<ProtocolPayload>(options())
+        .await
+        .is_ok());
+
+    // An existing stream is still served
+    ctx.send(route!["stream"], CreateStreamRequest::new("a".to_string()))
+        .await?;
+    assert!(ctx
+        .receive_extended::<ProtocolPayload>(options())
+        .await
+        .is_ok());
+
+    ctx.send(route!["stream"], CreateStreamRequest::new("b".to_string()))
+        .await?;
+    assert!(ctx
+        .receive_extended::<ProtocolPayload>(options())
+        .await
+        .is_err());
+
+    ctx.stop().await
+}
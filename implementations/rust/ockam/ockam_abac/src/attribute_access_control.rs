@@ -10,13 +10,21 @@ use ockam_core::Result;
 use ockam_core::{IncomingAccessControl, RelayMessage};
 use tracing as log;
 
-use crate::expr::str;
+use crate::audit::{record, AuditSink, Decision};
+use crate::eval::eval_with_trace;
+use crate::expr::{int, str};
+use crate::trace::Trace;
+use crate::types::{Action, Resource};
 use crate::Expr::*;
 use crate::{eval, Env, Expr};
 use ockam_core::compat::format;
 use ockam_core::compat::string::ToString;
 use ockam_core::compat::sync::Arc;
-use ockam_identity::{Identifier, IdentitiesRepository, IdentitySecureChannelLocalInfo};
+use ockam_identity::utils::now;
+use ockam_identity::{
+    AttributesEntry, Identifier, IdentitiesRepository, IdentitySecureChannelLocalInfo,
+    TimestampInSeconds,
+};
 
 /// This AccessControl uses a storage for authenticated attributes in order
 /// to verify if a policy expression is valid
@@ -26,6 +34,9 @@ pub struct AbacAccessControl {
     repository: Arc<dyn IdentitiesRepository>,
     expression: Expr,
     environment: Env,
+    resource: Option<Resource>,
+    action: Option<Action>,
+    audit: Option<Arc<dyn AuditSink>>,
 }
 
 /// Debug implementation printing out the policy expression only
@@ -47,6 +58,37 @@ impl AbacAccessControl {
             repository,
             expression,
             environment,
+            resource: None,
+            action: None,
+            audit: None,
+        }
+    }
+
+    /// Record every decision made by this AccessControl in the given audit sink
+    pub fn with_audit_sink(mut self, sink: Arc<dyn AuditSink>) -> Self {
+        self.audit = Some(sink);
+        self
+    }
+
+    /// Set the resource and action reported in the audit decisions
+    pub(crate) fn with_resource_and_action(mut self, resource: Resource, action: Action) -> Self {
+        self.resource = Some(resource);
+        self.action = Some(action);
+        self
+    }
+
+    /// Create an audit decision for this AccessControl
+    fn decision(&self, subject: Option<Identifier>, environment: Env) -> Decision {
+        Decision {
+            resource: self.resource.clone(),
+            action: self.action.clone(),
+            subject,
+            environment,
+            policy: Some(self.expression.clone()),
+            is_authorized: false,
+            trace: Trace::new(),
+            error: None,
+            timestamp: now().ok(),
         }
     }
 
@@ -68,19 +110,20 @@ where {
 }
 
 impl AbacAccessControl {
-    /// Returns true if the identity is authorized
-    pub async fn is_identity_authorized(&self, id: Identifier) -> Result<bool> {
+    /// Return the environment in which the policy expression is evaluated
+    /// for a given identity and its authenticated attributes
+    pub fn environment(&self, id: Option<&Identifier>, entry: Option<&AttributesEntry>) -> Env {
         let mut environment = self.environment.clone();
 
-        // Get identity attributes and populate the environment:
-        if let Some(attrs) = self.repository.get_attributes(&id).await? {
+        // Populate the environment with the identity attributes:
+        if let Some(attrs) = entry {
             for (key, value) in attrs.attrs() {
                 let key = match from_utf8(key) {
                     Ok(key) => key,
                     Err(_) => {
                         log::warn! {
                             policy = %self.expression,
-                            id     = %id,
+                            id     = ?id,
                             "attribute key is not utf-8"
                         }
                         continue;
@@ -89,7 +132,7 @@ impl AbacAccessControl {
                 if key.find(|c: char| c.is_whitespace()).is_some() {
                     log::warn! {
                         policy = %self.expression,
-                        id     = %id,
+                        id     = ?id,
                         key    = %key,
                         "attribute key with whitespace ignored"
                     }
@@ -99,7 +142,7 @@ impl AbacAccessControl {
                         if environment.contains(key) {
                             log::debug! {
                                 policy = %self.expression,
-                                id     = %id,
+                                id     = ?id,
                                 key    = %key,
                                 "attribute already present"
                             }
@@ -110,7 +153,7 @@ impl AbacAccessControl {
                     Err(e) => {
                         log::warn! {
                             policy = %self.expression,
-                            id     = %id,
+                            id     = ?id,
                             key    = %key,
                             err    = %e,
                             "failed to interpret attribute as string"
@@ -121,10 +164,52 @@ impl AbacAccessControl {
         };
 
         // add the identifier itself as a subject parameter
-        environment.put("subject.identifier", str(id.to_string()));
+        if let Some(id) = id {
+            environment.put("subject.identifier", str(id.to_string()));
+        }
+
+        // add the trusted time-related parameters, overriding any attribute
+        // of the same name that may have been set by a credential
+        match now() {
+            Ok(t) => {
+                environment.put("now", timestamp(t));
+            }
+            Err(e) => {
+                log::warn! {
+                    policy = %self.expression,
+                    id     = ?id,
+                    err    = %e,
+                    "current time is unavailable"
+                }
+            }
+        }
+        environment.del("subject.credential.expires_at");
+        environment.del("subject.credential.issued_at");
+        if let Some(entry) = entry {
+            if let Some(t) = entry.expires() {
+                environment.put("subject.credential.expires_at", timestamp(t));
+            }
+            if let Some(t) = entry.issued() {
+                environment.put("subject.credential.issued_at", timestamp(t));
+            }
+        }
+
+        environment
+    }
+
+    /// Returns true if the identity is authorized
+    pub async fn is_identity_authorized(&self, id: Identifier) -> Result<bool> {
+        let entry = self.repository.get_attributes(&id).await?;
+        let environment = self.environment(Some(&id), entry.as_ref());
 
         // Finally, evaluate the expression and return the result:
-        match eval(&self.expression, &environment) {
+        let mut trace = Trace::new();
+        let result = if self.audit.is_some() {
+            eval_with_trace(&self.expression, &environment, &mut trace)
+        } else {
+            eval(&self.expression, &environment)
+        };
+        let (is_authorized, error) = match result {
             Ok(Expr::Bool(b)) => {
                 log::debug! {
                     policy        = %self.expression,
@@ -132,7 +217,7 @@ impl AbacAccessControl {
                     is_authorized = %b,
                     "policy evaluated"
                 }
-                Ok(b)
+                (b, None)
             }
             Ok(x) => {
                 log::warn! {
@@ -141,7 +226,10 @@ impl AbacAccessControl {
                     expr   = %x,
                     "evaluation did not yield a boolean result"
                 }
-                Ok(false)
+                (
+                    false,
+                    Some(format!("evaluation did not yield a boolean result: {x}")),
+                )
             }
             Err(e) => {
                 log::warn! {
@@ -150,9 +238,19 @@ impl AbacAccessControl {
                     err    = %e,
                     "policy evaluation failed"
                 }
-                Ok(false)
+                (false, Some(format!("policy evaluation failed: {e}")))
             }
+        };
+
+        if let Some(sink) = &self.audit {
+            let mut decision = self.decision(Some(id), environment);
+            decision.is_authorized = is_authorized;
+            decision.trace = trace;
+            decision.error = error;
+            record(sink.as_ref(), &decision).await
         }
+
+        Ok(is_authorized)
     }
 }
 
@@ -168,9 +266,19 @@ impl IncomingAccessControl for AbacAccessControl {
                 policy = %self.expression,
                 "identity identifier not found; access denied"
             }
+            if let Some(sink) = &self.audit {
+                let mut decision = self.decision(None, self.environment.clone());
+                decision.error = Some("identity identifier not found".to_string());
+                record(sink.as_ref(), &decision).await
+            }
             return Ok(false);
         };
 
         self.is_identity_authorized(id).await
     }
 }
+
+/// Convert a timestamp to an integer expression holding a number of seconds since the UNIX epoch
+fn timestamp(t: TimestampInSeconds) -> Expr {
+    int(i64::try_from(t.0).unwrap_or(i64::MAX))
+}
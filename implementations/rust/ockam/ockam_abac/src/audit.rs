@@ -0,0 +1,282 @@
+use crate::env::Env;
+use crate::expr::Expr;
+use crate::trace::Trace;
+use crate::types::{Action, Resource};
+use ockam_core::async_trait;
+use ockam_core::compat::boxed::Box;
+use ockam_core::compat::string::String;
+use ockam_core::compat::sync::{Arc, RwLock};
+use ockam_core::compat::vec::Vec;
+use ockam_core::Result;
+use ockam_identity::{Identifier, TimestampInSeconds};
+use tracing as log;
+
+/// An authorization decision made by an access control.
+#[derive(Debug, Clone)]
+pub struct Decision {
+    /// The resource being accessed, if known.
+    pub resource: Option<Resource>,
+    /// The action being performed on the resource, if known.
+    pub action: Option<Action>,
+    /// The identifier of the message sender, if the message came from a secure channel.
+    pub subject: Option<Identifier>,
+    /// The environment the policy was evaluated in.
+    pub environment: Env,
+    /// The policy expression, if a policy was found.
+    pub policy: Option<Expr>,
+    /// The outcome of the decision.
+    pub is_authorized: bool,
+    /// The evaluation steps of the policy expression.
+    pub trace: Trace,
+    /// The reason why the policy could not be evaluated, if any.
+    pub error: Option<String>,
+    /// The time at which the decision was made.
+    pub timestamp: Option<TimestampInSeconds>,
+}
+
+/// A sink recording the decisions made by access controls.
+#[async_trait]
+pub trait AuditSink: Send + Sync + 'static {
+    async fn record(&self, decision: &Decision) -> Result<()>;
+}
+
+/// Record a decision, logging a failure to do so instead of returning it,
+/// since a failing sink must not change the outcome of the decision.
+pub(crate) async fn record(sink: &dyn AuditSink, decision: &Decision) {
+    if let Err(e) = sink.record(decision).await {
+        log::warn! {
+            err = %e,
+            "failed to record access control decision"
+        }
+    }
+}
+
+/// An audit sink keeping decisions in memory (mostly for testing).
+#[derive(Debug, Clone, Default)]
+pub struct MemoryAuditSink {
+    decisions: Arc<RwLock<Vec<Decision>>>,
+}
+
+impl MemoryAuditSink {
+    pub fn new() -> Self {
+        MemoryAuditSink::default()
+    }
+
+    /// Return the decisions recorded so far.
+    pub fn decisions(&self) -> Vec<Decision> {
+        self.decisions.read().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl AuditSink for MemoryAuditSink {
+    async fn record(&self, decision: &Decision) -> Result<()> {
+        self.decisions.write().unwrap().push(decision.clone());
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+pub use file::FileAuditSink;
+
+#[cfg(feature = "std")]
+mod file {
+    use super::{AuditSink, Decision};
+    use ockam_core::async_trait;
+    use ockam_core::compat::boxed::Box;
+    use ockam_core::errcode::{Kind, Origin};
+    use ockam_core::{Error, Result};
+    use serde_json::{json, Map, Value};
+    use std::fs::{File, OpenOptions};
+    use std::io::Write;
+    use std::path::Path;
+    use std::thread;
+    use tokio::sync::mpsc;
+    use tracing as log;
+
+    /// Number of decisions which can wait to be written before recording blocks.
+    const QUEUE_SIZE: usize = 1024;
+
+    /// An audit sink appending decisions to a file, as one JSON object per line.
+    ///
+    /// The file is written by a dedicated thread, so that recording a decision
+    /// never blocks the runtime on file I/O.
+    #[derive(Debug)]
+    pub struct FileAuditSink {
+        lines: mpsc::Sender<String>,
+    }
+
+    impl FileAuditSink {
+        /// Open the given file for appending, creating it if it does not exist.
+        ///
+        /// The writer thread stops once the sink is dropped.
+        pub fn create<P: AsRef<Path>>(path: P) -> Result<Self> {
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(map_io_err)?;
+            let (lines, receiver) = mpsc::channel(QUEUE_SIZE);
+            thread::Builder::new()
+                .name("audit-log-writer".into())
+                .spawn(move || write_lines(file, receiver))
+                .map_err(map_io_err)?;
+            Ok(FileAuditSink { lines })
+        }
+    }
+
+    #[async_trait]
+    impl AuditSink for FileAuditSink {
+        async fn record(&self, decision: &Decision) -> Result<()> {
+            let mut line = to_json(decision).to_string();
+            line.push('\n');
+            self.lines.send(line).await.map_err(|_| {
+                Error::new(
+                    Origin::Application,
+                    Kind::Cancelled,
+                    "the audit log writer has stopped",
+                )
+            })
+        }
+    }
+
+    fn write_lines(mut file: File, mut lines: mpsc::Receiver<String>) {
+        while let Some(line) = lines.blocking_recv() {
+            if let Err(e) = file.write_all(line.as_bytes()).and_then(|_| file.flush()) {
+                log::warn! {
+                    err = %e,
+                    "failed to write an access control decision to the audit log"
+                }
+            }
+        }
+    }
+
+    fn to_json(d: &Decision) -> Value {
+        let environment: Map<String, Value> = d
+            .environment
+            .entries()
+            .map(|(k, v)| (k.to_string(), Value::String(v.to_string())))
+            .collect();
+        let trace: Vec<Value> = d
+            .trace
+            .steps()
+            .iter()
+            .map(|s| {
+                json!({
+                    "depth": s.depth,
+                    "expr": s.expr.to_string(),
+                    "value": s.value.as_ref().map(|v| v.to_string()),
+                })
+            })
+            .collect();
+        json!({
+            "timestamp": d.timestamp.map(|t| t.0),
+            "resource": d.resource.as_ref().map(|r| r.to_string()),
+            "action": d.action.as_ref().map(|a| a.to_string()),
+            "subject": d.subject.as_ref().map(|s| s.to_string()),
+            "policy": d.policy.as_ref().map(|p| p.to_string()),
+            "environment": environment,
+            "is_authorized": d.is_authorized,
+            "decisive": d.trace.decisive().map(|s| s.expr.to_string()),
+            "trace": trace,
+            "error": d.error,
+        })
+    }
+
+    fn map_io_err(err: std::io::Error) -> Error {
+        Error::new(Origin::Application, Kind::Io, err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AuditSink, MemoryAuditSink};
+    use crate::expr::{eq, ident, str};
+    use crate::{AbacAccessControl, Env};
+    use ockam_core::compat::collections::BTreeMap;
+    use ockam_core::compat::sync::Arc;
+    use ockam_core::Result;
+    use ockam_identity::utils::now;
+    use ockam_identity::{identities, AttributesEntry};
+
+    #[tokio::test]
+    async fn memory_sink() -> Result<()> {
+        let identities = identities();
+        let repository = identities.repository();
+        let identifier = identities
+            .identities_creation()
+            .create_identity()
+            .await?
+            .identifier()
+            .clone();
+        let attributes = BTreeMap::from([(b"role".to_vec(), b"member".to_vec())]);
+        repository
+            .put_attributes(
+                &identifier,
+                AttributesEntry::new(attributes, now()?, None, None),
+            )
+            .await?;
+
+        let sink = Arc::new(MemoryAuditSink::new());
+        let expression = eq([ident("subject.role"), str("admin")]);
+        let access_control = AbacAccessControl::new(repository, expression, Env::new())
+            .with_audit_sink(sink.clone() as Arc<dyn AuditSink>);
+
+        assert!(
+            !access_control
+                .is_identity_authorized(identifier.clone())
+                .await?
+        );
+
+        let decisions = sink.decisions();
+        assert_eq!(1, decisions.len());
+        let decision = &decisions[0];
+        assert!(!decision.is_authorized);
+        assert_eq!(Some(&identifier), decision.subject.as_ref());
+        assert!(decision.error.is_none());
+        assert!(decision.environment.contains("subject.role"));
+        assert_eq!(
+            r#"(= subject.role "admin")"#,
+            decision.trace.decisive().unwrap().expr.to_string()
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "std")]
+    #[tokio::test]
+    async fn file_sink() -> Result<()> {
+        use super::FileAuditSink;
+        use std::time::Duration;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.log");
+        let sink = Arc::new(FileAuditSink::create(&path)?);
+        let access_control =
+            AbacAccessControl::new(identities().repository(), str("ok"), Env::new())
+                .with_audit_sink(sink as Arc<dyn AuditSink>);
+        let identifier = identities()
+            .identities_creation()
+            .create_identity()
+            .await?
+            .identifier()
+            .clone();
+        access_control.is_identity_authorized(identifier).await?;
+
+        // the decision is written asynchronously by the writer thread
+        let mut lines = vec![];
+        for _ in 0..100 {
+            lines = std::fs::read_to_string(&path)
+                .unwrap()
+                .lines()
+                .map(|l| serde_json::from_str::<serde_json::Value>(l).unwrap())
+                .collect();
+            if !lines.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(1, lines.len());
+        assert_eq!(Some(false), lines[0]["is_authorized"].as_bool());
+        Ok(())
+    }
+}
@@ -22,6 +22,9 @@ pub enum EvalError {
     InvalidType(Expr, &'static str),
     TypeMismatch(Expr, Expr),
     Malformed(String),
+    InvalidRegex(String, String),
+    InvalidTimestamp(String),
+    Overflow,
 }
 
 #[derive(Debug)]
@@ -91,6 +94,9 @@ impl fmt::Display for EvalError {
             EvalError::InvalidType(e, m) => write!(f, "invalid type of expression {e}: {m}"),
             EvalError::Malformed(m) => write!(f, "malformed expression: {m}"),
             EvalError::TypeMismatch(a, b) => write!(f, "{a} and {b} are not of the same type"),
+            EvalError::InvalidRegex(r, e) => write!(f, "invalid regular expression {r:?}: {e}"),
+            EvalError::InvalidTimestamp(t) => write!(f, "invalid timestamp: {t:?}"),
+            EvalError::Overflow => f.write_str("integer overflow"),
         }
     }
 }
@@ -3,11 +3,33 @@ use core::cmp::Ordering;
 use crate::env::Env;
 use crate::error::EvalError;
 use crate::expr::{unit, Expr};
+use crate::trace::Trace;
+use ockam_core::compat::format;
 use ockam_core::compat::string::ToString;
 use ockam_core::compat::vec::Vec;
 
-#[rustfmt::skip]
+/// Number of seconds in a minute.
+const MINUTE: i64 = 60;
+
+/// Number of seconds in an hour.
+const HOUR: i64 = 60 * MINUTE;
+
+/// Number of seconds in a day.
+const DAY: i64 = 24 * HOUR;
+
+/// Evaluate an expression in a given environment.
 pub fn eval(expr: &Expr, env: &Env) -> Result<Expr, EvalError> {
+    eval_with(expr, env, None)
+}
+
+/// Evaluate an expression in a given environment and record every evaluation
+/// step in the given trace.
+pub fn eval_with_trace(expr: &Expr, env: &Env, trace: &mut Trace) -> Result<Expr, EvalError> {
+    eval_with(expr, env, Some(trace))
+}
+
+#[rustfmt::skip]
+fn eval_with(expr: &Expr, env: &Env, mut trace: Option<&mut Trace>) -> Result<Expr, EvalError> {
     /// A stack operation.
     ///
     /// Each operation uses the arguments stack as input. The number of
@@ -22,8 +44,22 @@ pub fn eval(expr: &Expr, env: &Env) -> Result<Expr, EvalError> {
         Eq(usize),
         Gt(usize),
         Lt(usize),
+        Ge(usize),
+        Le(usize),
         Member,
+        StartsWith,
+        EndsWith,
+        Contains,
+        Matches,
+        Subset,
+        Intersects,
+        Add(usize),
+        Sub(usize),
+        Duration(i64),
+        TimeOfDay,
+        Timestamp,
         Seq(usize),
+        Done(usize),
     }
 
     // Control stack.
@@ -36,11 +72,22 @@ pub fn eval(expr: &Expr, env: &Env) -> Result<Expr, EvalError> {
 
     while let Some(x) = ctrl.pop() {
         match x {
-            Op::Eval(Expr::Ident(id)) => ctrl.push(Op::Eval(env.get(id)?)),
-            Op::Eval(Expr::List(xs))  => match &xs[..] {
+            Op::Eval(e @ Expr::Ident(id)) => {
+                let v = env.get(id)?;
+                if let Some(t) = trace.as_deref_mut() {
+                    t.leaf(e, v)
+                }
+                ctrl.push(Op::Eval(v))
+            }
+            Op::Eval(e @ Expr::List(xs)) => match &xs[..] {
                 []                    => args.push(unit()),
                 [Expr::Ident(id), ..] => {
                     let nargs = xs.len() - 1; // number of arguments
+                    // When tracing, `Done` is put below the operation so that
+                    // the operation result can be recorded once it is known.
+                    if let Some(t) = trace.as_deref_mut() {
+                        ctrl.push(Op::Done(t.open(e)))
+                    }
                     match id.as_str() {
                         "and" => {
                             // 'and' evaluates its arguments lazily. As soon as a
@@ -114,6 +161,20 @@ pub fn eval(expr: &Expr, env: &Env) -> Result<Expr, EvalError> {
                             }
                             ctrl.push(Op::Gt(nargs))
                         }
+                        "<=" => {
+                            if nargs < 2 {
+                                let msg = "'<=' requires at least two arguments";
+                                return Err(EvalError::malformed(msg))
+                            }
+                            ctrl.push(Op::Le(nargs))
+                        }
+                        ">=" => {
+                            if nargs < 2 {
+                                let msg = "'>=' requires at least two arguments";
+                                return Err(EvalError::malformed(msg))
+                            }
+                            ctrl.push(Op::Ge(nargs))
+                        }
                         "=" => {
                             if nargs < 2 {
                                 let msg = "'=' requires at least two arguments";
@@ -132,6 +193,88 @@ pub fn eval(expr: &Expr, env: &Env) -> Result<Expr, EvalError> {
                             }
                             ctrl.push(Op::Member)
                         }
+                        "starts-with?" => {
+                            if nargs != 2 {
+                                let msg = "'starts-with?' requires two arguments";
+                                return Err(EvalError::malformed(msg))
+                            }
+                            ctrl.push(Op::StartsWith)
+                        }
+                        "ends-with?" => {
+                            if nargs != 2 {
+                                let msg = "'ends-with?' requires two arguments";
+                                return Err(EvalError::malformed(msg))
+                            }
+                            ctrl.push(Op::EndsWith)
+                        }
+                        "contains?" => {
+                            if nargs != 2 {
+                                let msg = "'contains?' requires two arguments";
+                                return Err(EvalError::malformed(msg))
+                            }
+                            ctrl.push(Op::Contains)
+                        }
+                        "matches?" => {
+                            if nargs != 2 {
+                                let msg = "'matches?' requires two arguments";
+                                return Err(EvalError::malformed(msg))
+                            }
+                            ctrl.push(Op::Matches)
+                        }
+                        "subset?" => {
+                            if nargs != 2 {
+                                let msg = "'subset?' requires two arguments";
+                                return Err(EvalError::malformed(msg))
+                            }
+                            ctrl.push(Op::Subset)
+                        }
+                        "intersects?" => {
+                            if nargs != 2 {
+                                let msg = "'intersects?' requires two arguments";
+                                return Err(EvalError::malformed(msg))
+                            }
+                            ctrl.push(Op::Intersects)
+                        }
+                        "+" => {
+                            if nargs < 2 {
+                                let msg = "'+' requires at least two arguments";
+                                return Err(EvalError::malformed(msg))
+                            }
+                            ctrl.push(Op::Add(nargs))
+                        }
+                        "-" => {
+                            if nargs < 2 {
+                                let msg = "'-' requires at least two arguments";
+                                return Err(EvalError::malformed(msg))
+                            }
+                            ctrl.push(Op::Sub(nargs))
+                        }
+                        "seconds" | "minutes" | "hours" | "days" => {
+                            if nargs != 1 {
+                                let msg = format!("'{id}' requires one argument");
+                                return Err(EvalError::malformed(msg))
+                            }
+                            ctrl.push(Op::Duration(match id.as_str() {
+                                "seconds" => 1,
+                                "minutes" => MINUTE,
+                                "hours"   => HOUR,
+                                _         => DAY
+                            }))
+                        }
+                        "time-of-day" => {
+                            if nargs != 1 {
+                                let msg = "'time-of-day' requires one argument";
+                                return Err(EvalError::malformed(msg))
+                            }
+                            ctrl.push(Op::TimeOfDay)
+                        }
+                        "timestamp" => {
+                            if nargs != 1 {
+                                let msg = "'timestamp' requires one argument";
+                                return Err(EvalError::malformed(msg))
+                            }
+                            ctrl.push(Op::Timestamp)
+                        }
                         "exists?" => {
                             let mut b = true;
                             for x in &xs[1 ..] {
@@ -235,22 +378,120 @@ pub fn eval(expr: &Expr, env: &Env) -> Result<Expr, EvalError> {
             Op::Gt(n) => eval_predicate(n, &mut args, |x, y| {
                 x.compare(y).map(|o| o == Some(Ordering::Greater))
             })?,
+            Op::Le(n) => eval_predicate(n, &mut args, |x, y| {
+                x.compare(y).map(|o| matches!(o, Some(Ordering::Less | Ordering::Equal)))
+            })?,
+            Op::Ge(n) => eval_predicate(n, &mut args, |x, y| {
+                x.compare(y).map(|o| matches!(o, Some(Ordering::Greater | Ordering::Equal)))
+            })?,
             Op::Member => {
                 let s = pop(&mut args);
                 let y = pop(&mut args);
                 match s {
-                    Expr::Seq(xs) => {
-                        let mut b = false;
+                    Expr::Seq(xs) => args.push(Expr::Bool(is_member(&y, &xs)?)),
+                    other => {
+                        let msg = "'member?' expects sequence as second argument";
+                        return Err(EvalError::InvalidType(other, msg))
+                    }
+                }
+            }
+            Op::StartsWith => {
+                let msg = "'starts-with?' expects string arguments";
+                eval_str_predicate(msg, &mut args, |s, p| s.starts_with(p))?
+            }
+            Op::EndsWith => {
+                let msg = "'ends-with?' expects string arguments";
+                eval_str_predicate(msg, &mut args, |s, p| s.ends_with(p))?
+            }
+            Op::Contains => {
+                let msg = "'contains?' expects string arguments";
+                eval_str_predicate(msg, &mut args, |s, p| s.contains(p))?
+            }
+            Op::Matches => {
+                let p = pop(&mut args);
+                let s = pop(&mut args);
+                match (s, p) {
+                    (Expr::Str(s), Expr::Str(p)) => args.push(Expr::Bool(is_match(&s, &p)?)),
+                    (Expr::Str(_), other) | (other, _) => {
+                        let msg = "'matches?' expects string arguments";
+                        return Err(EvalError::InvalidType(other, msg))
+                    }
+                }
+            }
+            Op::Subset => {
+                let b = pop(&mut args);
+                let a = pop(&mut args);
+                match (a, b) {
+                    (Expr::Seq(xs), Expr::Seq(ys)) => {
+                        let mut r = true;
+                        for x in &xs {
+                            if !is_member(x, &ys)? {
+                                r = false;
+                                break
+                            }
+                        }
+                        args.push(Expr::Bool(r))
+                    }
+                    (Expr::Seq(_), other) | (other, _) => {
+                        let msg = "'subset?' expects sequence arguments";
+                        return Err(EvalError::InvalidType(other, msg))
+                    }
+                }
+            }
+            Op::Intersects => {
+                let b = pop(&mut args);
+                let a = pop(&mut args);
+                match (a, b) {
+                    (Expr::Seq(xs), Expr::Seq(ys)) => {
+                        let mut r = false;
                         for x in &xs {
-                            if y.equals(x)? {
-                                b = true;
+                            if is_member(x, &ys)? {
+                                r = true;
                                 break
                             }
                         }
-                        args.push(Expr::Bool(b))
+                        args.push(Expr::Bool(r))
+                    }
+                    (Expr::Seq(_), other) | (other, _) => {
+                        let msg = "'intersects?' expects sequence arguments";
+                        return Err(EvalError::InvalidType(other, msg))
+                    }
+                }
+            }
+            Op::Add(n) => {
+                let msg = "'+' expects integer arguments";
+                eval_arithmetic(n, &mut args, msg, i64::checked_add)?
+            }
+            Op::Sub(n) => {
+                let msg = "'-' expects integer arguments";
+                eval_arithmetic(n, &mut args, msg, i64::checked_sub)?
+            }
+            Op::Duration(unit) => {
+                match pop(&mut args) {
+                    Expr::Int(i) => {
+                        let d = i.checked_mul(unit).ok_or(EvalError::Overflow)?;
+                        args.push(Expr::Int(d))
                     }
                     other => {
-                        let msg = "'member?' expects sequence as second argument";
+                        let msg = "durations expect an integer argument";
+                        return Err(EvalError::InvalidType(other, msg))
+                    }
+                }
+            }
+            Op::TimeOfDay => {
+                match pop(&mut args) {
+                    Expr::Int(t) => args.push(Expr::Int(t.rem_euclid(DAY))),
+                    other => {
+                        let msg = "'time-of-day' expects a timestamp argument";
+                        return Err(EvalError::InvalidType(other, msg))
+                    }
+                }
+            }
+            Op::Timestamp => {
+                match pop(&mut args) {
+                    Expr::Str(s) => args.push(Expr::Int(parse_timestamp(&s)?)),
+                    other => {
+                        let msg = "'timestamp' expects a string argument";
                         return Err(EvalError::InvalidType(other, msg))
                     }
                 }
@@ -259,6 +500,11 @@ pub fn eval(expr: &Expr, env: &Env) -> Result<Expr, EvalError> {
                 let s = args.split_off(args.len() - n);
                 args.push(Expr::Seq(s))
             }
+            Op::Done(i) => {
+                if let (Some(t), Some(v)) = (trace.as_deref_mut(), args.last()) {
+                    t.close(i, v)
+                }
+            }
         }
     }
 
@@ -292,3 +538,225 @@ where
     args.push(Expr::Bool(b));
     Ok(())
 }
+
+/// Fold the `n` topmost arguments, which must be integers, with an arithmetic operation.
+fn eval_arithmetic<F>(
+    n: usize,
+    args: &mut Vec<Expr>,
+    msg: &'static str,
+    f: F,
+) -> Result<(), EvalError>
+where
+    F: Fn(i64, i64) -> Option<i64>,
+{
+    let xs = args.split_off(args.len() - n);
+    let mut r: Option<i64> = None;
+    for x in xs {
+        match x {
+            Expr::Int(i) => {
+                r = match r {
+                    None => Some(i),
+                    Some(acc) => Some(f(acc, i).ok_or(EvalError::Overflow)?),
+                }
+            }
+            other => return Err(EvalError::InvalidType(other, msg)),
+        }
+    }
+    args.push(Expr::Int(r.unwrap_or(0)));
+    Ok(())
+}
+
+/// Parse an RFC 3339 UTC timestamp of the form `YYYY-MM-DDTHH:MM:SSZ` into
+/// a number of seconds since the UNIX epoch.
+fn parse_timestamp(s: &str) -> Result<i64, EvalError> {
+    let invalid = || EvalError::InvalidTimestamp(s.to_string());
+    let b = s.as_bytes();
+    if b.len() != 20
+        || b[4] != b'-'
+        || b[7] != b'-'
+        || !matches!(b[10], b'T' | b't')
+        || b[13] != b':'
+        || b[16] != b':'
+        || !matches!(b[19], b'Z' | b'z')
+    {
+        return Err(invalid());
+    }
+    let num = |range: core::ops::Range<usize>| -> Result<i64, EvalError> {
+        let digits = &s[range];
+        if !digits.bytes().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        digits.parse::<i64>().map_err(|_| invalid())
+    };
+    let (year, month, day) = (num(0..4)?, num(5..7)?, num(8..10)?);
+    let (hour, minute, second) = (num(11..13)?, num(14..16)?, num(17..19)?);
+    if !(1..=12).contains(&month)
+        || day < 1
+        || day > days_in_month(year, month)
+        || hour > 23
+        || minute > 59
+        || second > 59
+    {
+        return Err(invalid());
+    }
+    Ok(days_from_civil(year, month, day) * DAY + hour * HOUR + minute * MINUTE + second)
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Number of days since 1970-01-01 of a date in the proleptic Gregorian calendar.
+///
+/// See <http://howardhinnant.github.io/date_algorithms.html#days_from_civil>.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// Evaluate a predicate against the two topmost arguments, which must be strings.
+fn eval_str_predicate<F>(msg: &'static str, args: &mut Vec<Expr>, f: F) -> Result<(), EvalError>
+where
+    F: Fn(&str, &str) -> bool,
+{
+    let y = pop(args);
+    let x = pop(args);
+    match (x, y) {
+        (Expr::Str(x), Expr::Str(y)) => {
+            args.push(Expr::Bool(f(&x, &y)));
+            Ok(())
+        }
+        (Expr::Str(_), other) | (other, _) => Err(EvalError::InvalidType(other, msg)),
+    }
+}
+
+/// Check if `x` is equal to one of the elements of `xs`.
+fn is_member(x: &Expr, xs: &[Expr]) -> Result<bool, EvalError> {
+    for y in xs {
+        if x.equals(y)? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Maximum size of a compiled regular expression used by `matches?`.
+#[cfg(feature = "std")]
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+/// Check if the string `s` matches the regular expression `p`.
+#[cfg(feature = "std")]
+fn is_match(s: &str, p: &str) -> Result<bool, EvalError> {
+    let re = regex::RegexBuilder::new(p)
+        .size_limit(REGEX_SIZE_LIMIT)
+        .build()
+        .map_err(|e| EvalError::InvalidRegex(p.to_string(), e.to_string()))?;
+    Ok(re.is_match(s))
+}
+
+#[cfg(not(feature = "std"))]
+fn is_match(_s: &str, _p: &str) -> Result<bool, EvalError> {
+    Err(EvalError::Unknown("matches?".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::env::Env;
+    use crate::eval::eval;
+    use crate::expr::{int, seq, str};
+    use crate::parser::parse;
+    use crate::EvalError;
+
+    fn env() -> Env {
+        let mut e = Env::new();
+        e.put("subject.hostname", str("web-1.prod"))
+            .put("subject.groups", seq([str("dev"), str("ops")]))
+            .put("resource.allowed_groups", seq([str("ops"), str("sec")]))
+            // 2023-10-01T09:30:00Z
+            .put("now", int(1696152600))
+            .put("subject.credential.issued_at", int(1696150800))
+            .put(
+                "resource.all_groups",
+                seq([str("dev"), str("ops"), str("sec")]),
+            );
+        e
+    }
+
+    fn run(s: &str) -> Result<bool, EvalError> {
+        let x = parse(s).unwrap().unwrap();
+        eval(&x, &env()).map(|r| r.is_true())
+    }
+
+    #[test]
+    fn comparisons() {
+        assert!(run("(<= 1 1 2)").unwrap());
+        assert!(!run("(<= 2 1)").unwrap());
+        assert!(run("(>= 3 3 1)").unwrap());
+        assert!(!run("(>= 1 2)").unwrap());
+        assert!(matches!(run("(<= 1)"), Err(EvalError::Malformed(_))));
+    }
+
+    #[test]
+    fn strings() {
+        assert!(run(r#"(starts-with? subject.hostname "web-")"#).unwrap());
+        assert!(run(r#"(ends-with? subject.hostname ".prod")"#).unwrap());
+        assert!(!run(r#"(ends-with? subject.hostname ".dev")"#).unwrap());
+        assert!(run(r#"(contains? subject.hostname "-1.")"#).unwrap());
+        assert!(run(r#"(matches? subject.hostname "^web-[0-9]+[.]prod$")"#).unwrap());
+        assert!(!run(r#"(matches? subject.hostname "^db-")"#).unwrap());
+        assert!(matches!(
+            run(r#"(matches? subject.hostname "(")"#),
+            Err(EvalError::InvalidRegex(..))
+        ));
+        assert!(matches!(
+            run("(starts-with? subject.hostname 1)"),
+            Err(EvalError::InvalidType(..))
+        ));
+    }
+
+    #[test]
+    fn sets() {
+        assert!(run("(intersects? subject.groups resource.allowed_groups)").unwrap());
+        assert!(!run(r#"(intersects? subject.groups ["sec"])"#).unwrap());
+        assert!(run("(subset? subject.groups resource.all_groups)").unwrap());
+        assert!(!run("(subset? subject.groups resource.allowed_groups)").unwrap());
+        assert!(run("(subset? [] resource.allowed_groups)").unwrap());
+        assert!(matches!(
+            run(r#"(subset? subject.hostname ["a"])"#),
+            Err(EvalError::InvalidType(..))
+        ));
+    }
+
+    #[test]
+    fn time() {
+        assert!(run("(< (- now subject.credential.issued_at) (hours 1))").unwrap());
+        assert!(!run("(< (- now subject.credential.issued_at) (minutes 10))").unwrap());
+        assert!(
+            run("(and (>= (time-of-day now) (hours 8)) (< (time-of-day now) (hours 18)))").unwrap()
+        );
+        assert!(run(r#"(= (timestamp "2023-10-01T09:30:00Z") now)"#).unwrap());
+        assert!(run(r#"(> now (+ (timestamp "2023-09-30T09:00:00Z") (days 1)))"#).unwrap());
+        assert!(matches!(
+            run(r#"(< now (timestamp "2023-13-01T00:00:00Z"))"#),
+            Err(EvalError::InvalidTimestamp(_))
+        ));
+        assert!(matches!(
+            run("(> (+ 9223372036854775807 1) 0)"),
+            Err(EvalError::Overflow)
+        ));
+        assert!(matches!(
+            run(r#"(- now "1")"#),
+            Err(EvalError::InvalidType(..))
+        ));
+    }
+}
@@ -10,6 +10,7 @@ mod env;
 mod error;
 mod eval;
 mod policy;
+mod trace;
 mod traits;
 mod types;
 
@@ -17,19 +18,25 @@ mod types;
 mod parser;
 
 pub mod attribute_access_control;
+pub mod audit;
 pub mod expr;
 pub mod mem;
 mod storage;
 
 pub use attribute_access_control::AbacAccessControl;
+pub use audit::{AuditSink, Decision, MemoryAuditSink};
 pub use env::Env;
 pub use error::{EvalError, ParseError};
-pub use eval::eval;
+pub use eval::{eval, eval_with_trace};
 pub use expr::Expr;
 pub use policy::PolicyAccessControl;
+pub use trace::{Trace, TraceStep};
 pub use traits::PolicyStorage;
 pub use types::{Action, Resource, Subject};
 
+#[cfg(feature = "std")]
+pub use audit::FileAuditSink;
+
 #[cfg(feature = "std")]
 pub use parser::parse;
 
@@ -14,10 +14,13 @@ use wast::lexer::Lexer;
 use wast::lexer::{FloatKind, TokenKind};
 
 /// Allowed identifier patterns.
+///
+/// Besides regular identifiers, `+` and `-` on their own are accepted as
+/// identifiers of the arithmetic operators.
 fn ident_pattern() -> &'static Regex {
     static INSTANCE: OnceBox<Regex> = OnceBox::new();
     INSTANCE.get_or_init(|| {
-        Box::new(Regex::new("^[a-zA-Z!$%&*/<=>?~_^][a-zA-Z0-9!$%&*/<=>?~_^.+-@]*$").unwrap())
+        Box::new(Regex::new("^([a-zA-Z!$%&*/<=>?~_^][a-zA-Z0-9!$%&*/<=>?~_^.+-@]*|[+-])$").unwrap())
     })
 }
 
@@ -1,3 +1,5 @@
+use crate::audit::{record, AuditSink, Decision};
+use crate::trace::Trace;
 use crate::traits::PolicyStorage;
 use crate::types::{Action, Resource};
 use crate::AbacAccessControl;
@@ -6,10 +8,12 @@ use core::fmt;
 use core::fmt::{Debug, Formatter};
 use ockam_core::compat::boxed::Box;
 use ockam_core::compat::format;
+use ockam_core::compat::string::ToString;
 use ockam_core::compat::sync::Arc;
 use ockam_core::{async_trait, RelayMessage};
 use ockam_core::{IncomingAccessControl, Result};
-use ockam_identity::IdentitiesRepository;
+use ockam_identity::utils::now;
+use ockam_identity::{IdentitiesRepository, IdentitySecureChannelLocalInfo};
 use tracing as log;
 
 /// Evaluates a policy expression against an environment of attributes.
@@ -22,6 +26,7 @@ pub struct PolicyAccessControl {
     policies: Arc<dyn PolicyStorage>,
     repository: Arc<dyn IdentitiesRepository>,
     environment: Env,
+    audit: Option<Arc<dyn AuditSink>>,
 }
 
 /// Debug implementation writing out the resource, action and initial environment
@@ -55,6 +60,34 @@ impl PolicyAccessControl {
             policies,
             repository,
             environment: env,
+            audit: None,
+        }
+    }
+
+    /// Record every decision made by this `PolicyAccessControl` in the given audit sink.
+    pub fn with_audit_sink(mut self, sink: Arc<dyn AuditSink>) -> Self {
+        self.audit = Some(sink);
+        self
+    }
+
+    /// Record a decision which was made without evaluating the policy.
+    async fn audit(&self, msg: &RelayMessage, policy: Option<Expr>, is_authorized: bool) {
+        if let Some(sink) = &self.audit {
+            let subject = IdentitySecureChannelLocalInfo::find_info(msg.local_message())
+                .ok()
+                .map(|info| info.their_identity_id());
+            let decision = Decision {
+                resource: Some(self.resource.clone()),
+                action: Some(self.action.clone()),
+                subject,
+                environment: self.environment.clone(),
+                error: policy.is_none().then(|| "no policy found".to_string()),
+                policy,
+                is_authorized,
+                trace: Trace::new(),
+                timestamp: now().ok(),
+            };
+            record(sink.as_ref(), &decision).await
         }
     }
 }
@@ -71,6 +104,7 @@ impl IncomingAccessControl for PolicyAccessControl {
             if let Expr::Bool(b) = expr {
                 // If the policy is a constant there is no need to populate
                 // the environment or look for message metadata.
+                self.audit(msg, Some(expr), b).await;
                 return Ok(b);
             } else {
                 expr
@@ -82,11 +116,16 @@ impl IncomingAccessControl for PolicyAccessControl {
                 action   = %self.action,
                 "no policy found; access denied"
             }
+            self.audit(msg, None, false).await;
             return Ok(false);
         };
 
-        AbacAccessControl::new(self.repository.clone(), expr, self.environment.clone())
-            .is_authorized(msg)
-            .await
+        let mut access_control =
+            AbacAccessControl::new(self.repository.clone(), expr, self.environment.clone())
+                .with_resource_and_action(self.resource.clone(), self.action.clone());
+        if let Some(sink) = &self.audit {
+            access_control = access_control.with_audit_sink(sink.clone())
+        }
+        access_control.is_authorized(msg).await
     }
 }
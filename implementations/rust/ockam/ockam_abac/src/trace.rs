@@ -0,0 +1,184 @@
+use crate::expr::Expr;
+use core::fmt;
+use ockam_core::compat::vec::Vec;
+
+/// A single step of a policy evaluation.
+#[derive(Debug, Clone)]
+pub struct TraceStep {
+    /// Nesting level of the evaluated expression (0 for the toplevel expression).
+    pub depth: usize,
+    /// The evaluated expression.
+    pub expr: Expr,
+    /// The value the expression evaluated to, or `None` if evaluation failed.
+    pub value: Option<Expr>,
+}
+
+/// The record of a policy evaluation.
+///
+/// Every operation and every identifier lookup performed during evaluation
+/// is recorded as a step, in evaluation order. Sub-expressions which were
+/// not evaluated because of short-circuiting (e.g. in `and`, `or` or `if`)
+/// do not appear in the trace.
+#[derive(Debug, Clone, Default)]
+pub struct Trace {
+    steps: Vec<TraceStep>,
+    depth: usize,
+}
+
+impl Trace {
+    pub fn new() -> Self {
+        Trace::default()
+    }
+
+    /// The evaluation steps, in evaluation order.
+    pub fn steps(&self) -> &[TraceStep] {
+        &self.steps
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Return the sub-expression which made the decision.
+    ///
+    /// Starting from the toplevel expression, logical operators (`and`, `or`,
+    /// `not` and `if`) are followed into their last evaluated argument, which
+    /// is the one that determined their value. The first step which is not a
+    /// logical operator is returned.
+    pub fn decisive(&self) -> Option<&TraceStep> {
+        let mut i = 0;
+        loop {
+            let step = self.steps.get(i)?;
+            if !is_logical(&step.expr) {
+                return Some(step);
+            }
+            match self.last_child(i) {
+                Some(c) => i = c,
+                None => return Some(step),
+            }
+        }
+    }
+
+    /// Record the start of the evaluation of an operation.
+    pub(crate) fn open(&mut self, expr: &Expr) -> usize {
+        self.steps.push(TraceStep {
+            depth: self.depth,
+            expr: expr.clone(),
+            value: None,
+        });
+        self.depth += 1;
+        self.steps.len() - 1
+    }
+
+    /// Record the value of an operation previously started with `open`.
+    pub(crate) fn close(&mut self, i: usize, value: &Expr) {
+        if let Some(step) = self.steps.get_mut(i) {
+            step.value = Some(value.clone())
+        }
+        self.depth = self.depth.saturating_sub(1)
+    }
+
+    /// Record an expression which was evaluated in a single step.
+    pub(crate) fn leaf(&mut self, expr: &Expr, value: &Expr) {
+        self.steps.push(TraceStep {
+            depth: self.depth,
+            expr: expr.clone(),
+            value: Some(value.clone()),
+        })
+    }
+
+    /// Index of the last step directly nested under step `i`.
+    fn last_child(&self, i: usize) -> Option<usize> {
+        let depth = self.steps[i].depth;
+        let mut child = None;
+        for (j, s) in self.steps.iter().enumerate().skip(i + 1) {
+            if s.depth <= depth {
+                break;
+            }
+            if s.depth == depth + 1 {
+                child = Some(j)
+            }
+        }
+        child
+    }
+}
+
+/// Return true if the expression is an application of a logical operator.
+fn is_logical(expr: &Expr) -> bool {
+    match expr {
+        Expr::List(xs) => {
+            matches!(xs.first(), Some(Expr::Ident(id)) if matches!(id.as_str(), "and" | "or" | "not" | "if"))
+        }
+        _ => false,
+    }
+}
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for s in &self.steps {
+            let indent = s.depth * 2;
+            match &s.value {
+                Some(v) => writeln!(f, "{:indent$}{} => {v}", "", s.expr)?,
+                None => writeln!(f, "{:indent$}{} => <error>", "", s.expr)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::env::Env;
+    use crate::eval::eval_with_trace;
+    use crate::expr::str;
+    use crate::parser::parse;
+    use crate::trace::Trace;
+
+    fn explain(s: &str) -> (bool, Trace) {
+        let mut env = Env::new();
+        env.put("subject.role", str("member"))
+            .put("subject.team", str("ops"));
+        let x = parse(s).unwrap().unwrap();
+        let mut trace = Trace::new();
+        let r = eval_with_trace(&x, &env, &mut trace).unwrap();
+        (r.is_true(), trace)
+    }
+
+    #[test]
+    fn decisive() {
+        let (b, t) = explain(r#"(and (= subject.team "ops") (= subject.role "admin") (= 1 1))"#);
+        assert!(!b);
+        assert_eq!(
+            r#"(= subject.role "admin")"#,
+            t.decisive().unwrap().expr.to_string()
+        );
+        // the last argument of 'and' is never evaluated
+        assert!(t.steps().iter().all(|s| s.expr.to_string() != "(= 1 1)"));
+
+        let (b, t) = explain(r#"(or (not (= subject.role "admin")) (= subject.team "ops"))"#);
+        assert!(b);
+        assert_eq!(
+            r#"(= subject.role "admin")"#,
+            t.decisive().unwrap().expr.to_string()
+        );
+
+        let (b, t) = explain(r#"(if (= subject.role "member") (= subject.team "dev") (= 1 1))"#);
+        assert!(!b);
+        assert_eq!(
+            r#"(= subject.team "dev")"#,
+            t.decisive().unwrap().expr.to_string()
+        );
+    }
+
+    #[test]
+    fn steps() {
+        let (_, t) = explain(r#"(= subject.team "ops")"#);
+        let steps = t.steps();
+        assert_eq!(2, steps.len());
+        assert_eq!(0, steps[0].depth);
+        assert!(steps[0].value.as_ref().unwrap().is_true());
+        assert_eq!(1, steps[1].depth);
+        assert_eq!("subject.team", steps[1].expr.to_string());
+        assert_eq!(r#""ops""#, steps[1].value.as_ref().unwrap().to_string());
+    }
+}
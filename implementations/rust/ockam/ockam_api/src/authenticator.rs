@@ -1,2 +1,3 @@
 pub mod direct;
 pub mod enrollment_tokens;
+pub mod revocation;
@@ -0,0 +1,7 @@
+pub mod types;
+
+mod client;
+mod service;
+
+pub use client::*;
+pub use service::*;
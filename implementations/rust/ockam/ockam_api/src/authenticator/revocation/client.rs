@@ -0,0 +1,30 @@
+use ockam::identity::models::RevocationListAndPurposeKey;
+use ockam::identity::Identifier;
+use ockam_core::api::Request;
+use ockam_core::Result;
+use ockam_node::RpcClient;
+
+use crate::authenticator::revocation::types::RevokeMembers;
+
+pub struct RevocationListClient(RpcClient);
+
+impl RevocationListClient {
+    pub fn new(client: RpcClient) -> Self {
+        RevocationListClient(client)
+    }
+
+    /// Issue a revocation list replacing the previous one of the authority
+    pub async fn revoke_members(
+        &self,
+        members: Vec<Identifier>,
+    ) -> Result<RevocationListAndPurposeKey> {
+        self.0
+            .request(&Request::post("/").body(RevokeMembers::new(members)))
+            .await
+    }
+
+    /// Return the latest revocation list of the authority
+    pub async fn revocation_list(&self) -> Result<RevocationListAndPurposeKey> {
+        self.0.request(&Request::get("/revocation_list")).await
+    }
+}
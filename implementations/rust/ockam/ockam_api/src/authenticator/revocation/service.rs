@@ -0,0 +1,98 @@
+use minicbor::Decoder;
+use ockam::identity::{secure_channel_required, Credentials};
+use ockam::identity::{Identifier, IdentitySecureChannelLocalInfo};
+use ockam_core::api::{Method, Request, Response};
+use ockam_core::compat::sync::Arc;
+use ockam_core::{Result, Routed, Worker};
+use ockam_node::Context;
+use tracing::trace;
+
+use crate::authenticator::revocation::types::RevokeMembers;
+
+/// Serve the latest revocation list of an authority and, when it is started for
+/// the enrollers, issue the new lists
+///
+/// The revocation list is requested by the nodes of the trust context on the
+/// `revocation_list` path, the same path as the credentials service of a node.
+pub struct RevocationListService {
+    credentials: Arc<Credentials>,
+    authority: Identifier,
+    can_issue: bool,
+}
+
+impl RevocationListService {
+    /// Create a service only serving the latest revocation list
+    pub fn serving(credentials: Arc<Credentials>, authority: Identifier) -> Self {
+        Self {
+            credentials,
+            authority,
+            can_issue: false,
+        }
+    }
+
+    /// Create a service also issuing new revocation lists
+    pub fn issuing(credentials: Arc<Credentials>, authority: Identifier) -> Self {
+        Self {
+            can_issue: true,
+            ..Self::serving(credentials, authority)
+        }
+    }
+}
+
+#[ockam_core::worker]
+impl Worker for RevocationListService {
+    type Context = Context;
+    type Message = Vec<u8>;
+
+    async fn handle_message(&mut self, c: &mut Context, m: Routed<Self::Message>) -> Result<()> {
+        if let Ok(i) = IdentitySecureChannelLocalInfo::find_info(m.local_message()) {
+            let from = i.their_identity_id();
+            let mut dec = Decoder::new(m.as_body());
+            let req: Request = dec.decode()?;
+            trace! {
+                target: "ockam_api::authenticator::revocation::revocation_list_service",
+                from   = %from,
+                id     = %req.id(),
+                method = ?req.method(),
+                path   = %req.path(),
+                body   = %req.has_body(),
+                "request"
+            }
+            let path_segments = req.path_segments::<5>();
+            let res = match (req.method(), path_segments.as_slice()) {
+                (Some(Method::Get), ["revocation_list"]) => {
+                    let revocation_list = self
+                        .credentials
+                        .revocation_lists_repository()
+                        .retrieve_revocation_list(&self.authority)
+                        .await?;
+                    match revocation_list {
+                        Some(revocation_list) => {
+                            Response::ok(req.id()).body(revocation_list).to_vec()?
+                        }
+                        None => Response::not_found(req.id()).to_vec()?,
+                    }
+                }
+                (Some(Method::Post), [""]) if self.can_issue => {
+                    let revoke: RevokeMembers = dec.decode()?;
+                    // The new list replaces the previous one, and is stored to be served
+                    let revocation_list = self
+                        .credentials
+                        .credentials_creation()
+                        .issue_revocation_list(
+                            &self.authority,
+                            revoke.members().to_vec(),
+                            vec![],
+                        )
+                        .await?;
+                    info!(enroller = %from, members = ?revoke.members(), "issued a revocation list");
+                    Response::ok(req.id()).body(revocation_list).to_vec()?
+                }
+                _ => ockam_core::api::unknown_path(&req).to_vec()?,
+            };
+            c.send(m.return_route(), res).await
+        } else {
+            secure_channel_required(c, m).await
+        }
+    }
+}
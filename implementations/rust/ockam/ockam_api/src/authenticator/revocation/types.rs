@@ -0,0 +1,28 @@
+use minicbor::{Decode, Encode};
+use ockam::identity::Identifier;
+
+#[cfg(feature = "tag")]
+use ockam_core::TypeTag;
+
+#[derive(Debug, Decode, Encode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct RevokeMembers {
+    #[cfg(feature = "tag")]
+    #[n(0)] tag: TypeTag<7293851>,
+    #[n(1)] members: Vec<Identifier>,
+}
+
+impl RevokeMembers {
+    pub fn new(members: Vec<Identifier>) -> Self {
+        RevokeMembers {
+            #[cfg(feature = "tag")]
+            tag: TypeTag,
+            members,
+        }
+    }
+
+    pub fn members(&self) -> &[Identifier] {
+        &self.members
+    }
+}
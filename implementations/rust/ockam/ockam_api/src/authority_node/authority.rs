@@ -2,7 +2,7 @@ use std::path::Path;
 
 use tracing::info;
 
-use ockam::identity::storage::LmdbStorage;
+use ockam::identity::storage::{LmdbStorage, Storage};
 use ockam::identity::Vault;
 use ockam::identity::{
     CredentialsIssuer, Identifier, Identities, IdentitiesRepository, IdentitiesStorage,
@@ -19,6 +19,7 @@ use ockam_node::{Context, WorkerBuilder};
 use ockam_transport_tcp::{TcpListenerOptions, TcpTransport};
 
 use crate::authenticator::enrollment_tokens::EnrollmentTokenAuthenticator;
+use crate::authenticator::revocation::RevocationListService;
 use crate::authority_node::authority::EnrollerCheck::{AnyMember, EnrollerOnly};
 use crate::authority_node::Configuration;
 use crate::bootstrapped_identities_store::BootstrapedIdentityStore;
@@ -32,6 +33,7 @@ use crate::{actions, DefaultAddress};
 //   - a credential issuer
 //   - an enrollment token issuer
 //   - an enrollment token acceptor
+//   - a revocation list issuer
 pub struct Authority {
     identifier: Identifier,
     secure_channels: Arc<SecureChannels>,
@@ -57,10 +59,12 @@ impl Authority {
     pub async fn create(configuration: &Configuration) -> Result<Authority> {
         debug!(?configuration, "creating the authority");
         let vault = Self::create_secure_channels_vault(configuration).await?;
-        let repository = Self::create_identities_repository(configuration).await?;
+        let storage = Self::create_storage(configuration).await?;
+        let repository = Self::create_identities_repository(storage.clone(), configuration);
         let secure_channels = SecureChannels::builder()
             .with_vault(vault)
             .with_identities_repository(repository)
+            .with_revocation_lists_storage(storage)
             .build();
 
         let identifier = configuration.identifier();
@@ -209,6 +213,35 @@ impl Authority {
         Ok(())
     }
 
+    /// Start the revocation list services: the members fetch the latest revocation list
+    /// and the enrollers issue new lists, so that the credentials of offboarded members
+    /// stop being accepted before they expire
+    pub async fn start_revocation_list_services(
+        &self,
+        ctx: &Context,
+        secure_channel_flow_control_id: &FlowControlId,
+        configuration: &Configuration,
+    ) -> Result<()> {
+        let credentials = self.identities().credentials();
+
+        let address = DefaultAddress::REVOCATION_LIST_SERVICE.to_string();
+        ctx.flow_controls()
+            .add_consumer(address.clone(), secure_channel_flow_control_id);
+        let service = RevocationListService::serving(credentials.clone(), self.identifier());
+        self.start(ctx, configuration, address.clone(), AnyMember, service)
+            .await?;
+        info!("started a revocation list service at '{address}'");
+
+        let address = DefaultAddress::REVOCATION_LIST_ISSUER.to_string();
+        ctx.flow_controls()
+            .add_consumer(address.clone(), secure_channel_flow_control_id);
+        let issuer = RevocationListService::issuing(credentials, self.identifier());
+        self.start(ctx, configuration, address.clone(), EnrollerOnly, issuer)
+            .await?;
+        info!("started a revocation list issuer at '{address}'");
+        Ok(())
+    }
+
     /// Start the Okta service to retrieve attributes authenticated by Okta
     pub async fn start_okta(
         &self,
@@ -278,15 +311,20 @@ impl Authority {
         Ok(vault)
     }
 
-    /// Create an authenticated storage backed by a Lmdb database
-    async fn create_identities_repository(
-        configuration: &Configuration,
-    ) -> Result<Arc<dyn IdentitiesRepository>> {
+    /// Create a storage backed by a Lmdb database, for the identities and the revocation lists
+    async fn create_storage(configuration: &Configuration) -> Result<Arc<dyn Storage>> {
         let storage_path = &configuration.storage_path;
         Self::create_ockam_directory_if_necessary(storage_path)?;
-        let storage = Arc::new(LmdbStorage::new(&storage_path).await?);
+        Ok(Arc::new(LmdbStorage::new(&storage_path).await?))
+    }
+
+    /// Create an authenticated storage
+    fn create_identities_repository(
+        storage: Arc<dyn Storage>,
+        configuration: &Configuration,
+    ) -> Arc<dyn IdentitiesRepository> {
         let repository = Arc::new(IdentitiesStorage::new(storage));
-        Ok(Self::bootstrap_repository(repository, configuration))
+        Self::bootstrap_repository(repository, configuration)
     }
 
     /// Create a directory to save storage files if they haven't been  created before
@@ -161,6 +161,10 @@ impl NodeState {
         Ok(LmdbStorage::new(self.paths.policies_storage()).await?)
     }
 
+    pub async fn revocation_lists_storage(&self) -> Result<LmdbStorage> {
+        Ok(LmdbStorage::new(self.paths.revocation_lists_storage()).await?)
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
@@ -309,6 +313,9 @@ pub struct NodeSetupConfig {
     pub authority_node: Option<bool>,
     pub project: Option<ProjectLookup>,
     pub api_transport: Option<CreateTransportJson>,
+    /// Address of the HTTP listener exporting the node metrics, kept to restart the node with it.
+    /// The field might be missing in previous configuration files
+    pub metrics_listen: Option<String>,
 }
 
 impl NodeSetupConfig {
@@ -332,6 +339,11 @@ impl NodeSetupConfig {
         self
     }
 
+    pub fn set_metrics_listen(mut self, metrics_listen: impl Into<String>) -> Self {
+        self.metrics_listen = Some(metrics_listen.into());
+        self
+    }
+
     pub fn api_transport(&self) -> Result<&CreateTransportJson> {
         self.api_transport.as_ref().ok_or_else(|| {
             CliStateError::InvalidOperation(
@@ -384,6 +396,10 @@ impl NodePaths {
     fn policies_storage(&self) -> PathBuf {
         self.path.join("policies_storage.lmdb")
     }
+
+    fn revocation_lists_storage(&self) -> PathBuf {
+        self.path.join("revocation_lists_storage.lmdb")
+    }
 }
 
 mod backwards_compatibility {
@@ -492,6 +508,7 @@ mod traits {
                         authority_node: setup.authority_node,
                         project: setup.project,
                         api_transport: None,
+                        metrics_listen: None,
                     };
                     if let Some(t) = setup
                         .transports
@@ -736,4 +753,17 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn node_setup_keeps_the_metrics_listener() {
+        // Configuration files written before the metrics listener was kept
+        let setup: NodeSetupConfig =
+            serde_json::from_str(r#"{"verbose":0,"project":null,"api_transport":null}"#).unwrap();
+        assert_eq!(setup.metrics_listen, None);
+
+        let setup = setup.set_metrics_listen("127.0.0.1:9090");
+        let setup: NodeSetupConfig =
+            serde_json::from_str(&serde_json::to_string(&setup).unwrap()).unwrap();
+        assert_eq!(setup.metrics_listen.as_deref(), Some("127.0.0.1:9090"));
+    }
 }
@@ -5,6 +5,8 @@ use std::sync::Arc;
 use serde::{Deserialize, Serialize};
 
 use ockam::identity::Vault;
+use ockam_core::env::get_env;
+use ockam_vault::storage::{PersistentStorage, StorageKey};
 use ockam_vault_aws::AwsSigningVault;
 
 use crate::cli_state::traits::StateItemTrait;
@@ -12,6 +14,22 @@ use crate::cli_state::{CliStateError, StateDirTrait, DATA_DIR_NAME};
 
 use super::Result;
 
+/// Passphrase used to encrypt the vaults storage
+pub const OCKAM_VAULT_PASSPHRASE: &str = "OCKAM_VAULT_PASSPHRASE";
+/// Path to a file containing the 32 bytes key used to encrypt the vaults storage
+pub const OCKAM_VAULT_KEY_FILE: &str = "OCKAM_VAULT_KEY_FILE";
+
+/// Return the key used to encrypt the vaults storage if it is set in the environment
+pub fn storage_key_from_env() -> Result<Option<StorageKey>> {
+    if let Some(passphrase) = get_env::<String>(OCKAM_VAULT_PASSPHRASE)? {
+        return Ok(Some(StorageKey::Passphrase(passphrase)));
+    }
+    if let Some(path) = get_env::<PathBuf>(OCKAM_VAULT_KEY_FILE)? {
+        return Ok(Some(StorageKey::from_key_file(&path)?));
+    }
+    Ok(None)
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct VaultsState {
     dir: PathBuf,
@@ -53,10 +71,7 @@ impl VaultState {
 
             Ok(vault)
         } else {
-            let vault =
-                Vault::create_with_persistent_storage_path(self.vault_file_path().as_path())
-                    .await?;
-            Ok(vault)
+            self.vault().await
         }
     }
 
@@ -73,10 +88,71 @@ impl VaultState {
 
     pub async fn vault(&self) -> Result<Vault> {
         let path = self.vault_file_path().clone();
-        let vault = Vault::create_with_persistent_storage_path(path.as_path()).await?;
+        let vault = if self.has_encrypted_storage().await? {
+            let key = storage_key_from_env()?.ok_or_else(|| Self::missing_key_error(&self.name))?;
+            Vault::create_with_encrypted_persistent_storage_path(path.as_path(), key).await?
+        } else {
+            Vault::create_with_persistent_storage_path(path.as_path()).await?
+        };
         Ok(vault)
     }
 
+    /// Encrypt the vault storage with a new key, or rotate the key if the storage is
+    /// already encrypted. The current key is read from the environment.
+    ///
+    /// The re-encrypted storage is written to a temporary file which then replaces the vault
+    /// file with a rename. The configuration is only updated after that: if this last step
+    /// fails, the vault file is still detected as encrypted when the vault is opened
+    pub async fn rotate_key(&self, new_key: StorageKey) -> Result<VaultState> {
+        if self.config.aws_kms {
+            return Err(CliStateError::InvalidOperation(format!(
+                "The vault {} is an AWS KMS vault, its keys are not stored locally",
+                self.name
+            )));
+        }
+        let current_key = if self.has_encrypted_storage().await? {
+            Some(storage_key_from_env()?.ok_or_else(|| Self::missing_key_error(&self.name))?)
+        } else {
+            None
+        };
+
+        let path = self.vault_file_path();
+        let temp_path = path.with_extension("json.rotate");
+        std::fs::copy(path, &temp_path)?;
+        let rotated = PersistentStorage::rotate_key(&temp_path, current_key, Some(new_key)).await;
+        let _ = std::fs::remove_file(temp_path.with_extension("rotate.lock"));
+        if let Err(e) = rotated {
+            let _ = std::fs::remove_file(&temp_path);
+            return Err(e.into());
+        }
+        std::fs::rename(&temp_path, path)?;
+
+        let config = VaultConfig {
+            encrypted: true,
+            ..self.config.clone()
+        };
+        VaultState::new(self.path.clone(), config)
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.config.encrypted
+    }
+
+    /// Return true if the configuration or the vault file itself says that the storage is
+    /// encrypted. They can only differ if a key rotation was interrupted
+    async fn has_encrypted_storage(&self) -> Result<bool> {
+        Ok(
+            self.config.encrypted
+                || PersistentStorage::is_encrypted(self.vault_file_path()).await?,
+        )
+    }
+
+    fn missing_key_error(name: &str) -> CliStateError {
+        CliStateError::InvalidOperation(format!(
+            "The vault {name} is encrypted. Set {OCKAM_VAULT_PASSPHRASE} or {OCKAM_VAULT_KEY_FILE} to open it"
+        ))
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
@@ -93,6 +169,9 @@ impl Display for VaultState {
                 false => "OCKAM",
             }
         )?;
+        if self.config.encrypted {
+            writeln!(f, "Encrypted: true")?;
+        }
         Ok(())
     }
 }
@@ -101,11 +180,24 @@ impl Display for VaultState {
 pub struct VaultConfig {
     #[serde(default)]
     aws_kms: bool,
+    #[serde(default)]
+    encrypted: bool,
 }
 
 impl VaultConfig {
     pub fn new(aws_kms: bool) -> Result<Self> {
-        Ok(Self { aws_kms })
+        Ok(Self {
+            aws_kms,
+            encrypted: false,
+        })
+    }
+
+    /// Configuration for a vault whose storage is encrypted with a key read from the environment
+    pub fn encrypted() -> Self {
+        Self {
+            aws_kms: false,
+            encrypted: true,
+        }
     }
 
     pub fn is_aws(&self) -> bool {
@@ -11,7 +11,7 @@ use ockam::identity::{
     SecureChannels, TrustContext,
 };
 use ockam_core::compat::sync::Arc;
-use ockam_core::{Result, Route};
+use ockam_core::{Address, Result, Route};
 use ockam_multiaddr::MultiAddr;
 use ockam_transport_tcp::TcpTransport;
 use serde::{Deserialize, Serialize};
@@ -280,11 +280,9 @@ impl CredentialRetrieverConfig {
             )),
             CredentialRetrieverConfig::FromCredentialIssuer(issuer_config) => {
                 let _ = tcp_transport.ok_or_else(|| ApiError::core("TCP Transport was not provided when credential retriever was defined as an issuer."))?;
-                let credential_issuer_info = RemoteCredentialsRetrieverInfo::new(
-                    issuer_config.resolve_identity().await?.identifier().clone(),
-                    issuer_config.resolve_route().await?,
-                    DefaultAddress::CREDENTIAL_ISSUER.into(),
-                );
+                let credential_issuer_info = issuer_config
+                    .remote_info(DefaultAddress::CREDENTIAL_ISSUER)
+                    .await?;
 
                 Ok(Arc::new(RemoteCredentialsRetriever::new(
                     secure_channels,
@@ -383,4 +381,16 @@ impl CredentialIssuerConfig {
             .import(None, &encoded)
             .await
     }
+
+    /// Return the information necessary to reach a service of the credential issuer
+    pub async fn remote_info(
+        &self,
+        service_address: impl Into<Address>,
+    ) -> Result<RemoteCredentialsRetrieverInfo> {
+        Ok(RemoteCredentialsRetrieverInfo::new(
+            self.resolve_identity().await?.identifier().clone(),
+            self.resolve_route().await?,
+            service_address.into(),
+        ))
+    }
 }
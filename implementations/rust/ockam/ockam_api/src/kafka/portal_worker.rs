@@ -10,7 +10,7 @@ use ockam_core::{
     LocalMessage, Route, Routed, TransportMessage, Worker,
 };
 use ockam_node::{Context, WorkerBuilder};
-use ockam_transport_tcp::{PortalMessage, MAX_PAYLOAD_SIZE};
+use ockam_transport_tcp::{PortalCapabilities, PortalMessage, MAX_PAYLOAD_SIZE};
 
 use crate::kafka::inlet_controller::KafkaInletController;
 use crate::kafka::length_delimited::{length_encode, KafkaMessageDecoder};
@@ -127,7 +127,9 @@ impl Worker for KafkaPortalWorker {
                     context.stop_worker(context.address()).await?;
                 }
             }
-            PortalMessage::Ping => self.forward(context, routed_message).await?,
+            PortalMessage::Ping | PortalMessage::ReadClosed => {
+                self.forward(context, routed_message).await?
+            }
 
             PortalMessage::Pong => {
                 match self.receiving {
@@ -157,6 +159,10 @@ impl Worker for KafkaPortalWorker {
                     }
                 }
             }
+            // kafka messages are split and merged by the interceptor, so the credits
+            // of one side don't match the payloads of the other one. Credits are not
+            // forwarded, and not advertised with the Ping and Pong messages either
+            PortalMessage::Credit(_) => {}
         }
 
         Ok(())
@@ -181,8 +187,28 @@ impl KafkaPortalWorker {
             routed_message.local_message().transport().onward_route,
             routed_message.local_message().transport().return_route
         );
+        let capabilities = match routed_message.as_body() {
+            PortalMessage::Ping | PortalMessage::Pong => Some(
+                routed_message
+                    .as_body()
+                    .decode_capabilities(routed_message.payload())
+                    .without(PortalCapabilities::CREDITS),
+            ),
+            _ => None,
+        };
+        let body = capabilities
+            .map(|capabilities| {
+                routed_message
+                    .as_body()
+                    .encode_with_capabilities(capabilities)
+            })
+            .transpose()?;
+
         let mut local_message = routed_message.into_local_message();
         let transport = local_message.transport_mut();
+        if let Some(body) = body {
+            transport.payload = body;
+        }
 
         if let Some(fixed_onward_route) = &self.fixed_onward_route {
             trace!(
@@ -136,6 +136,7 @@ pub mod error;
 pub mod hop;
 pub mod identity;
 pub mod kafka;
+pub mod metrics;
 pub mod minicbor_url;
 pub mod nodes;
 pub mod okta;
@@ -170,6 +171,8 @@ impl DefaultAddress {
     pub const CREDENTIAL_ISSUER: &'static str = "credential_issuer";
     pub const ENROLLMENT_TOKEN_ISSUER: &'static str = "enrollment_token_issuer";
     pub const ENROLLMENT_TOKEN_ACCEPTOR: &'static str = "enrollment_token_acceptor";
+    pub const REVOCATION_LIST_ISSUER: &'static str = "revocation_list_issuer";
+    pub const REVOCATION_LIST_SERVICE: &'static str = "revocation_list";
     pub const OKTA_IDENTITY_PROVIDER: &'static str = "okta";
     pub const KAFKA_OUTLET: &'static str = "kafka_outlet";
     pub const KAFKA_CONSUMER: &'static str = "kafka_consumer";
@@ -191,6 +194,8 @@ impl DefaultAddress {
                 | Self::CREDENTIAL_ISSUER
                 | Self::ENROLLMENT_TOKEN_ISSUER
                 | Self::ENROLLMENT_TOKEN_ACCEPTOR
+                | Self::REVOCATION_LIST_ISSUER
+                | Self::REVOCATION_LIST_SERVICE
                 | Self::OKTA_IDENTITY_PROVIDER
                 | Self::KAFKA_CONSUMER
                 | Self::KAFKA_PRODUCER
@@ -213,6 +218,8 @@ impl DefaultAddress {
             Self::CREDENTIAL_ISSUER,
             Self::ENROLLMENT_TOKEN_ISSUER,
             Self::ENROLLMENT_TOKEN_ACCEPTOR,
+            Self::REVOCATION_LIST_ISSUER,
+            Self::REVOCATION_LIST_SERVICE,
             Self::OKTA_IDENTITY_PROVIDER,
             Self::KAFKA_CONSUMER,
             Self::KAFKA_PRODUCER,
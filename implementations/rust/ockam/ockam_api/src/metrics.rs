@@ -0,0 +1,77 @@
+use std::net::SocketAddr;
+use std::str::FromStr;
+
+use tiny_http::{Header, Response, Server};
+use tracing::{info, warn};
+
+use crate::error::ApiError;
+use ockam_core::Result;
+
+/// Path on which the metrics are served
+pub const METRICS_PATH: &str = "/metrics";
+
+/// Start an HTTP server exporting the metrics of this process on `/metrics`,
+/// in the Prometheus text format
+///
+/// The server runs on a background thread for the lifetime of the process.
+/// The address it is bound to is returned, so that a port `0` can be used.
+pub fn start_metrics_exporter(listen_address: &str) -> Result<SocketAddr> {
+    let server = Server::http(listen_address).map_err(|e| {
+        ApiError::core(format!(
+            "failed to start the metrics exporter on {listen_address}: {e}"
+        ))
+    })?;
+    let address = server.server_addr().to_ip().ok_or_else(|| {
+        ApiError::core(format!(
+            "the metrics exporter is not listening on an IP address: {listen_address}"
+        ))
+    })?;
+    info!("metrics are exported at http://{address}{METRICS_PATH}");
+
+    std::thread::Builder::new()
+        .name("metrics-exporter".to_string())
+        .spawn(move || {
+            for request in server.incoming_requests() {
+                let response = if request.url() == METRICS_PATH {
+                    Response::from_string(ockam_node::metrics::registry().encode()).with_header(
+                        Header::from_str("Content-Type: text/plain; version=0.0.4").unwrap(),
+                    )
+                } else {
+                    Response::from_string("not found").with_status_code(404)
+                };
+                if let Err(e) = request.respond(response) {
+                    warn!("failed to send the metrics: {e}");
+                }
+            }
+        })
+        .map_err(|e| ApiError::core(format!("failed to start the metrics exporter: {e}")))?;
+
+    Ok(address)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+
+    #[test]
+    fn metrics_are_served() -> Result<()> {
+        ockam_node::metrics::registry()
+            .counter("ockam_api_test_total", "A test counter", vec![])
+            .inc();
+        let address = start_metrics_exporter("127.0.0.1:0")?;
+
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream
+            .write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200"));
+        assert!(response.contains("# TYPE ockam_api_test_total counter"));
+        assert!(response.contains("ockam_api_test_total 1"));
+        Ok(())
+    }
+}
@@ -1,4 +1,8 @@
 mod plain_tcp;
+mod plain_udp;
+#[cfg(unix)]
+mod plain_uds;
+mod plain_ws;
 mod project;
 mod secure;
 
@@ -10,10 +14,16 @@ use ockam_multiaddr::proto::Service;
 use ockam_multiaddr::{Match, MultiAddr, Protocol};
 use ockam_node::Context;
 use ockam_transport_tcp::TcpConnection;
-use std::fmt::{Debug, Formatter};
+
+use crate::udp_peer_address;
+use std::fmt::{Debug, Display, Formatter};
 use std::time::Duration;
 
 pub(crate) use plain_tcp::PlainTcpInstantiator;
+pub(crate) use plain_udp::PlainUdpInstantiator;
+#[cfg(unix)]
+pub(crate) use plain_uds::PlainUdsInstantiator;
+pub(crate) use plain_ws::PlainWebSocketInstantiator;
 pub(crate) use project::ProjectInstantiator;
 pub(crate) use secure::SecureChannelInstantiator;
 
@@ -65,6 +75,29 @@ impl<'a> Connection<'a> {
     }
 }
 
+/// A transport connection created when instantiating a [`MultiAddr`], which must
+/// be disconnected when the connection is closed or replaced
+#[derive(Clone, Debug)]
+pub enum TransportConnection {
+    Tcp(TcpConnection),
+    /// The address of the sender worker of a WebSocket connection
+    WebSocket(Address),
+    /// The path of a Unix domain socket connection
+    #[cfg(unix)]
+    Uds(String),
+}
+
+impl Display for TransportConnection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TransportConnection::Tcp(connection) => write!(f, "{connection}"),
+            TransportConnection::WebSocket(address) => write!(f, "websocket {address}"),
+            #[cfg(unix)]
+            TransportConnection::Uds(path) => write!(f, "uds {path}"),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct ConnectionInstance {
     /// Transport route consists of only transport addresses,
@@ -79,8 +112,8 @@ pub struct ConnectionInstance {
     /// A list of secure channel encryptors created for the connection.
     /// Needed to cleanup the connection resources when it must be closed.
     pub secure_channel_encryptors: Vec<Address>,
-    /// A TCP worker address if used when instantiating the connection
-    pub tcp_connection: Option<TcpConnection>,
+    /// The transport connection if one was created when instantiating the connection
+    pub transport_connection: Option<TransportConnection>,
     /// If a flow control was created
     pub flow_control_id: Option<FlowControlId>,
 }
@@ -120,7 +153,7 @@ pub struct ConnectionInstanceBuilder {
     pub transport_route: Route,
     pub flow_control_id: Option<FlowControlId>,
     pub secure_channel_encryptors: Vec<Address>,
-    pub tcp_connection: Option<TcpConnection>,
+    pub transport_connection: Option<TransportConnection>,
 }
 
 impl Debug for ConnectionInstanceBuilder {
@@ -148,8 +181,8 @@ pub struct Changes {
     /// Optional, to keep track of resources used add every time
     /// a new secure channel encryptor is created
     pub secure_channel_encryptors: Vec<Address>,
-    /// Optional, to keep track of tcp worker when created for the connection
-    pub tcp_connection: Option<TcpConnection>,
+    /// Optional, to keep track of the transport connection when created for the connection
+    pub transport_connection: Option<TransportConnection>,
 }
 
 /// Takes in a [`MultiAddr`] and instantiate it, can be implemented for any protocol.
@@ -181,7 +214,7 @@ impl ConnectionInstanceBuilder {
             current_multiaddr: multi_addr,
             secure_channel_encryptors: vec![],
             flow_control_id: None,
-            tcp_connection: None,
+            transport_connection: None,
         }
     }
 
@@ -191,7 +224,7 @@ impl ConnectionInstanceBuilder {
             normalized_addr: self.current_multiaddr,
             original_addr: self.original_multiaddr,
             secure_channel_encryptors: self.secure_channel_encryptors,
-            tcp_connection: self.tcp_connection,
+            transport_connection: self.transport_connection,
             flow_control_id: self.flow_control_id,
         }
     }
@@ -223,15 +256,15 @@ impl ConnectionInstanceBuilder {
                     self.secure_channel_encryptors
                         .append(&mut changes.secure_channel_encryptors);
 
-                    if changes.tcp_connection.is_some() {
-                        if self.tcp_connection.is_some() {
+                    if changes.transport_connection.is_some() {
+                        if self.transport_connection.is_some() {
                             return Err(ockam_core::Error::new(
                                 Origin::Transport,
                                 Kind::Unsupported,
-                                "multiple tcp connections created in a `MultiAddr`",
+                                "multiple transport connections created in a `MultiAddr`",
                             ));
                         }
-                        self.tcp_connection = changes.tcp_connection;
+                        self.transport_connection = changes.transport_connection;
                     }
 
                     if changes.flow_control_id.is_some() {
@@ -251,7 +284,7 @@ impl ConnectionInstanceBuilder {
             secure_channel_encryptors: self.secure_channel_encryptors,
             current_multiaddr: self.current_multiaddr,
             flow_control_id: self.flow_control_id,
-            tcp_connection: self.tcp_connection,
+            transport_connection: self.transport_connection,
         })
     }
 
@@ -266,6 +299,15 @@ impl ConnectionInstanceBuilder {
         let mut route = Route::new();
         let mut peekable = current_before.iter().peekable();
         while let Some(protocol) = peekable.next() {
+            // udp addresses are kept as transport addresses, see `PlainUdpInstantiator`
+            if let Some(address) = peekable
+                .peek()
+                .and_then(|port| udp_peer_address(&protocol, port))
+            {
+                let _ = peekable.next();
+                route = route.append(address);
+                continue;
+            }
             if protocol.code() == Service::CODE {
                 if let Some(service) = protocol.cast::<Service>() {
                     let address = Address::new(LOCAL, &*service);
@@ -1,5 +1,7 @@
 use crate::error::ApiError;
-use crate::nodes::connection::{Changes, ConnectionInstanceBuilder, Instantiator};
+use crate::nodes::connection::{
+    Changes, ConnectionInstanceBuilder, Instantiator, TransportConnection,
+};
 use crate::{multiaddr_to_route, route_to_multiaddr};
 
 use ockam_core::{async_trait, Error};
@@ -64,7 +66,7 @@ impl Instantiator for PlainTcpInstantiator {
             current_multiaddr,
             flow_control_id: tcp.flow_control_id,
             secure_channel_encryptors: vec![],
-            tcp_connection: Some(tcp_connection),
+            transport_connection: Some(TransportConnection::Tcp(tcp_connection)),
         })
     }
 }
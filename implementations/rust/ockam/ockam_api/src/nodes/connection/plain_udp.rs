@@ -0,0 +1,59 @@
+use crate::nodes::connection::{Changes, ConnectionInstanceBuilder, Instantiator};
+use crate::nodes::NodeManager;
+
+use ockam::compat::tokio::sync::RwLock;
+use ockam_core::{async_trait, Error};
+use ockam_multiaddr::proto::{DnsAddr, Ip4, Ip6, Udp};
+use ockam_multiaddr::{Match, Protocol};
+use ockam_node::Context;
+
+use std::sync::Arc;
+
+/// Makes sure that the udp transport exists.
+///
+/// UDP has no connections: the udp address is kept in the [`MultiAddr`](ockam_multiaddr::MultiAddr)
+/// and is translated to a `(UDP, "host:port")` address, which is routed by the udp transport.
+/// The replies can reach the workers which sent messages to that address.
+pub(crate) struct PlainUdpInstantiator {
+    context: Arc<Context>,
+    node_manager: Arc<RwLock<NodeManager>>,
+}
+
+impl PlainUdpInstantiator {
+    pub(crate) fn new(context: Arc<Context>, node_manager: Arc<RwLock<NodeManager>>) -> Self {
+        Self {
+            context,
+            node_manager,
+        }
+    }
+}
+
+#[async_trait]
+impl Instantiator for PlainUdpInstantiator {
+    fn matches(&self) -> Vec<Match> {
+        vec![
+            // matches any udp address followed by a udp protocol
+            Match::any([DnsAddr::CODE, Ip4::CODE, Ip6::CODE]),
+            Udp::CODE.into(),
+        ]
+    }
+
+    async fn instantiate(
+        &self,
+        builder: &ConnectionInstanceBuilder,
+        _match_start: usize,
+    ) -> Result<Changes, Error> {
+        self.node_manager
+            .write()
+            .await
+            .udp_transport(&self.context)
+            .await?;
+
+        Ok(Changes {
+            current_multiaddr: builder.current_multiaddr.clone(),
+            flow_control_id: None,
+            secure_channel_encryptors: vec![],
+            transport_connection: None,
+        })
+    }
+}
@@ -0,0 +1,74 @@
+use crate::error::ApiError;
+use crate::nodes::connection::{
+    Changes, ConnectionInstanceBuilder, Instantiator, TransportConnection,
+};
+use crate::nodes::NodeManager;
+use crate::try_address_to_multiaddr;
+
+use ockam::compat::tokio::sync::RwLock;
+use ockam_core::{async_trait, Error};
+use ockam_multiaddr::proto::Unix;
+use ockam_multiaddr::{Match, Protocol};
+use ockam_node::Context;
+use ockam_transport_uds::UdsConnectionOptions;
+
+use std::sync::Arc;
+
+/// Creates the unix domain socket connection.
+pub(crate) struct PlainUdsInstantiator {
+    context: Arc<Context>,
+    node_manager: Arc<RwLock<NodeManager>>,
+}
+
+impl PlainUdsInstantiator {
+    pub(crate) fn new(context: Arc<Context>, node_manager: Arc<RwLock<NodeManager>>) -> Self {
+        Self {
+            context,
+            node_manager,
+        }
+    }
+}
+
+#[async_trait]
+impl Instantiator for PlainUdsInstantiator {
+    fn matches(&self) -> Vec<Match> {
+        vec![Unix::CODE.into()]
+    }
+
+    async fn instantiate(
+        &self,
+        builder: &ConnectionInstanceBuilder,
+        match_start: usize,
+    ) -> Result<Changes, Error> {
+        let (before, unix_piece, after) =
+            ConnectionInstanceBuilder::extract(&builder.current_multiaddr, match_start, 1);
+
+        let path = unix_piece
+            .first()
+            .and_then(|p| p.cast::<Unix>().map(|path| path.to_string()))
+            .ok_or_else(|| {
+                ApiError::core(format!("invalid unix path in multiaddr: {unix_piece}"))
+            })?;
+
+        let options = UdsConnectionOptions::new();
+        let flow_control_id = options.flow_control_id();
+        let sender_address = self
+            .node_manager
+            .write()
+            .await
+            .uds_transport(&self.context)
+            .await?
+            .connect(&path, options)
+            .await?;
+
+        let multiaddr = try_address_to_multiaddr(&sender_address)?;
+        let current_multiaddr = ConnectionInstanceBuilder::combine(before, multiaddr, after)?;
+
+        Ok(Changes {
+            current_multiaddr,
+            flow_control_id: Some(flow_control_id),
+            secure_channel_encryptors: vec![],
+            transport_connection: Some(TransportConnection::Uds(path)),
+        })
+    }
+}
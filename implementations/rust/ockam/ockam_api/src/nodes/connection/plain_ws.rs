@@ -0,0 +1,82 @@
+use crate::error::ApiError;
+use crate::nodes::connection::{
+    Changes, ConnectionInstanceBuilder, Instantiator, TransportConnection,
+};
+use crate::nodes::NodeManager;
+use crate::try_address_to_multiaddr;
+
+use ockam::compat::tokio::sync::RwLock;
+use ockam_core::{async_trait, Error};
+use ockam_multiaddr::proto::{DnsAddr, Ip4, Ip6, Tcp, Ws, Wss};
+use ockam_multiaddr::{Match, Protocol};
+use ockam_node::Context;
+use ockam_transport_websocket::WebSocketConnectionOptions;
+
+use std::sync::Arc;
+
+/// Creates the websocket connection, with TLS for a `/wss` address.
+pub(crate) struct PlainWebSocketInstantiator {
+    context: Arc<Context>,
+    node_manager: Arc<RwLock<NodeManager>>,
+}
+
+impl PlainWebSocketInstantiator {
+    pub(crate) fn new(context: Arc<Context>, node_manager: Arc<RwLock<NodeManager>>) -> Self {
+        Self {
+            context,
+            node_manager,
+        }
+    }
+}
+
+#[async_trait]
+impl Instantiator for PlainWebSocketInstantiator {
+    fn matches(&self) -> Vec<Match> {
+        vec![
+            // matches any tcp address followed by a tcp protocol and a websocket protocol
+            Match::any([DnsAddr::CODE, Ip4::CODE, Ip6::CODE]),
+            Tcp::CODE.into(),
+            Match::any([Ws::CODE, Wss::CODE]),
+        ]
+    }
+
+    async fn instantiate(
+        &self,
+        builder: &ConnectionInstanceBuilder,
+        match_start: usize,
+    ) -> Result<Changes, Error> {
+        let (before, ws_piece, after) =
+            ConnectionInstanceBuilder::extract(&builder.current_multiaddr, match_start, 3);
+
+        let peer = ws_piece.to_socket_addr().map_err(|e| {
+            ApiError::core(format!(
+                "Couldn't convert MultiAddr to socket address: ws_piece={ws_piece}, {e}"
+            ))
+        })?;
+        let scheme = match ws_piece.last() {
+            Some(p) if p.code() == Wss::CODE => "wss",
+            _ => "ws",
+        };
+
+        let options = WebSocketConnectionOptions::new();
+        let flow_control_id = options.flow_control_id();
+        let sender_address = self
+            .node_manager
+            .write()
+            .await
+            .ws_transport(&self.context)
+            .await?
+            .connect(format!("{scheme}://{peer}"), options)
+            .await?;
+
+        let multiaddr = try_address_to_multiaddr(&sender_address)?;
+        let current_multiaddr = ConnectionInstanceBuilder::combine(before, multiaddr, after)?;
+
+        Ok(Changes {
+            current_multiaddr,
+            flow_control_id: Some(flow_control_id),
+            secure_channel_encryptors: vec![],
+            transport_connection: Some(TransportConnection::WebSocket(sender_address)),
+        })
+    }
+}
@@ -1,5 +1,7 @@
 use crate::error::ApiError;
-use crate::nodes::connection::{Changes, ConnectionInstanceBuilder, Instantiator};
+use crate::nodes::connection::{
+    Changes, ConnectionInstanceBuilder, Instantiator, TransportConnection,
+};
 use crate::nodes::models::secure_channel::CredentialExchangeMode;
 use crate::nodes::NodeManager;
 use crate::{multiaddr_to_route, try_address_to_multiaddr};
@@ -98,7 +100,7 @@ impl Instantiator for ProjectInstantiator {
             flow_control_id: Some(sc.flow_control_id().clone()),
             current_multiaddr,
             secure_channel_encryptors: vec![sc.encryptor_address().clone()],
-            tcp_connection: tcp.tcp_connection,
+            transport_connection: tcp.tcp_connection.map(TransportConnection::Tcp),
         })
     }
 }
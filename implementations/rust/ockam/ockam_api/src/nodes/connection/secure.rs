@@ -85,7 +85,7 @@ impl Instantiator for SecureChannelInstantiator {
             current_multiaddr,
             flow_control_id: Some(sc.flow_control_id().clone()),
             secure_channel_encryptors: vec![sc.encryptor_address().clone()],
-            tcp_connection: None,
+            transport_connection: None,
         })
     }
 }
@@ -11,6 +11,7 @@ use ockam_core::compat::borrow::Cow;
 use ockam_core::TypeTag;
 use ockam_core::{Address, CowStr, Route};
 use ockam_multiaddr::MultiAddr;
+use ockam_transport_tcp::TcpPortalStats;
 use serde::{Deserialize, Serialize};
 
 use crate::error::ApiError;
@@ -43,6 +44,8 @@ pub struct CreateInlet<'a> {
     #[n(6)] suffix_route: Route,
     /// The maximum duration to wait for an outlet to be available
     #[n(7)] wait_for_outlet_duration: Option<Duration>,
+    /// Limits of the connections accepted by the inlet
+    #[n(8)] limits: Option<PortalLimits>,
 }
 
 impl<'a> CreateInlet<'a> {
@@ -62,6 +65,7 @@ impl<'a> CreateInlet<'a> {
             prefix_route,
             suffix_route,
             wait_for_outlet_duration: None,
+            limits: None,
         }
     }
 
@@ -82,6 +86,7 @@ impl<'a> CreateInlet<'a> {
             prefix_route,
             suffix_route,
             wait_for_outlet_duration: None,
+            limits: None,
         }
     }
 
@@ -93,6 +98,10 @@ impl<'a> CreateInlet<'a> {
         self.wait_for_outlet_duration = Some(Duration::from_millis(ms))
     }
 
+    pub fn set_limits(&mut self, limits: PortalLimits) {
+        self.limits = Some(limits)
+    }
+
     pub fn listen_addr(&self) -> String {
         self.listen_addr.clone()
     }
@@ -120,6 +129,41 @@ impl<'a> CreateInlet<'a> {
     pub fn wait_for_outlet_duration(&self) -> Option<Duration> {
         self.wait_for_outlet_duration
     }
+
+    pub fn limits(&self) -> PortalLimits {
+        self.limits.clone().unwrap_or_default()
+    }
+}
+
+/// Limits of the connections of a portal
+#[derive(Clone, Debug, Default, Decode, Encode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct PortalLimits {
+    #[cfg(feature = "tag")]
+    #[n(0)] tag: TypeTag<3392841>,
+    /// Maximum number of concurrent connections
+    #[n(1)] pub max_connections: Option<u64>,
+    /// Maximum number of new connections per second
+    #[n(2)] pub max_connections_per_second: Option<u32>,
+    /// Maximum number of bytes per second, for all the connections
+    #[n(3)] pub max_bytes_per_second: Option<u64>,
+}
+
+impl PortalLimits {
+    pub fn new(
+        max_connections: Option<u64>,
+        max_connections_per_second: Option<u32>,
+        max_bytes_per_second: Option<u64>,
+    ) -> Self {
+        Self {
+            #[cfg(feature = "tag")]
+            tag: TypeTag,
+            max_connections,
+            max_connections_per_second,
+            max_bytes_per_second,
+        }
+    }
 }
 
 /// Request body to create an outlet
@@ -138,6 +182,14 @@ pub struct CreateOutlet {
     /// Allow the outlet to be reachable from the default secure channel, useful when we want to
     /// tighten the flow control
     #[n(4)] pub reachable_from_default_secure_channel: bool,
+    /// Additional `host:port` targets, new connections are balanced between all the targets
+    #[n(5)] pub targets: Option<Vec<String>>,
+    /// Load balancing strategy between the targets: "round-robin" or "least-connections"
+    #[n(6)] pub load_balancing: Option<String>,
+    /// Interval between the health checks of the targets. No health checks if None
+    #[n(7)] pub health_check_interval: Option<Duration>,
+    /// Interval between two resolutions of the targets hostnames. Only resolved once if None
+    #[n(8)] pub dns_refresh_interval: Option<Duration>,
 }
 
 impl CreateOutlet {
@@ -154,8 +206,28 @@ impl CreateOutlet {
             worker_addr,
             alias: alias.into(),
             reachable_from_default_secure_channel,
+            targets: None,
+            load_balancing: None,
+            health_check_interval: None,
+            dns_refresh_interval: None,
         }
     }
+
+    pub fn set_targets(&mut self, targets: Vec<String>) {
+        self.targets = Some(targets)
+    }
+
+    pub fn set_load_balancing(&mut self, load_balancing: impl Into<String>) {
+        self.load_balancing = Some(load_balancing.into())
+    }
+
+    pub fn set_health_check_interval(&mut self, interval: Duration) {
+        self.health_check_interval = Some(interval)
+    }
+
+    pub fn set_dns_refresh_interval(&mut self, interval: Duration) {
+        self.dns_refresh_interval = Some(interval)
+    }
 }
 
 /// Response body when interacting with a portal endpoint
@@ -172,6 +244,9 @@ pub struct InletStatus {
     /// An optional status payload
     #[n(4)] pub payload: Option<String>,
     #[n(5)] pub outlet_route: String,
+    /// Counters of the inlet connections
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[n(6)] pub stats: Option<PortalStats>,
 }
 
 impl InletStatus {
@@ -184,6 +259,7 @@ impl InletStatus {
             alias: "".into(),
             payload: Some(reason.into()),
             outlet_route: "".into(),
+            stats: None,
         }
     }
 
@@ -202,8 +278,72 @@ impl InletStatus {
             alias: alias.into(),
             payload: payload.into(),
             outlet_route: outlet_route.into(),
+            stats: None,
         }
     }
+
+    pub fn with_stats(mut self, stats: PortalStats) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+}
+
+/// Counters of the connections of a portal
+#[derive(Clone, Debug, Decode, Encode, Serialize, Deserialize)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct PortalStats {
+    #[cfg(feature = "tag")]
+    #[serde(skip)]
+    #[n(0)] tag: TypeTag<7740532>,
+    /// Bytes read from the TCP connections
+    #[n(1)] pub bytes_in: u64,
+    /// Bytes written to the TCP connections
+    #[n(2)] pub bytes_out: u64,
+    #[n(3)] pub connections_opened: u64,
+    #[n(4)] pub connections_closed: u64,
+    /// Connections rejected because of the portal limits
+    #[n(5)] pub connections_rejected: u64,
+    /// Counters of the active connections
+    #[n(6)] pub connections: Vec<PortalConnectionStats>,
+}
+
+impl From<TcpPortalStats> for PortalStats {
+    fn from(stats: TcpPortalStats) -> Self {
+        Self {
+            #[cfg(feature = "tag")]
+            tag: TypeTag,
+            bytes_in: stats.bytes_in(),
+            bytes_out: stats.bytes_out(),
+            connections_opened: stats.connections_opened(),
+            connections_closed: stats.connections_closed(),
+            connections_rejected: stats.connections_rejected(),
+            connections: stats
+                .connections()
+                .iter()
+                .map(|c| PortalConnectionStats {
+                    #[cfg(feature = "tag")]
+                    tag: TypeTag,
+                    peer: c.peer(),
+                    bytes_in: c.bytes_in(),
+                    bytes_out: c.bytes_out(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Counters of one connection of a portal
+#[derive(Clone, Debug, Decode, Encode, Serialize, Deserialize)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct PortalConnectionStats {
+    #[cfg(feature = "tag")]
+    #[serde(skip)]
+    #[n(0)] tag: TypeTag<2158906>,
+    #[n(1)] pub peer: SocketAddr,
+    #[n(2)] pub bytes_in: u64,
+    #[n(3)] pub bytes_out: u64,
 }
 
 /// Response body when interacting with a portal endpoint
@@ -219,6 +359,9 @@ pub struct OutletStatus {
     #[n(3)] pub alias: String,
     /// An optional status payload
     #[n(4)] pub payload: Option<String>,
+    /// Status of the outlet targets
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[n(5)] pub targets: Option<Vec<OutletTargetStatus>>,
 }
 
 impl OutletStatus {
@@ -230,6 +373,7 @@ impl OutletStatus {
             worker_addr: "".into(),
             alias: "".into(),
             payload: Some(reason.into()),
+            targets: None,
         }
     }
 
@@ -246,9 +390,15 @@ impl OutletStatus {
             worker_addr,
             alias: alias.into(),
             payload: payload.into(),
+            targets: None,
         }
     }
 
+    pub fn with_targets(mut self, targets: Vec<OutletTargetStatus>) -> Self {
+        self.targets = Some(targets);
+        self
+    }
+
     pub fn worker_address(&self) -> Result<MultiAddr, ockam_core::Error> {
         route_to_multiaddr(&route![self.worker_addr.to_string()])
             .ok_or_else(|| ApiError::core("Invalid Worker Address"))
@@ -263,6 +413,33 @@ impl OutletStatus {
     }
 }
 
+/// Status of one of the targets of an outlet
+#[derive(Clone, Debug, Decode, Encode, Serialize, Deserialize)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct OutletTargetStatus {
+    #[cfg(feature = "tag")]
+    #[serde(skip)]
+    #[n(0)] tag: TypeTag<6270389>,
+    #[n(1)] pub socket_addr: SocketAddr,
+    /// False if the target was ejected after failing its health checks
+    #[n(2)] pub healthy: bool,
+    /// Number of active connections to the target
+    #[n(3)] pub connections: u64,
+}
+
+impl OutletTargetStatus {
+    pub fn new(socket_addr: SocketAddr, healthy: bool, connections: u64) -> Self {
+        Self {
+            #[cfg(feature = "tag")]
+            tag: TypeTag,
+            socket_addr,
+            healthy,
+            connections,
+        }
+    }
+}
+
 /// Response body when returning a list of Inlets
 #[derive(Debug, Clone, Decode, Encode)]
 #[rustfmt::skip]
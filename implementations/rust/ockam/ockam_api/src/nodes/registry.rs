@@ -203,4 +203,6 @@ pub(crate) struct Registry {
     pub(crate) forwarders: BTreeMap<String, RemoteForwarderInfo>,
     pub(crate) inlets: BTreeMap<Alias, InletInfo>,
     pub(crate) outlets: BTreeMap<Alias, OutletInfo>,
+    pub(crate) udp_inlets: BTreeMap<Alias, InletInfo>,
+    pub(crate) udp_outlets: BTreeMap<Alias, OutletInfo>,
 }
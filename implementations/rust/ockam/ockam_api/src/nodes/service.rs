@@ -4,6 +4,7 @@ use std::collections::BTreeMap;
 use std::error::Error as _;
 use std::net::SocketAddr;
 use std::path::PathBuf;
+use std::time::Duration;
 
 use minicbor::{Decoder, Encode};
 
@@ -12,14 +13,14 @@ use ockam::identity::Vault;
 use ockam::identity::{
     Credentials, CredentialsServer, Identities, IdentitiesRepository, IdentityAttributesReader,
 };
-use ockam::identity::{CredentialsServerModule, TrustContext};
+use ockam::identity::{CredentialsServerModule, RevocationListRefresher, TrustContext};
 use ockam::identity::{Identifier, SecureChannels};
 use ockam::{
     Address, Context, ForwardingService, ForwardingServiceOptions, Result, Routed, TcpTransport,
     Worker,
 };
 use ockam_abac::expr::{eq, ident, str};
-use ockam_abac::{Action, Env, Expr, PolicyAccessControl, PolicyStorage, Resource};
+use ockam_abac::{Action, AuditSink, Env, Expr, PolicyAccessControl, PolicyStorage, Resource};
 use ockam_core::api::{Error, Method, Request, Response, ResponseBuilder, Status};
 use ockam_core::compat::{string::String, sync::Arc};
 use ockam_core::flow_control::FlowControlId;
@@ -27,16 +28,23 @@ use ockam_core::IncomingAccessControl;
 use ockam_core::{AllowAll, AsyncTryClone};
 use ockam_multiaddr::MultiAddr;
 use ockam_node::compat::asynchronous::RwLock;
+use ockam_transport_udp::UdpTransport;
+#[cfg(unix)]
+use ockam_transport_uds::UdsTransport;
+use ockam_transport_websocket::WebSocketTransport;
 
 use crate::bootstrapped_identities_store::BootstrapedIdentityStore;
 use crate::bootstrapped_identities_store::PreTrustedIdentities;
 use crate::cli_state::{CliState, StateDirTrait, StateItemTrait};
-use crate::config::cli::TrustContextConfig;
+use crate::config::cli::{CredentialRetrieverConfig, TrustContextConfig};
 use crate::config::lookup::ProjectLookup;
 use crate::error::ApiError;
+#[cfg(unix)]
+use crate::nodes::connection::PlainUdsInstantiator;
 use crate::nodes::connection::{
     Connection, ConnectionInstance, ConnectionInstanceBuilder, PlainTcpInstantiator,
-    ProjectInstantiator, SecureChannelInstantiator,
+    PlainUdpInstantiator, PlainWebSocketInstantiator, ProjectInstantiator,
+    SecureChannelInstantiator,
 };
 use crate::nodes::models::base::NodeStatus;
 use crate::nodes::models::portal::{OutletList, OutletStatus};
@@ -61,9 +69,13 @@ mod policy;
 mod portals;
 mod secure_channel;
 mod transport;
+mod udp_portals;
 
 const TARGET: &str = "ockam_api::nodemanager::service";
 
+/// Interval between two fetches of the revocation list of the trust context authority
+const REVOCATION_LIST_REFRESH_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
 pub(crate) type Alias = String;
 
 /// Generate a new alias for some user created extension
@@ -89,6 +101,13 @@ pub struct NodeManager {
     node_name: String,
     api_transport_flow_control_id: FlowControlId,
     pub(crate) tcp_transport: TcpTransport,
+    /// Created when the first UDP portal or connection is created
+    udp_transport: Option<UdpTransport>,
+    /// Created when the first Unix domain socket connection is created
+    #[cfg(unix)]
+    uds_transport: Option<UdsTransport>,
+    /// Created when the first WebSocket connection is created
+    ws_transport: Option<WebSocketTransport>,
     pub(crate) controller_identity_id: Identifier,
     skip_defaults: bool,
     enable_credential_checks: bool,
@@ -98,6 +117,7 @@ pub struct NodeManager {
     pub(crate) registry: Registry,
     medic_handle: MedicHandle,
     policies: Arc<dyn PolicyStorage>,
+    audit_sink: Option<Arc<dyn AuditSink>>,
 }
 
 impl NodeManager {
@@ -202,13 +222,17 @@ impl NodeManager {
                 self.policies.set_policy(r, a, &fallback).await?
             }
             let policies = self.policies.clone();
-            Ok(Arc::new(PolicyAccessControl::new(
+            let mut access_control = PolicyAccessControl::new(
                 policies,
                 self.identities_repository(),
                 r.clone(),
                 a.clone(),
                 env,
-            )))
+            );
+            if let Some(sink) = &self.audit_sink {
+                access_control = access_control.with_audit_sink(sink.clone());
+            }
+            Ok(Arc::new(access_control))
         } else {
             Ok(Arc::new(AllowAll))
         }
@@ -226,6 +250,7 @@ pub struct NodeManagerGeneralOptions {
     node_name: String,
     skip_defaults: bool,
     pre_trusted_identities: Option<PreTrustedIdentities>,
+    audit_sink: Option<Arc<dyn AuditSink>>,
 }
 
 impl NodeManagerGeneralOptions {
@@ -240,8 +265,15 @@ impl NodeManagerGeneralOptions {
             node_name,
             skip_defaults,
             pre_trusted_identities,
+            audit_sink: None,
         }
     }
+
+    /// Record the decisions of the access controls created by the node in the given sink
+    pub fn with_audit_sink(mut self, audit_sink: Arc<dyn AuditSink>) -> Self {
+        self.audit_sink = Some(audit_sink);
+        self
+    }
 }
 
 #[derive(Clone)]
@@ -326,6 +358,7 @@ impl NodeManager {
         let secure_channels = SecureChannels::builder()
             .with_vault(vault)
             .with_identities_repository(identities_repository.clone())
+            .with_revocation_lists_storage(Arc::new(node_state.revocation_lists_storage().await?))
             .build();
 
         let policies: Arc<dyn PolicyStorage> = Arc::new(node_state.policies_storage().await?);
@@ -338,6 +371,10 @@ impl NodeManager {
             node_name: general_options.node_name,
             api_transport_flow_control_id: transport_options.api_transport_flow_control_id,
             tcp_transport: transport_options.tcp_transport,
+            udp_transport: None,
+            #[cfg(unix)]
+            uds_transport: None,
+            ws_transport: None,
             controller_identity_id: Self::load_controller_identifier()?,
             skip_defaults: general_options.skip_defaults,
             enable_credential_checks: trust_options.trust_context_config.is_some()
@@ -353,6 +390,7 @@ impl NodeManager {
             registry: Default::default(),
             medic_handle,
             policies,
+            audit_sink: general_options.audit_sink,
         };
 
         if !general_options.skip_defaults {
@@ -360,6 +398,7 @@ impl NodeManager {
             if let Some(tc) = trust_options.trust_context_config {
                 debug!("configuring trust context");
                 s.configure_trust_context(&tc).await?;
+                s.start_revocation_list_refresher(ctx, &tc).await?;
             }
         }
         info!("created a node manager for the node: {}", s.node_name);
@@ -381,6 +420,33 @@ impl NodeManager {
         Ok(())
     }
 
+    /// Keep the revocation list of the trust context authority up to date when the
+    /// node retrieves its credential from that authority
+    async fn start_revocation_list_refresher(
+        &self,
+        ctx: &Context,
+        tc: &TrustContextConfig,
+    ) -> Result<()> {
+        let issuer = match tc.authority().and_then(|a| a.own_credential()) {
+            Ok(CredentialRetrieverConfig::FromCredentialIssuer(issuer)) => issuer,
+            _ => return Ok(()),
+        };
+        let info = issuer
+            .remote_info(DefaultAddress::REVOCATION_LIST_SERVICE)
+            .await?;
+        RevocationListRefresher::remote(
+            self.secure_channels.clone(),
+            self.identifier.clone(),
+            info,
+            REVOCATION_LIST_REFRESH_INTERVAL,
+        )
+        .start(ctx, Address::random_tagged("RevocationListRefresher"))
+        .await?;
+
+        info!("NodeManager::start_revocation_list_refresher: revocation list refresher started");
+        Ok(())
+    }
+
     async fn initialize_defaults(
         &mut self,
         ctx: &Context,
@@ -424,7 +490,8 @@ impl NodeManager {
         Ok(())
     }
 
-    /// Resolve project ID (if any), create secure channel (if needed) and create a tcp connection
+    /// Resolve project ID (if any), create secure channel (if needed) and create a tcp, udp,
+    /// unix domain socket or websocket connection
     /// Returns [`ConnectionInstance`]
     pub(crate) async fn connect(
         node_manager: Arc<RwLock<NodeManager>>,
@@ -441,7 +508,7 @@ impl NodeManager {
             .async_try_clone()
             .await?;
 
-        let connection_instance = ConnectionInstanceBuilder::new(connection.addr.clone())
+        let builder = ConnectionInstanceBuilder::new(connection.addr.clone())
             .instantiate(ProjectInstantiator::new(
                 context.clone(),
                 node_manager.clone(),
@@ -450,8 +517,27 @@ impl NodeManager {
                 connection.identity_name.map(|x| x.to_string()),
             ))
             .await?
+            // WebSocket addresses start with a TCP address, they must be instantiated first
+            .instantiate(PlainWebSocketInstantiator::new(
+                context.clone(),
+                node_manager.clone(),
+            ))
+            .await?
             .instantiate(PlainTcpInstantiator::new(tcp_transport))
             .await?
+            .instantiate(PlainUdpInstantiator::new(
+                context.clone(),
+                node_manager.clone(),
+            ))
+            .await?;
+        #[cfg(unix)]
+        let builder = builder
+            .instantiate(PlainUdsInstantiator::new(
+                context.clone(),
+                node_manager.clone(),
+            ))
+            .await?;
+        let connection_instance = builder
             .instantiate(SecureChannelInstantiator::new(
                 context.clone(),
                 node_manager.clone(),
@@ -683,6 +769,26 @@ impl NodeManagerWorker {
                 encode_request_result(self.delete_inlet(req, alias).await)?
             }
             (Delete, ["node", "portal"]) => todo!(),
+            (Get, ["node", "udp", "inlet"]) => self.get_udp_inlets(req).await.to_vec()?,
+            (Get, ["node", "udp", "inlet", alias]) => {
+                encode_request_result(self.show_udp_inlet(req, alias).await)?
+            }
+            (Get, ["node", "udp", "outlet"]) => self.get_udp_outlets(req).await.to_vec()?,
+            (Get, ["node", "udp", "outlet", alias]) => {
+                encode_request_result(self.show_udp_outlet(req, alias).await)?
+            }
+            (Post, ["node", "udp", "inlet"]) => {
+                encode_request_result(self.create_udp_inlet(req, dec, ctx).await)?
+            }
+            (Post, ["node", "udp", "outlet"]) => {
+                encode_request_result(self.create_udp_outlet(ctx, req, dec.decode()?).await)?
+            }
+            (Delete, ["node", "udp", "inlet", alias]) => {
+                encode_request_result(self.delete_udp_inlet(req, alias).await)?
+            }
+            (Delete, ["node", "udp", "outlet", alias]) => {
+                encode_request_result(self.delete_udp_outlet(req, alias).await)?
+            }
 
             // ==*== Flow Controls ==*==
             (Post, ["node", "flow_controls", "add_consumer"]) => {
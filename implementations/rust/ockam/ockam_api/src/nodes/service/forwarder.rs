@@ -12,8 +12,8 @@ use ockam_multiaddr::MultiAddr;
 use ockam_node::tokio::time::timeout;
 use ockam_node::Context;
 
+use crate::connection_multiaddr_to_route;
 use crate::error::ApiError;
-use crate::local_multiaddr_to_route;
 use crate::nodes::connection::{Connection, ConnectionInstance};
 use crate::nodes::models::forwarder::{CreateForwarder, ForwarderInfo};
 use crate::session::sessions::{Replacer, Session};
@@ -64,7 +64,7 @@ impl NodeManagerWorker {
 
         let options = RemoteForwarderOptions::new();
 
-        let route = local_multiaddr_to_route(&connection_instance.normalized_addr)
+        let route = connection_multiaddr_to_route(&connection_instance.normalized_addr)
             .ok_or_else(|| ApiError::core("invalid address: {addr}"))?;
 
         let forwarder = if req.at_rust_node() {
@@ -239,13 +239,16 @@ fn replacer(
                         debug!("cannot delete secure channel `{encryptor}`: {error}");
                     }
                 }
-                if let Some(tcp_connection) = previous_connection_instance.tcp_connection.as_ref() {
+                if let Some(transport_connection) =
+                    previous_connection_instance.transport_connection.as_ref()
+                {
                     if let Err(error) = node_manager
-                        .tcp_transport
-                        .disconnect(tcp_connection.sender_address().clone())
+                        .disconnect_transport(transport_connection)
                         .await
                     {
-                        debug!("cannot stop tcp worker `{tcp_connection}`: {error}");
+                        debug!(
+                            "cannot stop transport connection `{transport_connection}`: {error}"
+                        );
                     }
                 }
                 drop(node_manager);
@@ -260,7 +263,7 @@ fn replacer(
 
                 *connection_instance_arc.lock().unwrap() = new_connection_instance.clone();
 
-                let route = local_multiaddr_to_route(&new_connection_instance.normalized_addr)
+                let route = connection_multiaddr_to_route(&new_connection_instance.normalized_addr)
                     .ok_or_else(|| {
                         ApiError::core(format!(
                             "invalid multiaddr: {}",
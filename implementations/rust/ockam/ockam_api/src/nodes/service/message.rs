@@ -44,8 +44,8 @@ mod node {
     use ockam_core::{self, Result};
     use ockam_node::Context;
 
+    use crate::connection_multiaddr_to_route;
     use crate::error::ApiError;
-    use crate::local_multiaddr_to_route;
     use crate::nodes::connection::Connection;
     use crate::nodes::{NodeManager, NodeManagerWorker};
 
@@ -67,7 +67,7 @@ mod node {
             let connection_instance =
                 NodeManager::connect(self.node_manager.clone(), connection).await?;
 
-            let route = local_multiaddr_to_route(&connection_instance.normalized_addr)
+            let route = connection_multiaddr_to_route(&connection_instance.normalized_addr)
                 .ok_or_else(|| ApiError::core("Invalid route"))?;
 
             trace!(target: TARGET, route = %route, msg_l = %msg_length, "sending message");
@@ -30,6 +30,7 @@ impl NodeIdentities {
     }
 
     /// Return an identities service, possibly backed by a specific vault
+    /// The revocation lists received by the node are used in both cases
     pub(crate) async fn get_identities(
         &self,
         vault_name: Option<String>,
@@ -39,6 +40,7 @@ impl NodeIdentities {
         Ok(Identities::builder()
             .with_vault(vault)
             .with_identities_repository(repository)
+            .with_revocation_lists_repository(self.identities.revocation_lists_repository())
             .build())
     }
 
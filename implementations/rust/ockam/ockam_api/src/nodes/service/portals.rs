@@ -16,15 +16,16 @@ use ockam_multiaddr::proto::Project;
 use ockam_multiaddr::MultiAddr;
 use ockam_node::compat::asynchronous::RwLock;
 use ockam_node::Context;
-use ockam_transport_tcp::{TcpInletOptions, TcpOutletOptions};
+use ockam_transport_tcp::{HealthCheckOptions, LoadBalancing, TcpInletOptions, TcpOutletOptions};
 
 use crate::cli_state::StateDirTrait;
 use crate::config::lookup::ProjectLookup;
+use crate::connection_multiaddr_to_route;
 use crate::error::ApiError;
-use crate::local_multiaddr_to_route;
 use crate::nodes::connection::{Connection, ConnectionInstance};
 use crate::nodes::models::portal::{
     CreateInlet, CreateOutlet, InletList, InletStatus, OutletList, OutletStatus,
+    OutletTargetStatus, PortalLimits,
 };
 use crate::nodes::registry::{InletInfo, OutletInfo};
 use crate::nodes::service::random_alias;
@@ -42,6 +43,36 @@ impl NodeManager {
         alias: Option<String>,
         reachable_from_default_secure_channel: bool,
     ) -> Result<OutletStatus> {
+        self.create_outlet_from_request(
+            ctx,
+            CreateOutlet::new(
+                socket_addr,
+                worker_addr,
+                alias,
+                reachable_from_default_secure_channel,
+            ),
+        )
+        .await
+    }
+
+    /// Create an outlet, balancing the connections between its targets if several are set
+    pub async fn create_outlet_from_request(
+        &mut self,
+        ctx: &Context,
+        create_outlet: CreateOutlet,
+    ) -> Result<OutletStatus> {
+        let CreateOutlet {
+            socket_addr,
+            worker_addr,
+            alias,
+            reachable_from_default_secure_channel,
+            targets,
+            load_balancing,
+            health_check_interval,
+            dns_refresh_interval,
+            ..
+        } = create_outlet;
+
         info!(
             "Handling request to create outlet portal at {:?}",
             socket_addr
@@ -75,6 +106,28 @@ impl NodeManager {
             .await?;
 
         let options = TcpOutletOptions::new().with_incoming_access_control(access_control);
+        let options = options.with_targets(targets.unwrap_or_default());
+        let options = match load_balancing {
+            Some(load_balancing) => {
+                let load_balancing = load_balancing.parse::<LoadBalancing>().map_err(|_| {
+                    ockam_core::Error::new(
+                        Origin::Node,
+                        Kind::Invalid,
+                        format!("Invalid load balancing strategy '{load_balancing}'"),
+                    )
+                })?;
+                options.with_load_balancing(load_balancing)
+            }
+            None => options,
+        };
+        let options = match health_check_interval {
+            Some(interval) => options.with_health_check(HealthCheckOptions::new(interval)),
+            None => options,
+        };
+        let options = match dns_refresh_interval {
+            Some(interval) => options.with_dns_refresh_interval(interval),
+            None => options,
+        };
         let options = if !check_credential {
             options.as_consumer(&self.api_transport_flow_control_id)
         } else {
@@ -238,7 +291,8 @@ impl NodeManagerWorker {
             NodeManager::connect(self.node_manager.clone(), connection).await?
         };
 
-        let outlet_route = match local_multiaddr_to_route(&connection_instance.normalized_addr) {
+        let outlet_route = match connection_multiaddr_to_route(&connection_instance.normalized_addr)
+        {
             Some(route) => route,
             None => {
                 let err_body = Error::new_without_path().with_message("Invalid outlet route.");
@@ -290,7 +344,7 @@ impl NodeManagerWorker {
             .access_control(&resource, &actions::HANDLE_MESSAGE, project_id, None)
             .await?;
 
-        let options = TcpInletOptions::new().with_incoming_access_control(access_control.clone());
+        let options = inlet_options(access_control.clone(), &req.limits());
 
         let res = node_manager
             .tcp_transport
@@ -329,6 +383,7 @@ impl NodeManagerWorker {
                         req.suffix_route().clone(),
                         req.authorized(),
                         access_control.clone(),
+                        req.limits(),
                         ctx,
                     );
                     session.set_replacer(repl);
@@ -402,13 +457,25 @@ impl NodeManagerWorker {
         info!(%alias, "Handling request to show inlet portal");
         if let Some(inlet_to_show) = node_manager.registry.inlets.get(alias) {
             debug!(%alias, "Inlet not found in node registry");
-            Ok(Response::ok(req.id()).body(InletStatus::new(
+            let mut status = InletStatus::new(
                 inlet_to_show.bind_addr.to_string(),
                 inlet_to_show.worker_addr.to_string(),
                 alias,
                 None,
                 inlet_to_show.outlet_route.to_string(),
-            )))
+            );
+            // The inlet is looked up by bind address, since its worker is
+            // replaced when the inlet is recreated by its session
+            if let Some(info) = node_manager
+                .tcp_transport
+                .registry()
+                .get_all_inlets()
+                .into_iter()
+                .find(|i| i.socket_address().to_string() == inlet_to_show.bind_addr)
+            {
+                status = status.with_stats(info.stats().into());
+            }
+            Ok(Response::ok(req.id()).body(status))
         } else {
             error!(%alias, "Inlet not found in the node registry");
             let err_body =
@@ -423,23 +490,17 @@ impl NodeManagerWorker {
         req: &Request,
         create_outlet: CreateOutlet,
     ) -> Result<ResponseBuilder<OutletStatus>, ResponseBuilder<Error>> {
-        let CreateOutlet {
-            socket_addr,
-            worker_addr,
-            alias,
-            reachable_from_default_secure_channel,
-            ..
-        } = create_outlet;
-
-        self.create_outlet_impl(
-            ctx,
-            req.id(),
-            socket_addr,
-            worker_addr,
-            alias,
-            reachable_from_default_secure_channel,
-        )
-        .await
+        let mut node_manager = self.inner().write().await;
+        match node_manager
+            .create_outlet_from_request(ctx, create_outlet)
+            .await
+        {
+            Ok(outlet_status) => Ok(Response::ok(req.id()).body(outlet_status)),
+            Err(e) => {
+                let err_body = Error::new_without_path().with_message(format!("{e:?}"));
+                Err(Response::bad_request(req.id()).body(err_body))
+            }
+        }
     }
 
     pub async fn create_outlet_impl(
@@ -508,12 +569,32 @@ impl NodeManagerWorker {
         info!(%alias, "Handling request to show outlet portal");
         if let Some(outlet_to_show) = node_manager.registry.outlets.get(alias) {
             debug!(%alias, "Outlet not found in node registry");
-            Ok(Response::ok(req.id()).body(OutletStatus::new(
-                outlet_to_show.socket_addr,
-                outlet_to_show.worker_addr.clone(),
-                alias,
-                None,
-            )))
+            let targets = node_manager
+                .tcp_transport
+                .registry()
+                .get_all_outlets()
+                .into_iter()
+                .find(|o| o.address() == &outlet_to_show.worker_addr)
+                .map(|o| o.targets())
+                .unwrap_or_default()
+                .into_iter()
+                .map(|t| {
+                    OutletTargetStatus::new(
+                        t.socket_address(),
+                        t.is_healthy(),
+                        t.connections() as u64,
+                    )
+                })
+                .collect();
+            Ok(Response::ok(req.id()).body(
+                OutletStatus::new(
+                    outlet_to_show.socket_addr,
+                    outlet_to_show.worker_addr.clone(),
+                    alias,
+                    None,
+                )
+                .with_targets(targets),
+            ))
         } else {
             error!(%alias, "Outlet not found in the node registry");
             let err_body =
@@ -527,6 +608,21 @@ impl NodeManagerWorker {
     }
 }
 
+/// Options of an inlet created with the given access control and limits
+fn inlet_options(access: Arc<dyn IncomingAccessControl>, limits: &PortalLimits) -> TcpInletOptions {
+    let mut options = TcpInletOptions::new().with_incoming_access_control(access);
+    if let Some(max_connections) = limits.max_connections {
+        options = options.with_max_connections(max_connections as usize);
+    }
+    if let Some(max_connections_per_second) = limits.max_connections_per_second {
+        options = options.with_max_connections_per_second(max_connections_per_second);
+    }
+    if let Some(max_bytes_per_second) = limits.max_bytes_per_second {
+        options = options.with_max_bytes_per_second(max_bytes_per_second);
+    }
+    options
+}
+
 /// Create a session replacer.
 ///
 /// This returns a function that accepts the previous ping address (e.g.
@@ -543,6 +639,7 @@ fn replacer(
     suffix_route: Route,
     auth: Option<Identifier>,
     access: Arc<dyn IncomingAccessControl>,
+    limits: PortalLimits,
     ctx: Arc<Context>,
 ) -> Replacer {
     let connection_instance_arc = Arc::new(Mutex::new(connection_instance));
@@ -554,6 +651,7 @@ fn replacer(
         let bind = bind.clone();
         let node_manager_arc = manager.clone();
         let access = access.clone();
+        let limits = limits.clone();
         let ctx = ctx.clone();
         let connection_instance_arc = connection_instance_arc.clone();
         let inlet_address_arc = inlet_address_arc.clone();
@@ -575,13 +673,16 @@ fn replacer(
                         debug!("cannot delete secure channel `{encryptor}`: {error}");
                     }
                 }
-                if let Some(tcp_connection) = previous_connection_instance.tcp_connection.as_ref() {
+                if let Some(transport_connection) =
+                    previous_connection_instance.transport_connection.as_ref()
+                {
                     if let Err(error) = node_manager
-                        .tcp_transport
-                        .disconnect(tcp_connection.sender_address().clone())
+                        .disconnect_transport(transport_connection)
                         .await
                     {
-                        debug!("cannot stop tcp worker `{tcp_connection}`: {error}");
+                        debug!(
+                            "cannot stop transport connection `{transport_connection}`: {error}"
+                        );
                     }
                 }
 
@@ -608,14 +709,14 @@ fn replacer(
                 //we expect a fully normalized MultiAddr
                 let normalized_route = route![
                     prefix_route,
-                    local_multiaddr_to_route(&new_connection_instance.normalized_addr.clone())
+                    connection_multiaddr_to_route(&new_connection_instance.normalized_addr.clone())
                         .ok_or_else(|| ApiError::core("invalid normalized address"))?,
                     suffix_route
                 ];
 
                 let node_manager = node_manager_arc.write().await;
 
-                let options = TcpInletOptions::new().with_incoming_access_control(access);
+                let options = inlet_options(access, &limits);
 
                 // Finally attempt to create a new inlet using the new route:
                 let new_inlet_address = node_manager
@@ -9,14 +9,64 @@ use ockam_node::Context;
 use ockam_transport_tcp::{
     TcpConnectionOptions, TcpListenerInfo, TcpListenerOptions, TcpSenderInfo, TcpTransport,
 };
+#[cfg(unix)]
+use ockam_transport_uds::UdsTransport;
+use ockam_transport_websocket::WebSocketTransport;
 
+use crate::nodes::connection::TransportConnection;
 use crate::nodes::models::transport::{
     CreateTcpConnection, CreateTcpListener, DeleteTransport, TransportList, TransportMode,
     TransportStatus, TransportType,
 };
 use crate::nodes::service::ApiTransport;
 
-use super::NodeManagerWorker;
+use super::{NodeManager, NodeManagerWorker};
+
+impl NodeManager {
+    /// Return the UDS transport of the node, creating it the first time a Unix domain
+    /// socket connection is created
+    #[cfg(unix)]
+    pub(crate) async fn uds_transport(&mut self, ctx: &Context) -> Result<&UdsTransport> {
+        let uds_transport = match self.uds_transport.take() {
+            Some(uds_transport) => uds_transport,
+            None => UdsTransport::create(ctx).await?,
+        };
+        Ok(self.uds_transport.insert(uds_transport))
+    }
+
+    /// Return the WebSocket transport of the node, creating it the first time a WebSocket
+    /// connection is created
+    pub(crate) async fn ws_transport(&mut self, ctx: &Context) -> Result<&WebSocketTransport> {
+        let ws_transport = match self.ws_transport.take() {
+            Some(ws_transport) => ws_transport,
+            None => WebSocketTransport::create(ctx).await?,
+        };
+        Ok(self.ws_transport.insert(ws_transport))
+    }
+
+    /// Disconnect a transport connection created for a [`ConnectionInstance`](crate::nodes::connection::ConnectionInstance)
+    pub(crate) async fn disconnect_transport(
+        &mut self,
+        connection: &TransportConnection,
+    ) -> Result<()> {
+        match connection {
+            TransportConnection::Tcp(connection) => {
+                self.tcp_transport
+                    .disconnect(connection.sender_address().clone())
+                    .await
+            }
+            TransportConnection::WebSocket(address) => match &self.ws_transport {
+                Some(ws_transport) => ws_transport.disconnect(address.clone()).await,
+                None => Ok(()),
+            },
+            #[cfg(unix)]
+            TransportConnection::Uds(path) => match &self.uds_transport {
+                Some(uds_transport) => uds_transport.disconnect(path).await,
+                None => Ok(()),
+            },
+        }
+    }
+}
 
 impl NodeManagerWorker {
     fn find_connection(tcp: &TcpTransport, address: String) -> Option<TcpSenderInfo> {
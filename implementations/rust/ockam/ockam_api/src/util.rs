@@ -1,21 +1,45 @@
 use miette::miette;
-use std::net::{SocketAddrV4, SocketAddrV6};
+use std::net::{IpAddr, SocketAddrV4, SocketAddrV6};
 
 use ockam::TcpTransport;
 use ockam_core::flow_control::FlowControlId;
 use ockam_core::{Address, Error, Result, Route, TransportType, LOCAL};
 use ockam_multiaddr::proto::{
-    DnsAddr, Ip4, Ip6, Node, Project, Secure, Service, Space, Tcp, Worker,
+    DnsAddr, Ip4, Ip6, Node, Project, Secure, Service, Space, Tcp, Udp, Unix, Worker, Ws, Wss,
 };
-use ockam_multiaddr::{Code, MultiAddr, Protocol};
+use ockam_multiaddr::{Code, MultiAddr, ProtoValue, Protocol};
 use ockam_transport_tcp::{TcpConnection, TcpConnectionOptions, TCP};
+use ockam_transport_udp::UDP;
 
 use crate::error::ApiError;
 
 /// Try to convert a multi-address to an Ockam route.
 pub fn local_multiaddr_to_route(ma: &MultiAddr) -> Option<Route> {
+    multiaddr_to_local_route(ma, false)
+}
+
+/// Try to convert the normalized multi-address of a connection to an Ockam route.
+///
+/// Unlike [`local_multiaddr_to_route`], UDP peers are allowed: they are kept in the
+/// multi-address of a connection, and reached through the UDP transport started
+/// when the connection was instantiated.
+pub fn connection_multiaddr_to_route(ma: &MultiAddr) -> Option<Route> {
+    multiaddr_to_local_route(ma, true)
+}
+
+fn multiaddr_to_local_route(ma: &MultiAddr, allow_udp: bool) -> Option<Route> {
     let mut rb = Route::new();
-    for p in ma.iter() {
+    let mut it = ma.iter().peekable();
+    while let Some(p) = it.next() {
+        if let Some(address) = it.peek().and_then(|port| udp_peer_address(&p, port)) {
+            if !allow_udp {
+                error!(target: "ockam_api", %address, "a UDP peer is not a local address");
+                return None;
+            }
+            let _ = it.next();
+            rb = rb.append(address);
+            continue;
+        }
         match p.code() {
             // Only hops that are directly translated to existing workers are allowed here
             Worker::CODE => {
@@ -64,6 +88,11 @@ pub async fn multiaddr_to_route(
     let mut tcp_connection = None;
 
     while let Some(p) = it.next() {
+        if let Some(address) = it.peek().and_then(|port| udp_peer_address(&p, port)) {
+            let _ = it.next();
+            rb = rb.append(address);
+            continue;
+        }
         match p.code() {
             Ip4::CODE => {
                 if number_of_tcp_hops >= 1 {
@@ -183,6 +212,11 @@ pub fn multiaddr_to_transport_route(ma: &MultiAddr) -> Option<Route> {
     let mut it = ma.iter().peekable();
 
     while let Some(p) = it.next() {
+        if let Some(address) = it.peek().and_then(|port| udp_peer_address(&p, port)) {
+            let _ = it.next();
+            route = route.append(address);
+            continue;
+        }
         match p.code() {
             Ip4::CODE => {
                 let ip4 = p.cast::<Ip4>()?;
@@ -229,6 +263,22 @@ pub fn multiaddr_to_transport_route(ma: &MultiAddr) -> Option<Route> {
     Some(route.into())
 }
 
+/// Convert a host followed by a UDP port, for example `/ip4/127.0.0.1/udp/4000`,
+/// to the Address (UDP, "127.0.0.1:4000")
+pub(crate) fn udp_peer_address(host: &ProtoValue, port: &ProtoValue) -> Option<Address> {
+    if port.code() != Udp::CODE {
+        return None;
+    }
+    let port = *port.cast::<Udp>()?;
+    let peer = match host.code() {
+        Ip4::CODE => SocketAddrV4::new(*host.cast::<Ip4>()?, port).to_string(),
+        Ip6::CODE => SocketAddrV6::new(*host.cast::<Ip6>()?, port, 0, 0).to_string(),
+        DnsAddr::CODE => format!("{}:{}", &*host.cast::<DnsAddr>()?, port),
+        _ => return None,
+    };
+    Some(Address::new(UDP, peer))
+}
+
 /// Try to convert a multiaddr to an Ockam Address
 pub fn multiaddr_to_addr(ma: &MultiAddr) -> Option<Address> {
     let mut it = ma.iter().peekable();
@@ -265,6 +315,20 @@ pub fn try_address_to_multiaddr(a: &Address) -> Result<MultiAddr, Error> {
     let mut ma = MultiAddr::default();
     match a.transport_type() {
         LOCAL => ma.push_back(Service::new(a.address()))?,
+        UDP => {
+            let (host, port) = a
+                .address()
+                .rsplit_once(':')
+                .and_then(|(host, port)| Some((host, port.parse::<u16>().ok()?)))
+                .ok_or_else(|| ApiError::core(format!("invalid udp address: {a}")))?;
+            let host = host.trim_start_matches('[').trim_end_matches(']');
+            match host.parse::<IpAddr>() {
+                Ok(IpAddr::V4(ip)) => ma.push_back(Ip4::new(ip))?,
+                Ok(IpAddr::V6(ip)) => ma.push_back(Ip6::new(ip))?,
+                Err(_) => ma.push_back(DnsAddr::new(host))?,
+            }
+            ma.push_back(Udp::new(port))?
+        }
         other => {
             error!(target: "ockam_api", transport = %other, "unsupported transport type");
             return Err(ApiError::core(format!("unknown transport type: {other}")));
@@ -315,6 +379,10 @@ pub fn is_local_node(ma: &MultiAddr) -> miette::Result<bool> {
                     .map(|ip6| ip6.is_loopback())
                     .ok_or_else(|| miette!("Invalid \"ip6\" value"))?;
             }
+            // A "/unix" socket is always on the local machine
+            Unix::CODE => {
+                at_rust_node = true;
+            }
             // A MultiAddr starting with "/service" could reference both local and remote nodes.
             _ => {
                 return Err(miette!("Invalid address, protocol not supported"));
@@ -336,6 +404,10 @@ pub fn local_worker(code: &Code) -> Result<bool> {
         | Ip4::CODE
         | Ip6::CODE
         | Tcp::CODE
+        | Udp::CODE
+        | Unix::CODE
+        | Ws::CODE
+        | Wss::CODE
         | Secure::CODE => Ok(false),
         Worker::CODE | Service::CODE => Ok(true),
 
@@ -496,3 +568,37 @@ pub mod test_utils {
         Ok(identity)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ockam_core::route;
+    use std::str::FromStr;
+
+    #[test]
+    fn udp_multiaddr_to_route() {
+        let ma = MultiAddr::from_str("/ip4/127.0.0.1/udp/4000/service/api").unwrap();
+        let expected: Route = route![(UDP, "127.0.0.1:4000"), "api"];
+        assert!(local_multiaddr_to_route(&ma).is_none());
+        assert_eq!(connection_multiaddr_to_route(&ma).unwrap(), expected);
+        assert_eq!(multiaddr_to_transport_route(&ma).unwrap(), expected);
+        assert_eq!(route_to_multiaddr(&expected).unwrap(), ma);
+
+        let ma = MultiAddr::from_str("/ip6/::1/udp/4000").unwrap();
+        assert_eq!(
+            try_address_to_multiaddr(&Address::new(UDP, "[::1]:4000")).unwrap(),
+            ma
+        );
+        let ma = MultiAddr::from_str("/dnsaddr/localhost/udp/4000").unwrap();
+        assert_eq!(
+            try_address_to_multiaddr(&Address::new(UDP, "localhost:4000")).unwrap(),
+            ma
+        );
+    }
+
+    #[test]
+    fn unix_multiaddr_is_local() {
+        let ma = MultiAddr::from_str("/unix/%2Ftmp%2Fockam.sock/service/api").unwrap();
+        assert!(is_local_node(&ma).unwrap());
+    }
+}
@@ -1,8 +1,10 @@
+use ockam::identity::models::RevocationListData;
 use ockam::identity::utils::now;
 use ockam::identity::{
     secure_channels, AttributesEntry, Identifier, SecureChannelOptions, SecureChannels,
 };
 use ockam_api::authenticator::direct::DirectAuthenticatorClient;
+use ockam_api::authenticator::revocation::RevocationListClient;
 use ockam_api::authority_node::{Authority, Configuration};
 use ockam_api::bootstrapped_identities_store::PreTrustedIdentities;
 use ockam_api::{authority_node, DefaultAddress};
@@ -292,6 +294,69 @@ async fn two_admins_two_members_exist_in_one_global_scope(ctx: &mut Context) ->
     Ok(())
 }
 
+#[ockam_macros::test]
+async fn admin_issues_revocation_list(ctx: &mut Context) -> Result<()> {
+    use std::collections::HashMap;
+
+    let secure_channels = secure_channels();
+
+    let admins = setup(ctx, secure_channels.clone(), 1).await?;
+    let admin = &admins[0];
+
+    let member = secure_channels
+        .identities()
+        .identities_creation()
+        .create_identity()
+        .await?
+        .identifier()
+        .clone();
+    admin
+        .client
+        .add_member(member.clone(), HashMap::<&str, &str>::default())
+        .await?;
+
+    let sc = secure_channels
+        .create_secure_channel(
+            ctx,
+            &admin.identifier,
+            route!["api"],
+            SecureChannelOptions::new(),
+        )
+        .await?;
+    let issuer = RevocationListClient::new(
+        RpcClient::new(
+            route![sc.clone(), DefaultAddress::REVOCATION_LIST_ISSUER],
+            ctx,
+        )
+        .await?,
+    );
+    let service = RevocationListClient::new(
+        RpcClient::new(route![sc, DefaultAddress::REVOCATION_LIST_SERVICE], ctx).await?,
+    );
+
+    // No revocation list has been issued yet
+    assert!(service.revocation_list().await.is_err());
+
+    let issued = issuer.revoke_members(vec![member.clone()]).await?;
+    let data = RevocationListData::get_data(&issued.revocation_list.get_versioned_data()?)?;
+    assert_eq!(data.revoked_subjects, vec![member]);
+    assert!(data.revoked_credentials.is_empty());
+
+    // The issued list is served to the members of the trust context
+    let served = service.revocation_list().await?;
+    assert_eq!(served, issued);
+
+    // A new list replaces the previous one
+    let issued = issuer.revoke_members(vec![]).await?;
+    let data = RevocationListData::get_data(&issued.revocation_list.get_versioned_data()?)?;
+    assert!(data.revoked_subjects.is_empty());
+    assert_eq!(service.revocation_list().await?, issued);
+
+    ctx.stop().await?;
+
+    Ok(())
+}
+
 // Default Configuration with fake TrustedIdentifier (which can be changed after the call),
 // with freshly created Authority Identifier and temporary files for storage and vault
 async fn default_configuration() -> Result<Configuration> {
@@ -56,6 +56,7 @@ mod subscription;
 pub mod tcp;
 mod terminal;
 mod trust_context;
+mod udp;
 mod upgrade;
 pub mod util;
 mod vault;
@@ -113,6 +114,7 @@ use tcp::{
     outlet::TcpOutletCommand,
 };
 use trust_context::TrustContextCommand;
+use udp::{inlet::UdpInletCommand, outlet::UdpOutletCommand};
 use upgrade::check_if_an_upgrade_is_available;
 use util::{exitcode, exitcode::ExitCode};
 use vault::VaultCommand;
@@ -319,6 +321,9 @@ pub enum OckamSubcommand {
     TcpOutlet(TcpOutletCommand),
     TcpInlet(TcpInletCommand),
 
+    UdpOutlet(UdpOutletCommand),
+    UdpInlet(UdpInletCommand),
+
     KafkaOutlet(KafkaOutletCommand),
     KafkaConsumer(KafkaConsumerCommand),
     KafkaDirect(KafkaDirectCommand),
@@ -442,6 +447,8 @@ impl OckamCommand {
             OckamSubcommand::TcpConnection(c) => c.run(options),
             OckamSubcommand::TcpOutlet(c) => c.run(options),
             OckamSubcommand::TcpInlet(c) => c.run(options),
+            OckamSubcommand::UdpOutlet(c) => c.run(options),
+            OckamSubcommand::UdpInlet(c) => c.run(options),
 
             OckamSubcommand::KafkaConsumer(c) => c.run(options),
             OckamSubcommand::KafkaProducer(c) => c.run(options),
@@ -1,3 +1,4 @@
+use crate::logs::otlp::{otlp_endpoint, start_otlp_exporter, OtlpGuard};
 use crate::logs::rolling::{RollingConditionBasic, RollingFileAppender};
 
 use ockam_core::env::{get_env, get_env_with_default, FromString};
@@ -10,6 +11,7 @@ use tracing_appender::non_blocking::WorkerGuard;
 use tracing_subscriber::fmt::layer;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
+mod otlp;
 #[allow(unused, clippy::enum_variant_names)]
 mod rolling;
 
@@ -55,11 +57,21 @@ impl std::fmt::Display for LogFormat {
     }
 }
 
+/// Keep the logs and the traces being exported while the command runs
+pub struct LoggingGuard {
+    _appender: WorkerGuard,
+    _otlp: Option<OtlpGuard>,
+}
+
+/// Set up the logging of the command
+///
+/// The spans are also exported to an OTLP collector when the
+/// `OCKAM_OTLP_ENDPOINT` environment variable is set.
 pub fn setup_logging(
     verbose: u8,
     no_color: bool,
     log_path: Option<PathBuf>,
-) -> Option<WorkerGuard> {
+) -> Option<LoggingGuard> {
     let level = {
         // Parse the the raw log level value (e.g. "info" or "-vvv").
         let level_raw = match get_env::<String>("OCKAM_LOG") {
@@ -96,9 +108,22 @@ pub fn setup_logging(
             .with_default_directive(level.into())
             .parse_lossy(ockam_crates.map(|c| format!("{c}={level}")).join(","))
     };
+    // The command keeps running without exporting its traces if the exporter can't be started
+    let (otlp_layer, otlp_guard) = match otlp_endpoint().map(|e| start_otlp_exporter(&e)) {
+        Some(Ok((tracer, guard))) => (
+            Some(tracing_opentelemetry::layer().with_tracer(tracer)),
+            Some(guard),
+        ),
+        Some(Err(e)) => {
+            eprintln!("{e:?}");
+            (None, None)
+        }
+        None => (None, None),
+    };
     let subscriber = tracing_subscriber::registry()
         .with(filter)
-        .with(tracing_error::ErrorLayer::default());
+        .with(tracing_error::ErrorLayer::default())
+        .with(otlp_layer);
     let (appender, guard) = match log_path {
         // If a log path is not provided, log to stdout.
         None => {
@@ -128,5 +153,8 @@ pub fn setup_logging(
         LogFormat::Default => subscriber.with(appender).try_init(),
     };
     res.expect("Failed to initialize tracing subscriber");
-    Some(guard)
+    Some(LoggingGuard {
+        _appender: guard,
+        _otlp: otlp_guard,
+    })
 }
@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+
+use miette::{IntoDiagnostic, WrapErr};
+use ockam_core::env::{get_env, get_env_with_default};
+use ockam_node::tracing_context::{
+    propagate_tracing_context_across_nodes, set_tracing_context_propagator,
+    TracingContextPropagator,
+};
+use opentelemetry::propagation::TextMapPropagator;
+use opentelemetry::sdk::propagation::TraceContextPropagator;
+use opentelemetry::sdk::trace::{config, Tracer};
+use opentelemetry::sdk::Resource;
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use tokio::runtime::Runtime;
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+const TRACEPARENT: &str = "traceparent";
+
+/// Endpoint of the OTLP collector receiving the traces, for example `http://localhost:4317`
+pub(super) fn otlp_endpoint() -> Option<String> {
+    match get_env::<String>("OCKAM_OTLP_ENDPOINT") {
+        Ok(Some(endpoint)) if !endpoint.is_empty() => Some(endpoint),
+        _ => None,
+    }
+}
+
+/// Send the tracing context to the other nodes, which must all support
+/// version 2 of the transport messages
+fn otlp_propagate_across_nodes() -> bool {
+    get_env_with_default("OCKAM_OTLP_PROPAGATE", false).unwrap_or(false)
+}
+
+/// Export the remaining spans when the command exits
+pub(super) struct OtlpGuard {
+    _runtime: Runtime,
+}
+
+impl Drop for OtlpGuard {
+    fn drop(&mut self) {
+        opentelemetry::global::shutdown_tracer_provider();
+    }
+}
+
+/// Start exporting the spans to the OTLP collector at `endpoint`, and propagate
+/// the tracing context in the messages sent by this process so that the path
+/// of a message shows up as a single trace
+///
+/// The tracing context is only sent to the other nodes when `OCKAM_OTLP_PROPAGATE` is set.
+pub(super) fn start_otlp_exporter(endpoint: &str) -> miette::Result<(Tracer, OtlpGuard)> {
+    // The span processor and the gRPC client need a Tokio runtime,
+    // and the node runtime is not started yet when the logging is set up
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(1)
+        .thread_name("otlp-exporter")
+        .enable_all()
+        .build()
+        .into_diagnostic()
+        .wrap_err("Failed to create the OTLP exporter runtime")?;
+    let tracer = {
+        let _entered = runtime.enter();
+        opentelemetry_otlp::new_pipeline()
+            .tracing()
+            .with_exporter(
+                opentelemetry_otlp::new_exporter()
+                    .tonic()
+                    .with_endpoint(endpoint),
+            )
+            .with_trace_config(
+                config().with_resource(Resource::new(vec![KeyValue::new("service.name", "ockam")])),
+            )
+            .install_batch(opentelemetry::runtime::Tokio)
+            .into_diagnostic()
+            .wrap_err("Failed to start the OTLP exporter")?
+    };
+    set_tracing_context_propagator(OpenTelemetryPropagator(TraceContextPropagator::new()));
+    propagate_tracing_context_across_nodes(otlp_propagate_across_nodes());
+    Ok((tracer, OtlpGuard { _runtime: runtime }))
+}
+
+/// Propagate the W3C trace context of the OpenTelemetry spans
+struct OpenTelemetryPropagator(TraceContextPropagator);
+
+impl TracingContextPropagator for OpenTelemetryPropagator {
+    fn current_context(&self) -> Option<String> {
+        let mut carrier = HashMap::new();
+        self.0
+            .inject_context(&Span::current().context(), &mut carrier);
+        carrier.remove(TRACEPARENT)
+    }
+
+    fn set_parent(&self, span: &Span, traceparent: &str) {
+        let carrier = HashMap::from([(TRACEPARENT.to_string(), traceparent.to_string())]);
+        span.set_parent(self.0.extract(&carrier));
+    }
+}
@@ -12,8 +12,10 @@ use tokio::try_join;
 
 use ockam::{Address, AsyncTryClone, TcpListenerOptions};
 use ockam::{Context, TcpTransport};
+use ockam_abac::FileAuditSink;
 use ockam_api::cli_state::traits::{StateDirTrait, StateItemTrait};
 use ockam_api::cli_state::{add_project_info_to_node_state, init_node_state};
+use ockam_api::metrics::start_metrics_exporter;
 use ockam_api::nodes::models::transport::CreateTransportJson;
 use ockam_api::nodes::service::NodeManagerTrustOptions;
 use ockam_api::{
@@ -25,6 +27,7 @@ use ockam_api::{
     },
 };
 use ockam_core::api::{RequestBuilder, Response, Status};
+use ockam_core::compat::sync::Arc;
 use ockam_core::{route, LOCAL};
 
 use crate::node::util::spawn_node;
@@ -72,6 +75,16 @@ pub struct CreateCommand {
     )]
     pub tcp_listener_address: String,
 
+    /// Address of an HTTP listener exporting the node metrics on `/metrics`,
+    /// in the Prometheus text format
+    #[arg(display_order = 900, long, value_name = "SOCKET_ADDRESS")]
+    pub metrics_listen: Option<String>,
+
+    /// File to which the decisions of the node access controls are appended,
+    /// as one JSON object per line
+    #[arg(display_order = 900, long, value_name = "PATH")]
+    pub audit_log: Option<PathBuf>,
+
     /// `node create` started a child process to run this node in foreground.
     #[arg(long, hide = true)]
     pub child_process: bool,
@@ -113,6 +126,8 @@ impl Default for CreateCommand {
             node_name: hex::encode(random::<[u8; 4]>()),
             exit_on_eof: false,
             tcp_listener_address: "127.0.0.1:0".to_string(),
+            metrics_listen: None,
+            audit_log: None,
             foreground: false,
             child_process: false,
             launch_config: None,
@@ -276,6 +291,10 @@ async fn run_foreground_node(
         .with_credential_name(cmd.credential.as_ref())
         .build();
 
+    if let Some(metrics_listen) = &cmd.metrics_listen {
+        start_metrics_exporter(metrics_listen).into_diagnostic()?;
+    }
+
     let tcp = TcpTransport::create(&ctx).await.into_diagnostic()?;
     let options = TcpListenerOptions::new();
     let listener = tcp
@@ -285,31 +304,40 @@ async fn run_foreground_node(
 
     let node_state = opts.state.nodes.get(&node_name)?;
     node_state.set_pid(process::id() as i32)?;
-    node_state.set_setup(
-        &node_state
-            .config()
-            .setup_mut()
-            .set_verbose(opts.global_args.verbose)
-            .set_api_transport(
-                CreateTransportJson::new(
-                    TransportType::Tcp,
-                    TransportMode::Listen,
-                    &listener.socket_address().to_string(),
-                )
-                .into_diagnostic()?,
-            ),
-    )?;
+    let mut node_setup = node_state
+        .config()
+        .setup_mut()
+        .set_verbose(opts.global_args.verbose)
+        .set_api_transport(
+            CreateTransportJson::new(
+                TransportType::Tcp,
+                TransportMode::Listen,
+                &listener.socket_address().to_string(),
+            )
+            .into_diagnostic()?,
+        );
+    // Keep the metrics listener so that `ockam node start` restarts the node with it
+    if let Some(metrics_listen) = &cmd.metrics_listen {
+        node_setup = node_setup.set_metrics_listen(metrics_listen);
+    }
+    node_state.set_setup(&node_setup)?;
 
     let pre_trusted_identities = load_pre_trusted_identities(&cmd)?;
 
+    let mut general_options = NodeManagerGeneralOptions::new(
+        opts.state.clone(),
+        cmd.node_name.clone(),
+        cmd.launch_config.is_some(),
+        pre_trusted_identities,
+    );
+    if let Some(audit_log) = &cmd.audit_log {
+        let sink = FileAuditSink::create(audit_log).into_diagnostic()?;
+        general_options = general_options.with_audit_sink(Arc::new(sink));
+    }
+
     let node_man = NodeManager::create(
         &ctx,
-        NodeManagerGeneralOptions::new(
-            opts.state.clone(),
-            cmd.node_name.clone(),
-            cmd.launch_config.is_some(),
-            pre_trusted_identities,
-        ),
+        general_options,
         NodeManagerTransportOptions::new(
             listener.flow_control_id().clone(),
             tcp.async_try_clone().await.into_diagnostic()?,
@@ -470,6 +498,8 @@ pub async fn spawn_background_node(
         cmd.credential.as_ref(),
         trust_context_path.as_ref(),
         cmd.trust_context_opts.project.as_ref(),
+        cmd.metrics_listen.as_ref(),
+        cmd.audit_log.as_ref(),
         cmd.logging_to_file(),
     )?;
 
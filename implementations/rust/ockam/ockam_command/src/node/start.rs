@@ -72,6 +72,8 @@ async fn run_impl(
         None,                                          // Credential
         None,                                          // Trust Context
         None,                                          // Project Name
+        node_setup.metrics_listen.as_ref(),            // Metrics listener
+        None,                                          // Audit log
         true,                                          // Restarted nodes will log to files
     )?;
 
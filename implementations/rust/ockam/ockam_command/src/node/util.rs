@@ -143,6 +143,8 @@ pub fn spawn_node(
     credential: Option<&String>,
     trust_context: Option<&PathBuf>,
     project_name: Option<&String>,
+    metrics_listen: Option<&String>,
+    audit_log: Option<&PathBuf>,
     logging_to_file: bool,
 ) -> miette::Result<()> {
     let mut args = vec![
@@ -219,6 +221,21 @@ pub fn spawn_node(
         args.push(project_name.to_string());
     }
 
+    if let Some(metrics_listen) = metrics_listen {
+        args.push("--metrics-listen".to_string());
+        args.push(metrics_listen.to_string());
+    }
+
+    if let Some(audit_log) = audit_log {
+        args.push("--audit-log".to_string());
+        args.push(
+            audit_log
+                .to_str()
+                .unwrap_or_else(|| panic!("unsupported path {audit_log:?}"))
+                .to_string(),
+        );
+    }
+
     args.push(name.to_owned());
 
     run_ockam(opts, name, args, logging_to_file)
@@ -0,0 +1,159 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use miette::{miette, IntoDiagnostic};
+
+use ockam::identity::models::CredentialAndPurposeKey;
+use ockam::identity::utils::now;
+use ockam::identity::{AttributesEntry, Identifier};
+use ockam::Context;
+use ockam_abac::expr::str;
+use ockam_abac::{AbacAccessControl, Action, Env, Resource};
+use ockam_api::nodes::models::policy::Policy;
+use ockam_core::api::Request;
+use ockam_core::compat::collections::BTreeMap;
+
+use crate::credential::identities;
+use crate::node::get_node_name;
+use crate::policy::explain::{split_binding, write_explanation};
+use crate::policy::policy_path;
+use crate::util::parsers::identity_identifier_parser;
+use crate::util::{node_rpc, Rpc};
+use crate::vault::default_vault_name;
+use crate::CommandGlobalOpts;
+
+/// Evaluate the policy stored on a node against a set of attributes
+#[derive(Clone, Debug, Args)]
+pub struct EvaluateCommand {
+    #[arg(long, display_order = 900, id = "NODE_NAME")]
+    at: Option<String>,
+
+    #[arg(short, long)]
+    resource: Resource,
+
+    #[arg(short, long, default_value = "handle_message")]
+    action: Action,
+
+    /// Attributes in `key=value` format. Keys without a `subject.`, `resource.`
+    /// or `action.` prefix are considered to be subject attributes
+    #[arg(long = "attribute", value_name = "ATTRIBUTE")]
+    attributes: Vec<String>,
+
+    /// Path to a hex-encoded credential providing the subject attributes
+    #[arg(long, value_name = "CREDENTIAL_FILE", requires = "issuer")]
+    credential_path: Option<PathBuf>,
+
+    /// Identifier of the authority which issued the credential.
+    /// The credential is verified before its attributes are used
+    #[arg(long, value_name = "IDENTIFIER", value_parser = identity_identifier_parser)]
+    issuer: Option<Identifier>,
+
+    /// Identifier of an identity whose attributes are stored in the local identities repository
+    #[arg(long, value_name = "IDENTIFIER", value_parser = identity_identifier_parser)]
+    identity: Option<Identifier>,
+}
+
+impl EvaluateCommand {
+    pub fn run(self, opts: CommandGlobalOpts) {
+        node_rpc(run_impl, (opts, self));
+    }
+}
+
+async fn run_impl(
+    ctx: Context,
+    (opts, cmd): (CommandGlobalOpts, EvaluateCommand),
+) -> miette::Result<()> {
+    // The policy is retrieved from the node, which owns its policies storage
+    let node_name = get_node_name(&opts.state, &cmd.at);
+    let mut rpc = Rpc::background(&ctx, &opts, &node_name).await?;
+    let policy: Policy = rpc
+        .ask(Request::get(policy_path(&cmd.resource, &cmd.action)))
+        .await
+        .map_err(|e| {
+            miette!(
+                "No policy found for resource '{}' and action '{}' on node '{node_name}': {e}",
+                cmd.resource,
+                cmd.action
+            )
+        })?;
+    let expression = policy.expression().clone();
+
+    // The same attributes as the ones set by the node when creating an access control
+    let mut env = Env::new();
+    env.put("resource.id", str(cmd.resource.as_str()));
+    env.put("action.id", str(cmd.action.as_str()));
+
+    // Collect the subject attributes, from the local repository first, then
+    // from the credential and finally from the command line
+    let repository = opts.state.identities.identities_repository().await?;
+    let mut subject = cmd.identity.clone();
+    let mut entry = match &subject {
+        Some(identifier) => repository
+            .get_attributes(identifier)
+            .await
+            .into_diagnostic()?,
+        None => None,
+    };
+
+    if let (Some(path), Some(issuer)) = (&cmd.credential_path, &cmd.issuer) {
+        let encoded = hex::decode(std::fs::read_to_string(path).into_diagnostic()?.trim())
+            .into_diagnostic()?;
+        let credential: CredentialAndPurposeKey = minicbor::decode(&encoded).into_diagnostic()?;
+        // Only the attributes of a valid credential, issued to the evaluated identity, are used
+        let data = identities(&default_vault_name(&opts.state), &opts)
+            .await?
+            .credentials()
+            .credentials_verification()
+            .verify_credential(subject.as_ref(), &[issuer.clone()], &credential)
+            .await
+            .map_err(|e| miette!("The credential is not valid: {e}"))?
+            .credential_data;
+        let mut attrs = entry
+            .as_ref()
+            .map(|e| e.attrs().clone())
+            .unwrap_or_default();
+        for (key, value) in data.subject_attributes.map {
+            attrs.insert(key.to_vec(), value.to_vec());
+        }
+        entry = Some(
+            AttributesEntry::new(
+                attrs,
+                data.created_at,
+                Some(data.expires_at),
+                Some(issuer.clone()),
+            )
+            .with_issued(data.created_at),
+        );
+        subject = subject.or(data.subject);
+    }
+
+    let mut inline = BTreeMap::new();
+    for attr in &cmd.attributes {
+        let (key, value) = split_binding(attr)?;
+        if key.starts_with("resource.") || key.starts_with("action.") {
+            env.put(key, str(value));
+        } else {
+            let key = key.strip_prefix("subject.").unwrap_or(key);
+            inline.insert(key.as_bytes().to_vec(), value.as_bytes().to_vec());
+        }
+    }
+    if !inline.is_empty() {
+        entry = Some(match entry {
+            Some(e) => {
+                let mut attrs = e.attrs().clone();
+                attrs.append(&mut inline);
+                let mut updated =
+                    AttributesEntry::new(attrs, e.added(), e.expires(), e.attested_by());
+                if let Some(issued) = e.issued() {
+                    updated = updated.with_issued(issued)
+                }
+                updated
+            }
+            None => AttributesEntry::new(inline, now().into_diagnostic()?, None, None),
+        });
+    }
+
+    let env = AbacAccessControl::new(repository, expression.clone(), env)
+        .environment(subject.as_ref(), entry.as_ref());
+    write_explanation(&opts, &expression, &env)
+}
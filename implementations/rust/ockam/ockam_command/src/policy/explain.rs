@@ -0,0 +1,135 @@
+use clap::Args;
+use colorful::Colorful;
+use miette::{miette, IntoDiagnostic};
+
+use ockam::identity::utils::now;
+use ockam_abac::expr::{int, str};
+use ockam_abac::{eval_with_trace, Env, Expr, Trace};
+
+use crate::util::local_cmd;
+use crate::{fmt_log, fmt_ok, fmt_warn, CommandGlobalOpts};
+
+/// Explain how a policy expression is evaluated against a set of attributes
+#[derive(Clone, Debug, Args)]
+pub struct ExplainCommand {
+    #[arg(short, long)]
+    expression: Expr,
+
+    /// Attributes in `key=value` format. Keys without a `subject.`, `resource.`
+    /// or `action.` prefix are considered to be subject attributes
+    #[arg(short, long = "attribute", value_name = "ATTRIBUTE")]
+    attributes: Vec<String>,
+
+    /// Additional bindings in `name=expression` format, e.g. `now=1696152600`
+    #[arg(long = "env", value_name = "BINDING")]
+    bindings: Vec<String>,
+}
+
+impl ExplainCommand {
+    pub fn run(self, opts: CommandGlobalOpts) {
+        local_cmd(run_impl(opts, self));
+    }
+
+    fn environment(&self) -> miette::Result<Env> {
+        let mut env = Env::new();
+        if let Ok(t) = now() {
+            env.put("now", int(i64::try_from(t.0).unwrap_or(i64::MAX)));
+        }
+        for attr in &self.attributes {
+            let (key, value) = split_binding(attr)?;
+            if key.starts_with("subject.")
+                || key.starts_with("resource.")
+                || key.starts_with("action.")
+            {
+                env.put(key, str(value));
+            } else {
+                env.put(format!("subject.{key}"), str(value));
+            }
+        }
+        for binding in &self.bindings {
+            let (name, value) = split_binding(binding)?;
+            let value: Expr = value.parse().into_diagnostic()?;
+            env.put(name, value);
+        }
+        Ok(env)
+    }
+}
+
+pub(crate) fn split_binding(s: &str) -> miette::Result<(&str, &str)> {
+    let mut parts = s.splitn(2, '=');
+    let key = parts.next().ok_or(miette!("key expected"))?;
+    let value = parts.next().ok_or(miette!("value expected"))?;
+    Ok((key, value))
+}
+
+fn run_impl(opts: CommandGlobalOpts, cmd: ExplainCommand) -> miette::Result<()> {
+    let env = cmd.environment()?;
+    write_explanation(&opts, &cmd.expression, &env)
+}
+
+/// Evaluate a policy expression and write the decision, the sub-expression
+/// which made it and the evaluation trace
+pub(crate) fn write_explanation(
+    opts: &CommandGlobalOpts,
+    expression: &Expr,
+    env: &Env,
+) -> miette::Result<()> {
+    let mut trace = Trace::new();
+    let (is_authorized, error) = match eval_with_trace(expression, env, &mut trace) {
+        Ok(Expr::Bool(b)) => (b, None),
+        Ok(x) => (
+            false,
+            Some(format!("evaluation did not yield a boolean result: {x}")),
+        ),
+        Err(e) => (false, Some(format!("policy evaluation failed: {e}"))),
+    };
+    let decisive = trace.decisive().map(|s| {
+        let value = s
+            .value
+            .as_ref()
+            .map(|v| v.to_string())
+            .unwrap_or_else(|| "<error>".to_string());
+        (s.expr.to_string(), value)
+    });
+
+    let mut plain = if is_authorized {
+        fmt_ok!("Access allowed")
+    } else {
+        fmt_warn!("Access denied")
+    };
+    if let Some(error) = &error {
+        plain.push_str(&format!("\n{}", fmt_log!("{error}")));
+    }
+    if let Some((expr, value)) = &decisive {
+        plain.push_str(&format!("\n{}", fmt_log!("Decided by {expr} => {value}")));
+    }
+    for line in trace.to_string().lines() {
+        plain.push_str(&format!("\n{}", fmt_log!("{line}")));
+    }
+
+    let steps: Vec<_> = trace
+        .steps()
+        .iter()
+        .map(|s| {
+            serde_json::json!({
+                "depth": s.depth,
+                "expr": s.expr.to_string(),
+                "value": s.value.as_ref().map(|v| v.to_string()),
+            })
+        })
+        .collect();
+
+    opts.terminal
+        .clone()
+        .stdout()
+        .plain(plain)
+        .machine(if is_authorized { "allow" } else { "deny" })
+        .json(serde_json::json!({
+            "is_authorized": is_authorized,
+            "decisive": decisive.map(|(expr, _)| expr),
+            "error": error,
+            "trace": steps,
+        }))
+        .write_line()?;
+    Ok(())
+}
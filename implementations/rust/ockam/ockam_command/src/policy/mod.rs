@@ -8,6 +8,8 @@ use ockam_api::{config::lookup::ProjectLookup, nodes::models::policy::Policy};
 use ockam_core::api::Request;
 
 use crate::policy::delete::DeleteCommand;
+use crate::policy::evaluate::EvaluateCommand;
+use crate::policy::explain::ExplainCommand;
 use crate::policy::list::ListCommand;
 use crate::policy::show::ShowCommand;
 use crate::{policy::create::CreateCommand, util::Rpc};
@@ -15,6 +17,8 @@ use crate::{CommandGlobalOpts, Result};
 
 mod create;
 mod delete;
+mod evaluate;
+mod explain;
 mod list;
 mod show;
 
@@ -31,6 +35,8 @@ pub enum PolicySubcommand {
     Show(ShowCommand),
     Delete(DeleteCommand),
     List(ListCommand),
+    Explain(ExplainCommand),
+    Evaluate(EvaluateCommand),
 }
 
 impl PolicyCommand {
@@ -40,6 +46,8 @@ impl PolicyCommand {
             PolicySubcommand::Show(c) => c.run(opts),
             PolicySubcommand::Delete(c) => c.run(opts),
             PolicySubcommand::List(c) => c.run(opts),
+            PolicySubcommand::Explain(c) => c.run(opts),
+            PolicySubcommand::Evaluate(c) => c.run(opts),
         }
     }
 }
@@ -4,6 +4,7 @@ mod delete;
 pub(crate) mod enroll;
 mod info;
 mod list;
+mod revoke;
 mod show;
 mod ticket;
 pub mod util;
@@ -18,6 +19,7 @@ pub use delete::DeleteCommand;
 pub use enroll::EnrollCommand;
 pub use info::InfoCommand;
 pub use list::ListCommand;
+pub use revoke::RevokeCommand;
 pub use show::ShowCommand;
 pub use ticket::TicketCommand;
 pub use version::VersionCommand;
@@ -48,6 +50,7 @@ pub enum ProjectSubcommand {
     Version(VersionCommand),
     Information(InfoCommand),
     Ticket(TicketCommand),
+    Revoke(RevokeCommand),
     Addon(AddonCommand),
     Enroll(EnrollCommand),
 }
@@ -61,6 +64,7 @@ impl ProjectCommand {
             ProjectSubcommand::Show(c) => c.run(options),
             ProjectSubcommand::Version(c) => c.run(options),
             ProjectSubcommand::Ticket(c) => c.run(options),
+            ProjectSubcommand::Revoke(c) => c.run(options),
             ProjectSubcommand::Information(c) => c.run(options),
             ProjectSubcommand::Addon(c) => c.run(options),
             ProjectSubcommand::Enroll(c) => c.run(options),
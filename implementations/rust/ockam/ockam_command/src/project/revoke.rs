@@ -0,0 +1,129 @@
+use clap::Args;
+use colorful::Colorful;
+use std::time::Duration;
+
+use miette::{miette, IntoDiagnostic};
+use ockam::identity::Identifier;
+use ockam::Context;
+use ockam_api::authenticator::revocation::RevocationListClient;
+use ockam_api::cloud::ORCHESTRATOR_RESTART_TIMEOUT;
+use ockam_api::config::cli::CredentialRetrieverConfig;
+use ockam_api::DefaultAddress;
+use ockam_core::route;
+use ockam_multiaddr::MultiAddr;
+use ockam_node::RpcClient;
+
+use crate::identity::{get_identity_name, initialize_identity_if_default};
+use crate::node::util::delete_embedded_node;
+use crate::project::ticket::get_project;
+use crate::project::util::create_secure_channel_to_authority;
+use crate::util::api::{CloudOpts, TrustContextOpts};
+use crate::util::{node_rpc, Rpc};
+use crate::{docs, fmt_ok, CommandGlobalOpts};
+
+const LONG_ABOUT: &str = include_str!("./static/revoke/long_about.txt");
+const AFTER_LONG_HELP: &str = include_str!("./static/revoke/after_long_help.txt");
+
+/// Revoke the credentials of project members as an authorised enroller.
+#[derive(Clone, Debug, Args)]
+#[command(
+    long_about = docs::about(LONG_ABOUT),
+    after_long_help = docs::after_help(AFTER_LONG_HELP),
+)]
+pub struct RevokeCommand {
+    /// Orchestrator address to resolve projects present in the `at` argument
+    #[command(flatten)]
+    cloud_opts: CloudOpts,
+
+    #[command(flatten)]
+    trust_opts: TrustContextOpts,
+
+    /// Identifiers of the members whose credentials are revoked
+    #[arg(long, short, value_name = "IDENTIFIER", required = true)]
+    member: Vec<Identifier>,
+
+    #[arg(long, short, default_value = "/project/default")]
+    to: MultiAddr,
+}
+
+impl RevokeCommand {
+    pub fn run(self, options: CommandGlobalOpts) {
+        initialize_identity_if_default(&options, &self.cloud_opts.identity);
+        node_rpc(rpc, (options, self));
+    }
+}
+
+async fn rpc(ctx: Context, (opts, cmd): (CommandGlobalOpts, RevokeCommand)) -> miette::Result<()> {
+    let mut rpc = Rpc::embedded_with_trust_options(&ctx, &opts, &cmd.trust_opts).await?;
+    let identity = get_identity_name(&opts.state, &cmd.cloud_opts.identity);
+
+    let base_addr = if let Some(tc) = cmd.trust_opts.trust_context.as_ref() {
+        let tc = &opts.state.trust_contexts.read_config_from_path(tc)?;
+        let authority = tc.authority().into_diagnostic()?;
+        let addr = match authority.own_credential().into_diagnostic()? {
+            CredentialRetrieverConfig::FromCredentialIssuer(c) => &c.multiaddr,
+            _ => {
+                return Err(miette!(
+                    "Trust context must be configured with a credential issuer"
+                ));
+            }
+        };
+        create_secure_channel_to_authority(
+            &mut rpc,
+            authority
+                .identity()
+                .await
+                .into_diagnostic()?
+                .identifier()
+                .clone(),
+            addr,
+            Some(identity),
+        )
+        .await?
+    } else if let (Some(_), Some(a)) = get_project(&opts.state, &cmd.to).await? {
+        create_secure_channel_to_authority(
+            &mut rpc,
+            a.identity_id().clone(),
+            a.address(),
+            Some(identity),
+        )
+        .await?
+    } else {
+        cmd.to.clone()
+    };
+
+    let revocation_list_issuer_route = {
+        let service = MultiAddr::try_from(
+            format!("/service/{}", DefaultAddress::REVOCATION_LIST_ISSUER).as_str(),
+        )
+        .into_diagnostic()?;
+        let mut addr = base_addr.clone();
+        for proto in service.iter() {
+            addr.push_back_value(&proto).into_diagnostic()?;
+        }
+        ockam_api::local_multiaddr_to_route(&addr).ok_or(miette!("Invalid MultiAddr {addr}"))?
+    };
+    let client = RevocationListClient::new(
+        RpcClient::new(
+            route![DefaultAddress::RPC_PROXY, revocation_list_issuer_route],
+            &ctx,
+        )
+        .await
+        .into_diagnostic()?
+        .with_timeout(Duration::from_secs(ORCHESTRATOR_RESTART_TIMEOUT)),
+    );
+    client
+        .revoke_members(cmd.member.clone())
+        .await
+        .into_diagnostic()?;
+
+    delete_embedded_node(&opts, rpc.node_name()).await;
+    opts.terminal
+        .stdout()
+        .plain(fmt_ok!(
+            "The credentials of {} member(s) have been revoked",
+            cmd.member.len()
+        ))
+        .write_line()?;
+    Ok(())
+}
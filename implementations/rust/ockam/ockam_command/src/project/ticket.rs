@@ -216,7 +216,7 @@ impl Runner {
 /// Get the project authority from the first address protocol.
 ///
 /// If the first protocol is a `/project`, look up the project's config.
-async fn get_project(
+pub(crate) async fn get_project(
     cli_state: &CliState,
     input: &MultiAddr,
 ) -> Result<(Option<ProjectLookup>, Option<ProjectAuthority>)> {
@@ -16,6 +16,7 @@ use ockam_abac::Resource;
 use ockam_api::cli_state::{StateDirTrait, StateItemTrait};
 use ockam_api::nodes::models::portal::CreateInlet;
 use ockam_api::nodes::models::portal::InletStatus;
+use ockam_api::nodes::models::portal::PortalLimits;
 use ockam_core::api::{Reply, Request, Status};
 use ockam_core::errcode::{Kind, Origin};
 use ockam_core::{route, Error};
@@ -67,6 +68,18 @@ pub struct CreateCommand {
     /// Time to wait before retrying to connect to outlet.
     #[arg(long, display_order = 900, id = "RETRY", default_value = "20s", value_parser = duration_parser)]
     retry_wait: Duration,
+
+    /// Maximum number of concurrent connections accepted by the inlet.
+    #[arg(long, display_order = 901, id = "MAX_CONNECTIONS")]
+    max_connections: Option<u64>,
+
+    /// Maximum number of new connections accepted by the inlet per second.
+    #[arg(long, display_order = 901, id = "MAX_CONNECTIONS_PER_SECOND")]
+    max_connections_per_second: Option<u32>,
+
+    /// Maximum number of bytes per second transferred by all the inlet connections.
+    #[arg(long, display_order = 901, id = "MAX_BYTES_PER_SECOND")]
+    max_bytes_per_second: Option<u64>,
 }
 
 pub(crate) fn default_from_addr() -> SocketAddr {
@@ -155,6 +168,11 @@ async fn rpc(
                     payload.set_alias(a)
                 }
                 payload.set_wait_ms(cmd.connection_wait.as_millis() as u64);
+                payload.set_limits(PortalLimits::new(
+                    cmd.max_connections,
+                    cmd.max_connections_per_second,
+                    cmd.max_bytes_per_second,
+                ));
 
                 Request::post("/node/inlet").body(payload)
             };
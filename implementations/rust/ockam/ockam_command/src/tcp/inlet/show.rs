@@ -53,14 +53,28 @@ pub async fn run_impl(
         alias,
         bind_addr,
         outlet_route,
+        stats,
         ..
     } = inlet_status;
-    let plain = formatdoc! {r#"
+    let mut plain = formatdoc! {r#"
         Inlet:
           Alias: {alias}
           TCP Address: {bind_addr}
           To Outlet Address: {outlet_route}
     "#};
+    if let Some(stats) = stats {
+        plain.push_str(&format!(
+            "  Connections: {} active, {} opened, {} closed, {} rejected\n",
+            stats.connections.len(),
+            stats.connections_opened,
+            stats.connections_closed,
+            stats.connections_rejected,
+        ));
+        plain.push_str(&format!(
+            "  Bytes: {} in, {} out\n",
+            stats.bytes_in, stats.bytes_out
+        ));
+    }
     let machine = bind_addr;
     opts.terminal
         .stdout()
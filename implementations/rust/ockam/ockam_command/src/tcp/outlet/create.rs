@@ -1,4 +1,5 @@
 use std::net::SocketAddr;
+use std::time::Duration;
 
 use clap::Args;
 use colorful::Colorful;
@@ -17,7 +18,8 @@ use crate::node::{get_node_name, initialize_node_if_default};
 use crate::policy::{add_default_project_policy, has_policy};
 use crate::tcp::util::alias_parser;
 use crate::terminal::OckamColor;
-use crate::util::parsers::socket_addr_parser;
+use crate::util::duration::duration_parser;
+use crate::util::parsers::{host_port_parser, socket_addr_parser};
 use crate::util::{node_rpc, Rpc};
 use crate::{display_parse_logs, fmt_log};
 use crate::{docs, fmt_ok, CommandGlobalOpts};
@@ -43,6 +45,22 @@ pub struct CreateCommand {
     /// Assign a name to this outlet.
     #[arg(long, display_order = 900, id = "ALIAS", value_parser = alias_parser)]
     alias: Option<String>,
+
+    /// Additional `host:port` address to send raw tcp traffic to. New connections are balanced between all the targets.
+    #[arg(long = "target", display_order = 903, id = "TARGET_ADDRESS", value_parser = host_port_parser)]
+    targets: Vec<String>,
+
+    /// Strategy used to choose the target of a new connection.
+    #[arg(long, display_order = 904, value_parser = ["round-robin", "least-connections"])]
+    load_balancing: Option<String>,
+
+    /// Check the targets periodically with a TCP connection, and stop using the ones that fail.
+    #[arg(long, display_order = 905, id = "INTERVAL", value_parser = duration_parser)]
+    health_check_interval: Option<Duration>,
+
+    /// Resolve the targets hostnames again periodically, and use every address they resolve to.
+    #[arg(long, display_order = 906, id = "DNS_REFRESH_INTERVAL", value_parser = duration_parser)]
+    dns_refresh_interval: Option<Duration>,
 }
 
 impl CreateCommand {
@@ -88,12 +106,24 @@ pub async fn run_impl(
     let is_finished: Mutex<bool> = Mutex::new(false);
 
     let send_req = async {
-        let payload = CreateOutlet::new(
+        let mut payload = CreateOutlet::new(
             cmd.to,
             extract_address_value(&cmd.from)?.into(),
             cmd.alias,
             true,
         );
+        if !cmd.targets.is_empty() {
+            payload.set_targets(cmd.targets);
+        }
+        if let Some(load_balancing) = cmd.load_balancing {
+            payload.set_load_balancing(load_balancing);
+        }
+        if let Some(interval) = cmd.health_check_interval {
+            payload.set_health_check_interval(interval);
+        }
+        if let Some(interval) = cmd.dns_refresh_interval {
+            payload.set_dns_refresh_interval(interval);
+        }
         let res = send_request(&ctx, &opts, payload, node_name.clone()).await;
         *is_finished.lock().await = true;
         res
@@ -53,6 +53,17 @@ pub async fn run_impl(
         .ok_or_else(|| miette!("Invalid Outlet Address"))?;
     println!("  From Outlet: {addr}");
     println!("  To TCP: {}", outlet_status.socket_addr);
+    if let Some(targets) = outlet_status.targets.filter(|t| t.len() > 1) {
+        println!("  Targets:");
+        for target in targets {
+            println!(
+                "    {} ({}, {} connections)",
+                target.socket_addr,
+                if target.healthy { "healthy" } else { "ejected" },
+                target.connections
+            );
+        }
+    }
     Ok(())
 }
 
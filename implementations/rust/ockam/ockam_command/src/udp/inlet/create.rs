@@ -0,0 +1,171 @@
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use clap::Args;
+use colorful::Colorful;
+use miette::{miette, IntoDiagnostic};
+use tokio::sync::Mutex;
+use tokio::try_join;
+
+use ockam::identity::Identifier;
+use ockam::Context;
+use ockam_abac::Resource;
+use ockam_api::cli_state::{StateDirTrait, StateItemTrait};
+use ockam_api::nodes::models::portal::{CreateInlet, InletStatus};
+use ockam_core::api::Request;
+use ockam_core::route;
+use ockam_multiaddr::proto::Project;
+use ockam_multiaddr::{MultiAddr, Protocol as _};
+
+use crate::node::{get_node_name, initialize_node_if_default};
+use crate::policy::{add_default_project_policy, has_policy};
+use crate::tcp::util::alias_parser;
+use crate::terminal::OckamColor;
+use crate::util::duration::duration_parser;
+use crate::util::parsers::socket_addr_parser;
+use crate::util::{node_rpc, parse_node_name, process_nodes_multiaddr, Rpc};
+use crate::{display_parse_logs, docs, fmt_log, fmt_ok, CommandGlobalOpts};
+
+const AFTER_LONG_HELP: &str = include_str!("./static/create/after_long_help.txt");
+
+/// Create UDP Inlets
+#[derive(Clone, Debug, Args)]
+#[command(after_long_help = docs::after_help(AFTER_LONG_HELP))]
+pub struct CreateCommand {
+    /// Node on which to start the udp inlet.
+    #[arg(long, display_order = 900, id = "NODE")]
+    at: Option<String>,
+
+    /// Address on which to accept datagrams.
+    #[arg(long, display_order = 900, id = "SOCKET_ADDRESS", value_parser = socket_addr_parser)]
+    from: SocketAddr,
+
+    /// Route to a udp outlet.
+    #[arg(long, display_order = 900, id = "ROUTE")]
+    to: MultiAddr,
+
+    /// Authorized identity for secure channel connection
+    #[arg(long, name = "AUTHORIZED", display_order = 900)]
+    authorized: Option<Identifier>,
+
+    /// Assign a name to this inlet.
+    #[arg(long, display_order = 900, id = "ALIAS", value_parser = alias_parser)]
+    alias: Option<String>,
+
+    /// Time to wait for the outlet to be available.
+    #[arg(long, display_order = 900, id = "WAIT", default_value = "5s", value_parser = duration_parser)]
+    connection_wait: Duration,
+}
+
+impl CreateCommand {
+    pub fn run(self, opts: CommandGlobalOpts) {
+        initialize_node_if_default(&opts, &self.at);
+        node_rpc(rpc, (opts, self));
+    }
+}
+
+async fn rpc(
+    ctx: Context,
+    (opts, mut cmd): (CommandGlobalOpts, CreateCommand),
+) -> miette::Result<()> {
+    opts.terminal.write_line(&fmt_log!(
+        "Creating UDP Inlet at {}...\n",
+        cmd.from
+            .to_string()
+            .color(OckamColor::PrimaryResource.color())
+    ))?;
+    display_parse_logs(&opts);
+
+    cmd.to = process_nodes_multiaddr(&cmd.to, &opts.state)?;
+
+    let node_name = get_node_name(&opts.state, &cmd.at);
+    let node = parse_node_name(&node_name)?;
+
+    let mut rpc = Rpc::background(&ctx, &opts, &node).await?;
+    let is_finished: Mutex<bool> = Mutex::new(false);
+    let create_inlet = async {
+        let project = opts
+            .state
+            .nodes
+            .get(&node)?
+            .config()
+            .setup()
+            .project
+            .to_owned();
+        let resource = Resource::new("udp-inlet");
+        if let Some(p) = project {
+            if !has_policy(&node, &ctx, &opts, &resource).await? {
+                add_default_project_policy(&node, &ctx, &opts, p, &resource).await?;
+            }
+        }
+
+        let mut payload = if cmd.to.clone().matches(0, &[Project::CODE.into()]) {
+            if cmd.authorized.is_some() {
+                return Err(miette!("--authorized can not be used with project addresses").into());
+            }
+            CreateInlet::via_project(cmd.from.to_string(), cmd.to.clone(), route![], route![])
+        } else {
+            CreateInlet::to_node(
+                cmd.from.to_string(),
+                cmd.to.clone(),
+                route![],
+                route![],
+                cmd.authorized.clone(),
+            )
+        };
+        if let Some(a) = cmd.alias.as_ref() {
+            payload.set_alias(a)
+        }
+        payload.set_wait_ms(cmd.connection_wait.as_millis() as u64);
+
+        let inlet: InletStatus = rpc
+            .ask(Request::post("/node/udp/inlet").body(payload))
+            .await?;
+        *is_finished.lock().await = true;
+        Ok(inlet)
+    };
+
+    let output_messages = vec![
+        format!(
+            "Creating UDP Inlet on {}...",
+            &node.to_string().color(OckamColor::PrimaryResource.color())
+        ),
+        format!(
+            "Establishing connection to outlet {}...",
+            &cmd.to
+                .to_string()
+                .color(OckamColor::PrimaryResource.color())
+        ),
+    ];
+    let progress_output = opts
+        .terminal
+        .progress_output(&output_messages, &is_finished);
+
+    let (inlet, _) = try_join!(create_inlet, progress_output)?;
+
+    let machine_output = inlet.bind_addr.to_string();
+    let json_output = serde_json::to_string_pretty(&inlet).into_diagnostic()?;
+
+    opts.terminal
+        .stdout()
+        .plain(
+            fmt_ok!(
+                "UDP Inlet {} on node {} is now sending datagrams\n",
+                &inlet
+                    .bind_addr
+                    .to_string()
+                    .color(OckamColor::PrimaryResource.color()),
+                &node.to_string().color(OckamColor::PrimaryResource.color())
+            ) + &fmt_log!(
+                "to the outlet at {}",
+                &cmd.to
+                    .to_string()
+                    .color(OckamColor::PrimaryResource.color())
+            ),
+        )
+        .machine(machine_output)
+        .json(json_output)
+        .write_line()?;
+
+    Ok(())
+}
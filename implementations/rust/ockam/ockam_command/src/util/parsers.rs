@@ -24,6 +24,17 @@ pub(crate) fn socket_addr_parser(input: &str) -> Result<SocketAddr> {
         .map_err(|e| miette!("cannot parse the address {address} as a socket address: {e}"))?)
 }
 
+/// Helper function for parsing a `host:port` address from user input, without resolving the host
+/// It is possible to just input a `port`. In that case the address will be assumed to be
+/// 127.0.0.1:<port>
+pub(crate) fn host_port_parser(input: &str) -> Result<String> {
+    let (host, port) = input.rsplit_once(':').unwrap_or(("127.0.0.1", input));
+    if host.is_empty() || port.parse::<u16>().is_err() {
+        return Err(miette!("cannot parse the address {input} as a host:port address").into());
+    }
+    Ok(format!("{host}:{port}"))
+}
+
 /// Helper fn for parsing an identity from user input by using
 /// [`ockam_identity::Identifier::from_str()`]
 pub(crate) fn identity_identifier_parser(input: &str) -> Result<Identifier> {
@@ -97,4 +108,18 @@ mod tests {
         let invalid_input = "192,166,0.1:9999";
         assert!(socket_addr_parser(invalid_input).is_err());
     }
+
+    #[test]
+    fn test_host_port() {
+        assert_eq!(host_port_parser("9000").unwrap(), "127.0.0.1:9000");
+        assert_eq!(
+            host_port_parser("unresolvable.invalid:9999").unwrap(),
+            "unresolvable.invalid:9999"
+        );
+        assert_eq!(host_port_parser("[::1]:9999").unwrap(), "[::1]:9999");
+
+        assert!(host_port_parser("invalid").is_err());
+        assert!(host_port_parser("localhost:invalid").is_err());
+        assert!(host_port_parser(":9999").is_err());
+    }
 }
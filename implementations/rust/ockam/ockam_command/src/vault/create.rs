@@ -28,6 +28,10 @@ pub struct CreateCommand {
 
     #[arg(long, default_value = "false")]
     aws_kms: bool,
+
+    /// Encrypt the vault storage with a key set with OCKAM_VAULT_PASSPHRASE or OCKAM_VAULT_KEY_FILE
+    #[arg(long, default_value = "false", conflicts_with = "aws_kms")]
+    encrypted: bool,
 }
 
 impl CreateCommand {
@@ -48,8 +52,17 @@ async fn run_impl(
     opts: CommandGlobalOpts,
     cmd: CreateCommand,
 ) -> miette::Result<()> {
-    let CreateCommand { name, aws_kms, .. } = cmd;
-    let config = cli_state::VaultConfig::new(aws_kms)?;
+    let CreateCommand {
+        name,
+        aws_kms,
+        encrypted,
+        ..
+    } = cmd;
+    let config = if encrypted {
+        cli_state::VaultConfig::encrypted()
+    } else {
+        cli_state::VaultConfig::new(aws_kms)?
+    };
     if opts.state.vaults.is_empty()? {
         opts.terminal.write_line(&fmt_info!(
             "This is the first vault to be created in this environment. It will be set as the default vault"
@@ -0,0 +1,94 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use colorful::Colorful;
+use miette::{miette, IntoDiagnostic};
+
+use ockam::Context;
+use ockam_api::cli_state::traits::StateDirTrait;
+use ockam_api::cli_state::{storage_key_from_env, OCKAM_VAULT_KEY_FILE, OCKAM_VAULT_PASSPHRASE};
+use ockam_core::env::get_env;
+use ockam_vault::storage::StorageKey;
+
+use crate::util::node_rpc;
+use crate::{docs, fmt_ok, CommandGlobalOpts};
+
+const LONG_ABOUT: &str = include_str!("./static/migrate/long_about.txt");
+const AFTER_LONG_HELP: &str = include_str!("./static/migrate/after_long_help.txt");
+
+/// Encrypt the storage of a vault, or change its encryption key
+#[derive(Clone, Debug, Args)]
+#[command(
+    long_about = docs::about(LONG_ABOUT),
+    after_long_help = docs::after_help(AFTER_LONG_HELP)
+)]
+pub struct MigrateCommand {
+    /// Name of the vault
+    name: Option<String>,
+
+    /// Path to a file containing the new 32 bytes key, either hex-encoded or raw
+    #[arg(long, value_name = "PATH", conflicts_with = "passphrase_env")]
+    key_file: Option<PathBuf>,
+
+    /// Name of the environment variable containing the new passphrase
+    #[arg(long, value_name = "VARIABLE")]
+    passphrase_env: Option<String>,
+}
+
+impl MigrateCommand {
+    pub fn run(self, opts: CommandGlobalOpts) {
+        node_rpc(rpc, (opts, self));
+    }
+
+    fn new_key(&self) -> miette::Result<StorageKey> {
+        if let Some(path) = &self.key_file {
+            return StorageKey::from_key_file(path).into_diagnostic();
+        }
+        if let Some(variable) = &self.passphrase_env {
+            let passphrase = get_env::<String>(variable)
+                .into_diagnostic()?
+                .ok_or_else(|| miette!("The environment variable {variable} is not set"))?;
+            return Ok(StorageKey::Passphrase(passphrase));
+        }
+        storage_key_from_env()?.ok_or_else(|| {
+            miette!(
+                "No key was specified. Use --key-file, --passphrase-env or set {OCKAM_VAULT_PASSPHRASE} or {OCKAM_VAULT_KEY_FILE}"
+            )
+        })
+    }
+}
+
+async fn rpc(
+    mut ctx: Context,
+    (opts, cmd): (CommandGlobalOpts, MigrateCommand),
+) -> miette::Result<()> {
+    run_impl(&mut ctx, opts, cmd).await
+}
+
+async fn run_impl(
+    _ctx: &mut Context,
+    opts: CommandGlobalOpts,
+    cmd: MigrateCommand,
+) -> miette::Result<()> {
+    let state = match &cmd.name {
+        Some(name) => opts.state.vaults.get(name)?,
+        None => opts.state.vaults.default()?,
+    };
+    let new_key = cmd.new_key()?;
+    let was_encrypted = state.is_encrypted();
+    let state = state.rotate_key(new_key).await?;
+
+    let name = state.name();
+    let message = if was_encrypted {
+        format!("The encryption key of the vault '{name}' has been changed")
+    } else {
+        format!("The vault '{name}' is now encrypted")
+    };
+    opts.terminal
+        .stdout()
+        .plain(fmt_ok!("{message}"))
+        .machine(name)
+        .json(serde_json::json!({ "vault": { "name": name, "encrypted": true } }))
+        .write_line()?;
+    Ok(())
+}
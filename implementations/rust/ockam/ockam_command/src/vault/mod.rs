@@ -3,6 +3,7 @@ mod create;
 mod default;
 mod delete;
 mod list;
+mod migrate;
 mod show;
 
 use crate::vault::attach_key::AttachKeyCommand;
@@ -10,6 +11,7 @@ use crate::vault::create::CreateCommand;
 use crate::vault::default::DefaultCommand;
 use crate::vault::delete::DeleteCommand;
 use crate::vault::list::ListCommand;
+use crate::vault::migrate::MigrateCommand;
 use crate::vault::show::ShowCommand;
 use crate::{docs, CommandGlobalOpts};
 
@@ -39,6 +41,7 @@ pub enum VaultSubcommand {
     Delete(DeleteCommand),
     List(ListCommand),
     Default(DefaultCommand),
+    Migrate(MigrateCommand),
 }
 
 impl VaultCommand {
@@ -50,6 +53,7 @@ impl VaultCommand {
             VaultSubcommand::List(cmd) => cmd.run(opts),
             VaultSubcommand::Delete(cmd) => cmd.run(opts),
             VaultSubcommand::Default(cmd) => cmd.run(opts),
+            VaultSubcommand::Migrate(cmd) => cmd.run(opts),
         }
     }
 }
@@ -0,0 +1,61 @@
+#!/bin/bash
+
+# ===== SETUP
+
+setup() {
+  load load/base.bash
+  load_bats_ext
+  setup_home_dir
+}
+
+teardown() {
+  teardown_home_dir
+}
+
+# ===== TESTS
+
+@test "policy - explain an expression" {
+  run_success "$OCKAM" policy explain --expression '(= subject.app "app1")' --attribute app=app1
+  assert_output --partial "allow"
+
+  run_success "$OCKAM" policy explain --expression '(= subject.app "app1")' --attribute app=app2
+  assert_output --partial "deny"
+}
+
+@test "policy - evaluate the policy of a node" {
+  n="$(random_str)"
+  run_success "$OCKAM" node create "$n"
+  run_success "$OCKAM" policy create --at "$n" --resource tcp-outlet --expression '(= subject.app "app1")'
+
+  run_success "$OCKAM" policy evaluate --at "$n" --resource tcp-outlet --attribute app=app1
+  assert_output --partial "allow"
+
+  run_success "$OCKAM" policy evaluate --at "$n" --resource tcp-outlet --attribute app=app2
+  assert_output --partial "deny"
+
+  run_failure "$OCKAM" policy evaluate --at "$n" --resource tcp-inlet --attribute app=app1
+}
+
+@test "policy - evaluate with a verified credential" {
+  n="$(random_str)"
+  run_success "$OCKAM" node create "$n"
+  run_success "$OCKAM" policy create --at "$n" --resource tcp-outlet --expression '(= subject.app "app1")'
+
+  run_success "$OCKAM" identity create authority
+  authority=$($OCKAM identity show authority)
+  run_success "$OCKAM" identity create other
+  other=$($OCKAM identity show other)
+  run_success "$OCKAM" identity create client
+  client=$($OCKAM identity show client)
+
+  "$OCKAM" credential issue --as authority --for "$client" --attribute app=app1 --encoding hex >"$OCKAM_HOME/credential"
+
+  # A credential is only used along with its issuer
+  run_failure "$OCKAM" policy evaluate --at "$n" --resource tcp-outlet --credential-path "$OCKAM_HOME/credential"
+
+  run_success "$OCKAM" policy evaluate --at "$n" --resource tcp-outlet --credential-path "$OCKAM_HOME/credential" --issuer "$authority"
+  assert_output --partial "allow"
+
+  run_failure "$OCKAM" policy evaluate --at "$n" --resource tcp-outlet --credential-path "$OCKAM_HOME/credential" --issuer "$other"
+  assert_output --partial "The credential is not valid"
+}
@@ -67,6 +67,34 @@ teardown() {
   assert_output --partial "not found"
 }
 
+@test "portals - udp inlet and outlet CRUD" {
+  outlet_port="$(random_port)"
+  inlet_port="$(random_port)"
+
+  run_success "$OCKAM" node create n1
+  run_success "$OCKAM" node create n2
+
+  run_success $OCKAM udp-outlet create --at /node/n1 --to "127.0.0.1:$outlet_port" --alias "test-udp-outlet"
+  assert_output --partial "/service/udp_outlet"
+
+  run_success $OCKAM udp-outlet show test-udp-outlet --at /node/n1
+  assert_output --regexp "To UDP: 127.0.0.1:$outlet_port"
+
+  run_success $OCKAM udp-inlet create --at /node/n2 --from 127.0.0.1:$inlet_port --to /node/n1/service/udp_outlet --alias "test-udp-inlet"
+
+  run_success $OCKAM udp-inlet list --at /node/n2
+  assert_output --partial "test-udp-inlet"
+  assert_output --partial "127.0.0.1:$inlet_port"
+
+  run_success $OCKAM udp-inlet delete "test-udp-inlet" --at /node/n2 --yes
+  run_failure $OCKAM udp-inlet delete "test-udp-inlet" --at /node/n2 --yes
+  assert_output --partial "not found"
+
+  run_success $OCKAM udp-outlet delete "test-udp-outlet" --at /node/n1 --yes
+  run_failure $OCKAM udp-outlet delete "test-udp-outlet" --at /node/n1 --yes
+  assert_output --partial "not found"
+}
+
 @test "portals - list inlets on a node" {
   port="$(random_port)"
   run_success "$OCKAM" node create n1
@@ -122,6 +150,21 @@ teardown() {
   assert_output --partial "not found"
 }
 
+@test "portals - show a tcp outlet with several targets" {
+  port1="$(random_port)"
+  port2="$(random_port)"
+  run_success "$OCKAM" node create n1
+
+  run_success $OCKAM tcp-outlet create --at /node/n1 --to "127.0.0.1:$port1" --target "127.0.0.1:$port2" \
+    --load-balancing least-connections --alias "test-outlet"
+  assert_output --partial "/service/outlet"
+
+  run_success $OCKAM tcp-outlet show "test-outlet"
+  assert_output --partial "Targets:"
+  assert_output --partial "127.0.0.1:$port1 (healthy, 0 connections)"
+  assert_output --partial "127.0.0.1:$port2 (healthy, 0 connections)"
+}
+
 @test "portals - create an inlet/outlet pair and move tcp traffic through it" {
   port="$(random_port)"
   run_success "$OCKAM" node create n1
@@ -133,6 +176,22 @@ teardown() {
   run_success curl --fail --head --max-time 10 "127.0.0.1:$port"
 }
 
+@test "portals - limit the connections of a tcp inlet and count its traffic" {
+  port="$(random_port)"
+  run_success "$OCKAM" node create n1
+  run_success "$OCKAM" node create n2
+
+  run_success "$OCKAM" tcp-outlet create --at /node/n1 --to 127.0.0.1:5000
+  run_success "$OCKAM" tcp-inlet create --at /node/n2 --from "127.0.0.1:$port" --to /node/n1/service/outlet \
+    --alias "test-inlet" --max-connections 5 --max-bytes-per-second 1000000
+
+  run_success curl --fail --head --max-time 10 "127.0.0.1:$port"
+
+  run_success $OCKAM tcp-inlet show "test-inlet" --at /node/n2
+  assert_output --partial "1 opened"
+  assert_output --partial "0 rejected"
+}
+
 @test "portals - create an inlet/outlet pair with relay through a relay and move tcp traffic through it" {
   port="$(random_port)"
   run_success "$OCKAM" node create relay
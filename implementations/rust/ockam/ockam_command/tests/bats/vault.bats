@@ -57,3 +57,29 @@ teardown() {
   run_failure "$OCKAM" vault show "${v}"
   run_success "$OCKAM" identity show "${i}"
 }
+
+@test "vault - encrypted storage" {
+  v=$(random_str)
+  i=$(random_str)
+
+  run_success "$OCKAM" vault create "${v}"
+  run_success "$OCKAM" identity create "${i}" --vault "${v}"
+
+  # Migrate the plaintext vault to an encrypted vault
+  export OCKAM_VAULT_PASSPHRASE="passphrase"
+  run_success "$OCKAM" vault migrate "${v}"
+  run_success "$OCKAM" vault show "${v}"
+  assert_output --partial "Encrypted: true"
+
+  # Rotate the key
+  key_file="$OCKAM_HOME/vault-key"
+  printf '%064d' 7 >"$key_file"
+  run_success "$OCKAM" vault migrate "${v}" --key-file "$key_file"
+
+  # The vault can only be opened with the new key
+  run_failure "$OCKAM" identity create "$(random_str)" --vault "${v}"
+  unset OCKAM_VAULT_PASSPHRASE
+  export OCKAM_VAULT_KEY_FILE="$key_file"
+  run_success "$OCKAM" identity create "$(random_str)" --vault "${v}"
+  unset OCKAM_VAULT_KEY_FILE
+}
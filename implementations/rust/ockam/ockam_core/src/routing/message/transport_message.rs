@@ -1,6 +1,13 @@
-use crate::{compat::vec::Vec, Message, Route};
+use crate::{compat::string::String, compat::vec::Vec, Message, Route};
 use core::fmt::{self, Display, Formatter};
-use serde::{Deserialize, Serialize};
+use serde::de::{Error as _, SeqAccess, Visitor};
+use serde::ser::SerializeTuple;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Version of the transport messages without a tracing context
+const VERSION_1: u8 = 1;
+/// First version of the transport messages carrying a tracing context
+const VERSION_2: u8 = 2;
 
 /// A generic transport message type.
 ///
@@ -15,7 +22,16 @@ use serde::{Deserialize, Serialize};
 ///
 /// See `ockam_transport_tcp::workers::sender::TcpSendWorker` for a usage example.
 ///
-#[derive(Serialize, Deserialize, Debug, Clone, Hash, Ord, PartialOrd, Eq, PartialEq, Message)]
+/// # Encoding
+///
+/// The tracing context is only encoded in messages of version 2 and above.
+/// Some implementations, the Elixir one for example, only decode version 1
+/// messages, so a message is only upgraded to version 2 with
+/// [`TransportMessage::upgrade_to_v2`], when all the nodes on its route
+/// support it. The tracing context of a version 1 message is dropped when it
+/// is encoded.
+///
+#[derive(Debug, Clone, Hash, Ord, PartialOrd, Eq, PartialEq, Message)]
 pub struct TransportMessage {
     /// The transport protocol version.
     pub version: u8,
@@ -28,6 +44,10 @@ pub struct TransportMessage {
     pub return_route: Route,
     /// The message payload.
     pub payload: Vec<u8>,
+    /// W3C `traceparent` of the span which sent this message.
+    ///
+    /// This field is only encoded in messages of version 2 and above.
+    pub tracing_context: Option<String>,
 }
 
 impl TransportMessage {
@@ -38,12 +58,27 @@ impl TransportMessage {
         payload: Vec<u8>,
     ) -> Self {
         Self {
-            version: 1,
+            version: VERSION_1,
             onward_route: onward_route.into(),
             return_route: return_route.into(),
             payload,
+            tracing_context: None,
         }
     }
+
+    /// Set the W3C `traceparent` of the span sending this message.
+    pub fn with_tracing_context(mut self, tracing_context: Option<String>) -> Self {
+        self.tracing_context = tracing_context;
+        self
+    }
+
+    /// Upgrade this message to version 2, so that its tracing context is encoded.
+    ///
+    /// Nodes only supporting version 1 reject the message.
+    pub fn upgrade_to_v2(mut self) -> Self {
+        self.version = self.version.max(VERSION_2);
+        self
+    }
 }
 
 impl Display for TransportMessage {
@@ -55,3 +90,131 @@ impl Display for TransportMessage {
         )
     }
 }
+
+impl Serialize for TransportMessage {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let has_tracing_context = self.version >= VERSION_2;
+
+        let mut tuple = serializer.serialize_tuple(if has_tracing_context { 5 } else { 4 })?;
+        tuple.serialize_element(&self.version)?;
+        tuple.serialize_element(&self.onward_route)?;
+        tuple.serialize_element(&self.return_route)?;
+        tuple.serialize_element(&self.payload)?;
+        if has_tracing_context {
+            tuple.serialize_element(&self.tracing_context)?;
+        }
+        tuple.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for TransportMessage {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct TransportMessageVisitor;
+
+        impl<'de> Visitor<'de> for TransportMessageVisitor {
+            type Value = TransportMessage;
+
+            fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+                write!(f, "a transport message")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let version: u8 = seq
+                    .next_element()?
+                    .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+                let onward_route = seq
+                    .next_element()?
+                    .ok_or_else(|| A::Error::invalid_length(1, &self))?;
+                let return_route = seq
+                    .next_element()?
+                    .ok_or_else(|| A::Error::invalid_length(2, &self))?;
+                let payload = seq
+                    .next_element()?
+                    .ok_or_else(|| A::Error::invalid_length(3, &self))?;
+                let tracing_context = if version >= VERSION_2 {
+                    seq.next_element()?
+                        .ok_or_else(|| A::Error::invalid_length(4, &self))?
+                } else {
+                    None
+                };
+                Ok(TransportMessage {
+                    version,
+                    onward_route,
+                    return_route,
+                    payload,
+                    tracing_context,
+                })
+            }
+        }
+
+        deserializer.deserialize_tuple(5, TransportMessageVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{route, Decodable, Encodable};
+
+    /// Encoding of the transport messages before the tracing context was added
+    #[derive(Serialize)]
+    struct TransportMessageV1 {
+        version: u8,
+        onward_route: Route,
+        return_route: Route,
+        payload: Vec<u8>,
+    }
+
+    #[test]
+    fn test_v1_encoding_is_unchanged() {
+        let msg = TransportMessage::v1(route!["a", "b"], route!["c"], vec![1, 2, 3]);
+        let legacy = TransportMessageV1 {
+            version: 1,
+            onward_route: route!["a", "b"],
+            return_route: route!["c"],
+            payload: vec![1, 2, 3],
+        };
+
+        let encoded = msg.encode().unwrap();
+        assert_eq!(encoded, serde_bare::to_vec(&legacy).unwrap());
+        assert_eq!(TransportMessage::decode(&encoded).unwrap(), msg);
+    }
+
+    #[test]
+    fn test_tracing_context_round_trip() {
+        let traceparent = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let msg = TransportMessage::v1(route!["a"], route!["b"], vec![1])
+            .with_tracing_context(Some(traceparent.into()))
+            .upgrade_to_v2();
+        assert_eq!(msg.version, 2);
+
+        let decoded = TransportMessage::decode(&msg.encode().unwrap()).unwrap();
+        assert_eq!(decoded.version, 2);
+        assert_eq!(decoded.tracing_context.as_deref(), Some(traceparent));
+        assert_eq!(decoded, msg);
+    }
+
+    #[test]
+    fn test_tracing_context_is_dropped_from_v1_messages() {
+        let traceparent = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let msg = TransportMessage::v1(route!["a"], route!["b"], vec![1])
+            .with_tracing_context(Some(traceparent.into()));
+
+        let decoded = TransportMessage::decode(&msg.encode().unwrap()).unwrap();
+        assert_eq!(decoded.version, 1);
+        assert_eq!(decoded.tracing_context, None);
+        assert_eq!(
+            decoded,
+            TransportMessage::v1(route!["a"], route!["b"], vec![1])
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::credentials::storage::RevocationListsRepository;
 use crate::models::{CredentialData, PurposeKeyAttestationData};
 use crate::{CredentialsCreation, CredentialsVerification, IdentitiesRepository, PurposeKeys};
 
@@ -20,6 +21,7 @@ pub struct Credentials {
     verifying_vault: Arc<dyn VerifyingVault>,
     purpose_keys: Arc<PurposeKeys>,
     identities_repository: Arc<dyn IdentitiesRepository>,
+    revocation_lists_repository: Arc<dyn RevocationListsRepository>,
 }
 
 impl Credentials {
@@ -29,12 +31,14 @@ impl Credentials {
         verifying_vault: Arc<dyn VerifyingVault>,
         purpose_keys: Arc<PurposeKeys>,
         identities_repository: Arc<dyn IdentitiesRepository>,
+        revocation_lists_repository: Arc<dyn RevocationListsRepository>,
     ) -> Self {
         Self {
             credential_vault,
             verifying_vault,
             purpose_keys,
             identities_repository,
+            revocation_lists_repository,
         }
     }
 
@@ -48,6 +52,11 @@ impl Credentials {
         self.identities_repository.clone()
     }
 
+    /// [`RevocationListsRepository`]
+    pub fn revocation_lists_repository(&self) -> Arc<dyn RevocationListsRepository> {
+        self.revocation_lists_repository.clone()
+    }
+
     /// Return [`CredentialsCreation`]
     pub fn credentials_creation(&self) -> Arc<CredentialsCreation> {
         Arc::new(CredentialsCreation::new(
@@ -55,6 +64,7 @@ impl Credentials {
             self.credential_vault.clone(),
             self.verifying_vault.clone(),
             self.identities_repository.clone(),
+            self.revocation_lists_repository.clone(),
         ))
     }
 
@@ -64,6 +74,7 @@ impl Credentials {
             self.purpose_keys.purpose_keys_verification(),
             self.verifying_vault.clone(),
             self.identities_repository.clone(),
+            self.revocation_lists_repository.clone(),
         ))
     }
 }
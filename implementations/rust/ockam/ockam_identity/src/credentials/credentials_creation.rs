@@ -1,12 +1,15 @@
+use crate::credentials::storage::RevocationListsRepository;
 use crate::models::{
-    Attributes, Credential, CredentialAndPurposeKey, CredentialData, CredentialSignature,
-    Identifier, VersionedData,
+    Attributes, Credential, CredentialAndPurposeKey, CredentialData, CredentialHash,
+    CredentialSignature, Identifier, RevocationList, RevocationListAndPurposeKey,
+    RevocationListData, VersionedData,
 };
 use crate::utils::{add_seconds, now};
 use crate::{IdentitiesRepository, Identity, Purpose, PurposeKeysCreation};
 
 use core::time::Duration;
 use ockam_core::compat::sync::Arc;
+use ockam_core::compat::vec::Vec;
 use ockam_core::Result;
 use ockam_vault::{SigningVault, VerifyingVault};
 
@@ -16,6 +19,7 @@ pub struct CredentialsCreation {
     credential_vault: Arc<dyn SigningVault>,
     verifying_vault: Arc<dyn VerifyingVault>,
     identities_repository: Arc<dyn IdentitiesRepository>,
+    revocation_lists_repository: Arc<dyn RevocationListsRepository>,
 }
 
 impl CredentialsCreation {
@@ -25,12 +29,14 @@ impl CredentialsCreation {
         credential_vault: Arc<dyn SigningVault>,
         verifying_vault: Arc<dyn VerifyingVault>,
         identities_repository: Arc<dyn IdentitiesRepository>,
+        revocation_lists_repository: Arc<dyn RevocationListsRepository>,
     ) -> Self {
         Self {
             purpose_keys_creation,
             verifying_vault,
             credential_vault,
             identities_repository,
+            revocation_lists_repository,
         }
     }
 
@@ -102,4 +108,54 @@ impl CredentialsCreation {
 
         Ok(res)
     }
+
+    /// Issue a [`RevocationList`] replacing the previous one issued by that Authority.
+    /// The new list is also stored locally so that it can be served to other parties
+    pub async fn issue_revocation_list(
+        &self,
+        issuer: &Identifier,
+        revoked_subjects: Vec<Identifier>,
+        revoked_credentials: Vec<CredentialHash>,
+    ) -> Result<RevocationListAndPurposeKey> {
+        let issuer_purpose_key = self
+            .purpose_keys_creation
+            .get_or_create_purpose_key(issuer, Purpose::Credentials)
+            .await?;
+
+        let revocation_list_data = RevocationListData {
+            revoked_subjects,
+            revoked_credentials,
+            created_at: now()?,
+        };
+        let revocation_list_data = minicbor::to_vec(revocation_list_data)?;
+
+        let versioned_data = VersionedData {
+            version: 1,
+            data: revocation_list_data,
+        };
+        let versioned_data = minicbor::to_vec(&versioned_data)?;
+
+        let versioned_data_hash = self.verifying_vault.sha256(&versioned_data).await?;
+
+        let signature = self
+            .credential_vault
+            .sign(issuer_purpose_key.key_id(), &versioned_data_hash)
+            .await?;
+        let signature =
+            CredentialSignature::try_from_signature(signature, issuer_purpose_key.stype())?;
+
+        let res = RevocationListAndPurposeKey {
+            revocation_list: RevocationList {
+                data: versioned_data,
+                signature,
+            },
+            purpose_key_attestation: issuer_purpose_key.attestation().clone(),
+        };
+
+        self.revocation_lists_repository
+            .set_revocation_list(issuer, &res)
+            .await?;
+
+        Ok(res)
+    }
 }
@@ -8,7 +8,7 @@ use ockam_node::{Context, WorkerBuilder};
 
 use crate::credentials::credentials_server_worker::CredentialsServerWorker;
 use crate::credentials::Credentials;
-use crate::models::{CredentialAndPurposeKey, Identifier};
+use crate::models::{CredentialAndPurposeKey, Identifier, RevocationListAndPurposeKey};
 use crate::{IdentitySecureChannelLocalInfo, TrustContext};
 
 use async_trait::async_trait;
@@ -37,6 +37,23 @@ pub trait CredentialsServer: Send + Sync {
         credential: CredentialAndPurposeKey,
     ) -> Result<()>;
 
+    /// Fetch the latest revocation list known to the other party, verify it and store it
+    /// if it's newer than ours. Route shall use secure channel
+    async fn fetch_revocation_list(
+        &self,
+        ctx: &Context,
+        route: Route,
+        authorities: &[Identifier],
+    ) -> Result<()>;
+
+    /// Present a revocation list to other party, route shall use secure channel
+    async fn present_revocation_list(
+        &self,
+        ctx: &Context,
+        route: Route,
+        revocation_list: RevocationListAndPurposeKey,
+    ) -> Result<()>;
+
     /// Start this service as a worker
     async fn start(
         &self,
@@ -133,6 +150,72 @@ impl CredentialsServer for CredentialsServerModule {
         }
     }
 
+    /// Fetch the latest revocation list known to the other party, verify it and store it
+    /// if it's newer than ours
+    async fn fetch_revocation_list(
+        &self,
+        ctx: &Context,
+        route: Route,
+        authorities: &[Identifier],
+    ) -> Result<()> {
+        let buf = request(
+            ctx,
+            "credential",
+            None,
+            route,
+            Request::get("revocation_list"),
+        )
+        .await?;
+
+        let mut dec = Decoder::new(&buf);
+        let res: Response = dec.decode()?;
+        match res.status() {
+            Some(Status::Ok) => {}
+            // The other party doesn't have any revocation list yet
+            Some(Status::NotFound) => return Ok(()),
+            _ => {
+                return Err(Error::new(
+                    Origin::Application,
+                    Kind::Invalid,
+                    "revocation list retrieval failed",
+                ));
+            }
+        }
+
+        let revocation_list: RevocationListAndPurposeKey = dec.decode()?;
+        self.credentials
+            .credentials_verification()
+            .receive_revocation_list(authorities, &revocation_list)
+            .await
+    }
+
+    /// Present a revocation list to other party, route shall use secure channel
+    async fn present_revocation_list(
+        &self,
+        ctx: &Context,
+        route: Route,
+        revocation_list: RevocationListAndPurposeKey,
+    ) -> Result<()> {
+        let buf = request(
+            ctx,
+            "credential",
+            None,
+            route,
+            Request::post("actions/present_revocation_list").body(revocation_list),
+        )
+        .await?;
+
+        let res: Response = minicbor::decode(&buf)?;
+        match res.status() {
+            Some(Status::Ok) => Ok(()),
+            _ => Err(Error::new(
+                Origin::Application,
+                Kind::Invalid,
+                "revocation list presentation failed",
+            )),
+        }
+    }
+
     /// Start worker that will be available to receive others attributes and put them into storage,
     /// after successful verification
     async fn start(
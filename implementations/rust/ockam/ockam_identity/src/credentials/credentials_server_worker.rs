@@ -6,7 +6,7 @@ use ockam_core::{Result, Routed, Worker};
 use ockam_node::Context;
 
 use crate::credentials::Credentials;
-use crate::models::{CredentialAndPurposeKey, Identifier};
+use crate::models::{CredentialAndPurposeKey, Identifier, RevocationListAndPurposeKey};
 use crate::{IdentitySecureChannelLocalInfo, TrustContext};
 
 use minicbor::Decoder;
@@ -145,6 +145,52 @@ impl CredentialsServerWorker {
                     }
                 }
             }
+            (Get, ["revocation_list"]) => {
+                debug!("Received revocation list request from {}", sender);
+                match self.trust_context.authority() {
+                    Ok(authority) => {
+                        let revocation_list = self
+                            .credentials
+                            .revocation_lists_repository()
+                            .retrieve_revocation_list(authority.identifier())
+                            .await?;
+                        match revocation_list {
+                            Some(revocation_list) => {
+                                Response::ok(req.id()).body(revocation_list).to_vec()?
+                            }
+                            None => Response::not_found(req.id()).to_vec()?,
+                        }
+                    }
+                    // Without an authority there is no revocation list to serve
+                    Err(err) => {
+                        Self::bad_request(req.id(), req.path(), &err.to_string()).to_vec()?
+                    }
+                }
+            }
+            (Post, ["actions", "present_revocation_list"]) => {
+                debug!("Received revocation list presentation from {}", sender);
+                let revocation_list: RevocationListAndPurposeKey = dec.decode()?;
+
+                let res = self
+                    .credentials
+                    .credentials_verification()
+                    .receive_revocation_list(
+                        self.trust_context.authorities().await?.as_slice(),
+                        &revocation_list,
+                    )
+                    .await;
+
+                match res {
+                    Ok(()) => Response::ok(req.id()).to_vec()?,
+                    Err(err) => {
+                        debug!(
+                            "Revocation list presentation processing error: {} from {}",
+                            err, sender
+                        );
+                        Self::bad_request(req.id(), req.path(), &err.to_string()).to_vec()?
+                    }
+                }
+            }
 
             // ==*== Catch-all for Unimplemented APIs ==*==
             _ => {
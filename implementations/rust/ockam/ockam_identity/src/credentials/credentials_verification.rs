@@ -1,5 +1,10 @@
+use crate::credentials::storage::RevocationListsRepository;
 use crate::identities::AttributesEntry;
-use crate::models::{CredentialAndPurposeKey, CredentialData, Identifier, PurposePublicKey};
+use crate::models::{
+    CredentialAndPurposeKey, CredentialData, CredentialHash, CredentialSignature, Identifier,
+    PurposeKeyAttestation, PurposeKeyAttestationData, PurposePublicKey,
+    RevocationListAndPurposeKey, RevocationListData,
+};
 use crate::utils::now;
 use crate::{
     CredentialAndPurposeKeyData, IdentitiesRepository, IdentityError, PurposeKeysVerification,
@@ -21,6 +26,7 @@ pub struct CredentialsVerification {
     purpose_keys_verification: Arc<PurposeKeysVerification>,
     verifying_vault: Arc<dyn VerifyingVault>,
     identities_repository: Arc<dyn IdentitiesRepository>,
+    revocation_lists_repository: Arc<dyn RevocationListsRepository>,
 }
 
 impl CredentialsVerification {
@@ -29,11 +35,13 @@ impl CredentialsVerification {
         purpose_keys_verification: Arc<PurposeKeysVerification>,
         verifying_vault: Arc<dyn VerifyingVault>,
         identities_repository: Arc<dyn IdentitiesRepository>,
+        revocation_lists_repository: Arc<dyn RevocationListsRepository>,
     ) -> Self {
         Self {
             purpose_keys_verification,
             verifying_vault,
             identities_repository,
+            revocation_lists_repository,
         }
     }
 
@@ -41,6 +49,11 @@ impl CredentialsVerification {
     pub fn identities_repository(&self) -> Arc<dyn IdentitiesRepository> {
         self.identities_repository.clone()
     }
+
+    /// [`RevocationListsRepository`]
+    pub fn revocation_lists_repository(&self) -> Arc<dyn RevocationListsRepository> {
+        self.revocation_lists_repository.clone()
+    }
 }
 
 impl CredentialsVerification {
@@ -52,47 +65,16 @@ impl CredentialsVerification {
         authorities: &[Identifier],
         credential_and_purpose_key: &CredentialAndPurposeKey,
     ) -> Result<CredentialAndPurposeKeyData> {
-        let purpose_key_data = self
-            .purpose_keys_verification
-            .verify_purpose_key_attestation(
-                None,
+        let (purpose_key_data, versioned_data_hash) = self
+            .verify_signed_data(
+                authorities,
                 &credential_and_purpose_key.purpose_key_attestation,
+                &credential_and_purpose_key.credential.data,
+                &credential_and_purpose_key.credential.signature,
+                IdentityError::CredentialVerificationFailed,
             )
             .await?;
 
-        if !authorities.contains(&purpose_key_data.subject) {
-            return Err(IdentityError::UnknownAuthority.into());
-        }
-
-        let public_key = match purpose_key_data.public_key.clone() {
-            PurposePublicKey::SecureChannelStaticKey(_) => {
-                return Err(IdentityError::InvalidKeyType.into())
-            }
-
-            PurposePublicKey::CredentialSigningKey(public_key) => public_key,
-        };
-
-        let public_key = public_key.into();
-
-        let versioned_data_hash = self
-            .verifying_vault
-            .sha256(&credential_and_purpose_key.credential.data)
-            .await?;
-
-        let signature = credential_and_purpose_key
-            .credential
-            .signature
-            .clone()
-            .into();
-
-        if !self
-            .verifying_vault
-            .verify(&public_key, &versioned_data_hash, &signature)
-            .await?
-        {
-            return Err(IdentityError::CredentialVerificationFailed.into());
-        }
-
         let versioned_data = credential_and_purpose_key.credential.get_versioned_data()?;
         if versioned_data.version != 1 {
             return Err(IdentityError::UnknownCredentialVersion.into());
@@ -140,6 +122,17 @@ impl CredentialsVerification {
             return Err(IdentityError::CredentialVerificationFailed.into());
         }
 
+        if self
+            .is_revoked(
+                &purpose_key_data.subject,
+                &CredentialHash(versioned_data_hash),
+                &credential_data,
+            )
+            .await?
+        {
+            return Err(IdentityError::CredentialRevoked.into());
+        }
+
         if let Some(_subject_latest_change_hash) = &credential_data.subject_latest_change_hash {
             // TODO: Check how that aligns with the ChangeHistory of the subject that we have in the storage
             //     For example, if we just established a secure channel with that subject,
@@ -192,10 +185,171 @@ impl CredentialsVerification {
                     now()?,
                     Some(credential_data.credential_data.expires_at),
                     Some(credential_data.purpose_key_data.subject),
-                ),
+                )
+                .with_issued(credential_data.credential_data.created_at),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Verify a [`crate::models::RevocationList`] signed by one of the given authorities
+    pub async fn verify_revocation_list(
+        &self,
+        authorities: &[Identifier],
+        revocation_list_and_purpose_key: &RevocationListAndPurposeKey,
+    ) -> Result<(Identifier, RevocationListData)> {
+        let (purpose_key_data, _) = self
+            .verify_signed_data(
+                authorities,
+                &revocation_list_and_purpose_key.purpose_key_attestation,
+                &revocation_list_and_purpose_key.revocation_list.data,
+                &revocation_list_and_purpose_key.revocation_list.signature,
+                IdentityError::RevocationListVerificationFailed,
             )
             .await?;
 
+        let versioned_data = revocation_list_and_purpose_key
+            .revocation_list
+            .get_versioned_data()?;
+        if versioned_data.version != 1 {
+            return Err(IdentityError::RevocationListVerificationFailed.into());
+        }
+
+        let revocation_list_data = RevocationListData::get_data(&versioned_data)?;
+
+        if revocation_list_data.created_at < purpose_key_data.created_at
+            || revocation_list_data.created_at > purpose_key_data.expires_at
+        {
+            // RevocationList should be created while the purpose key was valid
+            return Err(IdentityError::RevocationListVerificationFailed.into());
+        }
+
+        let now = now()?;
+        if revocation_list_data.created_at > now
+            && revocation_list_data.created_at - now > MAX_ALLOWED_TIME_DRIFT
+        {
+            // RevocationList can't be created in the future
+            return Err(IdentityError::RevocationListVerificationFailed.into());
+        }
+
+        Ok((purpose_key_data.subject, revocation_list_data))
+    }
+
+    /// Receive an Authority's [`crate::models::RevocationList`]: verify it, store it if it's
+    /// newer than the one we already have, and remove the attributes of revoked subjects
+    /// that were attested by that Authority
+    pub async fn receive_revocation_list(
+        &self,
+        authorities: &[Identifier],
+        revocation_list_and_purpose_key: &RevocationListAndPurposeKey,
+    ) -> Result<()> {
+        let (authority, revocation_list_data) = self
+            .verify_revocation_list(authorities, revocation_list_and_purpose_key)
+            .await?;
+
+        if let Some(current) = self.get_revocation_list_data(&authority).await? {
+            if current.created_at > revocation_list_data.created_at {
+                // We already have a newer list
+                return Ok(());
+            }
+        }
+
+        self.revocation_lists_repository
+            .set_revocation_list(&authority, revocation_list_and_purpose_key)
+            .await?;
+
+        for subject in &revocation_list_data.revoked_subjects {
+            if let Some(entry) = self.identities_repository.get_attributes(subject).await? {
+                if entry.attested_by().as_ref() == Some(&authority)
+                    && entry.added() <= revocation_list_data.created_at
+                {
+                    self.identities_repository.delete(subject).await?;
+                }
+            }
+        }
+
         Ok(())
     }
+
+    /// Verify that data was signed by a Credentials [`PurposeKeyAttestation`] of one of the given
+    /// authorities. Return the [`PurposeKeyAttestationData`] and the SHA256 of the data
+    async fn verify_signed_data(
+        &self,
+        authorities: &[Identifier],
+        purpose_key_attestation: &PurposeKeyAttestation,
+        data: &[u8],
+        signature: &CredentialSignature,
+        verification_error: IdentityError,
+    ) -> Result<(PurposeKeyAttestationData, [u8; 32])> {
+        let purpose_key_data = self
+            .purpose_keys_verification
+            .verify_purpose_key_attestation(None, purpose_key_attestation)
+            .await?;
+
+        if !authorities.contains(&purpose_key_data.subject) {
+            return Err(IdentityError::UnknownAuthority.into());
+        }
+
+        let public_key = match purpose_key_data.public_key.clone() {
+            PurposePublicKey::SecureChannelStaticKey(_) => {
+                return Err(IdentityError::InvalidKeyType.into())
+            }
+
+            PurposePublicKey::CredentialSigningKey(public_key) => public_key,
+        };
+
+        let public_key = public_key.into();
+
+        let versioned_data_hash = self.verifying_vault.sha256(data).await?;
+
+        let signature = signature.clone().into();
+
+        if !self
+            .verifying_vault
+            .verify(&public_key, &versioned_data_hash, &signature)
+            .await?
+        {
+            return Err(verification_error.into());
+        }
+
+        Ok((purpose_key_data, versioned_data_hash))
+    }
+
+    /// Return the latest [`RevocationListData`] stored for the given Authority
+    async fn get_revocation_list_data(
+        &self,
+        authority: &Identifier,
+    ) -> Result<Option<RevocationListData>> {
+        match self
+            .revocation_lists_repository
+            .retrieve_revocation_list(authority)
+            .await?
+        {
+            Some(revocation_list_and_purpose_key) => {
+                let versioned_data = revocation_list_and_purpose_key
+                    .revocation_list
+                    .get_versioned_data()?;
+                Ok(Some(RevocationListData::get_data(&versioned_data)?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Check a [`crate::models::Credential`] against the latest revocation list of its Authority
+    async fn is_revoked(
+        &self,
+        authority: &Identifier,
+        credential_hash: &CredentialHash,
+        credential_data: &CredentialData,
+    ) -> Result<bool> {
+        Ok(match self.get_revocation_list_data(authority).await? {
+            Some(revocation_list_data) => revocation_list_data.is_revoked(
+                credential_hash,
+                credential_data.subject.as_ref(),
+                credential_data.created_at,
+            ),
+            None => false,
+        })
+    }
 }
@@ -0,0 +1,155 @@
+use core::time::Duration;
+use tracing::{debug, warn};
+
+use ockam_core::compat::boxed::Box;
+use ockam_core::compat::sync::Arc;
+use ockam_core::compat::vec::Vec;
+use ockam_core::{async_trait, route, Address, DenyAll, Processor, Result, Route};
+use ockam_node::{Context, ProcessorBuilder};
+
+use crate::models::Identifier;
+use crate::{
+    CredentialsServer, RemoteCredentialsRetrieverInfo, SecureChannelOptions, SecureChannels,
+    TrustMultiIdentifiersPolicy,
+};
+
+/// Periodically fetch the revocation list of the given authorities from a remote
+/// credentials server and store it in the local
+/// [`RevocationListsRepository`](crate::credentials::storage::RevocationListsRepository)
+///
+/// A node verifying credentials only learns about revocations when a list is presented
+/// to it. Running this processor against a route to the authority (or to any node
+/// caching its list) keeps the local copy up to date. The route shall use a secure channel.
+pub struct RevocationListRefresher {
+    credentials_server: Arc<dyn CredentialsServer>,
+    source: RevocationListSource,
+    authorities: Vec<Identifier>,
+    interval: Duration,
+}
+
+/// Where the revocation list is fetched from
+enum RevocationListSource {
+    /// Route to a credentials server, going through a secure channel
+    Route(Route),
+    /// Remote node reached through a new secure channel on each refresh
+    Remote {
+        secure_channels: Arc<SecureChannels>,
+        identifier: Identifier,
+        info: RemoteCredentialsRetrieverInfo,
+    },
+}
+
+impl RevocationListRefresher {
+    /// Create a new revocation list refresher
+    pub fn new(
+        credentials_server: Arc<dyn CredentialsServer>,
+        route: Route,
+        authorities: Vec<Identifier>,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            credentials_server,
+            source: RevocationListSource::Route(route),
+            authorities,
+            interval,
+        }
+    }
+
+    /// Create a refresher fetching the revocation list of the authority described by `info`.
+    /// A secure channel is created as `identifier` to the authority for each refresh
+    pub fn remote(
+        secure_channels: Arc<SecureChannels>,
+        identifier: Identifier,
+        info: RemoteCredentialsRetrieverInfo,
+        interval: Duration,
+    ) -> Self {
+        Self {
+            credentials_server: secure_channels.identities().credentials_server(),
+            authorities: vec![info.identifier.clone()],
+            source: RevocationListSource::Remote {
+                secure_channels,
+                identifier,
+                info,
+            },
+            interval,
+        }
+    }
+
+    /// Start the refresher as a processor. The first fetch happens immediately
+    pub async fn start(self, ctx: &Context, address: Address) -> Result<()> {
+        ProcessorBuilder::new(self)
+            .with_address(address)
+            .with_incoming_access_control(DenyAll)
+            .with_outgoing_access_control(DenyAll)
+            .start(ctx)
+            .await
+    }
+
+    /// Fetch the revocation list once
+    pub async fn refresh(&self, ctx: &Context) -> Result<()> {
+        match &self.source {
+            RevocationListSource::Route(route) => {
+                debug!("Fetching the revocation list from {}", route);
+                self.credentials_server
+                    .fetch_revocation_list(ctx, route.clone(), &self.authorities)
+                    .await
+            }
+            RevocationListSource::Remote {
+                secure_channels,
+                identifier,
+                info,
+            } => {
+                debug!("Fetching the revocation list from {}", info.route);
+                let resolved_route = ctx.resolve_transport_route(info.route.clone()).await?;
+                let options = SecureChannelOptions::new().with_trust_policy(
+                    TrustMultiIdentifiersPolicy::new(vec![info.identifier.clone()]),
+                );
+                let sc = secure_channels
+                    .create_secure_channel(ctx, identifier, resolved_route, options)
+                    .await?;
+                let encryptor = sc.encryptor_address().clone();
+
+                let res = self
+                    .credentials_server
+                    .fetch_revocation_list(
+                        ctx,
+                        route![sc, info.service_address.clone()],
+                        &self.authorities,
+                    )
+                    .await;
+
+                // The channel is only used for this refresh
+                if let Err(err) = secure_channels.stop_secure_channel(ctx, &encryptor).await {
+                    warn!("Failed to stop the secure channel {}: {}", encryptor, err);
+                }
+                res
+            }
+        }
+    }
+
+    fn source_route(&self) -> &Route {
+        match &self.source {
+            RevocationListSource::Route(route) => route,
+            RevocationListSource::Remote { info, .. } => &info.route,
+        }
+    }
+}
+
+#[async_trait]
+impl Processor for RevocationListRefresher {
+    type Context = Context;
+
+    async fn process(&mut self, ctx: &mut Self::Context) -> Result<bool> {
+        // A failed fetch keeps the cached list, the next attempt may succeed
+        if let Err(err) = self.refresh(ctx).await {
+            warn!(
+                "Failed to fetch the revocation list from {}: {}",
+                self.source_route(),
+                err
+            );
+        }
+
+        ctx.sleep(self.interval).await;
+        Ok(true)
+    }
+}
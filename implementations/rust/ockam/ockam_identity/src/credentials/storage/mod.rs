@@ -0,0 +1,5 @@
+mod revocation_lists_repository_impl;
+mod revocation_lists_repository_trait;
+
+pub use revocation_lists_repository_impl::*;
+pub use revocation_lists_repository_trait::*;
@@ -0,0 +1,63 @@
+use ockam_core::async_trait;
+use ockam_core::compat::boxed::Box;
+use ockam_core::compat::string::ToString;
+use ockam_core::compat::sync::Arc;
+use ockam_core::Result;
+
+use crate::credentials::storage::RevocationListsRepository;
+use crate::identity::IdentityConstants;
+use crate::models::{Identifier, RevocationListAndPurposeKey};
+use crate::storage::{InMemoryStorage, Storage};
+
+/// Storage for [`crate::models::RevocationList`]s received from Authorities
+#[derive(Clone)]
+pub struct RevocationListsStorage {
+    storage: Arc<dyn Storage>,
+}
+
+impl RevocationListsStorage {
+    /// Create a new Storage
+    pub fn new(storage: Arc<dyn Storage>) -> Self {
+        Self { storage }
+    }
+
+    /// Create a new in-memory Storage
+    pub fn create() -> Arc<Self> {
+        Arc::new(Self::new(InMemoryStorage::create()))
+    }
+}
+
+#[async_trait]
+impl RevocationListsRepository for RevocationListsStorage {
+    async fn set_revocation_list(
+        &self,
+        authority: &Identifier,
+        revocation_list: &RevocationListAndPurposeKey,
+    ) -> Result<()> {
+        self.storage
+            .set(
+                &authority.to_string(),
+                IdentityConstants::REVOCATION_LIST_KEY.to_string(),
+                minicbor::to_vec(revocation_list)?,
+            )
+            .await
+    }
+
+    async fn retrieve_revocation_list(
+        &self,
+        authority: &Identifier,
+    ) -> Result<Option<RevocationListAndPurposeKey>> {
+        if let Some(data) = self
+            .storage
+            .get(
+                &authority.to_string(),
+                IdentityConstants::REVOCATION_LIST_KEY,
+            )
+            .await?
+        {
+            Ok(Some(minicbor::decode(&data)?))
+        } else {
+            Ok(None)
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use ockam_core::async_trait;
+use ockam_core::compat::boxed::Box;
+use ockam_core::Result;
+
+use crate::models::{Identifier, RevocationListAndPurposeKey};
+
+/// Storage for the latest verified [`crate::models::RevocationList`] of each Authority
+#[async_trait]
+pub trait RevocationListsRepository: Send + Sync + 'static {
+    /// Set the [`crate::models::RevocationList`] for given Authority [`Identifier`]
+    /// overwriting existing one (if any)
+    async fn set_revocation_list(
+        &self,
+        authority: &Identifier,
+        revocation_list: &RevocationListAndPurposeKey,
+    ) -> Result<()>;
+
+    /// Retrieve the [`crate::models::RevocationList`] for given Authority [`Identifier`]
+    async fn retrieve_revocation_list(
+        &self,
+        authority: &Identifier,
+    ) -> Result<Option<RevocationListAndPurposeKey>>;
+}
@@ -66,6 +66,12 @@ pub enum IdentityError {
     ExpectedSecretKeyInsteadOfPublic,
     /// Expected Public Key, got Secret Key
     ExpectedPublicKeyInsteadOfSecret,
+    /// Credential was revoked by its Authority
+    CredentialRevoked,
+    /// RevocationList Verification Failed
+    RevocationListVerificationFailed,
+    /// Maximum number of messages or bytes sent on a Secure Channel reached
+    SecureChannelLimitReached,
 }
 
 impl ockam_core::compat::error::Error for IdentityError {}
@@ -1,3 +1,4 @@
+use crate::credentials::storage::{RevocationListsRepository, RevocationListsStorage};
 use crate::identities::{IdentitiesKeys, IdentitiesRepository};
 use crate::purpose_keys::storage::{PurposeKeysRepository, PurposeKeysStorage};
 use crate::{
@@ -15,6 +16,7 @@ pub struct Identities {
     vault: Vault,
     identities_repository: Arc<dyn IdentitiesRepository>,
     purpose_keys_repository: Arc<dyn PurposeKeysRepository>,
+    revocation_lists_repository: Arc<dyn RevocationListsRepository>,
 }
 
 impl Identities {
@@ -33,6 +35,11 @@ impl Identities {
         self.purpose_keys_repository.clone()
     }
 
+    /// Return the revocation lists repository
+    pub fn revocation_lists_repository(&self) -> Arc<dyn RevocationListsRepository> {
+        self.revocation_lists_repository.clone()
+    }
+
     /// Get an [`Identity`] from the repository
     pub async fn get_identity(&self, identifier: &Identifier) -> Result<Identity> {
         let change_history = self.identities_repository.get_identity(identifier).await?;
@@ -88,6 +95,7 @@ impl Identities {
             self.vault.verifying_vault.clone(),
             self.purpose_keys(),
             self.identities_repository.clone(),
+            self.revocation_lists_repository.clone(),
         ))
     }
 
@@ -103,11 +111,13 @@ impl Identities {
         vault: Vault,
         identities_repository: Arc<dyn IdentitiesRepository>,
         purpose_keys_repository: Arc<dyn PurposeKeysRepository>,
+        revocation_lists_repository: Arc<dyn RevocationListsRepository>,
     ) -> Identities {
         Identities {
             vault,
             identities_repository,
             purpose_keys_repository,
+            revocation_lists_repository,
         }
     }
 
@@ -117,6 +127,7 @@ impl Identities {
             vault: Vault::create(),
             repository: IdentitiesStorage::create(),
             purpose_keys_repository: PurposeKeysStorage::create(),
+            revocation_lists_repository: RevocationListsStorage::create(),
         }
     }
 }
@@ -1,3 +1,4 @@
+use crate::credentials::storage::{RevocationListsRepository, RevocationListsStorage};
 use crate::identities::{Identities, IdentitiesRepository, IdentitiesStorage};
 use crate::purpose_keys::storage::{PurposeKeysRepository, PurposeKeysStorage};
 use crate::storage::Storage;
@@ -11,6 +12,7 @@ pub struct IdentitiesBuilder {
     pub(crate) vault: Vault,
     pub(crate) repository: Arc<dyn IdentitiesRepository>,
     pub(crate) purpose_keys_repository: Arc<dyn PurposeKeysRepository>,
+    pub(crate) revocation_lists_repository: Arc<dyn RevocationListsRepository>,
 }
 
 /// Return a default identities
@@ -56,12 +58,27 @@ impl IdentitiesBuilder {
         self
     }
 
+    /// Set a specific storage for Revocation Lists
+    pub fn with_revocation_lists_storage(self, storage: Arc<dyn Storage>) -> Self {
+        self.with_revocation_lists_repository(Arc::new(RevocationListsStorage::new(storage)))
+    }
+
+    /// Set a specific repository for Revocation Lists
+    pub fn with_revocation_lists_repository(
+        mut self,
+        repository: Arc<dyn RevocationListsRepository>,
+    ) -> Self {
+        self.revocation_lists_repository = repository;
+        self
+    }
+
     /// Build identities
     pub fn build(self) -> Arc<Identities> {
         Arc::new(Identities::new(
             self.vault,
             self.repository,
             self.purpose_keys_repository,
+            self.revocation_lists_repository,
         ))
     }
 }
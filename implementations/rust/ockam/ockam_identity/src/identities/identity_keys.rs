@@ -107,7 +107,7 @@ impl IdentitiesKeys {
         match identity_options.stype {
             SecretType::Ed25519 | SecretType::NistP256 => {}
 
-            SecretType::Buffer | SecretType::Aes | SecretType::X25519 => {
+            SecretType::Buffer | SecretType::Aes | SecretType::X25519 | SecretType::Kyber768 => {
                 return Err(IdentityError::InvalidKeyType.into());
             }
         }
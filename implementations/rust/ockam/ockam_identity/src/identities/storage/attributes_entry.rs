@@ -14,6 +14,8 @@ pub struct AttributesEntry {
     #[n(2)] added: TimestampInSeconds,
     #[n(3)] expires: Option<TimestampInSeconds>,
     #[n(4)] attested_by: Option<Identifier>,
+    #[serde(default)]
+    #[n(5)] issued: Option<TimestampInSeconds>,
 }
 
 impl AttributesEntry {
@@ -33,9 +35,16 @@ impl AttributesEntry {
             added,
             expires,
             attested_by,
+            issued: None,
         }
     }
 
+    /// Set the time at which the credential attesting these attributes was issued
+    pub fn with_issued(mut self, issued: TimestampInSeconds) -> Self {
+        self.issued = Some(issued);
+        self
+    }
+
     /// The entry attributes
     pub fn attrs(&self) -> &BTreeMap<Vec<u8>, Vec<u8>> {
         &self.attrs
@@ -51,6 +60,11 @@ impl AttributesEntry {
         self.added
     }
 
+    /// Issuance time of the credential attesting this entry, if any
+    pub fn issued(&self) -> Option<TimestampInSeconds> {
+        self.issued
+    }
+
     /// Who attested this attributes for this identity identifier
     pub fn attested_by(&self) -> Option<Identifier> {
         self.attested_by.to_owned()
@@ -10,4 +10,6 @@ impl IdentityConstants {
     pub const CREDENTIALS_PURPOSE_KEY: &'static str = "C_PK";
     /// Attributes key for AttributesStorage
     pub const ATTRIBUTES_KEY: &'static str = "ATTRIBUTES";
+    /// Key used to persist an Authority's latest RevocationList
+    pub const REVOCATION_LIST_KEY: &'static str = "REVOCATION_LIST";
 }
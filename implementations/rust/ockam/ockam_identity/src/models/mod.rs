@@ -4,6 +4,7 @@ mod credential_and_purpose_key;
 mod identifiers;
 mod public_keys;
 mod purpose_key_attestation;
+mod revocation_list;
 mod signatures;
 mod timestamp;
 mod utils;
@@ -15,6 +16,7 @@ pub use credential_and_purpose_key::*;
 pub use identifiers::*;
 pub use public_keys::*;
 pub use purpose_key_attestation::*;
+pub use revocation_list::*;
 pub use signatures::*;
 pub use timestamp::*;
 pub use versioned_data::*;
@@ -0,0 +1,49 @@
+use crate::models::{CredentialSignature, Identifier, PurposeKeyAttestation, TimestampInSeconds};
+use minicbor::{Decode, Encode};
+use ockam_core::compat::vec::Vec;
+
+/// CredentialHash length
+pub const CREDENTIAL_HASH_LEN: usize = 32;
+
+/// SHA256 of the [`super::Credential::data`] binary, uniquely identifies a [`super::Credential`]
+#[derive(Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
+pub struct CredentialHash(pub [u8; CREDENTIAL_HASH_LEN]);
+
+/// List of revoked [`super::Credential`]s signed by an Authority
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct RevocationList {
+    /// CBOR serialized [`super::VersionedData`]
+    /// where VersionedData::data is CBOR serialized [`RevocationListData`]
+    #[cbor(with = "minicbor::bytes")]
+    #[n(1)] pub data: Vec<u8>,
+    /// Signature over data field using Authority's Credentials [`super::PurposeKeyAttestation`]
+    #[n(2)] pub signature: CredentialSignature,
+}
+
+/// Data inside a [`RevocationList`]
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct RevocationListData {
+    /// Subjects whose [`super::Credential`]s issued before `created_at` are revoked
+    #[n(1)] pub revoked_subjects: Vec<Identifier>,
+    /// [`CredentialHash`]es of individually revoked [`super::Credential`]s
+    #[n(2)] pub revoked_credentials: Vec<CredentialHash>,
+    /// Creation [`TimestampInSeconds`] (UTC). A newer list always replaces an older one
+    #[n(3)] pub created_at: TimestampInSeconds,
+}
+
+/// [`RevocationList`] and the corresponding [`PurposeKeyAttestation`] that was used to sign that
+/// [`RevocationList`] and will be used to verify it
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct RevocationListAndPurposeKey {
+    /// [`RevocationList`]
+    #[n(1)] pub revocation_list: RevocationList,
+    /// Corresponding [`PurposeKeyAttestation`] that was used to sign that
+    /// [`RevocationList`] and will be used to verify it
+    #[n(2)] pub purpose_key_attestation: PurposeKeyAttestation,
+}
@@ -62,7 +62,7 @@ impl TryFrom<PublicKey> for PrimaryPublicKey {
                     .map_err(|_| IdentityError::InvalidKeyData)?,
             ))),
 
-            SecretType::X25519 | SecretType::Buffer | SecretType::Aes => {
+            SecretType::X25519 | SecretType::Buffer | SecretType::Aes | SecretType::Kyber768 => {
                 Err(IdentityError::InvalidKeyType.into())
             }
         }
@@ -95,7 +95,7 @@ impl ChangeSignature {
                     .map_err(|_| IdentityError::InvalidSignatureData)?,
             ))),
 
-            SecretType::Buffer | SecretType::Aes | SecretType::X25519 => {
+            SecretType::Buffer | SecretType::Aes | SecretType::X25519 | SecretType::Kyber768 => {
                 Err(IdentityError::InvalidKeyType.into())
             }
         }
@@ -10,5 +10,6 @@ mod credentials;
 mod identifiers;
 mod public_keys;
 mod purpose_key_attestation;
+mod revocation_list;
 mod signatures;
 mod timestamp;
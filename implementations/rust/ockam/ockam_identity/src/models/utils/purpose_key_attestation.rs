@@ -50,7 +50,7 @@ impl PurposeKeyAttestationSignature {
                     .map_err(|_| IdentityError::InvalidSignatureData)?,
             ))),
 
-            SecretType::Buffer | SecretType::Aes | SecretType::X25519 => {
+            SecretType::Buffer | SecretType::Aes | SecretType::X25519 | SecretType::Kyber768 => {
                 Err(IdentityError::InvalidKeyType.into())
             }
         }
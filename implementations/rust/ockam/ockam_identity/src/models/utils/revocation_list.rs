@@ -0,0 +1,65 @@
+use crate::models::utils::get_versioned_data;
+use crate::models::{
+    CredentialHash, Identifier, RevocationList, RevocationListData, TimestampInSeconds,
+    VersionedData, CREDENTIAL_HASH_LEN,
+};
+
+use core::ops::Deref;
+use minicbor::bytes::ByteArray;
+use minicbor::encode::Write;
+use minicbor::{Decode, Decoder, Encode, Encoder};
+use ockam_core::Result;
+
+impl RevocationList {
+    /// Extract [`VersionedData`]
+    pub fn get_versioned_data(&self) -> Result<VersionedData> {
+        get_versioned_data(&self.data)
+    }
+}
+
+impl RevocationListData {
+    /// Extract [`RevocationListData`] from [`VersionedData`]
+    pub fn get_data(versioned_data: &VersionedData) -> Result<Self> {
+        Ok(minicbor::decode(&versioned_data.data)?)
+    }
+
+    /// Return true if the [`crate::models::Credential`] with given [`CredentialHash`], issued to
+    /// given subject at given time, is revoked by this list
+    pub fn is_revoked(
+        &self,
+        credential_hash: &CredentialHash,
+        subject: Option<&Identifier>,
+        created_at: TimestampInSeconds,
+    ) -> bool {
+        if self.revoked_credentials.contains(credential_hash) {
+            return true;
+        }
+
+        match subject {
+            // A subject revocation only applies to credentials issued before the list,
+            // so that a subject can be re-enrolled later on
+            Some(subject) => {
+                created_at <= self.created_at && self.revoked_subjects.contains(subject)
+            }
+            None => false,
+        }
+    }
+}
+
+impl<C> Encode<C> for CredentialHash {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        ctx: &mut C,
+    ) -> Result<(), minicbor::encode::Error<W::Error>> {
+        ByteArray::from(self.0).encode(e, ctx)
+    }
+}
+
+impl<'b, C> Decode<'b, C> for CredentialHash {
+    fn decode(d: &mut Decoder<'b>, ctx: &mut C) -> Result<Self, minicbor::decode::Error> {
+        let data = ByteArray::<CREDENTIAL_HASH_LEN>::decode(d, ctx)?;
+
+        Ok(Self(*data.deref()))
+    }
+}
@@ -156,7 +156,8 @@ impl PurposeKeysCreation {
                     SecretType::Buffer
                     | SecretType::Aes
                     | SecretType::Ed25519
-                    | SecretType::NistP256 => {
+                    | SecretType::NistP256
+                    | SecretType::Kyber768 => {
                         return Err(IdentityError::InvalidKeyType.into());
                     }
                 }
@@ -171,7 +172,10 @@ impl PurposeKeysCreation {
                 match options.stype {
                     SecretType::Ed25519 | SecretType::NistP256 => {}
 
-                    SecretType::Buffer | SecretType::Aes | SecretType::X25519 => {
+                    SecretType::Buffer
+                    | SecretType::Aes
+                    | SecretType::X25519
+                    | SecretType::Kyber768 => {
                         return Err(IdentityError::InvalidKeyType.into());
                     }
                 }
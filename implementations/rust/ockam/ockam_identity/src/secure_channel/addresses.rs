@@ -14,12 +14,16 @@ pub(crate) struct Addresses {
     pub(crate) decryptor_remote: Address,
     // Used to encrypt messages without sending them with Ockam Routing to the other end of the channel
     pub(crate) decryptor_api: Address,
+    // Used to receive timer events to renew the channel keys or to close the channel when it is idle
+    pub(crate) decryptor_timer: Address,
 
     // Encryptor worker address used to receive plain messages that will be encrypted and forwarded
     // to the other end of the channel
     pub(crate) encryptor: Address,
     // Used to decrypt messages that were received though some channel other than Ockam Routing from the other end of the channel
     pub(crate) encryptor_api: Address,
+    // Used to receive a new encryption key when the channel keys are renewed
+    pub(crate) encryptor_internal: Address,
 }
 
 impl Addresses {
@@ -31,17 +35,23 @@ impl Addresses {
             Address::random_tagged(&format!("SecureChannel.{}.decryptor.remote", role_str));
         let decryptor_api =
             Address::random_tagged(&format!("SecureChannel.{}.decryptor.api", role_str));
+        let decryptor_timer =
+            Address::random_tagged(&format!("SecureChannel.{}.decryptor.timer", role_str));
 
         let encryptor = Address::random_tagged(&format!("SecureChannel.{}.encryptor", role_str));
         let encryptor_api =
             Address::random_tagged(&format!("SecureChannel.{}.encryptor.api", role_str));
+        let encryptor_internal =
+            Address::random_tagged(&format!("SecureChannel.{}.encryptor.internal", role_str));
 
         Self {
             decryptor_internal,
             decryptor_remote,
             decryptor_api,
+            decryptor_timer,
             encryptor,
             encryptor_api,
+            encryptor_internal,
         }
     }
 }
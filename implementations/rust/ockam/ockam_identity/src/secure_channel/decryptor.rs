@@ -6,9 +6,10 @@ use ockam_node::Context;
 use ockam_vault::{KeyId, SecureChannelVault};
 
 use crate::models::Identifier;
-use crate::secure_channel::encryptor::{Encryptor, KEY_RENEWAL_INTERVAL};
+use crate::secure_channel::encryptor::Encryptor;
 use crate::secure_channel::key_tracker::KeyTracker;
 use crate::secure_channel::nonce_tracker::NonceTracker;
+use crate::secure_channel::parameters::ChannelParameters;
 use crate::secure_channel::Addresses;
 use crate::{DecryptionRequest, DecryptionResponse, IdentityError, IdentitySecureChannelLocalInfo};
 
@@ -20,6 +21,8 @@ pub(crate) struct DecryptorHandler {
     pub(crate) addresses: Addresses,
     pub(crate) their_identity_id: Identifier,
     pub(crate) decryptor: Decryptor,
+    // decryptor for the keys used before the last handshake, for the messages still in flight
+    pub(crate) previous_decryptor: Option<Decryptor>,
 }
 
 impl DecryptorHandler {
@@ -29,12 +32,43 @@ impl DecryptorHandler {
         key: KeyId,
         vault: Arc<dyn SecureChannelVault>,
         their_identity_id: Identifier,
-    ) -> Self {
-        Self {
+        parameters: &ChannelParameters,
+    ) -> Result<Self> {
+        Ok(Self {
             role,
             addresses,
             their_identity_id,
-            decryptor: Decryptor::new(key, vault),
+            decryptor: Decryptor::new(key, vault, parameters)?,
+            previous_decryptor: None,
+        })
+    }
+
+    /// Start using a decryption key obtained with a new handshake.
+    /// The current key is kept to decrypt the messages which were sent before the handshake
+    pub(crate) async fn update_key(
+        &mut self,
+        key: KeyId,
+        parameters: &ChannelParameters,
+    ) -> Result<()> {
+        let decryptor = Decryptor::new(key, self.decryptor.vault.clone(), parameters)?;
+        let previous_decryptor = core::mem::replace(&mut self.decryptor, decryptor);
+        if let Some(oldest_decryptor) = self.previous_decryptor.replace(previous_decryptor) {
+            oldest_decryptor.shutdown().await?;
+        }
+        Ok(())
+    }
+
+    /// Decrypt a payload with the current keys or, if that fails, with the keys used
+    /// before the last handshake
+    async fn decrypt(&mut self, payload: &[u8]) -> Result<Vec<u8>> {
+        match self.decryptor.decrypt(payload).await {
+            Ok(decrypted_payload) => Ok(decrypted_payload),
+            Err(err) => match self.previous_decryptor.as_mut() {
+                Some(previous_decryptor) => {
+                    previous_decryptor.decrypt(payload).await.map_err(|_| err)
+                }
+                None => Err(err),
+            },
         }
     }
 
@@ -54,7 +88,7 @@ impl DecryptorHandler {
         let request = DecryptionRequest::decode(&msg.into_transport_message().payload)?;
 
         // Decrypt the binary
-        let decrypted_payload = self.decryptor.decrypt(&request.0).await;
+        let decrypted_payload = self.decrypt(&request.0).await;
 
         let response = match decrypted_payload {
             Ok(payload) => DecryptionResponse::Ok(payload),
@@ -68,11 +102,13 @@ impl DecryptorHandler {
         Ok(())
     }
 
+    /// Decrypt a message and forward it to its destination.
+    /// If the message is a handshake message used to renew the channel keys, it is returned instead
     pub(crate) async fn handle_decrypt(
         &mut self,
         ctx: &mut Context,
         msg: Routed<Any>,
-    ) -> Result<()> {
+    ) -> Result<Option<Vec<u8>>> {
         debug!(
             "SecureChannel {} received Decrypt {}",
             self.role, &self.addresses.decryptor_remote
@@ -82,11 +118,16 @@ impl DecryptorHandler {
         let payload = Vec::<u8>::decode(&msg.into_transport_message().payload)?;
 
         // Decrypt the binary
-        let decrypted_payload = self.decryptor.decrypt(&payload).await?;
+        let decrypted_payload = self.decrypt(&payload).await?;
 
         // Encrypted data should be a TransportMessage
         let mut transport_message = TransportMessage::decode(&decrypted_payload)?;
 
+        // Messages sent by the other party to this address are handshake messages
+        if transport_message.onward_route.next().ok() == Some(&self.addresses.decryptor_remote) {
+            return Ok(Some(Vec::<u8>::decode(&transport_message.payload)?));
+        }
+
         // Add encryptor hop in the return_route (instead of our address)
         transport_message
             .return_route
@@ -104,19 +145,22 @@ impl DecryptorHandler {
             .forward_from_address(msg, self.addresses.decryptor_internal.clone())
             .await
         {
-            Ok(_) => Ok(()),
+            Ok(_) => Ok(None),
             Err(err) => {
                 warn!(
                     "{} forwarding decrypted message from {}",
                     err, &self.addresses.encryptor
                 );
-                Ok(())
+                Ok(None)
             }
         }
     }
 
     /// Remove the channel keys on shutdown
     pub(crate) async fn shutdown(&self) -> Result<()> {
+        if let Some(previous_decryptor) = &self.previous_decryptor {
+            previous_decryptor.shutdown().await?;
+        }
         self.decryptor.shutdown().await
     }
 }
@@ -128,12 +172,16 @@ pub(crate) struct Decryptor {
 }
 
 impl Decryptor {
-    pub fn new(key_id: KeyId, vault: Arc<dyn SecureChannelVault>) -> Self {
-        Self {
+    pub fn new(
+        key_id: KeyId,
+        vault: Arc<dyn SecureChannelVault>,
+        parameters: &ChannelParameters,
+    ) -> Result<Self> {
+        Ok(Self {
             vault,
-            key_tracker: KeyTracker::new(key_id, KEY_RENEWAL_INTERVAL),
-            nonce_tracker: NonceTracker::new(),
-        }
+            key_tracker: KeyTracker::new(key_id, parameters.rekey_interval),
+            nonce_tracker: NonceTracker::new(parameters.nonce_window)?,
+        })
     }
 
     /// Restore 12-byte nonce needed for AES GCM from 8 byte that we use for noise
@@ -151,7 +199,7 @@ impl Decryptor {
         }
 
         let (nonce, nonce_buffer) = Self::convert_nonce_from_small(&payload[..8])?;
-        let nonce_tracker = self.nonce_tracker.mark(nonce)?;
+        self.nonce_tracker.check(nonce)?;
 
         // get the key corresponding to the current nonce and
         // rekey if necessary
@@ -169,7 +217,7 @@ impl Decryptor {
             .await;
 
         if result.is_ok() {
-            self.nonce_tracker = nonce_tracker;
+            self.nonce_tracker.mark(nonce)?;
             if let Some(key_to_delete) = self.key_tracker.update_key(key)? {
                 self.vault.delete_secret(key_to_delete).await?;
             }
@@ -4,19 +4,22 @@ use ockam_core::errcode::{Kind, Origin};
 use ockam_core::{Error, Result};
 use ockam_vault::{KeyId, Secret, SecureChannelVault};
 
+use crate::secure_channel::parameters::ChannelParameters;
 use crate::IdentityError;
 
 pub(crate) struct Encryptor {
     key: KeyId,
     nonce: u64,
     vault: Arc<dyn SecureChannelVault>,
+    rekey_interval: u64,
+    max_messages: Option<u64>,
+    max_bytes: Option<u64>,
+    // messages and bytes encrypted over the lifetime of the channel, across key updates
+    messages: u64,
+    bytes: u64,
+    limit_reached: bool,
 }
 
-// To simplify the implementation we use the same constant for the size of the message
-// window we accept with the message period used to rekey.
-// This means we only need to keep the current key and the previous one.
-pub(crate) const KEY_RENEWAL_INTERVAL: u64 = 32;
-
 impl Encryptor {
     /// We use u64 nonce since it's convenient to work with it (e.g. increment)
     /// But we use 8-byte be format to send it over to the other side (according to noise spec)
@@ -51,9 +54,21 @@ impl Encryptor {
             return Err(IdentityError::NonceOverflow.into());
         }
 
+        // once a limit is reached, no more messages can be sent on this channel
+        let bytes = self.bytes.saturating_add(payload.len() as u64);
+        if self.limit_reached
+            || self.max_messages.map_or(false, |max| self.messages >= max)
+            || self.max_bytes.map_or(false, |max| bytes > max)
+        {
+            self.limit_reached = true;
+            return Err(IdentityError::SecureChannelLimitReached.into());
+        }
+        self.messages += 1;
+        self.bytes = bytes;
+
         self.nonce += 1;
 
-        if current_nonce > 0 && current_nonce % KEY_RENEWAL_INTERVAL == 0 {
+        if current_nonce > 0 && current_nonce % self.rekey_interval == 0 {
             let new_key = Self::rekey(&self.vault, &self.key).await?;
             let old_key = core::mem::replace(&mut self.key, new_key);
             self.vault.delete_secret(old_key).await?;
@@ -73,8 +88,38 @@ impl Encryptor {
         Ok(res)
     }
 
-    pub fn new(key: KeyId, nonce: u64, vault: Arc<dyn SecureChannelVault>) -> Self {
-        Self { key, nonce, vault }
+    pub fn new(
+        key: KeyId,
+        nonce: u64,
+        vault: Arc<dyn SecureChannelVault>,
+        parameters: &ChannelParameters,
+    ) -> Self {
+        Self {
+            key,
+            nonce,
+            vault,
+            rekey_interval: parameters.rekey_interval,
+            max_messages: parameters.max_messages,
+            max_bytes: parameters.max_bytes,
+            messages: 0,
+            bytes: 0,
+            limit_reached: false,
+        }
+    }
+
+    /// Replace the encryption key with a key obtained from a new handshake.
+    /// The nonce is reset, but the message limits still apply to the whole
+    /// lifetime of the channel
+    pub(crate) async fn update_key(&mut self, key: KeyId) -> Result<()> {
+        let old_key = core::mem::replace(&mut self.key, key);
+        self.nonce = 0;
+        self.vault.delete_secret(old_key).await?;
+        Ok(())
+    }
+
+    /// Return true if the maximum number of messages or bytes for this channel has been reached
+    pub(crate) fn is_limit_reached(&self) -> bool {
+        self.limit_reached
     }
 
     pub(crate) async fn shutdown(&self) -> Result<()> {
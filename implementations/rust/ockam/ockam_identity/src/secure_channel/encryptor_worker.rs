@@ -1,20 +1,32 @@
+use core::sync::atomic::{AtomicBool, Ordering};
 use ockam_core::compat::boxed::Box;
-use ockam_core::{async_trait, Decodable, Encodable, Route};
+use ockam_core::compat::string::String;
+use ockam_core::compat::sync::Arc;
+use ockam_core::{async_trait, Decodable, Encodable, Message, Route};
 use ockam_core::{Any, Result, Routed, TransportMessage, Worker};
 use ockam_node::Context;
-use tracing::debug;
+use ockam_vault::KeyId;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
 
 use crate::secure_channel::addresses::Addresses;
 use crate::secure_channel::api::{EncryptionRequest, EncryptionResponse};
 use crate::secure_channel::encryptor::Encryptor;
 use crate::IdentityError;
 
+/// Message sent by the `HandshakeWorker` to the `EncryptorWorker` internal address
+/// when the channel keys have been renewed with a new handshake
+#[derive(Serialize, Deserialize, Message)]
+pub(crate) struct EncryptionKeyUpdate(pub(crate) String);
+
 pub(crate) struct EncryptorWorker {
     //for debug purposes only
     role: &'static str,
     addresses: Addresses,
     remote_route: Route,
     encryptor: Encryptor,
+    // set every time a message is encrypted, to detect idle channels
+    activity: Arc<AtomicBool>,
 }
 
 impl EncryptorWorker {
@@ -23,12 +35,14 @@ impl EncryptorWorker {
         addresses: Addresses,
         remote_route: Route,
         encryptor: Encryptor,
+        activity: Arc<AtomicBool>,
     ) -> Self {
         Self {
             role,
             addresses,
             remote_route,
             encryptor,
+            activity,
         }
     }
 
@@ -43,6 +57,7 @@ impl EncryptorWorker {
         );
 
         let return_route = msg.return_route();
+        self.activity.store(true, Ordering::Relaxed);
 
         // Decode raw payload binary
         let request = EncryptionRequest::decode(&msg.into_transport_message().payload)?;
@@ -59,7 +74,24 @@ impl EncryptorWorker {
         ctx.send_from_address(return_route, response, self.addresses.encryptor_api.clone())
             .await?;
 
-        Ok(())
+        self.close_if_limit_reached(ctx).await
+    }
+
+    /// The channel can not be used anymore once its limits are reached.
+    ///
+    /// The whole channel is closed: stopping the decryptor stops this worker as well and
+    /// removes the channel from the registry. Messages sent to the channel then fail to be
+    /// delivered, so that a session using it detects the failure and creates a new channel.
+    async fn close_if_limit_reached(&self, ctx: &Context) -> Result<()> {
+        if !self.encryptor.is_limit_reached() {
+            return Ok(());
+        }
+        warn!(
+            "SecureChannel {} at {} reached its maximum number of messages or bytes, closing it",
+            self.role, &self.addresses.encryptor
+        );
+        ctx.stop_worker(self.addresses.decryptor_internal.clone())
+            .await
     }
 
     async fn handle_encrypt(
@@ -75,17 +107,34 @@ impl EncryptorWorker {
         let mut onward_route = msg.onward_route();
         let return_route = msg.return_route();
 
+        // Handshake messages renewing the channel keys are sent by the decryptor,
+        // they must not prevent the channel from being closed when idle
+        if return_route.next().ok() != Some(&self.addresses.decryptor_api) {
+            self.activity.store(true, Ordering::Relaxed);
+        }
+
         // Remove our address
         let _ = onward_route.step();
 
-        let msg = TransportMessage::v1(
+        // The tracing context of the message is carried by the encrypted message,
+        // with the same version so that it is only encoded when it was opted in
+        let transport_message = msg.into_transport_message();
+        let msg = TransportMessage {
+            version: transport_message.version,
             onward_route,
             return_route,
-            msg.into_transport_message().payload,
-        );
+            payload: transport_message.payload,
+            tracing_context: transport_message.tracing_context,
+        };
 
         // Encrypt the message
-        let encrypted_payload = self.encryptor.encrypt(&msg.encode()?).await?;
+        let encrypted_payload = match self.encryptor.encrypt(&msg.encode()?).await {
+            Ok(encrypted_payload) => encrypted_payload,
+            Err(err) => {
+                self.close_if_limit_reached(ctx).await?;
+                return Err(err);
+            }
+        };
 
         // Send the message to the decryptor on the other side
         ctx.send_from_address(
@@ -97,6 +146,19 @@ impl EncryptorWorker {
 
         Ok(())
     }
+
+    async fn handle_key_update(&mut self, msg: Routed<<Self as Worker>::Message>) -> Result<()> {
+        let update = EncryptionKeyUpdate::decode(&msg.into_transport_message().payload)?;
+        self.encryptor
+            .update_key(KeyId::from(update.0.as_str()))
+            .await?;
+
+        info!(
+            "SecureChannel {} renewed its encryption key at {}",
+            self.role, &self.addresses.encryptor
+        );
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -115,6 +177,8 @@ impl Worker for EncryptorWorker {
             self.handle_encrypt(ctx, msg).await?;
         } else if msg_addr == self.addresses.encryptor_api {
             self.handle_encrypt_api(ctx, msg).await?;
+        } else if msg_addr == self.addresses.encryptor_internal {
+            self.handle_key_update(msg).await?;
         } else {
             return Err(IdentityError::UnknownChannelMsgDestination.into());
         }
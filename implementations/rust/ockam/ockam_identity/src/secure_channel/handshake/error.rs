@@ -14,6 +14,8 @@ pub enum XXError {
     MessageLenMismatch,
     /// Invalid internal state.
     InvalidInternalState,
+    /// The other party did not use the hybrid key exchange.
+    HybridKeyExchangeRequired,
 }
 
 impl StdError for XXError {}
@@ -24,6 +26,7 @@ impl fmt::Display for XXError {
             Self::InternalVaultError => write!(f, "internal vault error"),
             Self::MessageLenMismatch => write!(f, "message length mismatch"),
             Self::InvalidInternalState => write!(f, "invalid internal state"),
+            Self::HybridKeyExchangeRequired => write!(f, "hybrid key exchange required"),
         }
     }
 }
@@ -35,6 +38,7 @@ impl From<XXError> for Error {
             XXError::InternalVaultError => Kind::Internal,
             XXError::MessageLenMismatch => Kind::Misuse,
             XXError::InvalidInternalState => Kind::Internal,
+            XXError::HybridKeyExchangeRequired => Kind::Unsupported,
         };
 
         Error::new(Origin::KeyExchange, kind, err)
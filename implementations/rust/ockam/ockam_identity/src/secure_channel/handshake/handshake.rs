@@ -3,8 +3,11 @@ use ockam_core::compat::sync::Arc;
 use ockam_core::compat::vec::Vec;
 use ockam_core::errcode::{Kind, Origin};
 use ockam_core::{Error, Result};
-use ockam_vault::constants::{AES256_SECRET_LENGTH_USIZE, X25519_PUBLIC_LENGTH_USIZE};
-use ockam_vault::SecretType::X25519;
+use ockam_vault::constants::{
+    AES256_SECRET_LENGTH_USIZE, KYBER768_CIPHERTEXT_LENGTH_USIZE, KYBER768_PUBLIC_LENGTH_USIZE,
+    X25519_PUBLIC_LENGTH_USIZE,
+};
+use ockam_vault::SecretType::{Kyber768, X25519};
 use ockam_vault::{KeyId, PublicKey, Secret, SecretAttributes, SecureChannelVault};
 use sha2::{Digest, Sha256};
 use Status::*;
@@ -26,6 +29,13 @@ pub const AES_GCM_TAGSIZE_USIZE: usize = 16;
 /// The variables used in the protocol itself: s, e, rs, re,... are handled in `HandshakeState`
 pub(super) struct Handshake {
     vault: Arc<dyn SecureChannelVault>,
+    /// Optional pre-shared key, mixed in the handshake after the last DH of the second message.
+    /// This is not one of the standard Noise psk modifiers, see `protocol_name`
+    psk: Option<Secret>,
+    /// If true, a KEM shared secret is mixed in the handshake in addition to the DH secrets.
+    /// The initiator then sends an ephemeral KEM public key and the responder rejects
+    /// initiators which don't send one
+    hybrid_key_exchange: bool,
     pub(super) state: HandshakeState,
 }
 
@@ -36,15 +46,13 @@ impl Handshake {
     /// Initialize the handshake variables
     pub(super) async fn initialize(&mut self) -> Result<()> {
         let mut state = self.state.clone();
-        state.h = *Self::protocol_name();
+        let protocol_name = self.protocol_name();
+        state.h = *protocol_name;
         state.k = Some(
             self.import_k_secret(vec![0u8; AES256_SECRET_LENGTH_USIZE])
                 .await?,
         );
-        state.ck = Some(
-            self.import_ck_secret(Self::protocol_name().to_vec())
-                .await?,
-        );
+        state.ck = Some(self.import_ck_secret(protocol_name.to_vec()).await?);
 
         state.h = HandshakeState::sha256(&state.h);
         self.state = state;
@@ -67,6 +75,38 @@ impl Handshake {
         Ok(message)
     }
 
+    /// Generate an ephemeral KEM key if the hybrid key exchange is used and return its public key,
+    /// which is sent as the payload of the first message. Return an empty payload otherwise
+    pub(super) async fn make_kem_public_key(&mut self) -> Result<Vec<u8>> {
+        if !self.hybrid_key_exchange {
+            return Ok(vec![]);
+        }
+        let e_kem = self
+            .vault
+            .generate_ephemeral_secret(SecretAttributes::Kyber768)
+            .await?;
+        let e_kem_pub_key = self.get_public_key(&e_kem).await?;
+        self.state.e_kem = Some(e_kem);
+        Ok(e_kem_pub_key.data().to_vec())
+    }
+
+    /// Read the ephemeral KEM public key of the initiator from the payload of the first message.
+    /// An empty payload means that the initiator doesn't use the hybrid key exchange
+    pub(super) fn set_their_kem_public_key(&mut self, payload: &[u8]) -> Result<()> {
+        if payload.is_empty() {
+            if self.hybrid_key_exchange {
+                return Err(XXError::HybridKeyExchangeRequired.into());
+            }
+            return Ok(());
+        }
+
+        if payload.len() != KYBER768_PUBLIC_LENGTH_USIZE {
+            return Err(XXError::MessageLenMismatch.into());
+        }
+        self.state.re_kem = Some(PublicKey::new(payload.to_vec(), Kyber768));
+        Ok(())
+    }
+
     /// Decode the first message to get the ephemeral public key sent by the initiator
     pub(super) async fn decode_message1(&mut self, message: &[u8]) -> Result<Vec<u8>> {
         let mut state = self.state.clone();
@@ -98,6 +138,15 @@ impl Handshake {
         let dh = self.dh(state.e()?, state.re()?).await?;
         self.hkdf(&mut state, dh).await?;
 
+        // if the initiator sent a KEM public key, encrypt and output a KEM ciphertext
+        // ck, k = HKDF(ck, KEM shared secret, 2)
+        if let Some(re_kem) = state.re_kem.clone() {
+            let (ciphertext, shared_secret) = self.vault.kem_encapsulate(&re_kem).await?;
+            let c = self.encrypt_and_hash(&mut state, &ciphertext).await?;
+            message2.extend_from_slice(c.as_slice());
+            self.hkdf(&mut state, shared_secret).await?;
+        }
+
         // encrypt and output s.pubKey
         let s_pub_key = self.get_public_key(state.s()?).await?;
         let c = self.encrypt_and_hash(&mut state, s_pub_key.data()).await?;
@@ -107,6 +156,9 @@ impl Handshake {
         let dh = self.dh(state.s()?, state.re()?).await?;
         self.hkdf(&mut state, dh).await?;
 
+        // ck, k = HKDF(ck, psk, 2)
+        self.mix_psk(&mut state).await?;
+
         // encrypt and output payload
         let c = self.encrypt_and_hash(&mut state, payload).await?;
         message2.extend(c);
@@ -126,8 +178,19 @@ impl Handshake {
         let dh = self.dh(state.e()?, state.re()?).await?;
         self.hkdf(&mut state, dh).await?;
 
+        // if we sent a KEM public key, decrypt the KEM ciphertext
+        // ck, k = HKDF(ck, KEM shared secret, 2)
+        let mut kem_size = 0;
+        if let Some(e_kem) = state.e_kem.clone() {
+            let c = Self::read_message2_kem_ciphertext(message)?;
+            let ciphertext = self.hash_and_decrypt(&mut state, c).await?;
+            let shared_secret = self.vault.kem_decapsulate(&e_kem, &ciphertext).await?;
+            self.hkdf(&mut state, shared_secret).await?;
+            kem_size = Self::encrypted_kem_ciphertext_size();
+        }
+
         // decrypt rs.pubKey
-        let rs_pub_key = Self::read_message2_encrypted_key(message)?;
+        let rs_pub_key = Self::read_message2_encrypted_key(message, kem_size)?;
         state.rs = Some(PublicKey::new(
             self.hash_and_decrypt(&mut state, rs_pub_key).await?,
             X25519,
@@ -137,8 +200,11 @@ impl Handshake {
         let dh = self.dh(state.e()?, state.rs()?).await?;
         self.hkdf(&mut state, dh).await?;
 
+        // ck, k = HKDF(ck, psk, 2)
+        self.mix_psk(&mut state).await?;
+
         // decrypt payload
-        let c = Self::read_message2_payload(message)?;
+        let c = Self::read_message2_payload(message, kem_size)?;
         let payload = self.hash_and_decrypt(&mut state, c).await?;
 
         self.state = state;
@@ -219,22 +285,40 @@ impl Handshake {
 }
 
 impl Handshake {
-    /// Create a new handshake
+    /// Create a new handshake, using a pre-shared key if one is provided
     pub(super) async fn new(
         vault: Arc<dyn SecureChannelVault>,
         static_key: KeyId,
+        psk: Option<Secret>,
+        hybrid_key_exchange: bool,
     ) -> Result<Handshake> {
         // 1. generate an ephemeral key pair for this handshake and set it to e
         let ephemeral_key = Self::generate_ephemeral_key(vault.clone()).await?;
 
         // 2. initialize the handshake
-        // We currently don't use any payload for message 1
+        // The only payload used for message 1 is the KEM public key of the hybrid key exchange
         Ok(Handshake {
             vault,
+            psk,
+            hybrid_key_exchange,
             state: HandshakeState::new(static_key, ephemeral_key),
         })
     }
 
+    /// Protocol name, used as a secret during the handshake initialization, padded to 32 bytes.
+    /// Using a different name when a pre-shared key is used makes the handshake fail early,
+    /// when receiving message 2, if only one party uses a pre-shared key.
+    ///
+    /// The pre-shared key is mixed with a plain HKDF, not with the `MixKeyAndHash` and
+    /// `MixKey(e.pub)` operations of the Noise psk modifiers, so the name is not a Noise one
+    fn protocol_name(&self) -> &'static [u8; 32] {
+        if self.psk.is_some() {
+            b"Ockam_XX_PSK_25519_AESGCM_SHA256"
+        } else {
+            b"Noise_XX_25519_AESGCM_SHA256\0\0\0\0"
+        }
+    }
+
     /// Import the k secret
     async fn import_k_secret(&self, content: Vec<u8>) -> Result<KeyId> {
         self.vault
@@ -293,6 +377,15 @@ impl Handshake {
         //_ => ,
     }
 
+    /// Mix the pre-shared key, if there is one, into the ck and k keys
+    async fn mix_psk(&self, state: &mut HandshakeState) -> Result<()> {
+        if let Some(psk) = &self.psk {
+            let psk = self.import_ck_secret(psk.as_ref().to_vec()).await?;
+            self.hkdf(state, psk).await?;
+        }
+        Ok(())
+    }
+
     /// Compute the final encryption and decryption keys
     async fn compute_final_keys(&self, state: &mut HandshakeState) -> Result<(KeyId, KeyId)> {
         let hkdf_output = self
@@ -346,6 +439,9 @@ impl Handshake {
 
     async fn delete_ephemeral_keys(&mut self) -> Result<()> {
         _ = self.vault.delete_secret(self.state.take_e()?).await?;
+        if let Some(e_kem) = self.state.e_kem.take() {
+            _ = self.vault.delete_secret(e_kem).await?;
+        }
 
         Ok(())
     }
@@ -353,11 +449,6 @@ impl Handshake {
 
 /// Static functions
 impl Handshake {
-    /// Protocol name, used as a secret during the handshake initialization, padded to 32 bytes
-    fn protocol_name() -> &'static [u8; 32] {
-        b"Noise_XX_25519_AESGCM_SHA256\0\0\0\0"
-    }
-
     /// Generate an ephemeral key for the key exchange
     async fn generate_ephemeral_key(vault: Arc<dyn SecureChannelVault>) -> Result<KeyId> {
         vault
@@ -380,14 +471,31 @@ impl Handshake {
         Self::read_end(message, Self::key_size())
     }
 
+    /// Read the message 2 encrypted KEM ciphertext, which is present after the public key
+    fn read_message2_kem_ciphertext(message: &[u8]) -> Result<&[u8]> {
+        Self::read_middle(
+            message,
+            Self::key_size(),
+            Self::encrypted_kem_ciphertext_size(),
+        )
+    }
+
     /// Read the message 2 encrypted key, which is present after the public key
-    fn read_message2_encrypted_key(message: &[u8]) -> Result<&[u8]> {
-        Self::read_middle(message, Self::key_size(), Self::encrypted_key_size())
+    /// and the encrypted KEM ciphertext, if any
+    fn read_message2_encrypted_key(message: &[u8], kem_size: usize) -> Result<&[u8]> {
+        Self::read_middle(
+            message,
+            Self::key_size() + kem_size,
+            Self::encrypted_key_size(),
+        )
     }
 
     /// Read the message 2 encrypted payload, which is present after the encrypted key
-    fn read_message2_payload(message: &[u8]) -> Result<&[u8]> {
-        Self::read_end(message, Self::key_size() + Self::encrypted_key_size())
+    fn read_message2_payload(message: &[u8], kem_size: usize) -> Result<&[u8]> {
+        Self::read_end(
+            message,
+            Self::key_size() + kem_size + Self::encrypted_key_size(),
+        )
     }
 
     /// Read the message 3 encrypted key at the beginning of the message
@@ -438,6 +546,11 @@ impl Handshake {
     fn encrypted_key_size() -> usize {
         Self::key_size() + AES_GCM_TAGSIZE_USIZE
     }
+
+    /// Size of an encrypted KEM ciphertext
+    fn encrypted_kem_ciphertext_size() -> usize {
+        KYBER768_CIPHERTEXT_LENGTH_USIZE + AES_GCM_TAGSIZE_USIZE
+    }
 }
 
 /// The `HandshakeState` contains all the variables necessary to follow the Noise protocol
@@ -445,8 +558,10 @@ impl Handshake {
 pub(super) struct HandshakeState {
     pub(super) s: Option<KeyId>,
     e: Option<KeyId>,
+    e_kem: Option<KeyId>,
     k: Option<KeyId>,
     re: Option<PublicKey>,
+    re_kem: Option<PublicKey>,
     pub(super) rs: Option<PublicKey>,
     n: u64,
     h: [u8; SHA256_SIZE_USIZE],
@@ -463,8 +578,10 @@ impl HandshakeState {
         HandshakeState {
             s: Some(s),
             e: Some(e),
+            e_kem: None,
             k: None,
             re: None,
+            re_kem: None,
             rs: None,
             n: 0,
             h: [0u8; SHA256_SIZE_USIZE],
@@ -595,7 +712,7 @@ mod tests {
         let static_key = vault
             .generate_static_secret(SecretAttributes::X25519)
             .await?;
-        let mut handshake = Handshake::new(vault.clone(), static_key).await?;
+        let mut handshake = Handshake::new(vault.clone(), static_key, None, false).await?;
         handshake.initialize().await?;
 
         let exp_h = [
@@ -653,10 +770,141 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_full_handshake_with_psk() -> Result<()> {
+        let psk = Secret::new([1u8; 32].to_vec());
+        check_handshake_with_psk(Some(psk.clone()), Some(psk)).await
+    }
+
+    #[tokio::test]
+    async fn test_handshake_with_different_psks() -> Result<()> {
+        let result = check_handshake_with_psk(
+            Some(Secret::new([1u8; 32].to_vec())),
+            Some(Secret::new([2u8; 32].to_vec())),
+        )
+        .await;
+        assert!(result.is_err());
+
+        let result = check_handshake_with_psk(Some(Secret::new([1u8; 32].to_vec())), None).await;
+        assert!(result.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_full_handshake_with_hybrid_key_exchange() -> Result<()> {
+        check_handshake_with_hybrid_key_exchange(true, true).await?;
+        // the responder accepts initiators using the hybrid key exchange by default
+        check_handshake_with_hybrid_key_exchange(true, false).await
+    }
+
+    #[tokio::test]
+    async fn test_handshake_with_hybrid_key_exchange_required() -> Result<()> {
+        let result = check_handshake_with_hybrid_key_exchange(false, true).await;
+        assert!(result.is_err());
+        Ok(())
+    }
+
     // --------------------
     // TESTS IMPLEMENTATION
     // --------------------
 
+    /// Run a full handshake where each party can use the hybrid key exchange,
+    /// the same way it is done in the initiator and responder state machines
+    async fn check_handshake_with_hybrid_key_exchange(
+        initiator_hybrid: bool,
+        responder_hybrid: bool,
+    ) -> Result<()> {
+        let vault = identities().vault().secure_channel_vault;
+
+        let initiator_static_key = vault
+            .generate_static_secret(SecretAttributes::X25519)
+            .await?;
+        let mut initiator =
+            Handshake::new(vault.clone(), initiator_static_key, None, initiator_hybrid).await?;
+
+        let responder_static_key = vault
+            .generate_static_secret(SecretAttributes::X25519)
+            .await?;
+        let mut responder =
+            Handshake::new(vault.clone(), responder_static_key, None, responder_hybrid).await?;
+
+        initiator.initialize().await?;
+        responder.initialize().await?;
+
+        let kem_public_key = initiator.make_kem_public_key().await?;
+        let message1 = initiator.encode_message1(&kem_public_key).await?;
+        let payload = responder.decode_message1(&message1).await?;
+        responder.set_their_kem_public_key(&payload)?;
+        let message2 = responder.encode_message2(b"message2").await?;
+        let payload = initiator.decode_message2(&message2).await?;
+        assert_eq!(payload, b"message2");
+        let message3 = initiator.encode_message3(b"message3").await?;
+        let payload = responder.decode_message3(&message3).await?;
+        assert_eq!(payload, b"message3");
+
+        initiator.set_final_state(Role::Initiator).await?;
+        responder.set_final_state(Role::Responder).await?;
+        let initiator_keys = initiator.get_handshake_keys().unwrap();
+        let responder_keys = responder.get_handshake_keys().unwrap();
+
+        let nonce = [0u8; 12];
+        let ciphertext = vault
+            .aead_aes_gcm_encrypt(&initiator_keys.encryption_key, b"hello", &nonce, &[])
+            .await?;
+        let plaintext = vault
+            .aead_aes_gcm_decrypt(&responder_keys.decryption_key, &ciphertext, &nonce, &[])
+            .await?;
+        assert_eq!(plaintext, b"hello");
+        Ok(())
+    }
+
+    /// Run a full handshake where each party can use a pre-shared key
+    /// and check that the resulting keys can be used to exchange messages
+    async fn check_handshake_with_psk(
+        initiator_psk: Option<Secret>,
+        responder_psk: Option<Secret>,
+    ) -> Result<()> {
+        let vault = identities().vault().secure_channel_vault;
+
+        let initiator_static_key = vault
+            .generate_static_secret(SecretAttributes::X25519)
+            .await?;
+        let mut initiator =
+            Handshake::new(vault.clone(), initiator_static_key, initiator_psk, false).await?;
+
+        let responder_static_key = vault
+            .generate_static_secret(SecretAttributes::X25519)
+            .await?;
+        let mut responder =
+            Handshake::new(vault.clone(), responder_static_key, responder_psk, false).await?;
+
+        initiator.initialize().await?;
+        responder.initialize().await?;
+
+        let message1 = initiator.encode_message1(&[]).await?;
+        responder.decode_message1(&message1).await?;
+        let message2 = responder.encode_message2(b"message2").await?;
+        initiator.decode_message2(&message2).await?;
+        let message3 = initiator.encode_message3(b"message3").await?;
+        let payload = responder.decode_message3(&message3).await?;
+        assert_eq!(payload, b"message3");
+
+        initiator.set_final_state(Role::Initiator).await?;
+        responder.set_final_state(Role::Responder).await?;
+        let initiator_keys = initiator.get_handshake_keys().unwrap();
+        let responder_keys = responder.get_handshake_keys().unwrap();
+
+        let nonce = [0u8; 12];
+        let ciphertext = vault
+            .aead_aes_gcm_encrypt(&initiator_keys.encryption_key, b"hello", &nonce, &[])
+            .await?;
+        let plaintext = vault
+            .aead_aes_gcm_decrypt(&responder_keys.decryption_key, &ciphertext, &nonce, &[])
+            .await?;
+        assert_eq!(plaintext, b"hello");
+        Ok(())
+    }
+
     struct HandshakeMessages {
         initiator_static_key: Vec<u8>,
         initiator_ephemeral_key: Vec<u8>,
@@ -691,6 +939,7 @@ mod tests {
             vault.secure_channel_vault.clone(),
             initiator_static_key_id,
             initiator_ephemeral_key_id,
+            None,
         )
         .await?;
 
@@ -712,6 +961,7 @@ mod tests {
             vault.secure_channel_vault.clone(),
             responder_static_key_id,
             responder_ephemeral_key_id,
+            None,
         )
         .await?;
         initiator.initialize().await?;
@@ -756,9 +1006,12 @@ mod tests {
             vault: Arc<dyn SecureChannelVault>,
             static_key: KeyId,
             ephemeral_key: KeyId,
+            psk: Option<Secret>,
         ) -> Result<Handshake> {
             Ok(Handshake {
                 vault,
+                psk,
+                hybrid_key_exchange: false,
                 state: HandshakeState::new(static_key, ephemeral_key),
             })
         }
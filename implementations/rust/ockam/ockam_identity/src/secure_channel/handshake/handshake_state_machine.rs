@@ -8,6 +8,7 @@ use tracing::debug;
 use crate::models::{
     ChangeHistory, CredentialAndPurposeKey, Identifier, PurposeKeyAttestation, PurposePublicKey,
 };
+use crate::secure_channel::parameters::ChannelParameters;
 use crate::{
     Identities, Identity, IdentityError, SecureChannelTrustInfo, TrustContext, TrustPolicy,
 };
@@ -53,10 +54,15 @@ pub(super) struct HandshakeKeys {
 
 /// The end result of a handshake with identity/credentials exchange is
 /// a pair of encryption/decryption keys + the identity of the other party
+/// + the channel parameters negotiated by both parties
 #[derive(Debug, Clone)]
 pub(super) struct HandshakeResults {
     pub(super) handshake_keys: HandshakeKeys,
     pub(super) their_identifier: Identifier,
+    pub(super) parameters: ChannelParameters,
+    /// True if the other party supports new handshakes on the established channel
+    /// to renew its keys
+    pub(super) renewable: bool,
 }
 
 /// This struct implements functions common to both initiator and the responder state machines
@@ -67,7 +73,9 @@ pub(super) struct CommonStateMachine {
     pub(super) credentials: Vec<CredentialAndPurposeKey>,
     pub(super) trust_policy: Arc<dyn TrustPolicy>,
     pub(super) trust_context: Option<TrustContext>,
+    pub(super) parameters: ChannelParameters,
     their_identifier: Option<Identifier>,
+    their_parameters: Option<ChannelParameters>,
 }
 
 impl CommonStateMachine {
@@ -78,6 +86,7 @@ impl CommonStateMachine {
         credentials: Vec<CredentialAndPurposeKey>,
         trust_policy: Arc<dyn TrustPolicy>,
         trust_context: Option<TrustContext>,
+        parameters: ChannelParameters,
     ) -> Self {
         Self {
             identities,
@@ -86,7 +95,9 @@ impl CommonStateMachine {
             credentials,
             trust_policy,
             trust_context,
+            parameters,
             their_identifier: None,
+            their_parameters: None,
         }
     }
 
@@ -96,6 +107,7 @@ impl CommonStateMachine {
     ///  - the current Identity Change History
     ///  - the current Secure Channel Purpose Key Attestation
     ///  - the Identity Credentials and corresponding Credentials Purpose Key Attestations
+    ///  - the parameters proposed for the secure channel
     ///
     pub(super) async fn make_identity_payload(&self) -> Result<Vec<u8>> {
         // prepare the payload that will be sent either in message 2 or message 3
@@ -108,6 +120,7 @@ impl CommonStateMachine {
             change_history,
             purpose_key_attestation: self.purpose_key_attestation.clone(),
             credentials: self.credentials.clone(),
+            parameters: Some(self.parameters.clone()),
         };
         Ok(minicbor::to_vec(payload)?)
     }
@@ -160,6 +173,7 @@ impl CommonStateMachine {
         self.verify_credentials(identity.identifier(), peer.credentials)
             .await?;
         self.their_identifier = Some(identity.identifier().clone());
+        self.their_parameters = peer.parameters;
         Ok(())
     }
 
@@ -211,6 +225,8 @@ impl CommonStateMachine {
     /// Return the results of the full handshake
     ///  - the other party identity
     ///  - the encryption and decryption keys to use on the next messages to exchange
+    ///  - the channel parameters agreed with the other party. If the other party
+    ///    did not send any parameters, it is using the default ones
     pub(super) fn make_handshake_results(
         &self,
         handshake_keys: Option<HandshakeKeys>,
@@ -219,6 +235,11 @@ impl CommonStateMachine {
             (Some(their_identifier), Some(handshake_keys)) => Some(HandshakeResults {
                 their_identifier,
                 handshake_keys,
+                parameters: self
+                    .parameters
+                    .negotiate(&self.their_parameters.clone().unwrap_or_default()),
+                // the parameters were introduced together with the renewal of the keys
+                renewable: self.their_parameters.is_some(),
             }),
             _ => None,
         }
@@ -239,4 +260,7 @@ pub(super) struct IdentityAndCredentials {
     /// Credentials associated to the identity along with corresponding Credentials Purpose Keys
     /// to verify those Credentials
     #[n(3)] pub(super) credentials: Vec<CredentialAndPurposeKey>,
+    /// Parameters proposed for the secure channel. They are absent when the other party
+    /// is using a version which does not support configurable parameters
+    #[n(4)] pub(super) parameters: Option<ChannelParameters>,
 }
@@ -1,20 +1,23 @@
 use alloc::sync::Arc;
+use core::sync::atomic::{AtomicBool, Ordering};
 use core::time::Duration;
-use ockam_core::compat::{boxed::Box, vec::Vec};
+use ockam_core::compat::{boxed::Box, string::ToString, vec::Vec};
 use ockam_core::errcode::{Kind, Origin};
 use ockam_core::{
-    AllowAll, Any, Decodable, DenyAll, Error, Mailbox, Mailboxes, OutgoingAccessControl, Route,
-    Routed,
+    route, Address, AllowAll, AllowSourceAddress, AllowSourceAddresses, Any, Decodable, DenyAll,
+    Error, Mailbox, Mailboxes, Message, OutgoingAccessControl, Route, Routed,
 };
 use ockam_core::{AllowOnwardAddress, Result, Worker};
 use ockam_node::callback::CallbackSender;
-use ockam_node::{Context, WorkerBuilder};
-use tracing::{debug, info};
+use ockam_node::{Context, DelayedEvent, WorkerBuilder};
+use ockam_vault::{Secret, SecureChannelVault};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
 
 use crate::models::{CredentialAndPurposeKey, Identifier};
 use crate::secure_channel::decryptor::DecryptorHandler;
 use crate::secure_channel::encryptor::Encryptor;
-use crate::secure_channel::encryptor_worker::EncryptorWorker;
+use crate::secure_channel::encryptor_worker::{EncryptionKeyUpdate, EncryptorWorker};
 use crate::secure_channel::handshake::handshake_state_machine::Action::SendMessage;
 use crate::secure_channel::handshake::handshake_state_machine::Event::{
     Initialize, ReceivedMessage,
@@ -24,10 +27,11 @@ use crate::secure_channel::handshake::handshake_state_machine::{
 };
 use crate::secure_channel::handshake::initiator_state_machine::InitiatorStateMachine;
 use crate::secure_channel::handshake::responder_state_machine::ResponderStateMachine;
+use crate::secure_channel::parameters::ChannelParameters;
 use crate::secure_channel::{Addresses, Role};
 use crate::{
-    IdentityError, PurposeKey, SecureChannelRegistryEntry, SecureChannels, TrustContext,
-    TrustPolicy,
+    Identities, IdentityError, PurposeKey, SecureChannelRegistryEntry, SecureChannels,
+    TrustContext, TrustPolicy,
 };
 
 /// This struct implements a Worker receiving and sending messages
@@ -41,6 +45,78 @@ pub(crate) struct HandshakeWorker {
     role: Role,
     remote_route: Option<Route>,
     decryptor_handler: Option<DecryptorHandler>,
+    settings: StateMachineSettings,
+    // state machine of a new handshake renewing the keys of the established channel
+    renewal: Option<Box<dyn StateMachine>>,
+    lifetime_timer: Option<(DelayedEvent<ChannelTimer>, Duration)>,
+    idle_timer: Option<(DelayedEvent<ChannelTimer>, Duration)>,
+    // set every time a message is encrypted or decrypted, to detect idle channels
+    activity: Arc<AtomicBool>,
+}
+
+/// Events sent to the `HandshakeWorker` by its timers once the channel is established
+#[derive(Serialize, Deserialize, Message, Clone, Debug)]
+enum ChannelTimer {
+    /// Perform a new handshake to renew the channel keys
+    Renew,
+    /// Check if messages were sent or received since the last check
+    IdleCheck,
+}
+
+/// Everything needed to create a state machine, kept in order to perform
+/// new handshakes when the channel keys are renewed
+#[derive(Clone)]
+struct StateMachineSettings {
+    vault: Arc<dyn SecureChannelVault>,
+    identities: Arc<Identities>,
+    identifier: Identifier,
+    purpose_key: PurposeKey,
+    credentials: Vec<CredentialAndPurposeKey>,
+    trust_policy: Arc<dyn TrustPolicy>,
+    trust_context: Option<TrustContext>,
+    parameters: ChannelParameters,
+    psk: Option<Secret>,
+    hybrid_key_exchange: bool,
+}
+
+impl StateMachineSettings {
+    /// Create a state machine for a new handshake with the given role
+    async fn create_state_machine(&self, role: Role) -> Result<Box<dyn StateMachine>> {
+        let state_machine: Box<dyn StateMachine> = if role.is_initiator() {
+            Box::new(
+                InitiatorStateMachine::new(
+                    self.vault.clone(),
+                    self.identities.clone(),
+                    self.identifier.clone(),
+                    self.purpose_key.clone(),
+                    self.credentials.clone(),
+                    self.trust_policy.clone(),
+                    self.trust_context.clone(),
+                    self.parameters.clone(),
+                    self.psk.clone(),
+                    self.hybrid_key_exchange,
+                )
+                .await?,
+            )
+        } else {
+            Box::new(
+                ResponderStateMachine::new(
+                    self.vault.clone(),
+                    self.identities.clone(),
+                    self.identifier.clone(),
+                    self.purpose_key.clone(),
+                    self.credentials.clone(),
+                    self.trust_policy.clone(),
+                    self.trust_context.clone(),
+                    self.parameters.clone(),
+                    self.psk.clone(),
+                    self.hybrid_key_exchange,
+                )
+                .await?,
+            )
+        };
+        Ok(state_machine)
+    }
 }
 
 #[ockam_core::worker]
@@ -83,20 +159,64 @@ impl Worker for HandshakeWorker {
         // Once the decryptor has been initialized, let it handle messages
         // Some messages can come from other systems using the remote address
         // and some messages can come from the current node when the decryptor
-        // used to support the decryption of Kafka messages for example
+        // used to support the decryption of Kafka messages for example.
+        // Handshake messages renewing the channel keys and timer events are handled here as well
         if let Some(decryptor_handler) = self.decryptor_handler.as_mut() {
             let msg_addr = message.msg_addr();
 
             let result = if msg_addr == self.addresses.decryptor_remote {
-                decryptor_handler.handle_decrypt(context, message).await
+                let handshake_message = decryptor_handler.handle_decrypt(context, message).await?;
+                match handshake_message {
+                    Some(handshake_message) => {
+                        self.handle_renewal_message(context, handshake_message)
+                            .await
+                    }
+                    None => {
+                        self.activity.store(true, Ordering::Relaxed);
+                        Ok(())
+                    }
+                }
             } else if msg_addr == self.addresses.decryptor_api {
                 decryptor_handler.handle_decrypt_api(context, message).await
+            } else if msg_addr == self.addresses.decryptor_timer {
+                let timer = ChannelTimer::decode(&message.into_transport_message().payload)?;
+                self.handle_timer(context, timer).await
             } else {
                 Err(IdentityError::UnknownChannelMsgDestination.into())
             };
             return result;
         };
 
+        let result = self.handle_handshake_message(context, message).await;
+        #[cfg(feature = "std")]
+        if result.is_err() {
+            record_handshake(self.role, "failure");
+        }
+        result
+    }
+
+    async fn shutdown(&mut self, context: &mut Self::Context) -> Result<()> {
+        let _ = context.stop_worker(self.addresses.encryptor.clone()).await;
+        self.secure_channels
+            .secure_channel_registry
+            .unregister_channel(&self.addresses.encryptor);
+
+        if let Some(handler) = &self.decryptor_handler {
+            handler.shutdown().await?
+        }
+
+        Ok(())
+    }
+}
+
+impl HandshakeWorker {
+    /// Let the state machine handle a handshake message, and finalize the
+    /// secure channel once the handshake is complete
+    async fn handle_handshake_message(
+        &mut self,
+        context: &mut Context,
+        message: Routed<Any>,
+    ) -> Result<()> {
         let transport_message = message.into_transport_message();
         if let SendMessage(message) = self
             .state_machine
@@ -124,6 +244,8 @@ impl Worker for HandshakeWorker {
         if let Some(final_state) = self.state_machine.get_handshake_results() {
             // start the encryptor worker and return the decryptor
             self.decryptor_handler = Some(self.finalize(context, final_state).await?);
+            #[cfg(feature = "std")]
+            record_handshake(self.role, "success");
             if let Some(callback_sender) = self.callback_sender.take() {
                 callback_sender.send(())?;
             }
@@ -132,21 +254,6 @@ impl Worker for HandshakeWorker {
         Ok(())
     }
 
-    async fn shutdown(&mut self, context: &mut Self::Context) -> Result<()> {
-        let _ = context.stop_worker(self.addresses.encryptor.clone()).await;
-        self.secure_channels
-            .secure_channel_registry
-            .unregister_channel(&self.addresses.encryptor);
-
-        if let Some(handler) = &self.decryptor_handler {
-            handler.shutdown().await?
-        }
-
-        Ok(())
-    }
-}
-
-impl HandshakeWorker {
     /// Create a new HandshakeWorker with a role of either INITIATOR or RESPONDER
     #[allow(clippy::too_many_arguments)]
     pub(crate) async fn create(
@@ -161,37 +268,58 @@ impl HandshakeWorker {
         trust_context: Option<TrustContext>,
         remote_route: Option<Route>,
         timeout: Option<Duration>,
+        parameters: ChannelParameters,
+        psk: Option<Secret>,
+        hybrid_key_exchange: bool,
+        max_lifetime: Option<Duration>,
+        idle_timeout: Option<Duration>,
         role: Role,
     ) -> Result<()> {
-        let vault = secure_channels.identities.vault().secure_channel_vault;
-        let identities = secure_channels.identities();
-        let state_machine: Box<dyn StateMachine> = if role.is_initiator() {
-            Box::new(
-                InitiatorStateMachine::new(
-                    vault,
-                    identities,
-                    identifier.clone(),
-                    purpose_key,
-                    credentials,
-                    trust_policy,
-                    trust_context,
+        let settings = StateMachineSettings {
+            vault: secure_channels.identities.vault().secure_channel_vault,
+            identities: secure_channels.identities(),
+            identifier: identifier.clone(),
+            purpose_key,
+            credentials,
+            trust_policy,
+            trust_context,
+            parameters,
+            psk,
+            hybrid_key_exchange,
+        };
+        let state_machine = settings.create_state_machine(role).await?;
+
+        // the timers are scheduled once the handshake is finished.
+        // Only the initiator starts new handshakes to renew the keys
+        let lifetime_timer = match max_lifetime {
+            Some(max_lifetime) if role.is_initiator() => Some((
+                DelayedEvent::create(
+                    context,
+                    addresses.decryptor_timer.clone(),
+                    ChannelTimer::Renew,
                 )
                 .await?,
-            )
-        } else {
-            Box::new(
-                ResponderStateMachine::new(
-                    vault,
-                    identities,
-                    identifier.clone(),
-                    purpose_key,
-                    credentials,
-                    trust_policy,
-                    trust_context,
+                max_lifetime,
+            )),
+            _ => None,
+        };
+        let idle_timer = match idle_timeout {
+            Some(idle_timeout) => Some((
+                DelayedEvent::create(
+                    context,
+                    addresses.decryptor_timer.clone(),
+                    ChannelTimer::IdleCheck,
                 )
                 .await?,
-            )
+                idle_timeout,
+            )),
+            None => None,
         };
+        let timer_addresses = lifetime_timer
+            .iter()
+            .chain(idle_timer.iter())
+            .map(|(timer, _)| timer.address())
+            .collect();
 
         let (callback_waiter, callback_sender) = if role.is_initiator() {
             let callback = ockam_node::callback::new_callback();
@@ -209,12 +337,18 @@ impl HandshakeWorker {
             remote_route: remote_route.clone(),
             addresses: addresses.clone(),
             decryptor_handler: None,
+            settings,
+            renewal: None,
+            lifetime_timer,
+            idle_timer,
+            activity: Arc::new(AtomicBool::new(false)),
         };
 
         WorkerBuilder::new(worker)
             .with_mailboxes(Self::create_mailboxes(
                 &addresses,
                 decryptor_outgoing_access_control,
+                timer_addresses,
             ))
             .start(context)
             .await?;
@@ -252,10 +386,21 @@ impl HandshakeWorker {
         })
     }
 
+    /// Return the address of the other party's decryptor
+    fn their_decryptor_address(&self) -> Result<Address> {
+        Ok(self
+            .remote_route()?
+            .iter()
+            .last()
+            .expect("the remote route should not be empty")
+            .clone())
+    }
+
     /// Create mailboxes and access rights for the workers involved in the secure channel creation
     pub(crate) fn create_mailboxes(
         addresses: &Addresses,
         decryptor_outgoing_access_control: Arc<dyn OutgoingAccessControl>,
+        timer_addresses: Vec<Address>,
     ) -> Mailboxes {
         let remote_mailbox = Mailbox::new(
             addresses.decryptor_remote.clone(),
@@ -276,15 +421,23 @@ impl HandshakeWorker {
             Arc::new(AllowAll),
             Arc::new(AllowAll),
         );
+        let timer_mailbox = Mailbox::new(
+            addresses.decryptor_timer.clone(),
+            Arc::new(AllowSourceAddresses(timer_addresses)),
+            Arc::new(DenyAll),
+        );
 
-        Mailboxes::new(remote_mailbox, vec![internal_mailbox, api_mailbox])
+        Mailboxes::new(
+            remote_mailbox,
+            vec![internal_mailbox, api_mailbox, timer_mailbox],
+        )
     }
 
     /// Finalize the handshake by creating a `Decryptor` and an `EncryptorWorker`
     /// Note that `EncryptorWorker` is actually started as an independent worker while
     /// the `Decryptor` is directly used by this worker to delegate the decryption of messages
     async fn finalize(
-        &self,
+        &mut self,
         context: &Context,
         handshake_results: HandshakeResults,
     ) -> Result<DecryptorHandler> {
@@ -295,7 +448,8 @@ impl HandshakeWorker {
             handshake_results.handshake_keys.decryption_key,
             self.secure_channels.identities.vault().secure_channel_vault,
             handshake_results.their_identifier.clone(),
-        );
+            &handshake_results.parameters,
+        )?;
 
         // create a separate encryptor worker which will be started independently
         {
@@ -307,7 +461,9 @@ impl HandshakeWorker {
                     handshake_results.handshake_keys.encryption_key,
                     0,
                     self.secure_channels.identities.vault().secure_channel_vault,
+                    &handshake_results.parameters,
                 ),
+                self.activity.clone(),
             );
 
             let next_hop = self.remote_route()?.next()?.clone();
@@ -321,13 +477,37 @@ impl HandshakeWorker {
                 Arc::new(AllowAll),
                 Arc::new(AllowAll),
             );
+            // only this worker can send new keys to the encryptor
+            let internal_mailbox = Mailbox::new(
+                self.addresses.encryptor_internal.clone(),
+                Arc::new(AllowSourceAddress(self.addresses.decryptor_api.clone())),
+                Arc::new(DenyAll),
+            );
 
             WorkerBuilder::new(encryptor)
-                .with_mailboxes(Mailboxes::new(main_mailbox, vec![api_mailbox]))
+                .with_mailboxes(Mailboxes::new(
+                    main_mailbox,
+                    vec![api_mailbox, internal_mailbox],
+                ))
                 .start(context)
                 .await?;
         }
 
+        if let Some((timer, max_lifetime)) = self.lifetime_timer.as_mut() {
+            if handshake_results.renewable {
+                timer.schedule(*max_lifetime).await?;
+            } else {
+                warn!(
+                    "SecureChannel {} at {} can not renew its keys: the other party does not support it",
+                    self.role.str(),
+                    &self.addresses.encryptor
+                );
+            }
+        }
+        if let Some((timer, idle_timeout)) = self.idle_timer.as_mut() {
+            timer.schedule(*idle_timeout).await?;
+        }
+
         info!(
             "Initialized SecureChannel {} at local: {}, remote: {}",
             self.role.str(),
@@ -335,12 +515,7 @@ impl HandshakeWorker {
             &self.addresses.decryptor_remote
         );
 
-        let their_decryptor_address = self
-            .remote_route()?
-            .iter()
-            .last()
-            .expect("the remote route should not be empty")
-            .clone();
+        let their_decryptor_address = self.their_decryptor_address()?;
 
         let info = SecureChannelRegistryEntry::new(
             self.addresses.encryptor.clone(),
@@ -359,4 +534,156 @@ impl HandshakeWorker {
 
         Ok(decryptor)
     }
+
+    /// Handle the events sent by the timers once the channel is established
+    async fn handle_timer(&mut self, context: &mut Context, timer: ChannelTimer) -> Result<()> {
+        match timer {
+            ChannelTimer::Renew => {
+                if let Some((timer, max_lifetime)) = self.lifetime_timer.as_mut() {
+                    timer.schedule(*max_lifetime).await?;
+                }
+                debug!(
+                    "SecureChannel {} at {} starts a new handshake",
+                    self.role.str(),
+                    &self.addresses.encryptor
+                );
+
+                let mut state_machine = self.settings.create_state_machine(self.role).await?;
+                if let SendMessage(message) = state_machine.on_event(Initialize).await? {
+                    self.send_renewal_message(context, message).await?;
+                }
+                self.renewal = Some(state_machine);
+                Ok(())
+            }
+            ChannelTimer::IdleCheck => {
+                if self.activity.swap(false, Ordering::Relaxed) {
+                    if let Some((timer, idle_timeout)) = self.idle_timer.as_mut() {
+                        timer.schedule(*idle_timeout).await?;
+                    }
+                    return Ok(());
+                }
+                info!(
+                    "SecureChannel {} at {} is idle, closing it",
+                    self.role.str(),
+                    &self.addresses.encryptor
+                );
+                // stopping the encryptor also stops this worker and unregisters the channel
+                context.stop_worker(self.addresses.encryptor.clone()).await
+            }
+        }
+    }
+
+    /// Handle a handshake message sent by the other party on the established channel.
+    /// Once the new handshake is finished, the new keys replace the current ones
+    async fn handle_renewal_message(
+        &mut self,
+        context: &mut Context,
+        message: Vec<u8>,
+    ) -> Result<()> {
+        let mut state_machine = match self.renewal.take() {
+            Some(state_machine) => state_machine,
+            // the other party starts a new handshake
+            None if !self.role.is_initiator() => {
+                let mut state_machine = self.settings.create_state_machine(self.role).await?;
+                state_machine.on_event(Initialize).await?;
+                state_machine
+            }
+            None => {
+                return Err(Error::new(
+                    Origin::KeyExchange,
+                    Kind::Invalid,
+                    "no new handshake was started",
+                ))
+            }
+        };
+
+        if let SendMessage(message) = state_machine.on_event(ReceivedMessage(message)).await? {
+            self.send_renewal_message(context, message).await?;
+        }
+
+        match state_machine.get_handshake_results() {
+            Some(handshake_results) => self.renew_keys(context, handshake_results).await,
+            None => {
+                self.renewal = Some(state_machine);
+                Ok(())
+            }
+        }
+    }
+
+    /// Send a handshake message to the other party's decryptor through the established channel
+    async fn send_renewal_message(&self, context: &Context, message: Vec<u8>) -> Result<()> {
+        context
+            .send_from_address(
+                route![
+                    self.addresses.encryptor.clone(),
+                    self.their_decryptor_address()?
+                ],
+                message,
+                self.addresses.decryptor_api.clone(),
+            )
+            .await
+    }
+
+    /// Replace the channel keys with the keys obtained with a new handshake
+    async fn renew_keys(
+        &mut self,
+        context: &Context,
+        handshake_results: HandshakeResults,
+    ) -> Result<()> {
+        let decryptor_handler = match self.decryptor_handler.as_mut() {
+            Some(decryptor_handler) => decryptor_handler,
+            None => return Err(IdentityError::ConsistencyError.into()),
+        };
+        let keys = handshake_results.handshake_keys;
+
+        // the identity of the other party can not change during the lifetime of the channel
+        if handshake_results.their_identifier != decryptor_handler.their_identity_id {
+            self.settings
+                .vault
+                .delete_secret(keys.encryption_key)
+                .await?;
+            self.settings
+                .vault
+                .delete_secret(keys.decryption_key)
+                .await?;
+            return Err(IdentityError::SecureChannelVerificationFailed.into());
+        }
+
+        // the last handshake message was sent before that update, with the previous key
+        context
+            .send_from_address(
+                self.addresses.encryptor_internal.clone(),
+                EncryptionKeyUpdate(keys.encryption_key.to_string()),
+                self.addresses.decryptor_api.clone(),
+            )
+            .await?;
+        decryptor_handler
+            .update_key(keys.decryption_key, &handshake_results.parameters)
+            .await?;
+        self.secure_channels
+            .secure_channel_registry
+            .record_key_renewal(&self.addresses.encryptor);
+
+        info!(
+            "SecureChannel {} at {} renewed its keys",
+            self.role.str(),
+            &self.addresses.encryptor
+        );
+        Ok(())
+    }
+}
+
+/// Count the completed and failed handshakes, per role
+#[cfg(feature = "std")]
+fn record_handshake(role: Role, result: &'static str) {
+    ockam_node::metrics::registry()
+        .counter(
+            "ockam_secure_channel_handshakes_total",
+            "Number of secure channel handshakes",
+            vec![
+                ("role", role.str().to_string()),
+                ("result", result.to_string()),
+            ],
+        )
+        .inc()
 }
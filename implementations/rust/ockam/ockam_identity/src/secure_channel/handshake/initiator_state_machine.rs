@@ -4,7 +4,7 @@ use ockam_core::compat::sync::Arc;
 use ockam_core::compat::{boxed::Box, vec::Vec};
 use ockam_core::errcode::{Kind, Origin};
 use ockam_core::{Error, Result};
-use ockam_vault::{PublicKey, SecureChannelVault};
+use ockam_vault::{PublicKey, Secret, SecureChannelVault};
 use Action::*;
 use Event::*;
 use Role::*;
@@ -17,6 +17,7 @@ use crate::secure_channel::handshake::handshake_state_machine::{
     Action, CommonStateMachine, Event, HandshakeKeys, HandshakeResults, IdentityAndCredentials,
     StateMachine, Status,
 };
+use crate::secure_channel::parameters::ChannelParameters;
 use crate::{Identities, PurposeKey, Role, TrustContext, TrustPolicy};
 
 /// Implementation of a state machine for the key exchange on the initiator side
@@ -28,7 +29,9 @@ impl StateMachine for InitiatorStateMachine {
             // Initialize the handshake and send message 1
             (Initial, Initialize) => {
                 self.initialize_handshake().await?;
-                let message1 = self.encode_message1(&[]).await?;
+                // the message 1 payload is a KEM public key if the hybrid key exchange is used
+                let kem_public_key = self.make_kem_public_key().await?;
+                let message1 = self.encode_message1(&kem_public_key).await?;
 
                 // Send message 1 and wait for message 2
                 self.handshake.state.status = WaitingForMessage2;
@@ -85,6 +88,7 @@ impl InitiatorStateMachine {
         to self.handshake {
             #[call(initialize)]
             async fn initialize_handshake(&mut self) -> Result<()>;
+            async fn make_kem_public_key(&mut self) -> Result<Vec<u8>>;
             async fn encode_message1(&mut self, payload: &[u8]) -> Result<Vec<u8>>;
             async fn decode_message2(&mut self, message: &[u8]) -> Result<Vec<u8>>;
             async fn encode_message3(&mut self, payload: &[u8]) -> Result<Vec<u8>>;
@@ -95,7 +99,8 @@ impl InitiatorStateMachine {
 }
 
 impl InitiatorStateMachine {
-    pub async fn new(
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) async fn new(
         vault: Arc<dyn SecureChannelVault>,
         identities: Arc<Identities>,
         identifier: Identifier,
@@ -103,6 +108,9 @@ impl InitiatorStateMachine {
         credentials: Vec<CredentialAndPurposeKey>,
         trust_policy: Arc<dyn TrustPolicy>,
         trust_context: Option<TrustContext>,
+        parameters: ChannelParameters,
+        psk: Option<Secret>,
+        hybrid_key_exchange: bool,
     ) -> Result<InitiatorStateMachine> {
         let common = CommonStateMachine::new(
             identities,
@@ -111,12 +119,19 @@ impl InitiatorStateMachine {
             credentials,
             trust_policy,
             trust_context,
+            parameters,
         );
         let identity_payload = common.make_identity_payload().await?;
 
         Ok(InitiatorStateMachine {
             common,
-            handshake: Handshake::new(vault, purpose_key.key_id().clone()).await?,
+            handshake: Handshake::new(
+                vault,
+                purpose_key.key_id().clone(),
+                psk,
+                hybrid_key_exchange,
+            )
+            .await?,
             identity_payload: Some(identity_payload),
         })
     }
@@ -4,7 +4,7 @@ use ockam_core::compat::sync::Arc;
 use ockam_core::compat::{boxed::Box, vec::Vec};
 use ockam_core::errcode::{Kind, Origin};
 use ockam_core::{Error, Result};
-use ockam_vault::{PublicKey, SecureChannelVault};
+use ockam_vault::{PublicKey, Secret, SecureChannelVault};
 use Action::*;
 use Event::*;
 use Role::*;
@@ -17,6 +17,7 @@ use crate::secure_channel::handshake::handshake_state_machine::{
     Action, CommonStateMachine, Event, HandshakeKeys, HandshakeResults, IdentityAndCredentials,
     StateMachine, Status,
 };
+use crate::secure_channel::parameters::ChannelParameters;
 use crate::{Identities, PurposeKey, Role, TrustContext, TrustPolicy};
 
 /// Implementation of a state machine for the key exchange on the responder side
@@ -33,7 +34,8 @@ impl StateMachine for ResponderStateMachine {
             }
             // Process message 1 and send message 2
             (WaitingForMessage1, ReceivedMessage(message)) => {
-                self.decode_message1(&message).await?;
+                let message1_payload = self.decode_message1(&message).await?;
+                self.set_their_kem_public_key(&message1_payload)?;
                 let identity_payload = self
                     .identity_payload
                     .take()
@@ -89,6 +91,7 @@ impl ResponderStateMachine {
             #[call(initialize)]
             async fn initialize_handshake(&mut self) -> Result<()>;
             async fn decode_message1(&mut self, message: &[u8]) -> Result<Vec<u8>>;
+            fn set_their_kem_public_key(&mut self, payload: &[u8]) -> Result<()>;
             async fn encode_message2(&mut self, payload: &[u8]) -> Result<Vec<u8>>;
             async fn decode_message3(&mut self, message: &[u8]) -> Result<Vec<u8>>;
             async fn set_final_state(&mut self, role: Role) -> Result<()>;
@@ -98,7 +101,8 @@ impl ResponderStateMachine {
 }
 
 impl ResponderStateMachine {
-    pub async fn new(
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) async fn new(
         vault: Arc<dyn SecureChannelVault>,
         identities: Arc<Identities>,
         identifier: Identifier,
@@ -106,6 +110,9 @@ impl ResponderStateMachine {
         credentials: Vec<CredentialAndPurposeKey>,
         trust_policy: Arc<dyn TrustPolicy>,
         trust_context: Option<TrustContext>,
+        parameters: ChannelParameters,
+        psk: Option<Secret>,
+        hybrid_key_exchange: bool,
     ) -> Result<ResponderStateMachine> {
         let common = CommonStateMachine::new(
             identities,
@@ -114,12 +121,19 @@ impl ResponderStateMachine {
             credentials,
             trust_policy,
             trust_context,
+            parameters,
         );
         let identity_payload = common.make_identity_payload().await?;
 
         Ok(ResponderStateMachine {
             common,
-            handshake: Handshake::new(vault, purpose_key.key_id().clone()).await?,
+            handshake: Handshake::new(
+                vault,
+                purpose_key.key_id().clone(),
+                psk,
+                hybrid_key_exchange,
+            )
+            .await?,
             identity_payload: Some(identity_payload),
         })
     }
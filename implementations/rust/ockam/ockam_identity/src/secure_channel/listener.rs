@@ -111,6 +111,11 @@ impl Worker for IdentityChannelListener {
             self.options.trust_context.clone(),
             None,
             None,
+            self.options.parameters.clone(),
+            self.options.psk.clone(),
+            self.options.hybrid_key_exchange,
+            None,
+            self.options.idle_timeout,
             Role::Responder,
         )
         .await?;
@@ -11,6 +11,7 @@ mod listener;
 mod local_info;
 mod nonce_tracker;
 mod options;
+mod parameters;
 mod registry;
 mod role;
 /// List of trust policies to setup ABAC controls
@@ -23,12 +24,14 @@ pub(crate) use handshake::*;
 pub(crate) use listener::*;
 pub use local_info::*;
 pub use options::*;
+pub use parameters::{DEFAULT_NONCE_WINDOW, DEFAULT_REKEY_INTERVAL};
 pub use registry::*;
 pub(crate) use role::*;
 pub use trust_policy::*;
 
 #[cfg(test)]
 mod tests {
+    use crate::secure_channel::parameters::ChannelParameters;
     use crate::secure_channel::{decryptor::Decryptor, encryptor::Encryptor};
     use crate::Vault;
     use ockam_core::compat::rand::RngCore;
@@ -132,7 +135,102 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_encrypt_decrypt_out_of_order_with_large_window() {
+        let parameters = ChannelParameters {
+            rekey_interval: 256,
+            nonce_window: 256,
+            ..Default::default()
+        };
+        let (mut encryptor, mut decryptor) =
+            create_encryptor_decryptor_with_parameters(&parameters)
+                .await
+                .unwrap();
+
+        let mut all_msgs: Vec<(Vec<u8>, Vec<u8>)> = Vec::new();
+        for n in 0..200u8 {
+            let msg = vec![n];
+            let ciphertext = encryptor.encrypt(&msg).await.unwrap();
+            all_msgs.push((msg, ciphertext));
+        }
+
+        // Messages displaced by up to 200 are accepted with a window of 256
+        all_msgs.reverse();
+        for (plaintext, ciphertext) in all_msgs.iter() {
+            assert_eq!(plaintext, &decryptor.decrypt(ciphertext).await.unwrap());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_encrypt_max_messages() {
+        let parameters = ChannelParameters {
+            max_messages: Some(10),
+            ..Default::default()
+        };
+        let (mut encryptor, _decryptor) = create_encryptor_decryptor_with_parameters(&parameters)
+            .await
+            .unwrap();
+
+        for n in 0..10 {
+            encryptor.encrypt(&[n]).await.unwrap();
+        }
+        assert!(!encryptor.is_limit_reached());
+        assert!(encryptor.encrypt(&[10]).await.is_err());
+        assert!(encryptor.is_limit_reached());
+    }
+
+    #[tokio::test]
+    async fn test_encrypt_max_bytes() {
+        let parameters = ChannelParameters {
+            max_bytes: Some(100),
+            ..Default::default()
+        };
+        let (mut encryptor, _decryptor) = create_encryptor_decryptor_with_parameters(&parameters)
+            .await
+            .unwrap();
+
+        encryptor.encrypt(&[0; 60]).await.unwrap();
+        encryptor.encrypt(&[0; 40]).await.unwrap();
+        assert!(encryptor.encrypt(&[0; 1]).await.is_err());
+        assert!(encryptor.is_limit_reached());
+    }
+
+    #[tokio::test]
+    async fn test_limits_apply_across_key_updates() {
+        let parameters = ChannelParameters {
+            max_messages: Some(10),
+            ..Default::default()
+        };
+        let vault = Vault::create_secure_channel_vault();
+        let key = vault
+            .import_ephemeral_secret(Secret::new(vec![1; 32]), SecretAttributes::Aes256)
+            .await
+            .unwrap();
+        let renewed_key = vault
+            .import_ephemeral_secret(Secret::new(vec![2; 32]), SecretAttributes::Aes256)
+            .await
+            .unwrap();
+        let mut encryptor = Encryptor::new(key, 0, vault, &parameters);
+
+        for n in 0..5 {
+            encryptor.encrypt(&[n]).await.unwrap();
+        }
+        // the messages sent with the previous key count towards the limit
+        encryptor.update_key(renewed_key).await.unwrap();
+        for n in 5..10 {
+            encryptor.encrypt(&[n]).await.unwrap();
+        }
+        assert!(encryptor.encrypt(&[10]).await.is_err());
+        assert!(encryptor.is_limit_reached());
+    }
+
     async fn create_encryptor_decryptor() -> Result<(Encryptor, Decryptor)> {
+        create_encryptor_decryptor_with_parameters(&ChannelParameters::default()).await
+    }
+
+    async fn create_encryptor_decryptor_with_parameters(
+        parameters: &ChannelParameters,
+    ) -> Result<(Encryptor, Decryptor)> {
         let vault1 = Vault::create_secure_channel_vault();
         let vault2 = Vault::create_secure_channel_vault();
 
@@ -152,8 +250,8 @@ mod tests {
             .unwrap();
 
         Ok((
-            Encryptor::new(key_on_v1, 0, vault1),
-            Decryptor::new(key_on_v2, vault2),
+            Encryptor::new(key_on_v1, 0, vault1, parameters),
+            Decryptor::new(key_on_v2, vault2, parameters)?,
         ))
     }
 }
@@ -1,83 +1,157 @@
-use crate::secure_channel::encryptor::KEY_RENEWAL_INTERVAL;
+use ockam_core::compat::vec::{vec, Vec};
+
 use crate::IdentityError;
 
-/// fails compilation if [`KEY_RENEWAL_INTERVAL`] + 1 is bigger than [`BitmapType::BITS`].
-///
-/// the +1 is needed since the current nonce is also marked as received, taking an extra bit
-/// even though we could check `current_nonce`, this compromise is for the sake of simplicity
-const _: [(); (KEY_RENEWAL_INTERVAL + 1 > BitmapType::BITS as u64) as usize] = [];
 type BitmapType = u64;
 
-#[derive(Debug)]
+/// Number of bits in one word of the bitmap
+const WORD_BITS: u64 = BitmapType::BITS as u64;
+
+#[derive(Debug, Clone)]
 pub(crate) struct NonceTracker {
-    nonce_bitmap: BitmapType,
+    /// bit `i` is set if the nonce `current_nonce - i` has already been received
+    nonce_bitmap: Vec<BitmapType>,
     current_nonce: u64,
+    window: u64,
 }
 
 impl NonceTracker {
-    pub(crate) fn new() -> Self {
-        Self {
-            nonce_bitmap: 0,
+    /// Create a tracker accepting nonces which are at most `window` messages
+    /// older than the most recent nonce
+    pub(crate) fn new(window: u64) -> ockam_core::Result<Self> {
+        // the +1 is needed since the current nonce is also marked as received, taking an extra bit
+        // even though we could check `current_nonce`, this compromise is for the sake of simplicity
+        let words = window
+            .checked_add(WORD_BITS)
+            .map(|bits| bits / WORD_BITS)
+            .and_then(|words| usize::try_from(words).ok())
+            .ok_or(IdentityError::InvalidNonce)?;
+        Ok(Self {
+            nonce_bitmap: vec![0; words],
             current_nonce: 0,
-        }
+            window,
+        })
     }
 
-    /// Mark a nonce as received, reject all invalid nonce values
-    pub(crate) fn mark(&self, nonce: u64) -> ockam_core::Result<NonceTracker> {
-        let new_tracker = if nonce > self.current_nonce {
-            // normal case, we increase the nonce and move the window
-            let relative_shift: u64 = nonce - self.current_nonce;
-            if relative_shift > KEY_RENEWAL_INTERVAL {
+    /// Check that a nonce can be received, without marking it
+    pub(crate) fn check(&self, nonce: u64) -> ockam_core::Result<()> {
+        if nonce > self.current_nonce {
+            if nonce - self.current_nonce > self.window {
                 return Err(IdentityError::InvalidNonce.into());
             }
-            NonceTracker {
-                nonce_bitmap: self.nonce_bitmap.overflowing_shl(relative_shift as u32).0 | 1,
-                current_nonce: nonce,
-            }
         } else {
-            // first message or an out of order message
-            let relative: u64 = self.current_nonce - nonce;
-            if relative > KEY_RENEWAL_INTERVAL {
+            let relative = self.current_nonce - nonce;
+            if relative > self.window {
                 return Err(IdentityError::InvalidNonce.into());
             }
-
-            #[allow(trivial_numeric_casts)]
-            let bit = (1 as BitmapType).overflowing_shl(relative as u32).0;
-            if self.nonce_bitmap & bit != 0 {
+            let (word, bit) = Self::position(relative);
+            if self.nonce_bitmap[word] & bit != 0 {
                 // we already processed this nonce
                 return Err(IdentityError::InvalidNonce.into());
             }
-            NonceTracker {
-                nonce_bitmap: self.nonce_bitmap | bit,
-                current_nonce: self.current_nonce,
-            }
-        };
+        }
+        Ok(())
+    }
 
-        Ok(new_tracker)
+    /// Mark a nonce as received, reject all invalid nonce values
+    pub(crate) fn mark(&mut self, nonce: u64) -> ockam_core::Result<()> {
+        self.check(nonce)?;
+        if nonce > self.current_nonce {
+            // normal case, we increase the nonce and move the window
+            shift_left(&mut self.nonce_bitmap, nonce - self.current_nonce);
+            self.nonce_bitmap[0] |= 1;
+            self.current_nonce = nonce;
+        } else {
+            // first message or an out of order message
+            let (word, bit) = Self::position(self.current_nonce - nonce);
+            self.nonce_bitmap[word] |= bit;
+        }
+        Ok(())
+    }
+
+    /// Word and bit of the bitmap tracking the nonce `current_nonce - relative`
+    fn position(relative: u64) -> (usize, BitmapType) {
+        #[allow(trivial_numeric_casts)]
+        let bit = (1 as BitmapType) << (relative % WORD_BITS);
+        ((relative / WORD_BITS) as usize, bit)
+    }
+}
+
+/// Shift a bitmap stored as a list of words, least significant word first, by `n` bits.
+/// The bits shifted past the last word are discarded.
+fn shift_left(bitmap: &mut [BitmapType], n: u64) {
+    let words = (n / WORD_BITS) as usize;
+    let bits = (n % WORD_BITS) as u32;
+    for i in (0..bitmap.len()).rev() {
+        bitmap[i] = match i.checked_sub(words) {
+            Some(j) => {
+                let mut word = bitmap[j] << bits;
+                if bits > 0 && j > 0 {
+                    word |= bitmap[j - 1] >> (WORD_BITS as u32 - bits);
+                }
+                word
+            }
+            None => 0,
+        }
     }
 }
 
-#[test]
-pub fn check_nonce_tracker() {
-    let mut tracker = NonceTracker::new();
-    tracker = tracker.mark(0).unwrap();
-    tracker = tracker.mark(1).unwrap();
-    tracker.mark(0).unwrap_err();
-    tracker.mark(KEY_RENEWAL_INTERVAL + 2).unwrap_err();
-    tracker = tracker.mark(KEY_RENEWAL_INTERVAL + 1).unwrap();
-    tracker.mark(1).unwrap_err();
-    tracker = tracker.mark(KEY_RENEWAL_INTERVAL + 2).unwrap();
-    tracker = tracker.mark(KEY_RENEWAL_INTERVAL + 3).unwrap();
-    tracker.mark(KEY_RENEWAL_INTERVAL + 1).unwrap_err();
-    tracker.mark(KEY_RENEWAL_INTERVAL + 2).unwrap_err();
-    tracker = tracker.mark(2 * KEY_RENEWAL_INTERVAL).unwrap();
-    tracker.mark(KEY_RENEWAL_INTERVAL - 1).unwrap_err();
-    tracker = tracker.mark(3 * KEY_RENEWAL_INTERVAL).unwrap();
-    tracker = tracker.mark(4 * KEY_RENEWAL_INTERVAL).unwrap();
-    for n in 3 * KEY_RENEWAL_INTERVAL + 1..4 * KEY_RENEWAL_INTERVAL {
-        tracker = tracker.mark(n).unwrap();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secure_channel::parameters::DEFAULT_NONCE_WINDOW;
+
+    #[test]
+    pub fn check_nonce_tracker() {
+        const WINDOW: u64 = DEFAULT_NONCE_WINDOW;
+
+        let mut tracker = NonceTracker::new(WINDOW).unwrap();
+        tracker.mark(0).unwrap();
+        tracker.mark(1).unwrap();
+        tracker.mark(0).unwrap_err();
+        tracker.mark(WINDOW + 2).unwrap_err();
+        tracker.mark(WINDOW + 1).unwrap();
+        tracker.mark(1).unwrap_err();
+        tracker.mark(WINDOW + 2).unwrap();
+        tracker.mark(WINDOW + 3).unwrap();
+        tracker.mark(WINDOW + 1).unwrap_err();
+        tracker.mark(WINDOW + 2).unwrap_err();
+        tracker.mark(2 * WINDOW).unwrap();
+        tracker.mark(WINDOW - 1).unwrap_err();
+        tracker.mark(3 * WINDOW).unwrap();
+        tracker.mark(4 * WINDOW).unwrap();
+        for n in 3 * WINDOW + 1..4 * WINDOW {
+            tracker.mark(n).unwrap();
+        }
+        for n in 4 * WINDOW + 1..5 * WINDOW + 1 {
+            tracker.mark(n).unwrap();
+        }
     }
-    for n in 4 * KEY_RENEWAL_INTERVAL + 1..5 * KEY_RENEWAL_INTERVAL + 1 {
-        tracker = tracker.mark(n).unwrap();
+
+    #[test]
+    pub fn check_large_nonce_tracker() {
+        const WINDOW: u64 = 200;
+
+        let mut tracker = NonceTracker::new(WINDOW).unwrap();
+        tracker.mark(0).unwrap();
+        tracker.mark(WINDOW).unwrap();
+        tracker.mark(WINDOW).unwrap_err();
+        tracker.mark(0).unwrap_err();
+        tracker.mark(2 * WINDOW + 1).unwrap_err();
+
+        // all the nonces in the window can be received once, in any order
+        for n in (1..WINDOW).rev() {
+            tracker.mark(n).unwrap();
+        }
+        for n in 0..=WINDOW {
+            tracker.mark(n).unwrap_err();
+        }
+
+        // the received nonces are still tracked after the window moved across words
+        tracker.mark(WINDOW + 70).unwrap();
+        tracker.mark(WINDOW).unwrap_err();
+        tracker.mark(75).unwrap_err();
+        tracker.mark(WINDOW + 69).unwrap();
+        tracker.mark(69).unwrap_err();
     }
 }
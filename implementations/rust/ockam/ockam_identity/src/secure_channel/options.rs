@@ -2,8 +2,10 @@ use ockam_core::compat::sync::Arc;
 use ockam_core::compat::vec::Vec;
 use ockam_core::flow_control::{FlowControlId, FlowControlOutgoingAccessControl, FlowControls};
 use ockam_core::{Address, OutgoingAccessControl, Result};
+use ockam_vault::Secret;
 
 use crate::models::CredentialAndPurposeKey;
+use crate::secure_channel::parameters::ChannelParameters;
 use crate::secure_channel::Addresses;
 use crate::{TrustContext, TrustEveryonePolicy, TrustPolicy};
 
@@ -13,6 +15,9 @@ use core::time::Duration;
 
 const DEFAULT_TIMEOUT: Duration = Duration::from_secs(120);
 
+/// Length of a pre-shared key used in a secure channel handshake
+pub const PSK_LENGTH: usize = 32;
+
 /// Trust options for a Secure Channel
 pub struct SecureChannelOptions {
     pub(crate) flow_control_id: FlowControlId,
@@ -20,6 +25,11 @@ pub struct SecureChannelOptions {
     pub(crate) trust_context: Option<TrustContext>,
     pub(crate) credentials: Vec<CredentialAndPurposeKey>,
     pub(crate) timeout: Duration,
+    pub(crate) parameters: ChannelParameters,
+    pub(crate) psk: Option<Secret>,
+    pub(crate) hybrid_key_exchange: bool,
+    pub(crate) max_lifetime: Option<Duration>,
+    pub(crate) idle_timeout: Option<Duration>,
 }
 
 impl fmt::Debug for SecureChannelOptions {
@@ -42,6 +52,11 @@ impl SecureChannelOptions {
             trust_context: None,
             credentials: vec![],
             timeout: DEFAULT_TIMEOUT,
+            parameters: ChannelParameters::default(),
+            psk: None,
+            hybrid_key_exchange: false,
+            max_lifetime: None,
+            idle_timeout: None,
         }
     }
 
@@ -75,6 +90,68 @@ impl SecureChannelOptions {
         self
     }
 
+    /// Sets the number of messages encrypted with the same key before rekeying,
+    /// instead of [`DEFAULT_REKEY_INTERVAL`](crate::secure_channel::DEFAULT_REKEY_INTERVAL).
+    /// The smallest interval proposed by both parties is used
+    pub fn with_rekey_interval(mut self, rekey_interval: u64) -> Self {
+        self.parameters.rekey_interval = rekey_interval;
+        self
+    }
+
+    /// Sets the number of messages which can be received out of order,
+    /// instead of [`DEFAULT_NONCE_WINDOW`](crate::secure_channel::DEFAULT_NONCE_WINDOW).
+    /// The largest window proposed by both parties is used, up to the rekey interval
+    pub fn with_nonce_window(mut self, nonce_window: u64) -> Self {
+        self.parameters.nonce_window = nonce_window;
+        self
+    }
+
+    /// Sets the maximum number of messages sent on the channel before it is closed.
+    /// The smallest limit proposed by both parties is used
+    pub fn with_max_messages(mut self, max_messages: u64) -> Self {
+        self.parameters.max_messages = Some(max_messages);
+        self
+    }
+
+    /// Sets the maximum number of bytes sent on the channel before it is closed.
+    /// The smallest limit proposed by both parties is used
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.parameters.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Sets the maximum duration during which the same keys are used.
+    /// Once that duration has elapsed, a new handshake is performed with the other party
+    /// and the channel keeps the same addresses with new keys.
+    /// This has no effect if the other party does not support renewing the channel keys
+    pub fn with_max_lifetime(mut self, max_lifetime: Duration) -> Self {
+        self.max_lifetime = Some(max_lifetime);
+        self
+    }
+
+    /// Sets a pre-shared key which is mixed into the handshake, in addition to the identity keys.
+    /// The other party must use the same pre-shared key, otherwise the handshake fails
+    pub fn with_psk(mut self, psk: [u8; PSK_LENGTH]) -> Self {
+        self.psk = Some(Secret::new(psk.to_vec()));
+        self
+    }
+
+    /// Use a hybrid key exchange: a post-quantum KEM (Kyber768) shared secret is mixed into
+    /// the handshake in addition to the X25519 Diffie-Hellman secrets.
+    /// The handshake fails if the listener doesn't support it
+    pub fn with_hybrid_key_exchange(mut self) -> Self {
+        self.hybrid_key_exchange = true;
+        self
+    }
+
+    /// Sets the duration after which the channel is closed if no message was sent or received.
+    /// The channel is closed once it has been idle for at least that duration,
+    /// but no later than twice that duration
+    pub fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = Some(idle_timeout);
+        self
+    }
+
     /// Freshly generated [`FlowControlId`]
     pub fn producer_flow_control_id(&self) -> FlowControlId {
         self.flow_control_id.clone()
@@ -129,6 +206,10 @@ pub struct SecureChannelListenerOptions {
     pub(crate) trust_policy: Arc<dyn TrustPolicy>,
     pub(crate) trust_context: Option<TrustContext>,
     pub(crate) credentials: Vec<CredentialAndPurposeKey>,
+    pub(crate) parameters: ChannelParameters,
+    pub(crate) psk: Option<Secret>,
+    pub(crate) hybrid_key_exchange: bool,
+    pub(crate) idle_timeout: Option<Duration>,
 }
 
 impl fmt::Debug for SecureChannelListenerOptions {
@@ -149,6 +230,10 @@ impl SecureChannelListenerOptions {
             trust_policy: Arc::new(TrustEveryonePolicy),
             trust_context: None,
             credentials: vec![],
+            parameters: ChannelParameters::default(),
+            psk: None,
+            hybrid_key_exchange: false,
+            idle_timeout: None,
         }
     }
 
@@ -185,6 +270,59 @@ impl SecureChannelListenerOptions {
         self
     }
 
+    /// Sets the number of messages encrypted with the same key before rekeying,
+    /// instead of [`DEFAULT_REKEY_INTERVAL`](crate::secure_channel::DEFAULT_REKEY_INTERVAL).
+    /// The smallest interval proposed by both parties is used
+    pub fn with_rekey_interval(mut self, rekey_interval: u64) -> Self {
+        self.parameters.rekey_interval = rekey_interval;
+        self
+    }
+
+    /// Sets the number of messages which can be received out of order,
+    /// instead of [`DEFAULT_NONCE_WINDOW`](crate::secure_channel::DEFAULT_NONCE_WINDOW).
+    /// The largest window proposed by both parties is used, up to the rekey interval
+    pub fn with_nonce_window(mut self, nonce_window: u64) -> Self {
+        self.parameters.nonce_window = nonce_window;
+        self
+    }
+
+    /// Sets the maximum number of messages sent on the channel before it is closed.
+    /// The smallest limit proposed by both parties is used
+    pub fn with_max_messages(mut self, max_messages: u64) -> Self {
+        self.parameters.max_messages = Some(max_messages);
+        self
+    }
+
+    /// Sets the maximum number of bytes sent on the channel before it is closed.
+    /// The smallest limit proposed by both parties is used
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.parameters.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Sets a pre-shared key which is mixed into the handshake, in addition to the identity keys.
+    /// The other party must use the same pre-shared key, otherwise the handshake fails
+    pub fn with_psk(mut self, psk: [u8; PSK_LENGTH]) -> Self {
+        self.psk = Some(Secret::new(psk.to_vec()));
+        self
+    }
+
+    /// Require a hybrid key exchange: initiators which don't mix a post-quantum KEM (Kyber768)
+    /// shared secret into the handshake are rejected.
+    /// Initiators using the hybrid key exchange are accepted even without this option
+    pub fn with_hybrid_key_exchange(mut self) -> Self {
+        self.hybrid_key_exchange = true;
+        self
+    }
+
+    /// Sets the duration after which the channel is closed if no message was sent or received.
+    /// The channel is closed once it has been idle for at least that duration,
+    /// but no later than twice that duration
+    pub fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = Some(idle_timeout);
+        self
+    }
+
     /// Freshly generated [`FlowControlId`]
     pub fn spawner_flow_control_id(&self) -> FlowControlId {
         self.flow_control_id.clone()
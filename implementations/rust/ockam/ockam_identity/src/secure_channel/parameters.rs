@@ -0,0 +1,115 @@
+use minicbor::{Decode, Encode};
+
+/// Default number of messages encrypted with the same key before rekeying
+pub const DEFAULT_REKEY_INTERVAL: u64 = 32;
+
+/// Default number of messages which can be received out of order
+pub const DEFAULT_NONCE_WINDOW: u64 = 32;
+
+/// Parameters used to encrypt and decrypt messages once a secure channel is established.
+///
+/// Each party sends its own parameters during the handshake and both parties
+/// then use the same negotiated parameters, see [`ChannelParameters::negotiate`].
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub(crate) struct ChannelParameters {
+    /// Number of messages encrypted with the same key before rekeying
+    #[n(1)] pub(crate) rekey_interval: u64,
+    /// Number of messages which can be received out of order
+    #[n(2)] pub(crate) nonce_window: u64,
+    /// Maximum number of messages encrypted before the channel is closed
+    #[n(3)] pub(crate) max_messages: Option<u64>,
+    /// Maximum number of bytes encrypted before the channel is closed
+    #[n(4)] pub(crate) max_bytes: Option<u64>,
+}
+
+impl Default for ChannelParameters {
+    fn default() -> Self {
+        Self {
+            rekey_interval: DEFAULT_REKEY_INTERVAL,
+            nonce_window: DEFAULT_NONCE_WINDOW,
+            max_messages: None,
+            max_bytes: None,
+        }
+    }
+}
+
+impl ChannelParameters {
+    /// Compute the parameters used by both parties, given our parameters and theirs.
+    ///
+    /// The computation is symmetric, so both parties end up with the same values:
+    ///
+    ///  - the smallest rekey interval and the smallest message limits are used
+    ///  - the largest nonce window is used, but it can not exceed the rekey interval
+    ///    since only the current and the previous keys are kept to decrypt messages
+    ///
+    pub(crate) fn negotiate(&self, theirs: &ChannelParameters) -> ChannelParameters {
+        let rekey_interval = self.rekey_interval.min(theirs.rekey_interval).max(1);
+        let nonce_window = self
+            .nonce_window
+            .max(theirs.nonce_window)
+            .clamp(1, rekey_interval);
+        ChannelParameters {
+            rekey_interval,
+            nonce_window,
+            max_messages: min_limit(self.max_messages, theirs.max_messages),
+            max_bytes: min_limit(self.max_bytes, theirs.max_bytes),
+        }
+    }
+}
+
+/// Return the smallest of two optional limits, where `None` means "no limit"
+fn min_limit(a: Option<u64>, b: Option<u64>) -> Option<u64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, None) => a,
+        (None, b) => b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_negotiate() {
+        let ours = ChannelParameters {
+            rekey_interval: 64,
+            nonce_window: 64,
+            max_messages: Some(1000),
+            max_bytes: None,
+        };
+        let theirs = ChannelParameters {
+            rekey_interval: 128,
+            nonce_window: 256,
+            max_messages: None,
+            max_bytes: Some(4096),
+        };
+
+        let expected = ChannelParameters {
+            rekey_interval: 64,
+            nonce_window: 64,
+            max_messages: Some(1000),
+            max_bytes: Some(4096),
+        };
+        assert_eq!(ours.negotiate(&theirs), expected);
+        assert_eq!(theirs.negotiate(&ours), expected);
+    }
+
+    #[test]
+    fn test_negotiate_with_defaults() {
+        let ours = ChannelParameters {
+            rekey_interval: 1024,
+            nonce_window: 512,
+            max_messages: None,
+            max_bytes: None,
+        };
+
+        // a party which doesn't send parameters uses the default ones
+        assert_eq!(
+            ours.negotiate(&ChannelParameters::default()),
+            ChannelParameters::default()
+        );
+    }
+}
@@ -17,6 +17,7 @@ pub struct SecureChannelRegistryEntry {
     my_id: Identifier,
     their_id: Identifier,
     their_decryptor_address: Address,
+    key_renewals: u64,
 }
 
 impl SecureChannelRegistryEntry {
@@ -41,6 +42,7 @@ impl SecureChannelRegistryEntry {
             my_id,
             their_id,
             their_decryptor_address,
+            key_renewals: 0,
         }
     }
 
@@ -83,6 +85,11 @@ impl SecureChannelRegistryEntry {
     pub fn their_decryptor_address(&self) -> Address {
         self.their_decryptor_address.clone()
     }
+
+    /// Number of times the channel keys were renewed with a new handshake
+    pub fn key_renewals(&self) -> u64 {
+        self.key_renewals
+    }
 }
 
 /// Registry of all known Secure Channels
@@ -125,6 +132,13 @@ impl SecureChannelRegistry {
         self.registry.write().unwrap().remove(encryptor_address)
     }
 
+    /// Record that the keys of a SecureChannel were renewed
+    pub(crate) fn record_key_renewal(&self, encryptor_address: &Address) {
+        if let Some(entry) = self.registry.write().unwrap().get_mut(encryptor_address) {
+            entry.key_renewals += 1;
+        }
+    }
+
     /// Get list of all known SecureChannels
     pub fn get_channel_list(&self) -> Vec<SecureChannelRegistryEntry> {
         self.registry.read().unwrap().values().cloned().collect()
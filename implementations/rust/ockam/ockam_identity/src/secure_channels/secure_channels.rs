@@ -117,6 +117,11 @@ impl SecureChannels {
             options.trust_context,
             Some(route),
             Some(options.timeout),
+            options.parameters,
+            options.psk,
+            options.hybrid_key_exchange,
+            options.max_lifetime,
+            options.idle_timeout,
             Role::Initiator,
         )
         .await?;
@@ -46,13 +46,22 @@ impl SecureChannelsBuilder {
         self
     }
 
+    /// Set a specific storage for the revocation lists repository
+    pub fn with_revocation_lists_storage(mut self, storage: Arc<dyn Storage>) -> Self {
+        self.identities_builder = self
+            .identities_builder
+            .with_revocation_lists_storage(storage);
+        self
+    }
+
     /// Set a specific identities
     pub fn with_identities(mut self, identities: Arc<Identities>) -> Self {
         self.identities_builder = self
             .identities_builder
             .with_identities_repository(identities.repository())
             .with_vault(identities.vault())
-            .with_purpose_keys_repository(identities.purpose_keys_repository());
+            .with_purpose_keys_repository(identities.purpose_keys_repository())
+            .with_revocation_lists_repository(identities.revocation_lists_repository());
         self
     }
 
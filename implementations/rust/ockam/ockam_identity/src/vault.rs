@@ -80,6 +80,17 @@ impl Vault {
         Ok(Self::create_with_persistent_storage(storage))
     }
 
+    /// Create Software Vaults with [`PersistentStorage`] with a given path,
+    /// where secrets are encrypted at rest with the given key
+    #[cfg(feature = "std")]
+    pub async fn create_with_encrypted_persistent_storage_path(
+        path: &std::path::Path,
+        key: ockam_vault::storage::StorageKey,
+    ) -> ockam_core::Result<Vault> {
+        let storage = ockam_vault::storage::PersistentStorage::create_encrypted(path, key).await?;
+        Ok(Self::create_with_persistent_storage(storage))
+    }
+
     /// Create Software Vaults with a given [`VaultStorage`]r
     pub fn create_with_persistent_storage(storage: VaultStorage) -> Vault {
         Self::new(
@@ -348,6 +348,248 @@ async fn test_channel_send_multiple_messages_both_directions(ctx: &mut Context)
     ctx.stop().await
 }
 
+#[ockam_macros::test]
+async fn test_channel_negotiated_parameters(ctx: &mut Context) -> Result<()> {
+    let secure_channels = secure_channels();
+    let identities_creation = secure_channels.identities().identities_creation();
+
+    let alice = identities_creation.create_identity().await?;
+    let bob = identities_creation.create_identity().await?;
+
+    // bob limits the number of messages, alice rekeys more often than the default
+    let bob_options = SecureChannelListenerOptions::new().with_max_messages(10);
+    let sc_listener_flow_control_id = bob_options.spawner_flow_control_id();
+    secure_channels
+        .create_secure_channel_listener(ctx, bob.identifier(), "bob_listener", bob_options)
+        .await?;
+
+    let alice_options = SecureChannelOptions::new()
+        .with_rekey_interval(4)
+        .with_nonce_window(64);
+    let alice_channel = secure_channels
+        .create_secure_channel(
+            ctx,
+            alice.identifier(),
+            route!["bob_listener"],
+            alice_options,
+        )
+        .await?;
+
+    let mut child_ctx = ctx
+        .new_detached_with_mailboxes(Mailboxes::main(
+            "child",
+            Arc::new(AllowAll),
+            Arc::new(AllowAll),
+        ))
+        .await?;
+    child_ctx
+        .flow_controls()
+        .add_consumer(child_ctx.address(), &sc_listener_flow_control_id);
+
+    // both parties use the same rekey interval, so messages can still be decrypted
+    for n in 0..10 {
+        let payload = format!("Hello, Bob! {}", n);
+        child_ctx
+            .send(
+                route![alice_channel.clone(), child_ctx.address()],
+                payload.clone(),
+            )
+            .await?;
+
+        let message = child_ctx.receive::<String>().await?;
+        assert_eq!(&payload, message.as_body());
+    }
+
+    let alice_decryptor = secure_channels
+        .secure_channel_registry()
+        .get_channel_by_encryptor_address(alice_channel.encryptor_address())
+        .unwrap()
+        .decryptor_messaging_address()
+        .clone();
+
+    // the limit set by bob applies to alice's side of the channel as well
+    child_ctx
+        .send(
+            route![alice_channel.clone(), child_ctx.address()],
+            "Hello, Bob!".to_string(),
+        )
+        .await?;
+    let result = child_ctx
+        .receive_extended::<String>(
+            MessageReceiveOptions::new().with_timeout(Duration::from_millis(100)),
+        )
+        .await;
+    assert!(result.is_err());
+
+    ctx.sleep(Duration::from_millis(100)).await;
+    let workers = ctx.list_workers().await?;
+    assert!(!workers.contains(alice_channel.encryptor_address()));
+    assert!(!workers.contains(&alice_decryptor));
+    assert!(secure_channels
+        .secure_channel_registry()
+        .get_channel_by_encryptor_address(alice_channel.encryptor_address())
+        .is_none());
+
+    ctx.stop().await
+}
+
+#[ockam_macros::test]
+async fn test_channel_renew_keys(ctx: &mut Context) -> Result<()> {
+    let secure_channels = secure_channels();
+    let identities_creation = secure_channels.identities().identities_creation();
+
+    let alice = identities_creation.create_identity().await?;
+    let bob = identities_creation.create_identity().await?;
+
+    let bob_options = SecureChannelListenerOptions::new();
+    let sc_listener_flow_control_id = bob_options.spawner_flow_control_id();
+    secure_channels
+        .create_secure_channel_listener(ctx, bob.identifier(), "bob_listener", bob_options)
+        .await?;
+
+    let alice_options = SecureChannelOptions::new().with_max_lifetime(Duration::from_millis(100));
+    let sc_flow_control_id = alice_options.producer_flow_control_id();
+    let alice_channel = secure_channels
+        .create_secure_channel(
+            ctx,
+            alice.identifier(),
+            route!["bob_listener"],
+            alice_options,
+        )
+        .await?;
+
+    let mut child_ctx = ctx
+        .new_detached_with_mailboxes(Mailboxes::main(
+            "child",
+            Arc::new(AllowAll),
+            Arc::new(AllowAll),
+        ))
+        .await?;
+    child_ctx
+        .flow_controls()
+        .add_consumer(child_ctx.address(), &sc_listener_flow_control_id);
+    child_ctx
+        .flow_controls()
+        .add_consumer(child_ctx.address(), &sc_flow_control_id);
+
+    // the keys are renewed several times while the channel is in use
+    for n in 0..5 {
+        let payload = format!("Hello, Bob! {}", n);
+        child_ctx
+            .send(
+                route![alice_channel.clone(), child_ctx.address()],
+                payload.clone(),
+            )
+            .await?;
+
+        let message = child_ctx.receive::<String>().await?;
+        assert_eq!(&payload, message.as_body());
+
+        let payload = format!("Hello, Alice! {}", n);
+        child_ctx
+            .send(message.return_route(), payload.clone())
+            .await?;
+
+        let message = child_ctx.receive::<String>().await?;
+        assert_eq!(&payload, message.as_body());
+
+        // wait for the keys to be renewed on both sides before sending the next messages
+        let renewed = wait_until(ctx, || {
+            secure_channels
+                .secure_channel_registry()
+                .get_channel_list()
+                .iter()
+                .all(|entry| entry.key_renewals() > n)
+        })
+        .await;
+        assert!(renewed);
+    }
+
+    // the channel keeps its addresses
+    assert!(secure_channels
+        .secure_channel_registry()
+        .get_channel_by_encryptor_address(alice_channel.encryptor_address())
+        .is_some());
+    assert_eq!(
+        secure_channels
+            .secure_channel_registry()
+            .get_channel_list()
+            .len(),
+        2
+    );
+
+    ctx.stop().await
+}
+
+#[ockam_macros::test]
+async fn test_channel_idle_timeout(ctx: &mut Context) -> Result<()> {
+    let secure_channels = secure_channels();
+    let identities_creation = secure_channels.identities().identities_creation();
+
+    let alice = identities_creation.create_identity().await?;
+    let bob = identities_creation.create_identity().await?;
+
+    let bob_options = SecureChannelListenerOptions::new();
+    let sc_listener_flow_control_id = bob_options.spawner_flow_control_id();
+    secure_channels
+        .create_secure_channel_listener(ctx, bob.identifier(), "bob_listener", bob_options)
+        .await?;
+
+    let alice_options = SecureChannelOptions::new().with_idle_timeout(Duration::from_millis(200));
+    let alice_channel = secure_channels
+        .create_secure_channel(
+            ctx,
+            alice.identifier(),
+            route!["bob_listener"],
+            alice_options,
+        )
+        .await?;
+
+    let mut child_ctx = ctx
+        .new_detached_with_mailboxes(Mailboxes::main(
+            "child",
+            Arc::new(AllowAll),
+            Arc::new(AllowAll),
+        ))
+        .await?;
+    child_ctx
+        .flow_controls()
+        .add_consumer(child_ctx.address(), &sc_listener_flow_control_id);
+
+    // the channel stays open while it is used, for longer than the idle timeout
+    for _ in 0..20 {
+        child_ctx
+            .send(
+                route![alice_channel.clone(), child_ctx.address()],
+                "Hello, Bob!".to_string(),
+            )
+            .await?;
+        child_ctx.receive::<String>().await?;
+        ctx.sleep(Duration::from_millis(20)).await;
+    }
+    let workers = ctx.list_workers().await?;
+    assert!(workers.contains(alice_channel.encryptor_address()));
+
+    // and it is closed once idle
+    let closed = wait_until(ctx, || {
+        secure_channels
+            .secure_channel_registry()
+            .get_channel_by_encryptor_address(alice_channel.encryptor_address())
+            .is_none()
+    })
+    .await;
+    assert!(closed);
+
+    let workers = ctx.list_workers().await?;
+    assert!(!workers.contains(alice_channel.encryptor_address()));
+    assert!(secure_channels
+        .secure_channel_registry()
+        .get_channel_by_encryptor_address(alice_channel.encryptor_address())
+        .is_none());
+
+    ctx.stop().await
+}
+
 #[ockam_macros::test]
 async fn test_channel_registry(ctx: &mut Context) -> Result<()> {
     let secure_channels = secure_channels();
@@ -1118,3 +1360,145 @@ async fn should_stop_encryptor__and__decryptor__in__secure_channel(
 
     ctx.stop().await
 }
+
+#[ockam_macros::test]
+async fn test_channel_with_psk(ctx: &mut Context) -> Result<()> {
+    let secure_channels = secure_channels();
+    let identities_creation = secure_channels.identities().identities_creation();
+
+    let alice = identities_creation.create_identity().await?;
+    let bob = identities_creation.create_identity().await?;
+
+    let psk = [7u8; 32];
+
+    let bob_options = SecureChannelListenerOptions::new().with_psk(psk);
+    let sc_listener_flow_control_id = bob_options.spawner_flow_control_id();
+    secure_channels
+        .create_secure_channel_listener(ctx, bob.identifier(), "bob_listener", bob_options)
+        .await?;
+
+    // a channel can be created when both parties use the same pre-shared key
+    let alice_channel = secure_channels
+        .create_secure_channel(
+            ctx,
+            alice.identifier(),
+            route!["bob_listener"],
+            SecureChannelOptions::new().with_psk(psk),
+        )
+        .await?;
+
+    let mut child_ctx = ctx
+        .new_detached_with_mailboxes(Mailboxes::main(
+            "child",
+            Arc::new(AllowAll),
+            Arc::new(AllowAll),
+        ))
+        .await?;
+    child_ctx
+        .flow_controls()
+        .add_consumer(child_ctx.address(), &sc_listener_flow_control_id);
+
+    child_ctx
+        .send(
+            route![alice_channel, child_ctx.address()],
+            "Hello, Bob!".to_string(),
+        )
+        .await?;
+    let msg = child_ctx.receive::<String>().await?;
+    assert_eq!("Hello, Bob!", msg.as_body());
+
+    // the handshake fails with a different pre-shared key
+    let res = secure_channels
+        .create_secure_channel(
+            ctx,
+            alice.identifier(),
+            route!["bob_listener"],
+            SecureChannelOptions::new()
+                .with_psk([8u8; 32])
+                .with_timeout(Duration::from_millis(500)),
+        )
+        .await;
+    assert!(res.is_err());
+
+    // or without a pre-shared key
+    let res = secure_channels
+        .create_secure_channel(
+            ctx,
+            alice.identifier(),
+            route!["bob_listener"],
+            SecureChannelOptions::new().with_timeout(Duration::from_millis(500)),
+        )
+        .await;
+    assert!(res.is_err());
+
+    ctx.stop().await
+}
+
+#[ockam_macros::test]
+async fn test_channel_with_hybrid_key_exchange(ctx: &mut Context) -> Result<()> {
+    let secure_channels = secure_channels();
+    let identities_creation = secure_channels.identities().identities_creation();
+
+    let alice = identities_creation.create_identity().await?;
+    let bob = identities_creation.create_identity().await?;
+
+    // bob only accepts channels using the hybrid key exchange
+    let bob_options = SecureChannelListenerOptions::new().with_hybrid_key_exchange();
+    let sc_listener_flow_control_id = bob_options.spawner_flow_control_id();
+    secure_channels
+        .create_secure_channel_listener(ctx, bob.identifier(), "bob_listener", bob_options)
+        .await?;
+
+    let alice_channel = secure_channels
+        .create_secure_channel(
+            ctx,
+            alice.identifier(),
+            route!["bob_listener"],
+            SecureChannelOptions::new().with_hybrid_key_exchange(),
+        )
+        .await?;
+
+    let mut child_ctx = ctx
+        .new_detached_with_mailboxes(Mailboxes::main(
+            "child",
+            Arc::new(AllowAll),
+            Arc::new(AllowAll),
+        ))
+        .await?;
+    child_ctx
+        .flow_controls()
+        .add_consumer(child_ctx.address(), &sc_listener_flow_control_id);
+
+    child_ctx
+        .send(
+            route![alice_channel, child_ctx.address()],
+            "Hello, Bob!".to_string(),
+        )
+        .await?;
+    let msg = child_ctx.receive::<String>().await?;
+    assert_eq!("Hello, Bob!", msg.as_body());
+
+    // a channel which doesn't use the hybrid key exchange is rejected
+    let res = secure_channels
+        .create_secure_channel(
+            ctx,
+            alice.identifier(),
+            route!["bob_listener"],
+            SecureChannelOptions::new().with_timeout(Duration::from_millis(500)),
+        )
+        .await;
+    assert!(res.is_err());
+
+    ctx.stop().await
+}
+
+/// Wait for a condition to become true, checking it every 10 milliseconds for at most 5 seconds
+async fn wait_until(ctx: &Context, condition: impl Fn() -> bool) -> bool {
+    for _ in 0..500 {
+        if condition() {
+            return true;
+        }
+        ctx.sleep(Duration::from_millis(10)).await;
+    }
+    condition()
+}
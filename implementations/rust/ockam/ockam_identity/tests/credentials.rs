@@ -4,12 +4,13 @@ use std::time::Duration;
 use ockam_core::compat::sync::Arc;
 use ockam_core::{async_trait, Any, DenyAll};
 use ockam_core::{route, Result, Routed, Worker};
-use ockam_identity::models::SchemaId;
+use ockam_identity::models::{CredentialAndPurposeKey, Identifier, SchemaId};
 use ockam_identity::secure_channels::secure_channels;
 use ockam_identity::utils::AttributesBuilder;
 use ockam_identity::{
-    AuthorityService, CredentialAccessControl, CredentialsMemoryRetriever,
-    SecureChannelListenerOptions, SecureChannelOptions, TrustContext, TrustIdentifierPolicy,
+    AuthorityService, CredentialAccessControl, CredentialsMemoryRetriever, RevocationListRefresher,
+    SecureChannel, SecureChannelListenerOptions, SecureChannelOptions, SecureChannels,
+    TrustContext, TrustIdentifierPolicy,
 };
 use ockam_node::{Context, WorkerBuilder};
 
@@ -310,6 +311,295 @@ async fn access_control(ctx: &mut Context) -> Result<()> {
     ctx.stop().await
 }
 
+/// Authority, server and client each use their own repositories, as if they were
+/// running on different nodes
+struct RevocationParties {
+    authority_channels: Arc<SecureChannels>,
+    server_channels: Arc<SecureChannels>,
+    client_channels: Arc<SecureChannels>,
+    authority: Identifier,
+    server: Identifier,
+    client: Identifier,
+    channel: SecureChannel,
+    credential: CredentialAndPurposeKey,
+}
+
+async fn setup_revocation_parties(ctx: &Context) -> Result<RevocationParties> {
+    let authority_channels = secure_channels();
+    let server_channels = secure_channels();
+    let client_channels = secure_channels();
+
+    let authority = authority_channels
+        .identities()
+        .identities_creation()
+        .create_identity()
+        .await?;
+    let server = server_channels
+        .identities()
+        .identities_creation()
+        .create_identity()
+        .await?;
+    let client = client_channels
+        .identities()
+        .identities_creation()
+        .create_identity()
+        .await?;
+
+    // The server knows the authority, but none of its revocation lists
+    server_channels
+        .identities()
+        .identities_creation()
+        .import(Some(authority.identifier()), &authority.export()?)
+        .await?;
+    // The authority knows the client it issues a credential to
+    authority_channels
+        .identities()
+        .identities_creation()
+        .import(Some(client.identifier()), &client.export()?)
+        .await?;
+
+    // The authority serves its own revocation list
+    let authority_listener = authority_channels
+        .create_secure_channel_listener(
+            ctx,
+            authority.identifier(),
+            "authority_listener",
+            SecureChannelListenerOptions::new(),
+        )
+        .await?;
+    ctx.flow_controls().add_consumer(
+        "authority_credential_exchange",
+        authority_listener.flow_control_id(),
+    );
+    let authority_trust_context = TrustContext::new(
+        "test_trust_context_id".to_string(),
+        Some(AuthorityService::new(
+            authority_channels.identities().credentials(),
+            authority.identifier().clone(),
+            None,
+        )),
+    );
+    authority_channels
+        .identities()
+        .credentials_server()
+        .start(
+            ctx,
+            authority_trust_context,
+            authority.identifier().clone(),
+            "authority_credential_exchange".into(),
+            false,
+        )
+        .await?;
+
+    let listener = server_channels
+        .create_secure_channel_listener(
+            ctx,
+            server.identifier(),
+            "listener",
+            SecureChannelListenerOptions::new(),
+        )
+        .await?;
+    ctx.flow_controls()
+        .add_consumer("credential_exchange", listener.flow_control_id());
+    let server_trust_context = TrustContext::new(
+        "test_trust_context_id".to_string(),
+        Some(AuthorityService::new(
+            server_channels.identities().credentials(),
+            authority.identifier().clone(),
+            None,
+        )),
+    );
+    server_channels
+        .identities()
+        .credentials_server()
+        .start(
+            ctx,
+            server_trust_context,
+            server.identifier().clone(),
+            "credential_exchange".into(),
+            false,
+        )
+        .await?;
+
+    let channel = client_channels
+        .create_secure_channel(
+            ctx,
+            client.identifier(),
+            route!["listener"],
+            SecureChannelOptions::new()
+                .with_trust_policy(TrustIdentifierPolicy::new(server.identifier().clone())),
+        )
+        .await?;
+
+    let credential = authority_channels
+        .identities()
+        .credentials()
+        .credentials_creation()
+        .issue_credential(
+            authority.identifier(),
+            client.identifier(),
+            AttributesBuilder::with_schema(SchemaId(0))
+                .with_attribute("is_superuser", "true")
+                .build(),
+            Duration::from_secs(60),
+        )
+        .await?;
+
+    client_channels
+        .identities()
+        .credentials_server()
+        .present_credential(
+            ctx,
+            route![channel.clone(), "credential_exchange"],
+            credential.clone(),
+        )
+        .await?;
+    assert!(server_channels
+        .identities()
+        .repository()
+        .get_attributes(client.identifier())
+        .await?
+        .is_some());
+
+    Ok(RevocationParties {
+        authority_channels,
+        server_channels,
+        client_channels,
+        authority: authority.identifier().clone(),
+        server: server.identifier().clone(),
+        client: client.identifier().clone(),
+        channel,
+        credential,
+    })
+}
+
+async fn assert_credential_revoked(ctx: &Context, parties: RevocationParties) -> Result<()> {
+    assert!(parties
+        .server_channels
+        .identities()
+        .repository()
+        .get_attributes(&parties.client)
+        .await?
+        .is_none());
+
+    let res = parties
+        .server_channels
+        .identities()
+        .credentials()
+        .credentials_verification()
+        .verify_credential(
+            Some(&parties.client),
+            &[parties.authority.clone()],
+            &parties.credential,
+        )
+        .await;
+    assert!(res.is_err());
+
+    let res = parties
+        .client_channels
+        .identities()
+        .credentials_server()
+        .present_credential(
+            ctx,
+            route![parties.channel, "credential_exchange"],
+            parties.credential,
+        )
+        .await;
+    assert!(res.is_err());
+    assert!(parties
+        .server_channels
+        .identities()
+        .repository()
+        .get_attributes(&parties.client)
+        .await?
+        .is_none());
+
+    Ok(())
+}
+
+#[ockam_macros::test]
+async fn revoked_credential_presented_by_authority(ctx: &mut Context) -> Result<()> {
+    let parties = setup_revocation_parties(ctx).await?;
+
+    let revocation_list = parties
+        .authority_channels
+        .identities()
+        .credentials()
+        .credentials_creation()
+        .issue_revocation_list(&parties.authority, vec![parties.client.clone()], vec![])
+        .await?;
+
+    let authority_channel = parties
+        .authority_channels
+        .create_secure_channel(
+            ctx,
+            &parties.authority,
+            route!["listener"],
+            SecureChannelOptions::new()
+                .with_trust_policy(TrustIdentifierPolicy::new(parties.server.clone())),
+        )
+        .await?;
+    parties
+        .authority_channels
+        .identities()
+        .credentials_server()
+        .present_revocation_list(
+            ctx,
+            route![authority_channel, "credential_exchange"],
+            revocation_list,
+        )
+        .await?;
+
+    assert_credential_revoked(ctx, parties).await?;
+
+    ctx.stop().await
+}
+
+#[ockam_macros::test]
+async fn revoked_credential_fetched_from_authority(ctx: &mut Context) -> Result<()> {
+    let parties = setup_revocation_parties(ctx).await?;
+
+    parties
+        .authority_channels
+        .identities()
+        .credentials()
+        .credentials_creation()
+        .issue_revocation_list(&parties.authority, vec![parties.client.clone()], vec![])
+        .await?;
+
+    // Nothing was presented to the server, it has to fetch the list from the authority
+    let authority_channel = parties
+        .server_channels
+        .create_secure_channel(
+            ctx,
+            &parties.server,
+            route!["authority_listener"],
+            SecureChannelOptions::new()
+                .with_trust_policy(TrustIdentifierPolicy::new(parties.authority.clone())),
+        )
+        .await?;
+    let refresher = RevocationListRefresher::new(
+        parties.server_channels.identities().credentials_server(),
+        route![authority_channel, "authority_credential_exchange"],
+        vec![parties.authority.clone()],
+        Duration::from_secs(3600),
+    );
+    refresher.refresh(ctx).await?;
+
+    assert!(parties
+        .server_channels
+        .identities()
+        .credentials()
+        .revocation_lists_repository()
+        .retrieve_revocation_list(&parties.authority)
+        .await?
+        .is_some());
+
+    assert_credential_revoked(ctx, parties).await?;
+
+    ctx.stop().await
+}
+
 struct CountingWorker {
     msgs_count: Arc<AtomicI8>,
 }
@@ -27,7 +27,7 @@ use once_cell::race::OnceBox;
 use std::net::{SocketAddrV4, SocketAddrV6};
 use tinyvec::{Array, ArrayVec, TinyVec};
 
-use crate::proto::{DnsAddr, Ip4, Ip6, Tcp};
+use crate::proto::{DnsAddr, Ip4, Ip6, Tcp, Udp};
 pub use error::Error;
 use ockam_core::env::FromString;
 pub use registry::{Registry, RegistryBuilder};
@@ -591,26 +591,36 @@ impl MultiAddr {
 
     /// If the input MultiAddr is "/dnsaddr/localhost/tcp/4000/service/api",
     /// then this will return string format of the SocketAddr: "127.0.0.1:4000".
+    ///
+    /// The port can be a TCP or a UDP port.
     pub fn to_socket_addr(&self) -> Result<String, Error> {
+        fn port(p: Option<ProtoValue>) -> Result<u16, Error> {
+            match p {
+                Some(p) if p.code() == Tcp::CODE => Ok(*p.cast::<Tcp>().unwrap()),
+                Some(p) if p.code() == Udp::CODE => Ok(*p.cast::<Udp>().unwrap()),
+                Some(p) => Err(Error::invalid_proto(p.code())),
+                None => Err(Error::message("No port found")),
+            }
+        }
         let mut it = self.iter().peekable();
         while let Some(p) = it.next() {
             match p.code() {
                 Ip4::CODE => {
                     let ip4 = p.cast::<Ip4>().unwrap();
-                    let port = it.next().unwrap().cast::<Tcp>().unwrap();
-                    return Ok(SocketAddrV4::new(*ip4, *port).to_string());
+                    let port = port(it.next())?;
+                    return Ok(SocketAddrV4::new(*ip4, port).to_string());
                 }
                 Ip6::CODE => {
                     let ip6 = p.cast::<Ip6>().unwrap();
-                    let port = it.next().unwrap().cast::<Tcp>().unwrap();
-                    return Ok(SocketAddrV6::new(*ip6, *port, 0, 0).to_string());
+                    let port = port(it.next())?;
+                    return Ok(SocketAddrV6::new(*ip6, port, 0, 0).to_string());
                 }
                 DnsAddr::CODE => {
                     let host = p.cast::<DnsAddr>().unwrap();
                     if let Some(p) = it.peek() {
-                        if p.code() == Tcp::CODE {
-                            let port = p.cast::<Tcp>().unwrap();
-                            return Ok(format!("{}:{}", &*host, *port));
+                        if p.code() == Tcp::CODE || p.code() == Udp::CODE {
+                            let port = port(it.next())?;
+                            return Ok(format!("{}:{}", &*host, port));
                         }
                     }
                 }
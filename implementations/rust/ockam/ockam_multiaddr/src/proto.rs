@@ -1,6 +1,8 @@
 use super::{Buffer, Checked, Code, Protocol};
 use crate::Error;
 use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
 use core::fmt;
 use core::ops::Deref;
 use core::str::{self, FromStr};
@@ -153,6 +155,127 @@ impl Protocol<'_> for Tcp {
     }
 }
 
+/// A UDP port number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Udp(pub u16);
+
+impl Udp {
+    pub fn new(v: u16) -> Self {
+        Udp(v)
+    }
+}
+
+impl Deref for Udp {
+    type Target = u16;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Protocol<'_> for Udp {
+    const CODE: Code = Code::new(273);
+    const PREFIX: &'static str = "udp";
+
+    fn read_str(input: Checked<&str>) -> Result<Self, Error> {
+        u16::from_str(&input).map(Udp).map_err(Error::message)
+    }
+
+    fn read_bytes(input: Checked<&[u8]>) -> Result<Self, Error> {
+        let mut b = [0; 2];
+        b.copy_from_slice(&input);
+        Ok(Udp(u16::from_be_bytes(b)))
+    }
+
+    fn write_str(&self, f: &mut fmt::Formatter) -> Result<(), Error> {
+        write!(f, "/{}/{}", Self::PREFIX, self.0)?;
+        Ok(())
+    }
+
+    fn write_bytes(&self, buf: &mut dyn Buffer) {
+        let mut b = encode::u32_buffer();
+        let uvi = encode::u32(Self::CODE.into(), &mut b);
+        buf.extend_with(uvi);
+        buf.extend_with(&self.0.to_be_bytes())
+    }
+}
+
+/// The path of a Unix domain socket.
+///
+/// In the textual representation the path is percent-encoded, so that
+/// it does not contain any '/', e.g. `/unix/%2Ftmp%2Fockam.sock`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Unix<'a>(Cow<'a, str>);
+
+impl<'a> Unix<'a> {
+    pub fn new<S: Into<Cow<'a, str>>>(s: S) -> Self {
+        Self(s.into())
+    }
+}
+
+impl Deref for Unix<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'a> Protocol<'a> for Unix<'a> {
+    const CODE: Code = Code::new(400);
+    const PREFIX: &'static str = "unix";
+
+    fn read_str(input: Checked<&'a str>) -> Result<Self, Error> {
+        if !input.0.contains('%') {
+            return Ok(Self(Cow::Borrowed(input.0)));
+        }
+        let mut bytes = Vec::with_capacity(input.0.len());
+        let mut it = input.0.bytes();
+        while let Some(b) = it.next() {
+            if b == b'%' {
+                let hex = [it.next(), it.next()];
+                let hex = match hex {
+                    [Some(h), Some(l)] => [h, l],
+                    _ => return Err(Error::message("invalid percent-encoding")),
+                };
+                let hex = str::from_utf8(&hex).map_err(Error::message)?;
+                bytes.push(u8::from_str_radix(hex, 16).map_err(Error::message)?)
+            } else {
+                bytes.push(b)
+            }
+        }
+        let s = String::from_utf8(bytes).map_err(Error::message)?;
+        Ok(Self(Cow::Owned(s)))
+    }
+
+    fn read_bytes(input: Checked<&'a [u8]>) -> Result<Self, Error> {
+        let s = str::from_utf8(&input).map_err(Error::message)?;
+        Ok(Self(Cow::Borrowed(s)))
+    }
+
+    fn write_str(&self, f: &mut fmt::Formatter) -> Result<(), Error> {
+        write!(f, "/{}/", Self::PREFIX)?;
+        for c in self.0.chars() {
+            match c {
+                '/' => f.write_str("%2F")?,
+                '%' => f.write_str("%25")?,
+                c => write!(f, "{c}")?,
+            }
+        }
+        Ok(())
+    }
+
+    fn write_bytes(&self, buf: &mut dyn Buffer) {
+        let mut b = encode::u32_buffer();
+        let uvi = encode::u32(Self::CODE.into(), &mut b);
+        buf.extend_with(uvi);
+        let mut b = encode::usize_buffer();
+        let uvi = encode::usize(self.0.len(), &mut b);
+        buf.extend_with(uvi);
+        buf.extend_with(self.0.as_bytes())
+    }
+}
+
 macro_rules! gen_str_proto {
     ($t:ident, $c:literal, $p:literal) => {
         #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -210,3 +333,52 @@ gen_str_proto!(Node, 72526, "node");
 gen_str_proto!(Project, 82526, "project");
 gen_str_proto!(Space, 92526, "space");
 gen_str_proto!(Secure, 99526, "secure");
+
+macro_rules! gen_marker_proto {
+    ($t:ident, $c:literal, $p:literal) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+        pub struct $t;
+
+        impl $t {
+            pub fn new() -> Self {
+                Self
+            }
+        }
+
+        impl Protocol<'_> for $t {
+            const CODE: Code = Code::new($c);
+            const PREFIX: &'static str = $p;
+
+            fn read_str(input: Checked<&str>) -> Result<Self, Error> {
+                if input.is_empty() {
+                    Ok(Self)
+                } else {
+                    Err(Error::message(concat!("/", $p, " has no value")))
+                }
+            }
+
+            fn read_bytes(input: Checked<&[u8]>) -> Result<Self, Error> {
+                if input.is_empty() {
+                    Ok(Self)
+                } else {
+                    Err(Error::message(concat!("/", $p, " has no value")))
+                }
+            }
+
+            fn write_str(&self, f: &mut fmt::Formatter) -> Result<(), Error> {
+                write!(f, "/{}", Self::PREFIX)?;
+                Ok(())
+            }
+
+            fn write_bytes(&self, buf: &mut dyn Buffer) {
+                let mut b = encode::u32_buffer();
+                let uvi = encode::u32(Self::CODE.into(), &mut b);
+                buf.extend_with(uvi)
+            }
+        }
+    };
+}
+
+// WebSocket markers, which follow the TCP address of a connection, e.g. `/ip4/10.0.0.1/tcp/80/ws`
+gen_marker_proto!(Ws, 477, "ws");
+gen_marker_proto!(Wss, 478, "wss");
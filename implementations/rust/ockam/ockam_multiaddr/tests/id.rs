@@ -1,5 +1,7 @@
 use core::fmt;
-use ockam_multiaddr::proto::{DnsAddr, Ip4, Ip6, Node, Project, Secure, Service, Space, Tcp};
+use ockam_multiaddr::proto::{
+    DnsAddr, Ip4, Ip6, Node, Project, Secure, Service, Space, Tcp, Udp, Unix, Ws, Wss,
+};
 use ockam_multiaddr::{Code, Match, MultiAddr, Protocol};
 use quickcheck::{quickcheck, Arbitrary, Gen};
 use rand::distributions::{Alphanumeric, DistString};
@@ -132,6 +134,22 @@ quickcheck! {
                         addr.push_back(Space::new("space")).unwrap();
                         prot.push_back(Space::CODE);
                     }
+                    Udp::CODE => {
+                        addr.push_back(Udp::new(0)).unwrap();
+                        prot.push_back(Udp::CODE);
+                    }
+                    Unix::CODE => {
+                        addr.push_back(Unix::new("/tmp/ockam.sock")).unwrap();
+                        prot.push_back(Unix::CODE);
+                    }
+                    Ws::CODE => {
+                        addr.push_back(Ws::new()).unwrap();
+                        prot.push_back(Ws::CODE);
+                    }
+                    Wss::CODE => {
+                        addr.push_back(Wss::new()).unwrap();
+                        prot.push_back(Wss::CODE);
+                    }
                     _ => unreachable!()
                 }
             }
@@ -153,6 +171,10 @@ const PROTOS: &[Code] = &[
     Node::CODE,
     Project::CODE,
     Space::CODE,
+    Udp::CODE,
+    Unix::CODE,
+    Ws::CODE,
+    Wss::CODE,
 ];
 
 impl Arbitrary for Addr {
@@ -169,6 +191,10 @@ impl Arbitrary for Addr {
                 Project::CODE => a.push_back(Project::new(gen_string())).unwrap(),
                 Space::CODE => a.push_back(Space::new(gen_string())).unwrap(),
                 Node::CODE => a.push_back(Node::new(gen_string())).unwrap(),
+                Udp::CODE => a.push_back(Udp::new(u16::arbitrary(g))).unwrap(),
+                Unix::CODE => a.push_back(Unix::new(gen_path())).unwrap(),
+                Ws::CODE => a.push_back(Ws::new()).unwrap(),
+                Wss::CODE => a.push_back(Wss::new()).unwrap(),
                 _ => unreachable!(),
             }
         }
@@ -176,6 +202,38 @@ impl Arbitrary for Addr {
     }
 }
 
+#[test]
+fn unix_path() {
+    let a = MultiAddr::from_str("/unix/%2Ftmp%2Fockam%25.sock/service/api").unwrap();
+    let p = a.first().unwrap();
+    assert_eq!(&*p.cast::<Unix>().unwrap(), "/tmp/ockam%.sock");
+    assert_eq!(a.to_string(), "/unix/%2Ftmp%2Fockam%25.sock/service/api");
+    assert!(MultiAddr::from_str("/unix/%2").is_err());
+}
+
+#[test]
+fn websocket_markers() {
+    let a = MultiAddr::from_str("/dnsaddr/localhost/tcp/443/wss/service/api").unwrap();
+    let codes = a.iter().map(|p| p.code()).collect::<Vec<_>>();
+    assert_eq!(
+        codes,
+        vec![DnsAddr::CODE, Tcp::CODE, Wss::CODE, Service::CODE]
+    );
+    assert_eq!(a.to_string(), "/dnsaddr/localhost/tcp/443/wss/service/api");
+    assert_eq!(a.to_socket_addr().unwrap(), "localhost:443");
+
+    let a = MultiAddr::from_str("/ip4/127.0.0.1/tcp/80/ws").unwrap();
+    assert_eq!(a.last().unwrap().code(), Ws::CODE);
+}
+
+#[test]
+fn udp_socket_addr() {
+    let a = MultiAddr::from_str("/ip4/1.2.3.4/udp/4000").unwrap();
+    assert_eq!(a.to_socket_addr().unwrap(), "1.2.3.4:4000");
+    let a = MultiAddr::from_str("/ip4/1.2.3.4/service/api").unwrap();
+    assert!(a.to_socket_addr().is_err());
+}
+
 /// An operation to perform on a MultiAddr.
 #[derive(Debug, Copy, Clone)]
 enum Op {
@@ -220,3 +278,13 @@ fn gen_string() -> String {
     s.retain(|c| c != '/');
     s
 }
+
+fn gen_path() -> String {
+    let mut g = rand::thread_rng();
+    let mut v = vec![String::new()];
+    for _ in 1..=3 {
+        v.push(Alphanumeric.sample_string(&mut g, 8))
+    }
+    v.push("100%.sock".to_string());
+    v.join("/")
+}
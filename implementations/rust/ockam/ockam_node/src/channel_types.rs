@@ -1,11 +1,25 @@
+use crate::mailbox_channel::{mailbox_channel, MailboxSettings};
+use crate::MailboxOverflowPolicy;
+
 /// Sender used to send payload messages
-pub type MessageSender<T> = crate::tokio::sync::mpsc::Sender<T>;
+pub type MessageSender<T> = crate::mailbox_channel::MailboxSender<T>;
 /// Receiver used to receive payload messages
-pub type MessageReceiver<T> = crate::tokio::sync::mpsc::Receiver<T>;
+pub type MessageReceiver<T> = crate::mailbox_channel::MailboxReceiver<T>;
 
 /// Create message channel
 pub fn message_channel<T>() -> (MessageSender<T>, MessageReceiver<T>) {
-    crate::tokio::sync::mpsc::channel(16)
+    mailbox_channel(MailboxSettings::default())
+}
+
+/// Create message channel holding up to `capacity` messages, with the given overflow policy
+pub fn bounded_message_channel<T>(
+    capacity: usize,
+    overflow_policy: MailboxOverflowPolicy,
+) -> (MessageSender<T>, MessageReceiver<T>) {
+    mailbox_channel(MailboxSettings {
+        capacity,
+        overflow_policy,
+    })
 }
 
 /// Router sender
@@ -1,4 +1,4 @@
-use crate::channel_types::{SmallReceiver, SmallSender};
+use crate::channel_types::{MessageReceiver, SmallSender};
 use crate::tokio::runtime::Handle;
 use crate::{error::*, AsyncDropSender, NodeMessage};
 use core::sync::atomic::AtomicUsize;
@@ -20,7 +20,7 @@ pub struct Context {
     pub(super) mailboxes: Mailboxes,
     pub(super) sender: SmallSender<NodeMessage>,
     pub(super) rt: Handle,
-    pub(super) receiver: SmallReceiver<RelayMessage>,
+    pub(super) receiver: MessageReceiver<RelayMessage>,
     pub(super) async_drop_sender: Option<AsyncDropSender>,
     pub(super) mailbox_count: Arc<AtomicUsize>,
     /// List of transports used to resolve external addresses to local workers in routes
@@ -11,7 +11,8 @@ use ockam_core::{
 use ockam_transport_core::Transport;
 
 use crate::async_drop::AsyncDrop;
-use crate::channel_types::{message_channel, small_channel, SmallReceiver, SmallSender};
+use crate::channel_types::{small_channel, SmallReceiver, SmallSender};
+use crate::mailbox_channel::{mailbox_channel, MailboxSettings};
 use crate::tokio::{self, runtime::Handle};
 use crate::{debugger, Context};
 use crate::{error::*, relay::CtrlSignal, router::SenderPair, NodeMessage};
@@ -59,11 +60,12 @@ impl Context {
         rt: Handle,
         sender: SmallSender<NodeMessage>,
         mailboxes: Mailboxes,
+        mailbox_settings: MailboxSettings,
         async_drop_sender: Option<AsyncDropSender>,
         transports: Arc<RwLock<HashMap<TransportType, Arc<dyn Transport>>>>,
         flow_controls: &FlowControls,
     ) -> (Self, SenderPair, SmallReceiver<CtrlSignal>) {
-        let (mailbox_tx, receiver) = message_channel();
+        let (mailbox_tx, receiver) = mailbox_channel(mailbox_settings);
         let (ctrl_tx, ctrl_rx) = small_channel();
         (
             Self {
@@ -87,11 +89,20 @@ impl Context {
     pub(crate) fn copy_with_mailboxes(
         &self,
         mailboxes: Mailboxes,
+    ) -> (Context, SenderPair, SmallReceiver<CtrlSignal>) {
+        self.copy_with_mailbox_settings(mailboxes, MailboxSettings::default())
+    }
+
+    pub(crate) fn copy_with_mailbox_settings(
+        &self,
+        mailboxes: Mailboxes,
+        mailbox_settings: MailboxSettings,
     ) -> (Context, SenderPair, SmallReceiver<CtrlSignal>) {
         Context::new(
             self.runtime().clone(),
             self.sender().clone(),
             mailboxes,
+            mailbox_settings,
             None,
             self.transports.clone(),
             &self.flow_controls,
@@ -107,6 +118,7 @@ impl Context {
             self.runtime().clone(),
             self.sender().clone(),
             mailboxes,
+            MailboxSettings::default(),
             Some(drop_sender),
             self.transports.clone(),
             &self.flow_controls,
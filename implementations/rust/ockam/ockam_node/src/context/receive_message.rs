@@ -57,6 +57,11 @@ impl MessageReceiveOptions {
 }
 
 impl Context {
+    /// Number of messages waiting in the mailbox
+    pub(crate) fn mailbox_depth(&self) -> usize {
+        self.receiver.len()
+    }
+
     /// Wait for the next message from the mailbox
     pub(crate) async fn receiver_next(&mut self) -> Result<Option<RelayMessage>> {
         loop {
@@ -77,6 +82,8 @@ impl Context {
             debugger::log_incoming_message(self, &relay_msg);
 
             if !self.mailboxes.is_incoming_authorized(&relay_msg).await? {
+                #[cfg(feature = "std")]
+                crate::metrics::INCOMING_ACCESS_CONTROL_DENIALS.inc();
                 warn!(
                     "Message received from {} for {} did not pass incoming access control",
                     relay_msg.return_route(),
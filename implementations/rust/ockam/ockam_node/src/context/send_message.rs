@@ -253,6 +253,8 @@ impl Context {
         // Pack the payload into a TransportMessage
         let payload = msg.encode().map_err(|_| NodeError::Data.internal())?;
         let transport_msg = TransportMessage::v1(route, route![sending_address.clone()], payload);
+        #[cfg(feature = "std")]
+        let transport_msg = crate::tracing_context::add_tracing_context(transport_msg);
 
         // Pack transport message into a LocalMessage wrapper
         let local_msg = LocalMessage::new(transport_msg, local_info);
@@ -263,6 +265,8 @@ impl Context {
         debugger::log_outgoing_message(self, &relay_msg);
 
         if !self.mailboxes.is_outgoing_authorized(&relay_msg).await? {
+            #[cfg(feature = "std")]
+            crate::metrics::OUTGOING_ACCESS_CONTROL_DENIALS.inc();
             warn!(
                 "Message sent from {} to {} did not pass outgoing access control",
                 relay_msg.source(),
@@ -275,7 +279,7 @@ impl Context {
         sender
             .send(relay_msg)
             .await
-            .map_err(NodeError::from_send_err)?;
+            .map_err(NodeError::from_mailbox_err)?;
 
         Ok(())
     }
@@ -348,6 +352,8 @@ impl Context {
         debugger::log_outgoing_message(self, &relay_msg);
 
         if !self.mailboxes.is_outgoing_authorized(&relay_msg).await? {
+            #[cfg(feature = "std")]
+            crate::metrics::OUTGOING_ACCESS_CONTROL_DENIALS.inc();
             warn!(
                 "Message forwarded from {} to {} did not pass outgoing access control",
                 relay_msg.source(),
@@ -360,7 +366,7 @@ impl Context {
         sender
             .send(relay_msg)
             .await
-            .map_err(NodeError::from_send_err)?;
+            .map_err(NodeError::from_mailbox_err)?;
 
         Ok(())
     }
@@ -37,8 +37,9 @@ pub mod compat;
 /// MPSC channel type aliases
 pub mod channel_types;
 
-#[cfg(feature = "metrics")]
-mod metrics;
+/// Metrics registry, exported in the Prometheus text format
+#[cfg(feature = "std")]
+pub mod metrics;
 
 /// Api helpers
 pub mod api;
@@ -49,11 +50,16 @@ pub mod debugger;
 /// Callback utility
 pub mod callback;
 
+/// Propagation of the tracing context across nodes
+#[cfg(feature = "std")]
+pub mod tracing_context;
+
 mod async_drop;
 mod context;
 mod delayed;
 mod error;
 mod executor;
+mod mailbox_channel;
 mod messages;
 mod node;
 mod parser;
@@ -61,6 +67,8 @@ mod processor_builder;
 mod relay;
 mod router;
 mod rpc_client;
+#[cfg(feature = "std")]
+mod supervisor;
 
 /// Support for storing persistent values
 pub mod storage;
@@ -70,10 +78,19 @@ pub use context::*;
 pub use delayed::*;
 pub use error::*;
 pub use executor::*;
+pub use mailbox_channel::{
+    MailboxOverflowPolicy, MailboxReceiver, MailboxSendError, MailboxSender,
+    DEFAULT_MAILBOX_CAPACITY,
+};
 pub use messages::*;
 pub use processor_builder::ProcessorBuilder;
 pub use rpc_client::*;
 pub use storage::*;
+#[cfg(feature = "std")]
+pub use supervisor::{
+    RestartStrategy, Supervisor, SupervisorOptions, DEFAULT_MAX_BACKOFF, DEFAULT_MAX_RESTARTS,
+    DEFAULT_MIN_BACKOFF, DEFAULT_RESTART_PERIOD,
+};
 pub use worker_builder::WorkerBuilder;
 
 pub use node::{NodeBuilder, NullWorker};
@@ -0,0 +1,320 @@
+use core::fmt;
+use core::future::poll_fn;
+use core::task::{Context, Poll, Waker};
+use ockam_core::compat::collections::VecDeque;
+use ockam_core::compat::sync::{Arc, Mutex};
+use ockam_core::compat::vec::Vec;
+
+/// Default number of messages a worker mailbox can hold
+pub const DEFAULT_MAILBOX_CAPACITY: usize = 16;
+
+/// What happens to a message sent to a full mailbox
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MailboxOverflowPolicy {
+    /// Wait until the worker handles a message
+    #[default]
+    Block,
+    /// Drop the message being sent
+    DropNewest,
+    /// Drop the oldest message of the mailbox to make room for the message being sent
+    DropOldest,
+    /// Return an error to the sender
+    Reject,
+}
+
+/// Capacity and overflow policy of a mailbox
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct MailboxSettings {
+    pub(crate) capacity: usize,
+    pub(crate) overflow_policy: MailboxOverflowPolicy,
+}
+
+impl Default for MailboxSettings {
+    fn default() -> Self {
+        Self {
+            capacity: DEFAULT_MAILBOX_CAPACITY,
+            overflow_policy: MailboxOverflowPolicy::Block,
+        }
+    }
+}
+
+/// Error returned when a message can't be put in a mailbox
+#[derive(Debug)]
+pub enum MailboxSendError<T> {
+    /// The receiver of the mailbox was dropped
+    Closed(T),
+    /// The mailbox is full and its overflow policy is [`MailboxOverflowPolicy::Reject`]
+    Full(T),
+}
+
+impl<T> fmt::Display for MailboxSendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Closed(_) => write!(f, "mailbox is closed"),
+            Self::Full(_) => write!(f, "mailbox is full"),
+        }
+    }
+}
+
+struct Shared<T> {
+    settings: MailboxSettings,
+    state: Mutex<State<T>>,
+}
+
+struct State<T> {
+    queue: VecDeque<T>,
+    senders: usize,
+    receiver_alive: bool,
+    receiver_waker: Option<Waker>,
+    /// Senders waiting for room in the mailbox, with the [`MailboxOverflowPolicy::Block`] policy
+    sender_wakers: Vec<Waker>,
+}
+
+impl<T> State<T> {
+    fn wake_receiver(&mut self) {
+        if let Some(waker) = self.receiver_waker.take() {
+            waker.wake();
+        }
+    }
+
+    /// All the waiting senders are woken up, since some of them may have been cancelled
+    fn wake_senders(&mut self) {
+        for waker in self.sender_wakers.drain(..) {
+            waker.wake();
+        }
+    }
+}
+
+/// Create a mailbox channel with the given settings
+pub(crate) fn mailbox_channel<T>(
+    settings: MailboxSettings,
+) -> (MailboxSender<T>, MailboxReceiver<T>) {
+    let settings = MailboxSettings {
+        capacity: settings.capacity.max(1),
+        ..settings
+    };
+    let shared = Arc::new(Shared {
+        settings,
+        state: Mutex::new(State {
+            queue: VecDeque::with_capacity(settings.capacity.min(DEFAULT_MAILBOX_CAPACITY)),
+            senders: 1,
+            receiver_alive: true,
+            receiver_waker: None,
+            sender_wakers: Vec::new(),
+        }),
+    });
+    (
+        MailboxSender {
+            shared: shared.clone(),
+        },
+        MailboxReceiver { shared },
+    )
+}
+
+/// Sending half of a worker mailbox
+pub struct MailboxSender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> MailboxSender<T> {
+    /// Put a message in the mailbox, applying its overflow policy when it is full
+    pub async fn send(&self, msg: T) -> Result<(), MailboxSendError<T>> {
+        let mut msg = Some(msg);
+        poll_fn(|cx| self.poll_send(cx, &mut msg)).await
+    }
+
+    fn poll_send(
+        &self,
+        cx: &mut Context<'_>,
+        slot: &mut Option<T>,
+    ) -> Poll<Result<(), MailboxSendError<T>>> {
+        let mut state = self.shared.state.lock().unwrap();
+        let msg = slot.take().expect("the message was already sent");
+
+        if !state.receiver_alive {
+            return Poll::Ready(Err(MailboxSendError::Closed(msg)));
+        }
+
+        if state.queue.len() >= self.shared.settings.capacity {
+            match self.shared.settings.overflow_policy {
+                MailboxOverflowPolicy::Block => {
+                    state.sender_wakers.push(cx.waker().clone());
+                    *slot = Some(msg);
+                    return Poll::Pending;
+                }
+                MailboxOverflowPolicy::DropNewest => {
+                    debug!("Mailbox is full, dropping the newest message");
+                    return Poll::Ready(Ok(()));
+                }
+                MailboxOverflowPolicy::DropOldest => {
+                    debug!("Mailbox is full, dropping the oldest message");
+                    state.queue.pop_front();
+                }
+                MailboxOverflowPolicy::Reject => {
+                    return Poll::Ready(Err(MailboxSendError::Full(msg)));
+                }
+            }
+        }
+
+        state.queue.push_back(msg);
+        state.wake_receiver();
+        Poll::Ready(Ok(()))
+    }
+
+    /// Return `true` if the receiving half of the mailbox was dropped
+    pub fn is_closed(&self) -> bool {
+        !self.shared.state.lock().unwrap().receiver_alive
+    }
+}
+
+impl<T> Clone for MailboxSender<T> {
+    fn clone(&self) -> Self {
+        self.shared.state.lock().unwrap().senders += 1;
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T> Drop for MailboxSender<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.state.lock().unwrap();
+        state.senders -= 1;
+        if state.senders == 0 {
+            state.wake_receiver();
+        }
+    }
+}
+
+impl<T> fmt::Debug for MailboxSender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MailboxSender")
+            .field("settings", &self.shared.settings)
+            .finish()
+    }
+}
+
+/// Receiving half of a worker mailbox
+pub struct MailboxReceiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> MailboxReceiver<T> {
+    /// Receive the next message of the mailbox, or `None` when all the senders were dropped
+    pub async fn recv(&mut self) -> Option<T> {
+        poll_fn(|cx| self.poll_recv(cx)).await
+    }
+
+    /// Number of messages waiting in the mailbox
+    pub fn len(&self) -> usize {
+        self.shared.state.lock().unwrap().queue.len()
+    }
+
+    /// Return `true` if no message is waiting in the mailbox
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let mut state = self.shared.state.lock().unwrap();
+        if let Some(msg) = state.queue.pop_front() {
+            state.wake_senders();
+            return Poll::Ready(Some(msg));
+        }
+        if state.senders == 0 {
+            return Poll::Ready(None);
+        }
+        state.receiver_waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+impl<T> Drop for MailboxReceiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.state.lock().unwrap();
+        state.receiver_alive = false;
+        state.queue.clear();
+        state.wake_senders();
+    }
+}
+
+impl<T> fmt::Debug for MailboxReceiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MailboxReceiver")
+            .field("settings", &self.shared.settings)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::time::Duration;
+
+    fn channel(
+        capacity: usize,
+        overflow_policy: MailboxOverflowPolicy,
+    ) -> (MailboxSender<u8>, MailboxReceiver<u8>) {
+        mailbox_channel(MailboxSettings {
+            capacity,
+            overflow_policy,
+        })
+    }
+
+    #[tokio::test]
+    async fn drop_newest() {
+        let (tx, mut rx) = channel(2, MailboxOverflowPolicy::DropNewest);
+        for i in 0..4 {
+            tx.send(i).await.unwrap();
+        }
+        drop(tx);
+        assert_eq!(rx.recv().await, Some(0));
+        assert_eq!(rx.recv().await, Some(1));
+        assert_eq!(rx.recv().await, None);
+    }
+
+    #[tokio::test]
+    async fn drop_oldest() {
+        let (tx, mut rx) = channel(2, MailboxOverflowPolicy::DropOldest);
+        for i in 0..4 {
+            tx.send(i).await.unwrap();
+        }
+        drop(tx);
+        assert_eq!(rx.recv().await, Some(2));
+        assert_eq!(rx.recv().await, Some(3));
+        assert_eq!(rx.recv().await, None);
+    }
+
+    #[tokio::test]
+    async fn reject() {
+        let (tx, mut rx) = channel(1, MailboxOverflowPolicy::Reject);
+        tx.send(0).await.unwrap();
+        assert!(matches!(tx.send(1).await, Err(MailboxSendError::Full(1))));
+        assert_eq!(rx.recv().await, Some(0));
+        tx.send(2).await.unwrap();
+        assert_eq!(rx.recv().await, Some(2));
+    }
+
+    #[tokio::test]
+    async fn block() {
+        let (tx, mut rx) = channel(1, MailboxOverflowPolicy::Block);
+        tx.send(0).await.unwrap();
+        assert!(tokio::time::timeout(Duration::from_millis(50), tx.send(1))
+            .await
+            .is_err());
+
+        let sender = tokio::spawn(async move { tx.send(2).await });
+        assert_eq!(rx.recv().await, Some(0));
+        assert_eq!(rx.recv().await, Some(2));
+        assert!(sender.await.unwrap().is_ok());
+        assert_eq!(rx.recv().await, None);
+    }
+
+    #[tokio::test]
+    async fn closed() {
+        let (tx, rx) = channel(1, MailboxOverflowPolicy::Block);
+        drop(rx);
+        assert!(tx.is_closed());
+        assert!(matches!(tx.send(0).await, Err(MailboxSendError::Closed(0))));
+    }
+}
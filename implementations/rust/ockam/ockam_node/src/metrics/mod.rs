@@ -0,0 +1,72 @@
+#[cfg(feature = "metrics")]
+mod collector;
+mod registry;
+
+#[cfg(feature = "metrics")]
+pub(crate) use collector::Metrics;
+pub use registry::*;
+
+use ockam_core::compat::string::ToString;
+use ockam_core::Address;
+use once_cell::sync::Lazy;
+
+/// Number of messages routed to a local worker or processor
+pub(crate) static MESSAGES_ROUTED: Lazy<Counter> = Lazy::new(|| {
+    registry().counter(
+        "ockam_node_messages_routed_total",
+        "Number of messages routed to a local worker or processor",
+        vec![],
+    )
+});
+
+/// Number of messages rejected by an incoming access control
+pub(crate) static INCOMING_ACCESS_CONTROL_DENIALS: Lazy<Counter> =
+    Lazy::new(|| access_control_denials("incoming"));
+
+/// Number of messages rejected by an outgoing access control
+pub(crate) static OUTGOING_ACCESS_CONTROL_DENIALS: Lazy<Counter> =
+    Lazy::new(|| access_control_denials("outgoing"));
+
+fn access_control_denials(direction: &'static str) -> Counter {
+    registry().counter(
+        "ockam_node_access_control_denials_total",
+        "Number of messages rejected by an access control",
+        vec![("direction", direction.to_string())],
+    )
+}
+
+const WORKER_MAILBOX_DEPTH: &str = "ockam_node_worker_mailbox_depth";
+const WORKER_HANDLE_DURATION: &str = "ockam_node_worker_handle_duration_seconds";
+
+/// Metrics of a single worker, removed from the registry when the worker stops
+pub(crate) struct WorkerMetrics {
+    labels: Labels,
+    pub(crate) mailbox_depth: Gauge,
+    pub(crate) handle_duration: Histogram,
+}
+
+impl WorkerMetrics {
+    pub(crate) fn new(address: &Address) -> Self {
+        let labels = vec![("address", address.to_string())];
+        Self {
+            mailbox_depth: registry().gauge(
+                WORKER_MAILBOX_DEPTH,
+                "Number of messages waiting in the mailbox of a worker",
+                labels.clone(),
+            ),
+            handle_duration: registry().histogram(
+                WORKER_HANDLE_DURATION,
+                "Time spent by a worker handling a message",
+                labels.clone(),
+            ),
+            labels,
+        }
+    }
+}
+
+impl Drop for WorkerMetrics {
+    fn drop(&mut self) {
+        registry().remove(WORKER_MAILBOX_DEPTH, &self.labels);
+        registry().remove(WORKER_HANDLE_DURATION, &self.labels);
+    }
+}
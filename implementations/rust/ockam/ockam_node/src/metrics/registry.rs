@@ -0,0 +1,359 @@
+use core::fmt::Write;
+use core::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use core::time::Duration;
+use ockam_core::compat::collections::BTreeMap;
+use ockam_core::compat::string::String;
+use ockam_core::compat::sync::{Arc, Mutex};
+use ockam_core::compat::vec::Vec;
+use once_cell::sync::Lazy;
+
+/// Upper bounds of the buckets of the latency histograms, in seconds
+pub const DEFAULT_LATENCY_BUCKETS: &[f64] = &[
+    0.0001, 0.0005, 0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+static REGISTRY: Lazy<Registry> = Lazy::new(Registry::new);
+
+/// Registry of the metrics of the current process
+pub fn registry() -> &'static Registry {
+    &REGISTRY
+}
+
+/// Labels of a metric, as `(name, value)` pairs
+pub type Labels = Vec<(&'static str, String)>;
+
+/// A value which can only increase
+#[derive(Debug, Clone, Default)]
+pub struct Counter(Arc<AtomicU64>);
+
+impl Counter {
+    /// Increment the counter by 1
+    pub fn inc(&self) {
+        self.inc_by(1)
+    }
+
+    /// Increment the counter by `value`
+    pub fn inc_by(&self, value: u64) {
+        self.0.fetch_add(value, Ordering::Relaxed);
+    }
+
+    /// Current value of the counter
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// A value which can go up and down
+#[derive(Debug, Clone, Default)]
+pub struct Gauge(Arc<AtomicI64>);
+
+impl Gauge {
+    /// Set the gauge to `value`
+    pub fn set(&self, value: i64) {
+        self.0.store(value, Ordering::Relaxed);
+    }
+
+    /// Increment the gauge by 1
+    pub fn inc(&self) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Decrement the gauge by 1
+    pub fn dec(&self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    /// Current value of the gauge
+    pub fn get(&self) -> i64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Distribution of observed values, counted in buckets
+#[derive(Debug, Clone)]
+pub struct Histogram(Arc<HistogramState>);
+
+#[derive(Debug)]
+struct HistogramState {
+    bounds: Vec<f64>,
+    /// One count per bound, plus one for the values above the last bound
+    buckets: Vec<AtomicU64>,
+    count: AtomicU64,
+    /// Sum of the observed values
+    sum: Mutex<f64>,
+}
+
+impl Histogram {
+    fn new(bounds: &[f64]) -> Self {
+        Self(Arc::new(HistogramState {
+            bounds: bounds.to_vec(),
+            buckets: (0..=bounds.len()).map(|_| AtomicU64::new(0)).collect(),
+            count: AtomicU64::new(0),
+            sum: Mutex::new(0.0),
+        }))
+    }
+
+    /// Record a value
+    pub fn observe(&self, value: f64) {
+        let state = &self.0;
+        let index = state
+            .bounds
+            .iter()
+            .position(|bound| value <= *bound)
+            .unwrap_or(state.bounds.len());
+        state.buckets[index].fetch_add(1, Ordering::Relaxed);
+        state.count.fetch_add(1, Ordering::Relaxed);
+        *state.sum.lock().unwrap() += value;
+    }
+
+    /// Record a duration, in seconds
+    pub fn observe_duration(&self, duration: Duration) {
+        self.observe(duration.as_secs_f64())
+    }
+
+    /// Number of observed values
+    pub fn count(&self) -> u64 {
+        self.0.count.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Metric {
+    Counter(Counter),
+    Gauge(Gauge),
+    Histogram(Histogram),
+}
+
+impl Metric {
+    fn kind(&self) -> &'static str {
+        match self {
+            Metric::Counter(_) => "counter",
+            Metric::Gauge(_) => "gauge",
+            Metric::Histogram(_) => "histogram",
+        }
+    }
+}
+
+struct Family {
+    help: &'static str,
+    series: BTreeMap<Labels, Metric>,
+}
+
+/// Set of named metrics, which can be encoded in the Prometheus text format
+///
+/// Registering a metric which already exists with the same labels returns the
+/// existing one, so that metrics can be registered where they are used.
+///
+/// ```rust
+/// use ockam_node::metrics::registry;
+///
+/// let sent = registry().counter("messages_sent_total", "Number of sent messages", vec![]);
+/// sent.inc();
+/// assert!(registry().encode().contains("messages_sent_total 1"));
+/// ```
+#[derive(Default)]
+pub struct Registry {
+    families: Mutex<BTreeMap<&'static str, Family>>,
+}
+
+impl Registry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the counter with the given name and labels, registering it if needed
+    pub fn counter(&self, name: &'static str, help: &'static str, labels: Labels) -> Counter {
+        match self.get_or_register(name, help, labels, || Metric::Counter(Counter::default())) {
+            Metric::Counter(counter) => counter,
+            metric => panic!("metric {} is a {}, not a counter", name, metric.kind()),
+        }
+    }
+
+    /// Return the gauge with the given name and labels, registering it if needed
+    pub fn gauge(&self, name: &'static str, help: &'static str, labels: Labels) -> Gauge {
+        match self.get_or_register(name, help, labels, || Metric::Gauge(Gauge::default())) {
+            Metric::Gauge(gauge) => gauge,
+            metric => panic!("metric {} is a {}, not a gauge", name, metric.kind()),
+        }
+    }
+
+    /// Return the latency histogram with the given name and labels, registering it if needed
+    ///
+    /// The histogram uses the [`DEFAULT_LATENCY_BUCKETS`].
+    pub fn histogram(&self, name: &'static str, help: &'static str, labels: Labels) -> Histogram {
+        match self.get_or_register(name, help, labels, || {
+            Metric::Histogram(Histogram::new(DEFAULT_LATENCY_BUCKETS))
+        }) {
+            Metric::Histogram(histogram) => histogram,
+            metric => panic!("metric {} is a {}, not a histogram", name, metric.kind()),
+        }
+    }
+
+    /// Remove the metric with the given name and labels
+    ///
+    /// This must be used for the metrics labelled with short-lived values,
+    /// like worker addresses, to keep the number of exported series bounded.
+    pub fn remove(&self, name: &'static str, labels: &Labels) {
+        let mut families = self.families.lock().unwrap();
+        if let Some(family) = families.get_mut(name) {
+            family.series.remove(labels);
+        }
+    }
+
+    fn get_or_register(
+        &self,
+        name: &'static str,
+        help: &'static str,
+        labels: Labels,
+        create: impl FnOnce() -> Metric,
+    ) -> Metric {
+        let mut families = self.families.lock().unwrap();
+        let family = families.entry(name).or_insert_with(|| Family {
+            help,
+            series: BTreeMap::new(),
+        });
+        family.series.entry(labels).or_insert_with(create).clone()
+    }
+
+    /// Encode all the metrics in the Prometheus text exposition format
+    pub fn encode(&self) -> String {
+        let families = self.families.lock().unwrap();
+        let mut out = String::new();
+        for (name, family) in families.iter() {
+            let kind = match family.series.values().next() {
+                Some(metric) => metric.kind(),
+                None => continue,
+            };
+            let _ = writeln!(out, "# HELP {} {}", name, family.help);
+            let _ = writeln!(out, "# TYPE {} {}", name, kind);
+            for (labels, metric) in family.series.iter() {
+                match metric {
+                    Metric::Counter(counter) => {
+                        let _ =
+                            writeln!(out, "{}{} {}", name, encode_labels(labels), counter.get());
+                    }
+                    Metric::Gauge(gauge) => {
+                        let _ = writeln!(out, "{}{} {}", name, encode_labels(labels), gauge.get());
+                    }
+                    Metric::Histogram(histogram) => {
+                        encode_histogram(&mut out, name, labels, histogram)
+                    }
+                }
+            }
+        }
+        out
+    }
+}
+
+fn encode_histogram(out: &mut String, name: &str, labels: &Labels, histogram: &Histogram) {
+    let state = &histogram.0;
+    let mut cumulative = 0;
+    for (index, bucket) in state.buckets.iter().enumerate() {
+        cumulative += bucket.load(Ordering::Relaxed);
+        let bound = match state.bounds.get(index) {
+            Some(bound) => bound.to_string(),
+            None => "+Inf".to_string(),
+        };
+        let mut labels = labels.clone();
+        labels.push(("le", bound));
+        let _ = writeln!(
+            out,
+            "{}_bucket{} {}",
+            name,
+            encode_labels(&labels),
+            cumulative
+        );
+    }
+    let sum = *state.sum.lock().unwrap();
+    let _ = writeln!(out, "{}_sum{} {}", name, encode_labels(labels), sum);
+    let _ = writeln!(
+        out,
+        "{}_count{} {}",
+        name,
+        encode_labels(labels),
+        cumulative
+    );
+}
+
+fn encode_labels(labels: &Labels) -> String {
+    if labels.is_empty() {
+        return String::new();
+    }
+    let labels = labels
+        .iter()
+        .map(|(name, value)| {
+            let value = value
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n");
+            format!("{}=\"{}\"", name, value)
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    format!("{{{}}}", labels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_counters_and_gauges() {
+        let registry = Registry::new();
+        registry
+            .counter("routed_total", "Routed messages", vec![])
+            .inc_by(3);
+        let depth = registry.gauge(
+            "mailbox_depth",
+            "Mailbox depth",
+            vec![("address", "a\"b".into())],
+        );
+        depth.set(2);
+
+        // The same metric is returned for the same name and labels
+        registry
+            .counter("routed_total", "Routed messages", vec![])
+            .inc();
+
+        assert_eq!(
+            registry.encode(),
+            "# HELP mailbox_depth Mailbox depth\n\
+             # TYPE mailbox_depth gauge\n\
+             mailbox_depth{address=\"a\\\"b\"} 2\n\
+             # HELP routed_total Routed messages\n\
+             # TYPE routed_total counter\n\
+             routed_total 4\n"
+        );
+
+        registry.remove("mailbox_depth", &vec![("address", "a\"b".into())]);
+        assert!(!registry.encode().contains("mailbox_depth"));
+    }
+
+    #[test]
+    fn encode_histogram() {
+        let registry = Registry::new();
+        let latency =
+            registry.histogram("latency_seconds", "Latency", vec![("worker", "w".into())]);
+        latency.observe(0.0002);
+        latency.observe(0.003);
+        latency.observe(20.0);
+
+        let encoded = registry.encode();
+        assert!(encoded.contains("# TYPE latency_seconds histogram\n"));
+        assert!(encoded.contains("latency_seconds_bucket{worker=\"w\",le=\"0.0001\"} 0\n"));
+        assert!(encoded.contains("latency_seconds_bucket{worker=\"w\",le=\"0.0005\"} 1\n"));
+        assert!(encoded.contains("latency_seconds_bucket{worker=\"w\",le=\"0.005\"} 2\n"));
+        assert!(encoded.contains("latency_seconds_bucket{worker=\"w\",le=\"10\"} 2\n"));
+        assert!(encoded.contains("latency_seconds_bucket{worker=\"w\",le=\"+Inf\"} 3\n"));
+        assert!(encoded.contains("latency_seconds_count{worker=\"w\"} 3\n"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn metric_kind_mismatch() {
+        let registry = Registry::new();
+        registry.counter("metric", "A metric", vec![]);
+        registry.gauge("metric", "A metric", vec![]);
+    }
+}
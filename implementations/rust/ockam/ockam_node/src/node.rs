@@ -2,6 +2,7 @@ use ockam_core::compat::sync::Arc;
 use ockam_core::flow_control::FlowControls;
 use ockam_core::{Address, AllowAll, Mailbox, Mailboxes};
 
+use crate::mailbox_channel::MailboxSettings;
 use crate::{debugger, Context, Executor};
 
 /// A minimal worker implementation that does nothing
@@ -62,6 +63,7 @@ impl NodeBuilder {
                 Mailbox::new(addr, Arc::new(AllowAll), Arc::new(AllowAll)),
                 vec![],
             ),
+            MailboxSettings::default(),
             None,
             Default::default(),
             &flow_controls,
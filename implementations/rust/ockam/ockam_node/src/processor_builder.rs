@@ -1,5 +1,7 @@
 use crate::debugger;
 use crate::error::{NodeError, NodeReason};
+#[cfg(feature = "std")]
+use crate::supervisor::{Supervised, Supervisor};
 use crate::{relay::ProcessorRelay, Context, NodeMessage};
 use ockam_core::compat::sync::Arc;
 use ockam_core::{
@@ -40,6 +42,8 @@ where
             outgoing_ac: Arc::new(DenyAll),
             processor: self.processor,
             address: address.into(),
+            #[cfg(feature = "std")]
+            supervised: None,
         }
     }
 
@@ -48,6 +52,8 @@ where
         ProcessorBuilderMultipleAddresses {
             mailboxes,
             processor: self.processor,
+            #[cfg(feature = "std")]
+            supervised: None,
         }
     }
 }
@@ -58,6 +64,8 @@ where
 {
     mailboxes: Mailboxes,
     processor: P,
+    #[cfg(feature = "std")]
+    supervised: Option<Supervised<P>>,
 }
 
 impl<P> ProcessorBuilderMultipleAddresses<P>
@@ -66,7 +74,26 @@ where
 {
     /// Consume this builder and start a new Ockam [`Processor`] from the given context
     pub async fn start(self, context: &Context) -> Result<()> {
-        start(context, self.mailboxes, self.processor).await
+        start(
+            context,
+            self.mailboxes,
+            self.processor,
+            #[cfg(feature = "std")]
+            self.supervised,
+        )
+        .await
+    }
+
+    /// Restart the processor with a new instance created by `factory` when it fails,
+    /// according to the options of the given [`Supervisor`]
+    #[cfg(feature = "std")]
+    pub fn with_supervisor(
+        mut self,
+        supervisor: &Supervisor,
+        factory: impl Fn() -> P + Send + Sync + 'static,
+    ) -> Self {
+        self.supervised = Some(supervisor.supervise(factory));
+        self
     }
 }
 
@@ -78,6 +105,8 @@ where
     outgoing_ac: Arc<dyn OutgoingAccessControl>,
     address: Address,
     processor: P,
+    #[cfg(feature = "std")]
+    supervised: Option<Supervised<P>>,
 }
 
 impl<P> ProcessorBuilderOneAddress<P>
@@ -90,9 +119,23 @@ where
             context,
             Mailboxes::main(self.address, self.incoming_ac, self.outgoing_ac),
             self.processor,
+            #[cfg(feature = "std")]
+            self.supervised,
         )
         .await
     }
+
+    /// Restart the processor with a new instance created by `factory` when it fails,
+    /// according to the options of the given [`Supervisor`]
+    #[cfg(feature = "std")]
+    pub fn with_supervisor(
+        mut self,
+        supervisor: &Supervisor,
+        factory: impl Fn() -> P + Send + Sync + 'static,
+    ) -> Self {
+        self.supervised = Some(supervisor.supervise(factory));
+        self
+    }
 }
 
 impl<P> ProcessorBuilderOneAddress<P>
@@ -137,7 +180,12 @@ where
 }
 
 /// Consume this builder and start a new Ockam [`Processor`] from the given context
-pub async fn start<P>(context: &Context, mailboxes: Mailboxes, processor: P) -> Result<()>
+async fn start<P>(
+    context: &Context,
+    mailboxes: Mailboxes,
+    processor: P,
+    #[cfg(feature = "std")] supervised: Option<Supervised<P>>,
+) -> Result<()>
 where
     P: Processor<Context = Context>,
 {
@@ -156,7 +204,14 @@ where
     debugger::log_inherit_context("PROCESSOR", context, &ctx);
 
     // Then initialise the processor message relay
-    ProcessorRelay::<P>::init(context.runtime(), processor, ctx, ctrl_rx);
+    ProcessorRelay::<P>::init(
+        context.runtime(),
+        processor,
+        ctx,
+        ctrl_rx,
+        #[cfg(feature = "std")]
+        supervised,
+    );
 
     // Send start request to router
     let (msg, mut rx) = NodeMessage::start_processor(main_address.clone(), sender);
@@ -1,4 +1,6 @@
 use crate::channel_types::SmallReceiver;
+#[cfg(feature = "std")]
+use crate::supervisor::{Supervised, SupervisorDecision};
 use crate::{relay::CtrlSignal, tokio::runtime::Handle, Context};
 use ockam_core::{Processor, Result};
 
@@ -8,6 +10,8 @@ where
 {
     processor: P,
     ctx: Context,
+    #[cfg(feature = "std")]
+    supervised: Option<Supervised<P>>,
 }
 
 impl<P> ProcessorRelay<P>
@@ -15,7 +19,69 @@ where
     P: Processor<Context = Context>,
 {
     pub fn new(processor: P, ctx: Context) -> Self {
-        Self { processor, ctx }
+        Self {
+            processor,
+            ctx,
+            #[cfg(feature = "std")]
+            supervised: None,
+        }
+    }
+
+    /// Apply the decision of the supervisor, replacing the processor with a new
+    /// instance until it initialises successfully
+    ///
+    /// Return `false` if the supervisor gave up and the processor is stopping
+    #[cfg(feature = "std")]
+    async fn restart(
+        processor: &mut P,
+        ctx: &mut Context,
+        supervised: &Supervised<P>,
+        mut decision: SupervisorDecision,
+    ) -> bool {
+        let address = ctx.address();
+
+        loop {
+            let delay = match decision {
+                SupervisorDecision::Restart(delay) => delay,
+                SupervisorDecision::Stop => {
+                    // The router answers with a shutdown signal
+                    if let Err(e) = ctx.stop_processor(address.clone()).await {
+                        error!("Failed to stop supervised processor '{}': {}", address, e);
+                    }
+                    return false;
+                }
+            };
+
+            tokio::time::sleep(delay).await;
+            if let Err(e) = processor.shutdown(ctx).await {
+                error!("Failure during '{}' processor shutdown: {}", address, e);
+            }
+
+            info!("Restarting processor '{}'", address);
+            *processor = supervised.create();
+            match processor.initialize(ctx).await {
+                Ok(()) => return true,
+                Err(e) => {
+                    error!(
+                        "Failure during '{}' processor initialisation: {}",
+                        address, e
+                    );
+                    decision = supervised.on_failure(&address);
+                }
+            }
+        }
+    }
+
+    /// Wait for the failure of a sibling of a supervised processor
+    #[cfg(feature = "std")]
+    async fn sibling_failure(
+        supervised: &mut Option<Supervised<P>>,
+        address: &ockam_core::Address,
+    ) -> SupervisorDecision {
+        match supervised {
+            Some(supervised) => supervised.sibling_failure(address).await,
+            None => core::future::pending().await,
+        }
     }
 
     #[cfg_attr(not(feature = "std"), allow(unused_mut))]
@@ -23,16 +89,21 @@ where
     async fn run(self, mut ctrl_rx: SmallReceiver<CtrlSignal>) {
         let mut ctx = self.ctx;
         let mut processor = self.processor;
+        #[cfg(feature = "std")]
+        let mut supervised = self.supervised;
         let ctx_addr = ctx.address();
 
-        match processor.initialize(&mut ctx).await {
-            Ok(()) => {}
-            Err(e) => {
-                error!(
-                    "Failure during '{}' processor initialisation: {}",
-                    ctx.address(),
-                    e
-                );
+        let mut running = true;
+        if let Err(e) = processor.initialize(&mut ctx).await {
+            error!(
+                "Failure during '{}' processor initialisation: {}",
+                ctx.address(),
+                e
+            );
+            #[cfg(feature = "std")]
+            if let Some(supervised) = &supervised {
+                let decision = supervised.on_failure(&ctx_addr);
+                running = Self::restart(&mut processor, &mut ctx, supervised, decision).await;
             }
         }
 
@@ -42,11 +113,25 @@ where
 
         // This future encodes the main processor run loop logic
         let run_loop = async {
-            loop {
+            while running {
                 // protect against accidental async executor deadlock
                 crate::tokio::task::yield_now().await;
 
-                match processor.process(&mut ctx).await {
+                #[cfg(feature = "std")]
+                let result = tokio::select! {
+                    result = processor.process(&mut ctx) => result,
+                    decision = Self::sibling_failure(&mut supervised, &ctx_addr) => {
+                        if let Some(supervised) = &supervised {
+                            running =
+                                Self::restart(&mut processor, &mut ctx, supervised, decision).await;
+                        }
+                        continue;
+                    }
+                };
+                #[cfg(not(feature = "std"))]
+                let result = processor.process(&mut ctx).await;
+
+                match result {
                     Ok(should_continue) => {
                         if !should_continue {
                             break;
@@ -60,6 +145,12 @@ where
                         );
                         #[cfg(not(feature = "debugger"))]
                         error!("Error encountered during '{}' processing: {}", ctx_addr, e);
+                        #[cfg(feature = "std")]
+                        if let Some(supervised) = &supervised {
+                            let decision = supervised.on_failure(&ctx_addr);
+                            running =
+                                Self::restart(&mut processor, &mut ctx, supervised, decision).await;
+                        }
                     }
                 }
             }
@@ -109,8 +200,14 @@ where
         processor: P,
         ctx: Context,
         ctrl_rx: SmallReceiver<CtrlSignal>,
+        #[cfg(feature = "std")] supervised: Option<Supervised<P>>,
     ) {
-        let relay = ProcessorRelay::<P>::new(processor, ctx);
+        #[cfg_attr(not(feature = "std"), allow(unused_mut))]
+        let mut relay = ProcessorRelay::<P>::new(processor, ctx);
+        #[cfg(feature = "std")]
+        {
+            relay.supervised = supervised;
+        }
         rt.spawn(relay.run(ctrl_rx));
     }
 }
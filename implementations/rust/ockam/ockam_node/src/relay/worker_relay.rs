@@ -1,8 +1,18 @@
 use crate::channel_types::SmallReceiver;
+#[cfg(feature = "std")]
+use crate::metrics::WorkerMetrics;
 use crate::relay::CtrlSignal;
+#[cfg(feature = "std")]
+use crate::supervisor::{Supervised, SupervisorDecision};
 use crate::tokio::runtime::Handle;
+#[cfg(feature = "std")]
+use crate::tracing_context;
 use crate::{parser, Context};
+#[cfg(feature = "std")]
+use ockam_core::Address;
 use ockam_core::{Message, RelayMessage, Result, Routed, Worker};
+#[cfg(feature = "std")]
+use tracing::Instrument;
 
 /// Worker relay machinery
 ///
@@ -12,11 +22,18 @@ use ockam_core::{Message, RelayMessage, Result, Routed, Worker};
 pub struct WorkerRelay<W> {
     worker: W,
     ctx: Context,
+    #[cfg(feature = "std")]
+    supervised: Option<Supervised<W>>,
 }
 
 impl<W: Worker> WorkerRelay<W> {
     pub fn new(worker: W, ctx: Context) -> Self {
-        Self { worker, ctx }
+        Self {
+            worker,
+            ctx,
+            #[cfg(feature = "std")]
+            supervised: None,
+        }
     }
 }
 
@@ -60,63 +77,149 @@ where
     ///
     /// Report errors as they occur, and signal whether the loop should
     /// continue running or not
-    async fn recv_message(&mut self) -> Result<bool> {
-        let relay_msg = match self.ctx.receiver_next().await? {
+    async fn recv_message(
+        worker: &mut W,
+        ctx: &mut Context,
+        #[cfg(feature = "std")] metrics: &WorkerMetrics,
+    ) -> Result<bool> {
+        let relay_msg = match ctx.receiver_next().await? {
             Some(msg) => msg,
             None => {
-                trace!("No more messages for worker {}", self.ctx.address());
+                trace!("No more messages for worker {}", ctx.address());
                 return Ok(false);
             }
         };
 
+        #[cfg(feature = "std")]
+        metrics.mailbox_depth.set(ctx.mailbox_depth() as i64);
+        #[cfg(feature = "std")]
+        let started = std::time::Instant::now();
+
         // Call the worker handle function - pass errors up
         let routed = Self::wrap_direct_message(relay_msg)?;
-        self.worker.handle_message(&mut self.ctx, routed).await?;
+        #[cfg(feature = "std")]
+        let result = {
+            let span = tracing_context::handle_message_span(ctx, routed.local_message());
+            worker.handle_message(ctx, routed).instrument(span).await
+        };
+        #[cfg(not(feature = "std"))]
+        let result = worker.handle_message(ctx, routed).await;
+
+        #[cfg(feature = "std")]
+        metrics.handle_duration.observe_duration(started.elapsed());
+        result?;
 
         // Signal to the outer loop that we would like to run again
         Ok(true)
     }
 
+    /// Report the failure of a supervised worker to its supervisor
+    #[cfg(feature = "std")]
+    fn on_failure(
+        supervised: &Option<Supervised<W>>,
+        address: &Address,
+    ) -> Option<SupervisorDecision> {
+        supervised
+            .as_ref()
+            .map(|supervised| supervised.on_failure(address))
+    }
+
+    /// Wait for the failure of a sibling of a supervised worker
+    #[cfg(feature = "std")]
+    async fn sibling_failure(
+        supervised: &mut Option<Supervised<W>>,
+        address: &Address,
+    ) -> SupervisorDecision {
+        match supervised {
+            Some(supervised) => supervised.sibling_failure(address).await,
+            None => core::future::pending().await,
+        }
+    }
+
+    /// Apply the decision of the supervisor, replacing the worker with a new
+    /// instance until it initialises successfully
+    ///
+    /// Return `false` if the supervisor gave up and the worker is stopping
+    #[cfg(feature = "std")]
+    async fn restart(&mut self, mut decision: SupervisorDecision) -> bool {
+        let supervised = match &self.supervised {
+            Some(supervised) => supervised,
+            None => return true,
+        };
+        let address = self.ctx.address();
+
+        loop {
+            let delay = match decision {
+                SupervisorDecision::Restart(delay) => delay,
+                SupervisorDecision::Stop => {
+                    // Stopping the worker closes its mailbox and ends the run loop
+                    if let Err(e) = self.ctx.stop_worker(address.clone()).await {
+                        error!("Failed to stop supervised worker '{}': {}", address, e);
+                    }
+                    return false;
+                }
+            };
+
+            crate::tokio::time::sleep(delay).await;
+            if let Err(e) = self.worker.shutdown(&mut self.ctx).await {
+                error!("Failure during '{}' worker shutdown: {}", address, e);
+            }
+
+            info!("Restarting worker '{}'", address);
+            self.worker = supervised.create();
+            match self.worker.initialize(&mut self.ctx).await {
+                Ok(()) => return true,
+                Err(e) => {
+                    error!("Failure during '{}' worker initialisation: {}", address, e);
+                    decision = supervised.on_failure(&address);
+                }
+            }
+        }
+    }
+
     #[cfg_attr(not(feature = "std"), allow(unused_mut))]
     #[cfg_attr(not(feature = "std"), allow(unused_variables))]
     async fn run(mut self, mut ctrl_rx: SmallReceiver<CtrlSignal>) {
-        match self.worker.initialize(&mut self.ctx).await {
-            Ok(()) => {}
-            Err(e) => {
-                error!(
-                    "Failure during '{}' worker initialisation: {}",
-                    self.ctx.address(),
-                    e
-                );
+        let address = self.ctx.address();
+
+        let mut running = true;
+        if let Err(e) = self.worker.initialize(&mut self.ctx).await {
+            error!("Failure during '{}' worker initialisation: {}", address, e);
+            #[cfg(feature = "std")]
+            if let Some(decision) = Self::on_failure(&self.supervised, &address) {
+                running = self.restart(decision).await;
             }
         }
 
-        let address = self.ctx.address();
-
         if let Err(e) = self.ctx.set_ready().await {
             error!("Failed to mark worker '{}' as 'ready': {}", address, e);
         }
 
         #[cfg(feature = "std")]
-        loop {
-            crate::tokio::select! {
-                result = self.recv_message() => {
+        let metrics = WorkerMetrics::new(&address);
+
+        #[cfg(feature = "std")]
+        while running {
+            let decision = crate::tokio::select! {
+                result = Self::recv_message(&mut self.worker, &mut self.ctx, &metrics) => {
                     match result {
                         // Successful message handling -- keep running
-                        Ok(true) => {},
+                        Ok(true) => None,
                         // Successful message handling -- stop now
                         Ok(false) => {
                             break;
                         },
-                        // An error occurred -- log and continue
+                        // An error occurred -- log and let the supervisor decide, if any
                         Err(e) => {
                             #[cfg(feature = "debugger")]
                             error!("Error encountered during '{}' message handling: {:?}", address, e);
                             #[cfg(not(feature = "debugger"))]
                             error!("Error encountered during '{}' message handling: {}", address, e);
+                            Self::on_failure(&self.supervised, &address)
                         }
                     }
                 },
+                decision = Self::sibling_failure(&mut self.supervised, &address) => Some(decision),
                 result = ctrl_rx.recv() => {
                     if result.is_some() {
                         debug!("Relay received shutdown signal, terminating!");
@@ -124,12 +227,17 @@ where
                     }
 
                     // We are stopping
+                    None
                 }
             };
+
+            if let Some(decision) = decision {
+                running = self.restart(decision).await;
+            }
         }
         #[cfg(not(feature = "std"))]
         loop {
-            match self.recv_message().await {
+            match Self::recv_message(&mut self.worker, &mut self.ctx).await {
                 // Successful message handling -- keep running
                 Ok(true) => {}
                 // Successful message handling -- stop now
@@ -164,8 +272,19 @@ where
     }
 
     /// Build and spawn a new worker relay, returning a send handle to it
-    pub(crate) fn init(rt: &Handle, worker: W, ctx: Context, ctrl_rx: SmallReceiver<CtrlSignal>) {
-        let relay = WorkerRelay::new(worker, ctx);
+    pub(crate) fn init(
+        rt: &Handle,
+        worker: W,
+        ctx: Context,
+        ctrl_rx: SmallReceiver<CtrlSignal>,
+        #[cfg(feature = "std")] supervised: Option<Supervised<W>>,
+    ) {
+        #[cfg_attr(not(feature = "std"), allow(unused_mut))]
+        let mut relay = WorkerRelay::new(worker, ctx);
+        #[cfg(feature = "std")]
+        {
+            relay.supervised = supervised;
+        }
         rt.spawn(relay.run(ctrl_rx));
     }
 }
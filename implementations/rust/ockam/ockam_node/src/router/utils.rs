@@ -34,6 +34,8 @@ pub(super) async fn resolve(
         Some(record) if record.check() => {
             trace!("{} OK", base);
             record.increment_msg_count();
+            #[cfg(feature = "std")]
+            crate::metrics::MESSAGES_ROUTED.inc();
             reply.send(RouterReply::sender(addr.clone(), record.sender()))
         }
         Some(_) => {
@@ -0,0 +1,275 @@
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::time::Duration;
+use ockam_core::compat::collections::VecDeque;
+use ockam_core::compat::sync::{Arc, Mutex};
+use ockam_core::Address;
+use std::time::Instant;
+use tokio::sync::broadcast;
+
+/// Default maximum number of restarts within the restart period
+pub const DEFAULT_MAX_RESTARTS: usize = 3;
+/// Default period over which the restarts are counted
+pub const DEFAULT_RESTART_PERIOD: Duration = Duration::from_secs(5);
+/// Default delay before the first restart
+pub const DEFAULT_MIN_BACKOFF: Duration = Duration::from_millis(100);
+/// Default maximum delay before a restart
+pub const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(5);
+
+/// Which children are restarted by a [`Supervisor`] when one of them fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestartStrategy {
+    /// Only the failed child is restarted
+    OneForOne,
+    /// All the children are restarted when one of them fails
+    OneForAll,
+}
+
+/// Options of a [`Supervisor`]
+#[derive(Debug, Clone)]
+pub struct SupervisorOptions {
+    strategy: RestartStrategy,
+    max_restarts: usize,
+    restart_period: Duration,
+    min_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl Default for SupervisorOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SupervisorOptions {
+    /// One-for-one strategy, allowing [`DEFAULT_MAX_RESTARTS`] restarts
+    /// within [`DEFAULT_RESTART_PERIOD`]
+    pub fn new() -> Self {
+        Self {
+            strategy: RestartStrategy::OneForOne,
+            max_restarts: DEFAULT_MAX_RESTARTS,
+            restart_period: DEFAULT_RESTART_PERIOD,
+            min_backoff: DEFAULT_MIN_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+        }
+    }
+
+    /// Set the [`RestartStrategy`]
+    pub fn with_strategy(mut self, strategy: RestartStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Give up restarting the children after `max_restarts` restarts within `period`
+    pub fn with_restart_intensity(mut self, max_restarts: usize, period: Duration) -> Self {
+        self.max_restarts = max_restarts;
+        self.restart_period = period;
+        self
+    }
+
+    /// Wait `min` before the first restart within the restart period, doubling
+    /// the delay for each following restart, up to `max`
+    pub fn with_backoff(mut self, min: Duration, max: Duration) -> Self {
+        self.min_backoff = min;
+        self.max_backoff = max.max(min);
+        self
+    }
+}
+
+/// Event sent by a [`Supervisor`] to its children
+#[derive(Debug, Clone)]
+pub(crate) enum SupervisorEvent {
+    /// The child with the given address failed and was restarted
+    Restart(Address),
+    /// The child with the given address failed too often, the supervisor gave up
+    Stop(Address),
+}
+
+/// What to do with a failed child
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SupervisorDecision {
+    /// Restart the child after the given delay
+    Restart(Duration),
+    /// Stop the child
+    Stop,
+}
+
+/// Supervisor of workers and processors, restarting them when they fail
+///
+/// A child fails when its initialisation, or the handling of a message
+/// (resp. the processing for a processor), returns an error. The child is then
+/// shut down and replaced with a new instance, created from the factory given to the
+/// [`WorkerBuilder`](crate::WorkerBuilder) or the [`ProcessorBuilder`](crate::ProcessorBuilder).
+/// The new instance keeps the addresses of the failed one, hence its access controls
+/// and flow control registrations.
+///
+/// When the children fail more than allowed by the restart intensity of the
+/// [`SupervisorOptions`], the supervisor gives up and stops the failed child, or all
+/// its children for the [`RestartStrategy::OneForAll`] strategy.
+///
+/// ```rust
+/// use core::time::Duration;
+/// use ockam_core::{worker, Result, Worker};
+/// use ockam_node::{Context, RestartStrategy, Supervisor, SupervisorOptions, WorkerBuilder};
+///
+/// struct Echoer;
+///
+/// #[worker]
+/// impl Worker for Echoer {
+///     type Context = Context;
+///     type Message = String;
+/// }
+///
+/// # async fn test(ctx: Context) -> Result<()> {
+/// let supervisor = Supervisor::new(
+///     SupervisorOptions::new()
+///         .with_strategy(RestartStrategy::OneForOne)
+///         .with_restart_intensity(5, Duration::from_secs(10)),
+/// );
+/// WorkerBuilder::new(Echoer)
+///     .with_address("echoer")
+///     .with_supervisor(&supervisor, || Echoer)
+///     .start(&ctx)
+///     .await?;
+/// # Ok(()) }
+/// ```
+#[derive(Clone)]
+pub struct Supervisor {
+    inner: Arc<SupervisorState>,
+}
+
+struct SupervisorState {
+    options: SupervisorOptions,
+    /// Times of the restarts within the restart period
+    restarts: Mutex<VecDeque<Instant>>,
+    restart_count: AtomicUsize,
+    events: broadcast::Sender<SupervisorEvent>,
+}
+
+impl Supervisor {
+    /// Create a supervisor with the given options
+    pub fn new(options: SupervisorOptions) -> Self {
+        let (events, _) = broadcast::channel(16);
+        Self {
+            inner: Arc::new(SupervisorState {
+                options,
+                restarts: Mutex::new(VecDeque::new()),
+                restart_count: AtomicUsize::new(0),
+                events,
+            }),
+        }
+    }
+
+    /// Total number of restarts of the children of this supervisor
+    pub fn restart_count(&self) -> usize {
+        self.inner.restart_count.load(Ordering::Relaxed)
+    }
+
+    /// Supervise a new child, created with the given factory
+    pub(crate) fn supervise<T: 'static>(
+        &self,
+        factory: impl Fn() -> T + Send + Sync + 'static,
+    ) -> Supervised<T> {
+        Supervised {
+            supervisor: self.clone(),
+            factory: Arc::new(factory),
+            events: self.inner.events.subscribe(),
+        }
+    }
+
+    /// Record the failure of a child and decide what to do with it
+    fn on_failure(&self, address: &Address) -> SupervisorDecision {
+        let options = &self.inner.options;
+        let now = Instant::now();
+
+        let restarts = {
+            let mut restarts = self.inner.restarts.lock().unwrap();
+            while let Some(first) = restarts.front() {
+                if now.duration_since(*first) <= options.restart_period {
+                    break;
+                }
+                restarts.pop_front();
+            }
+            if restarts.len() >= options.max_restarts {
+                None
+            } else {
+                restarts.push_back(now);
+                Some(restarts.len())
+            }
+        };
+
+        let restarts = match restarts {
+            Some(restarts) => restarts,
+            None => {
+                warn!(
+                    "Child '{}' failed more than {} times in {:?}, giving up",
+                    address, options.max_restarts, options.restart_period
+                );
+                if options.strategy == RestartStrategy::OneForAll {
+                    let _ = self
+                        .inner
+                        .events
+                        .send(SupervisorEvent::Stop(address.clone()));
+                }
+                return SupervisorDecision::Stop;
+            }
+        };
+
+        self.inner.restart_count.fetch_add(1, Ordering::Relaxed);
+        if options.strategy == RestartStrategy::OneForAll {
+            let _ = self
+                .inner
+                .events
+                .send(SupervisorEvent::Restart(address.clone()));
+        }
+
+        // Double the delay for each restart within the period
+        let factor = 1u32.checked_shl(restarts as u32 - 1).unwrap_or(u32::MAX);
+        let backoff = options
+            .min_backoff
+            .checked_mul(factor)
+            .unwrap_or(options.max_backoff)
+            .min(options.max_backoff);
+        SupervisorDecision::Restart(backoff)
+    }
+}
+
+/// Supervision of a single child
+pub(crate) struct Supervised<T> {
+    supervisor: Supervisor,
+    factory: Arc<dyn Fn() -> T + Send + Sync>,
+    events: broadcast::Receiver<SupervisorEvent>,
+}
+
+impl<T> Supervised<T> {
+    /// Create a new instance of the child
+    pub(crate) fn create(&self) -> T {
+        (self.factory)()
+    }
+
+    /// Report the failure of the child
+    pub(crate) fn on_failure(&self, address: &Address) -> SupervisorDecision {
+        self.supervisor.on_failure(address)
+    }
+
+    /// Wait for the failure of a sibling of the child with the given address,
+    /// returning what to do with the child
+    pub(crate) async fn sibling_failure(&mut self, address: &Address) -> SupervisorDecision {
+        loop {
+            match self.events.recv().await {
+                Ok(SupervisorEvent::Restart(sibling)) if &sibling != address => {
+                    debug!("Sibling '{}' of '{}' was restarted", sibling, address);
+                    return SupervisorDecision::Restart(Duration::ZERO);
+                }
+                Ok(SupervisorEvent::Stop(sibling)) if &sibling != address => {
+                    debug!("Sibling '{}' of '{}' was stopped", sibling, address);
+                    return SupervisorDecision::Stop;
+                }
+                Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                // The supervisor can't be dropped while one of its children is running
+                Err(broadcast::error::RecvError::Closed) => {
+                    core::future::pending::<()>().await;
+                }
+            }
+        }
+    }
+}
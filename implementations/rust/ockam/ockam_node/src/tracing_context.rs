@@ -0,0 +1,77 @@
+use crate::Context;
+use ockam_core::compat::boxed::Box;
+use ockam_core::compat::string::String;
+use ockam_core::{LocalMessage, TransportMessage};
+use once_cell::sync::OnceCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::Span;
+
+static PROPAGATOR: OnceCell<Box<dyn TracingContextPropagator>> = OnceCell::new();
+static ACROSS_NODES: AtomicBool = AtomicBool::new(false);
+
+/// Propagation of the `tracing` span context in the messages exchanged by workers
+///
+/// The context of the span sending a message is carried by its
+/// [`TransportMessage`](ockam_core::TransportMessage) as a W3C `traceparent`,
+/// and the worker receiving the message handles it in a span continuing that
+/// trace, even when the message went through other nodes.
+///
+/// The `traceparent` is produced and interpreted by the tracing backend, an
+/// OpenTelemetry exporter for example, which registers its propagator with
+/// [`set_tracing_context_propagator`]. No tracing context is added to the
+/// messages as long as no propagator is registered.
+///
+/// The tracing context only leaves the node once
+/// [`propagate_tracing_context_across_nodes`] is enabled, since the messages
+/// carrying it can't be decoded by nodes only supporting version 1 of the
+/// transport messages.
+pub trait TracingContextPropagator: Send + Sync + 'static {
+    /// Return the `traceparent` of the current span, if it belongs to a trace
+    fn current_context(&self) -> Option<String>;
+
+    /// Make `span` a child of the span identified by `traceparent`
+    fn set_parent(&self, span: &Span, traceparent: &str);
+}
+
+/// Register the propagator of the tracing context for this process
+///
+/// Return `false` if a propagator was already registered.
+pub fn set_tracing_context_propagator(propagator: impl TracingContextPropagator) -> bool {
+    PROPAGATOR.set(Box::new(propagator)).is_ok()
+}
+
+/// Send the tracing context to the other nodes, by upgrading the messages
+/// sent by this process to version 2
+///
+/// Only enable it when all the nodes receiving these messages support version 2.
+pub fn propagate_tracing_context_across_nodes(enabled: bool) {
+    ACROSS_NODES.store(enabled, Ordering::Relaxed);
+}
+
+/// Add the tracing context of the current span to a message
+pub(crate) fn add_tracing_context(msg: TransportMessage) -> TransportMessage {
+    let tracing_context = match PROPAGATOR.get().and_then(|p| p.current_context()) {
+        Some(tracing_context) => tracing_context,
+        None => return msg,
+    };
+    let msg = msg.with_tracing_context(Some(tracing_context));
+    if ACROSS_NODES.load(Ordering::Relaxed) {
+        msg.upgrade_to_v2()
+    } else {
+        msg
+    }
+}
+
+/// Return the span in which a worker handles a message, continuing the trace
+/// of the sender of the message
+pub(crate) fn handle_message_span(ctx: &Context, msg: &LocalMessage) -> Span {
+    let propagator = match PROPAGATOR.get() {
+        Some(propagator) => propagator,
+        None => return Span::none(),
+    };
+    let span = info_span!("handle_message", worker = %ctx.address());
+    if let Some(traceparent) = &msg.transport().tracing_context {
+        propagator.set_parent(&span, traceparent);
+    }
+    span
+}
@@ -1,6 +1,9 @@
 use crate::debugger;
 use crate::error::{NodeError, NodeReason};
-use crate::{relay::WorkerRelay, Context, NodeMessage};
+use crate::mailbox_channel::MailboxSettings;
+#[cfg(feature = "std")]
+use crate::supervisor::{Supervised, Supervisor};
+use crate::{relay::WorkerRelay, Context, MailboxOverflowPolicy, NodeMessage};
 use ockam_core::compat::sync::Arc;
 use ockam_core::{
     errcode::{Kind, Origin},
@@ -40,6 +43,9 @@ where
             outgoing_ac: Arc::new(AllowAll),
             worker: self.worker,
             address: address.into(),
+            mailbox_settings: MailboxSettings::default(),
+            #[cfg(feature = "std")]
+            supervised: None,
         }
     }
 
@@ -48,6 +54,9 @@ where
         WorkerBuilderMultipleAddresses {
             mailboxes,
             worker: self.worker,
+            mailbox_settings: MailboxSettings::default(),
+            #[cfg(feature = "std")]
+            supervised: None,
         }
     }
 }
@@ -58,6 +67,9 @@ where
 {
     mailboxes: Mailboxes,
     worker: W,
+    mailbox_settings: MailboxSettings,
+    #[cfg(feature = "std")]
+    supervised: Option<Supervised<W>>,
 }
 
 impl<W> WorkerBuilderMultipleAddresses<W>
@@ -66,7 +78,41 @@ where
 {
     /// Consume this builder and start a new Ockam [`Worker`] from the given context
     pub async fn start(self, context: &Context) -> Result<()> {
-        start(context, self.mailboxes, self.worker).await
+        start(
+            context,
+            self.mailboxes,
+            self.worker,
+            self.mailbox_settings,
+            #[cfg(feature = "std")]
+            self.supervised,
+        )
+        .await
+    }
+
+    /// Hold up to `capacity` messages in the mailbox of the worker,
+    /// [`DEFAULT_MAILBOX_CAPACITY`](crate::DEFAULT_MAILBOX_CAPACITY) by default
+    pub fn with_mailbox_capacity(mut self, capacity: usize) -> Self {
+        self.mailbox_settings.capacity = capacity;
+        self
+    }
+
+    /// Set what happens to the messages sent to the worker when its mailbox is full,
+    /// [`MailboxOverflowPolicy::Block`] by default
+    pub fn with_mailbox_overflow_policy(mut self, overflow_policy: MailboxOverflowPolicy) -> Self {
+        self.mailbox_settings.overflow_policy = overflow_policy;
+        self
+    }
+
+    /// Restart the worker with a new instance created by `factory` when it fails,
+    /// according to the options of the given [`Supervisor`]
+    #[cfg(feature = "std")]
+    pub fn with_supervisor(
+        mut self,
+        supervisor: &Supervisor,
+        factory: impl Fn() -> W + Send + Sync + 'static,
+    ) -> Self {
+        self.supervised = Some(supervisor.supervise(factory));
+        self
     }
 }
 
@@ -78,6 +124,9 @@ where
     outgoing_ac: Arc<dyn OutgoingAccessControl>,
     address: Address,
     worker: W,
+    mailbox_settings: MailboxSettings,
+    #[cfg(feature = "std")]
+    supervised: Option<Supervised<W>>,
 }
 
 impl<W> WorkerBuilderOneAddress<W>
@@ -90,9 +139,38 @@ where
             context,
             Mailboxes::main(self.address, self.incoming_ac, self.outgoing_ac),
             self.worker,
+            self.mailbox_settings,
+            #[cfg(feature = "std")]
+            self.supervised,
         )
         .await
     }
+
+    /// Hold up to `capacity` messages in the mailbox of the worker,
+    /// [`DEFAULT_MAILBOX_CAPACITY`](crate::DEFAULT_MAILBOX_CAPACITY) by default
+    pub fn with_mailbox_capacity(mut self, capacity: usize) -> Self {
+        self.mailbox_settings.capacity = capacity;
+        self
+    }
+
+    /// Set what happens to the messages sent to the worker when its mailbox is full,
+    /// [`MailboxOverflowPolicy::Block`] by default
+    pub fn with_mailbox_overflow_policy(mut self, overflow_policy: MailboxOverflowPolicy) -> Self {
+        self.mailbox_settings.overflow_policy = overflow_policy;
+        self
+    }
+
+    /// Restart the worker with a new instance created by `factory` when it fails,
+    /// according to the options of the given [`Supervisor`]
+    #[cfg(feature = "std")]
+    pub fn with_supervisor(
+        mut self,
+        supervisor: &Supervisor,
+        factory: impl Fn() -> W + Send + Sync + 'static,
+    ) -> Self {
+        self.supervised = Some(supervisor.supervise(factory));
+        self
+    }
 }
 
 impl<W> WorkerBuilderOneAddress<W>
@@ -137,7 +215,13 @@ where
 }
 
 /// Consume this builder and start a new Ockam [`Worker`] from the given context
-async fn start<W>(context: &Context, mailboxes: Mailboxes, worker: W) -> Result<()>
+async fn start<W>(
+    context: &Context,
+    mailboxes: Mailboxes,
+    worker: W,
+    mailbox_settings: MailboxSettings,
+    #[cfg(feature = "std")] supervised: Option<Supervised<W>>,
+) -> Result<()>
 where
     W: Worker<Context = Context>,
 {
@@ -151,12 +235,19 @@ where
     let addresses = mailboxes.addresses();
 
     // Pass it to the context
-    let (ctx, sender, ctrl_rx) = context.copy_with_mailboxes(mailboxes);
+    let (ctx, sender, ctrl_rx) = context.copy_with_mailbox_settings(mailboxes, mailbox_settings);
 
     debugger::log_inherit_context("WORKER", context, &ctx);
 
     // Then initialise the worker message relay
-    WorkerRelay::init(context.runtime(), worker, ctx, ctrl_rx);
+    WorkerRelay::init(
+        context.runtime(),
+        worker,
+        ctx,
+        ctrl_rx,
+        #[cfg(feature = "std")]
+        supervised,
+    );
 
     // Send start request to router
     let (msg, mut rx) =
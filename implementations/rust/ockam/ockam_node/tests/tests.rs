@@ -5,15 +5,20 @@ use ockam_core::compat::{
     string::{String, ToString},
     sync::Arc,
 };
-use ockam_core::{async_trait, Address, AllowAll, Any, Decodable, DenyAll, Message, LOCAL};
+use ockam_core::errcode::{Kind, Origin};
+use ockam_core::{async_trait, Address, AllowAll, Any, Decodable, DenyAll, Error, Message, LOCAL};
 use ockam_core::{route, Processor, Result, Routed, Worker};
 use ockam_node::compat::futures::FutureExt;
-use ockam_node::{Context, MessageReceiveOptions, NodeBuilder};
+use ockam_node::tracing_context::{set_tracing_context_propagator, TracingContextPropagator};
+use ockam_node::{
+    Context, MailboxOverflowPolicy, MessageReceiveOptions, NodeBuilder, RestartStrategy,
+    Supervisor, SupervisorOptions, WorkerBuilder,
+};
 use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicI8, AtomicU32};
 use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::time::sleep;
-use tracing::info;
+use tracing::{info, Span};
 
 #[allow(non_snake_case)]
 #[ockam_macros::test]
@@ -560,3 +565,224 @@ async fn starting_worker_with_dup_address_should_fail(ctx: &mut Context) -> Resu
         .is_err());
     ctx.stop().await
 }
+
+struct FailingWorker;
+
+#[async_trait]
+impl Worker for FailingWorker {
+    type Message = String;
+    type Context = Context;
+
+    async fn handle_message(
+        &mut self,
+        ctx: &mut Self::Context,
+        msg: Routed<Self::Message>,
+    ) -> Result<()> {
+        if msg.as_body() == "fail" {
+            return Err(Error::new(Origin::Application, Kind::Invalid, "failure"));
+        }
+        ctx.send(msg.return_route(), msg.body()).await
+    }
+}
+
+/// Start a supervised [`FailingWorker`], counting the instances created by the supervisor
+async fn start_failing_worker(
+    ctx: &Context,
+    address: &str,
+    supervisor: &Supervisor,
+) -> Result<Arc<AtomicU32>> {
+    let created = Arc::new(AtomicU32::new(0));
+    let created_clone = created.clone();
+    WorkerBuilder::new(FailingWorker)
+        .with_address(address)
+        .with_supervisor(supervisor, move || {
+            created_clone.fetch_add(1, Ordering::Relaxed);
+            FailingWorker
+        })
+        .start(ctx)
+        .await?;
+    Ok(created)
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test]
+async fn supervised_worker__failure__should_be_restarted(ctx: &mut Context) -> Result<()> {
+    let supervisor = Supervisor::new(SupervisorOptions::new());
+    let created = start_failing_worker(ctx, "failing_worker", &supervisor).await?;
+
+    ctx.send(route!["failing_worker"], "fail".to_string())
+        .await?;
+    let msg: String = ctx
+        .send_and_receive(route!["failing_worker"], "Hello".to_string())
+        .await?;
+    assert_eq!(msg, "Hello");
+
+    assert_eq!(supervisor.restart_count(), 1);
+    assert_eq!(created.load(Ordering::Relaxed), 1);
+
+    ctx.stop().await
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test]
+async fn supervised_worker__too_many_failures__should_be_stopped(ctx: &mut Context) -> Result<()> {
+    let supervisor = Supervisor::new(
+        SupervisorOptions::new()
+            .with_restart_intensity(1, Duration::from_secs(10))
+            .with_backoff(Duration::from_millis(10), Duration::from_millis(10)),
+    );
+    start_failing_worker(ctx, "failing_worker", &supervisor).await?;
+
+    ctx.send(route!["failing_worker"], "fail".to_string())
+        .await?;
+    ctx.send(route!["failing_worker"], "fail".to_string())
+        .await?;
+    sleep(Duration::from_millis(200)).await;
+
+    assert_eq!(supervisor.restart_count(), 1);
+    assert!(!ctx
+        .list_workers()
+        .await?
+        .contains(&Address::from_string("failing_worker")));
+
+    ctx.stop().await
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test]
+async fn supervised_workers__one_for_all__should_all_be_restarted(ctx: &mut Context) -> Result<()> {
+    let supervisor = Supervisor::new(
+        SupervisorOptions::new()
+            .with_strategy(RestartStrategy::OneForAll)
+            .with_backoff(Duration::from_millis(10), Duration::from_millis(10)),
+    );
+    let created1 = start_failing_worker(ctx, "failing_worker1", &supervisor).await?;
+    let created2 = start_failing_worker(ctx, "failing_worker2", &supervisor).await?;
+
+    ctx.send(route!["failing_worker1"], "fail".to_string())
+        .await?;
+    sleep(Duration::from_millis(200)).await;
+
+    assert_eq!(supervisor.restart_count(), 1);
+    assert_eq!(created1.load(Ordering::Relaxed), 1);
+    assert_eq!(created2.load(Ordering::Relaxed), 1);
+
+    let msg: String = ctx
+        .send_and_receive(route!["failing_worker2"], "Hello".to_string())
+        .await?;
+    assert_eq!(msg, "Hello");
+
+    ctx.stop().await
+}
+
+struct SlowStartingWorker;
+
+#[async_trait]
+impl Worker for SlowStartingWorker {
+    type Message = String;
+    type Context = Context;
+
+    async fn initialize(&mut self, _context: &mut Self::Context) -> Result<()> {
+        sleep(Duration::from_millis(500)).await;
+        Ok(())
+    }
+
+    async fn handle_message(
+        &mut self,
+        ctx: &mut Self::Context,
+        msg: Routed<Self::Message>,
+    ) -> Result<()> {
+        ctx.send(msg.return_route(), msg.body()).await
+    }
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test]
+async fn full_mailbox__reject_policy__should_return_error(ctx: &mut Context) -> Result<()> {
+    WorkerBuilder::new(SlowStartingWorker)
+        .with_address("slow_worker")
+        .with_mailbox_capacity(1)
+        .with_mailbox_overflow_policy(MailboxOverflowPolicy::Reject)
+        .start(ctx)
+        .await?;
+
+    ctx.send(route!["slow_worker"], "1".to_string()).await?;
+    assert!(ctx
+        .send(route!["slow_worker"], "2".to_string())
+        .await
+        .is_err());
+
+    let msg = ctx.receive::<String>().await?.body();
+    assert_eq!(msg, "1");
+
+    ctx.stop().await
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test]
+async fn full_mailbox__drop_oldest_policy__should_keep_newest_messages(
+    ctx: &mut Context,
+) -> Result<()> {
+    WorkerBuilder::new(SlowStartingWorker)
+        .with_address("slow_worker")
+        .with_mailbox_capacity(2)
+        .with_mailbox_overflow_policy(MailboxOverflowPolicy::DropOldest)
+        .start(ctx)
+        .await?;
+
+    for i in 0..4 {
+        ctx.send(route!["slow_worker"], i.to_string()).await?;
+    }
+
+    assert_eq!(ctx.receive::<String>().await?.body(), "2");
+    assert_eq!(ctx.receive::<String>().await?.body(), "3");
+
+    ctx.stop().await
+}
+
+const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+struct FixedTracingContext;
+
+impl TracingContextPropagator for FixedTracingContext {
+    fn current_context(&self) -> Option<String> {
+        Some(TRACEPARENT.to_string())
+    }
+
+    fn set_parent(&self, _span: &Span, _traceparent: &str) {}
+}
+
+struct TracingContextWorker;
+
+#[async_trait]
+impl Worker for TracingContextWorker {
+    type Context = Context;
+    type Message = Any;
+
+    async fn handle_message(&mut self, ctx: &mut Context, msg: Routed<Any>) -> Result<()> {
+        let tracing_context = msg
+            .local_message()
+            .transport()
+            .tracing_context
+            .clone()
+            .unwrap_or_default();
+        ctx.send(msg.return_route(), tracing_context).await
+    }
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test]
+async fn tracing_context__registered_propagator__should_be_sent_with_messages(
+    ctx: &mut Context,
+) -> Result<()> {
+    set_tracing_context_propagator(FixedTracingContext);
+    ctx.start_worker("tracing_context_worker", TracingContextWorker)
+        .await?;
+
+    let tracing_context: String = ctx
+        .send_and_receive(route!["tracing_context_worker"], "hello".to_string())
+        .await?;
+    assert_eq!(tracing_context, TRACEPARENT);
+
+    ctx.stop().await
+}
@@ -4,7 +4,7 @@ use ockam_core::{route, Result};
 use ockam_node::Context;
 
 use ockam_transport_ble::driver::btleplug::BleAdapter;
-use ockam_transport_ble::{BleClient, BleTransport, BLE};
+use ockam_transport_ble::{BleClient, BleConnectionOptions, BleTransport, BLE};
 
 fn main() -> Result<()> {
     let (ctx, mut exe) = ockam_node::NodeBuilder::new().build();
@@ -24,14 +24,19 @@ async fn async_main(mut ctx: Context) -> Result<()> {
     let ble = BleTransport::create(&ctx).await?;
 
     // Try to connect to BleServer
-    ble.connect(ble_client, "ockam_ble_1".to_string()).await?;
+    ble.connect(
+        ble_client,
+        "ockam_ble_1".to_string(),
+        BleConnectionOptions::new(),
+    )
+    .await?;
 
     // Send a message to the "echoer" worker, on a different node, over a ble transport.
-    let r = route![(BLE, "ockam_ble_1"), "echoer"];
-    ctx.send(r, "Hello Ockam!".to_string()).await?;
-
     // Wait to receive a reply and print it.
-    let reply = ctx.receive::<String>().await?;
+    let r = route![(BLE, "ockam_ble_1"), "echoer"];
+    let reply = ctx
+        .send_and_receive::<String>(r, "Hello Ockam!".to_string())
+        .await?;
     println!("[main] App Received: {reply}"); // should print "Hello Ockam!"
 
     // Stop all workers, stop the node, cleanup and return.
@@ -6,7 +6,7 @@ use ockam_node::Context;
 
 use ockam_transport_ble::driver::btleplug::BleAdapter;
 use ockam_transport_ble::driver::BleClient;
-use ockam_transport_ble::{BleTransport, BLE};
+use ockam_transport_ble::{BleConnectionOptions, BleTransport, BLE};
 
 fn main() -> Result<()> {
     let (ctx, mut exe) = ockam_node::NodeBuilder::new().build();
@@ -34,7 +34,12 @@ async fn async_main(mut ctx: Context) -> Result<()> {
         .await?;
 
     // Connect to BLE Server
-    ble.connect(ble_client, "ockam_ble_1".to_string()).await?;
+    ble.connect(
+        ble_client,
+        "ockam_ble_1".to_string(),
+        BleConnectionOptions::new(),
+    )
+    .await?;
 
     // Connect to a secure channel listener and perform a handshake.
     let r = route![(BLE, "ockam_ble_1"), "bob_listener"];
@@ -25,6 +25,7 @@ extern crate tracing;
 pub mod driver;
 mod error;
 mod macros;
+mod options;
 mod router;
 mod transport;
 mod types;
@@ -33,6 +34,7 @@ mod workers;
 use ockam_core::TransportType;
 
 pub use driver::{BleClient, BleServer};
+pub use options::*;
 pub use transport::BleTransport;
 pub use types::*;
 
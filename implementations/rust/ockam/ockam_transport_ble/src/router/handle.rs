@@ -1,7 +1,7 @@
 use crate::{
     driver::{BleClientDriver, BleServerDriver, BleStreamDriver},
     workers::{BleListenProcessor, BleSendWorker, WorkerPair},
-    BleAddr, BleClient, BleServer,
+    BleAddr, BleClient, BleConnectionOptions, BleListenerOptions, BleServer,
 };
 
 use crate::router::BleRouterMessage;
@@ -70,6 +70,7 @@ impl BleRouterHandle {
         &self,
         ble_server: BleServer<A>,
         addr: S,
+        options: BleListenerOptions,
     ) -> Result<()> {
         let ble_addr = addr.into();
         BleListenProcessor::start(
@@ -77,6 +78,7 @@ impl BleRouterHandle {
             &self.ctx,
             self.async_try_clone().await?,
             ble_addr,
+            options,
         )
         .await
     }
@@ -102,6 +104,7 @@ impl BleRouterHandle {
         &self,
         mut ble_client: BleClient<A>,
         peer: S,
+        options: BleConnectionOptions,
     ) -> Result<()> {
         let (peer_addr, servicenames) = Self::resolve_peer(peer.as_ref())?;
 
@@ -112,7 +115,17 @@ impl BleRouterHandle {
         ble_client.connect().await?;
 
         let stream = crate::driver::AsyncStream::with_ble_device(ble_client);
-        let pair = BleSendWorker::start_pair(&self.ctx, stream, peer_addr, servicenames).await?;
+        let pair = WorkerPair::new(peer_addr, servicenames);
+        options.setup_flow_control(
+            self.ctx.flow_controls(),
+            pair.rx_addr(),
+            &pair.sender_addresses(),
+        );
+        let receiver_outgoing_access_control =
+            options.create_receiver_access_control(self.ctx.flow_controls());
+        let pair =
+            BleSendWorker::start_pair(&self.ctx, stream, pair, receiver_outgoing_access_control)
+                .await?;
 
         self.register(&pair).await?;
 
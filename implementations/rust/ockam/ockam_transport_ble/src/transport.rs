@@ -8,7 +8,7 @@ use ockam_node::Context;
 use crate::driver::{BleClient, BleServer};
 use crate::driver::{BleClientDriver, BleServerDriver, BleStreamDriver};
 use crate::router::{BleRouter, BleRouterHandle};
-use crate::BleAddr;
+use crate::{BleAddr, BleConnectionOptions, BleListenerOptions};
 
 /// High level management interface for BLE transports
 ///
@@ -23,8 +23,12 @@ use crate::BleAddr;
 /// incoming connections use
 /// [`ble.listen()`](crate::BleTransport::listen)
 ///
+/// Messages received from a connection can only be delivered to the workers which
+/// were marked as Consumers of its [`FlowControlId`](ockam_core::flow_control::FlowControlId)
+/// with the [`BleListenerOptions`] and [`BleConnectionOptions`].
+///
 /// ```rust
-/// use ockam_transport_ble::{BleClient, BleTransport};
+/// use ockam_transport_ble::{BleClient, BleConnectionOptions, BleTransport};
 /// use ockam_transport_ble::driver::btleplug::BleAdapter;
 /// # use ockam_node::Context;
 /// # use ockam_core::Result;
@@ -37,7 +41,7 @@ use crate::BleAddr;
 ///     let ble = BleTransport::create(&ctx).await?;
 ///
 ///     // Try to connect to BleServer
-///     ble.connect(ble_client, "ockam_ble_1".to_string()).await?;
+///     ble.connect(ble_client, "ockam_ble_1".to_string(), BleConnectionOptions::new()).await?;
 /// # Ok(()) }
 /// ```
 pub struct BleTransport {
@@ -80,8 +84,11 @@ impl BleTransport {
         &self,
         ble_client: BleClient<A>,
         peer: S,
+        options: BleConnectionOptions,
     ) -> Result<()> {
-        self.router_handle.connect(ble_client, peer.as_ref()).await
+        self.router_handle
+            .connect(ble_client, peer.as_ref(), options)
+            .await
     }
 
     /// Start listening to incoming connections on an existing transport
@@ -92,10 +99,13 @@ impl BleTransport {
         &self,
         ble_server: BleServer<A>,
         listen_addr: S,
+        options: BleListenerOptions,
     ) -> Result<()> {
         let bind_addr = BleAddr::from_str(listen_addr.as_ref())?;
         debug!("BleTransport::listen -> {:?}", listen_addr);
-        self.router_handle.bind(ble_server, bind_addr).await?;
+        self.router_handle
+            .bind(ble_server, bind_addr, options)
+            .await?;
 
         Ok(())
     }
@@ -7,12 +7,14 @@ use crate::driver::{BleEvent, BleServer};
 use crate::driver::{BleServerDriver, BleStreamDriver};
 use crate::router::BleRouterHandle;
 use crate::workers::sender::BleSendWorker;
-use crate::BleAddr;
+use crate::workers::WorkerPair;
+use crate::{BleAddr, BleListenerOptions};
 
 /// BleListenProcessor
 pub struct BleListenProcessor<A> {
     inner: Option<BleServer<A>>,
     router_handle: BleRouterHandle,
+    options: BleListenerOptions,
 }
 
 impl<A> BleListenProcessor<A>
@@ -24,15 +26,19 @@ where
         ctx: &Context,
         router_handle: BleRouterHandle,
         addr: BleAddr,
+        options: BleListenerOptions,
     ) -> Result<()> {
         debug!("BleRouterHandle::bind binding BleServer to: {}", addr);
         ble_server.bind(&addr).await?;
 
+        let waddr = Address::random_local();
+        options.setup_flow_control_for_listener(ctx.flow_controls(), &waddr);
+
         let processor = Self {
             inner: Some(ble_server),
             router_handle,
+            options,
         };
-        let waddr = Address::random_local();
 
         debug!(
             "BleListenProcessor::start Starting processor with address: {:?}",
@@ -76,14 +82,22 @@ where
             // Spawn a WorkerPair for it
             let ble_server = self.inner.take().unwrap();
             let stream = AsyncStream::with_ble_device(ble_server);
-            let pair = BleSendWorker::start_pair(
-                ctx,
-                stream,
+            let pair = WorkerPair::new(
                 // TODO resolve connecting BleClient's addresses
                 crate::parse_ble_addr("ble_client_addr").unwrap(),
                 vec![],
-            )
-            .await?;
+            );
+            let receiver_flow_control_id = self.options.setup_flow_control_for_connection(
+                ctx.flow_controls(),
+                pair.rx_addr(),
+                &pair.sender_addresses(),
+            );
+            let receiver_outgoing_access_control = self
+                .options
+                .create_receiver_access_control(ctx.flow_controls(), receiver_flow_control_id);
+            let pair =
+                BleSendWorker::start_pair(ctx, stream, pair, receiver_outgoing_access_control)
+                    .await?;
 
             // Register the connection with the local BleRouter
             trace!("Registering WorkerPair tx stream with BleRouterHandle");
@@ -1,8 +1,9 @@
-use ockam_core::compat::{boxed::Box, string::String, vec::Vec};
+use ockam_core::compat::{boxed::Box, string::String, sync::Arc, vec::Vec};
 use ockam_core::{
-    async_trait, Address, AllowAll, Encodable, Result, Routed, TransportMessage, Worker,
+    async_trait, Address, DenyAll, Encodable, OutgoingAccessControl, Result, Routed,
+    TransportMessage, Worker,
 };
-use ockam_node::Context;
+use ockam_node::{Context, ProcessorBuilder};
 use ockam_transport_core::TransportError;
 
 use crate::driver::{AsyncStream, BleStreamDriver, PacketBuffer, Sink, Source};
@@ -14,9 +15,18 @@ pub(crate) struct WorkerPair {
     servicenames: Vec<String>,
     peer: BleAddr,
     tx_addr: Address,
+    rx_addr: Address,
 }
 
 impl WorkerPair {
+    pub fn new(peer: BleAddr, servicenames: Vec<String>) -> Self {
+        Self {
+            servicenames,
+            peer,
+            tx_addr: Address::random_local(),
+            rx_addr: Address::random_local(),
+        }
+    }
     pub fn servicenames(&self) -> &[String] {
         &self.servicenames
     }
@@ -26,6 +36,24 @@ impl WorkerPair {
     pub fn tx_addr(&self) -> Address {
         self.tx_addr.clone()
     }
+    pub fn rx_addr(&self) -> &Address {
+        &self.rx_addr
+    }
+
+    /// Addresses through which messages are sent to the peer: the transmit address,
+    /// and the BLE addresses that the router maps to it
+    pub fn sender_addresses(&self) -> Vec<Address> {
+        let mut addresses = vec![
+            self.tx_addr(),
+            Address::from_string(format!("{}#{}", crate::BLE, self.peer)),
+        ];
+        addresses.extend(
+            self.servicenames
+                .iter()
+                .map(|x| Address::from_string(format!("{}#{}", crate::BLE, x))),
+        );
+        addresses
+    }
 }
 
 /// A BLE sending message worker
@@ -37,40 +65,51 @@ pub(crate) struct BleSendWorker<A> {
     rx_stream: Option<Source<A>>,
     tx_stream: Option<Sink<A>>,
     peer: BleAddr,
+    rx_addr: Address,
+    receiver_outgoing_access_control: Arc<dyn OutgoingAccessControl>,
 }
 
 impl<A> BleSendWorker<A>
 where
     A: BleStreamDriver + Send + 'static,
 {
-    fn new(stream: AsyncStream<A>, peer: BleAddr) -> Self {
+    fn new(
+        stream: AsyncStream<A>,
+        peer: BleAddr,
+        rx_addr: Address,
+        receiver_outgoing_access_control: Arc<dyn OutgoingAccessControl>,
+    ) -> Self {
         let (tx, rx) = stream.split();
         Self {
             rx_stream: Some(rx),
             tx_stream: Some(tx),
             peer,
+            rx_addr,
+            receiver_outgoing_access_control,
         }
     }
 
+    /// Start the workers of a [`WorkerPair`], whose flow control was set up by the caller
     pub(crate) async fn start_pair(
         ctx: &Context,
         stream: AsyncStream<A>,
-        peer: BleAddr,
-        servicenames: Vec<String>,
+        pair: WorkerPair,
+        receiver_outgoing_access_control: Arc<dyn OutgoingAccessControl>,
     ) -> Result<WorkerPair> {
         debug!("Creating new BLE worker pair");
 
-        let tx_addr = Address::random_local();
-        let sender = BleSendWorker::new(stream, peer.clone());
+        let tx_addr = pair.tx_addr();
+        let sender = BleSendWorker::new(
+            stream,
+            pair.peer(),
+            pair.rx_addr().clone(),
+            receiver_outgoing_access_control,
+        );
 
         debug!("start send worker({:?})", tx_addr.clone());
-        ctx.start_worker(tx_addr.clone(), sender).await?;
+        ctx.start_worker(tx_addr, sender).await?;
 
-        Ok(WorkerPair {
-            servicenames,
-            peer,
-            tx_addr,
-        })
+        Ok(pair)
     }
 }
 
@@ -88,16 +127,14 @@ where
         debug!("initialize for peer: {:?}", self.peer);
 
         if let Some(rx_stream) = self.rx_stream.take() {
-            let rx_addr = Address::random_local();
             let receiver =
                 BleRecvProcessor::new(rx_stream, format!("{}#{}", crate::BLE, self.peer).into());
-            ctx.start_processor_with_access_control(
-                rx_addr.clone(),
-                receiver,
-                AllowAll, // FIXME: @ac
-                AllowAll, // FIXME: @ac
-            )
-            .await?;
+            ProcessorBuilder::new(receiver)
+                .with_address(self.rx_addr.clone())
+                .with_incoming_access_control(DenyAll)
+                .with_outgoing_access_control_arc(self.receiver_outgoing_access_control.clone())
+                .start(ctx)
+                .await?;
             debug!("started receiver");
         } else {
             error!("TransportError::GenericIo");
@@ -14,6 +14,8 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 mod error;
+#[cfg(feature = "tls")]
+pub mod tls;
 mod transport;
 
 pub use error::TransportError;
@@ -0,0 +1,74 @@
+//! Helpers to load the TLS certificates and keys used by the transports
+use core::fmt::Display;
+use ockam_core::errcode::{Kind, Origin};
+use ockam_core::{Error, Result};
+use rustls::{Certificate, PrivateKey, RootCertStore};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use crate::TransportError;
+
+/// Error returned when the TLS configuration of a transport is invalid
+pub fn tls_error(e: impl Display) -> Error {
+    Error::new(
+        Origin::Transport,
+        Kind::Invalid,
+        format!("invalid TLS configuration: {e}"),
+    )
+}
+
+fn open(path: &Path) -> Result<BufReader<File>> {
+    let file = File::open(path)
+        .map_err(|e| tls_error(format!("failed to open {}: {}", path.display(), e)))?;
+    Ok(BufReader::new(file))
+}
+
+/// Read the certificates of a PEM file, which must contain at least one
+pub fn read_certificates(path: &Path) -> Result<Vec<Certificate>> {
+    let certificates = rustls_pemfile::certs(&mut open(path)?).map_err(TransportError::from)?;
+    if certificates.is_empty() {
+        return Err(tls_error(format!(
+            "no certificate found in {}",
+            path.display()
+        )));
+    }
+    Ok(certificates.into_iter().map(Certificate).collect())
+}
+
+/// Read the certificates of a PEM file as trusted roots
+pub fn read_root_certificates(path: &Path) -> Result<RootCertStore> {
+    let mut roots = RootCertStore::empty();
+    for certificate in read_certificates(path)? {
+        roots
+            .add(&certificate)
+            .map_err(|e| tls_error(format!("{} in {}", e, path.display())))?;
+    }
+    Ok(roots)
+}
+
+/// Load the root certificates of the platform
+pub fn native_root_certificates() -> Result<RootCertStore> {
+    let mut roots = RootCertStore::empty();
+    for certificate in rustls_native_certs::load_native_certs().map_err(TransportError::from)? {
+        // Some platforms ship certificates which can't be parsed, they are skipped
+        let _ = roots.add(&Certificate(certificate.0));
+    }
+    Ok(roots)
+}
+
+/// Read the first RSA, PKCS8 or EC private key of a PEM file
+pub fn read_private_key(path: &Path) -> Result<PrivateKey> {
+    for item in rustls_pemfile::read_all(&mut open(path)?).map_err(TransportError::from)? {
+        match item {
+            rustls_pemfile::Item::RSAKey(key)
+            | rustls_pemfile::Item::PKCS8Key(key)
+            | rustls_pemfile::Item::ECKey(key) => return Ok(PrivateKey(key)),
+            _ => {}
+        }
+    }
+    Err(tls_error(format!(
+        "no private key found in {}",
+        path.display()
+    )))
+}
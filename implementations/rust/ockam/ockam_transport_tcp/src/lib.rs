@@ -21,12 +21,15 @@ extern crate alloc;
 mod options;
 mod portal;
 mod registry;
+mod tls;
 mod transport;
 
 use ockam_core::TransportType;
 pub use options::{TcpConnectionOptions, TcpListenerOptions};
-pub use portal::{PortalInternalMessage, PortalMessage, MAX_PAYLOAD_SIZE};
+pub use portal::{PortalCapabilities, PortalInternalMessage, PortalMessage, MAX_PAYLOAD_SIZE};
 pub use registry::*;
+pub(crate) use tls::TcpTlsAcceptor;
+pub use tls::{TcpClientTlsOptions, TcpServerTlsOptions, TLS_HANDSHAKE_TIMEOUT};
 pub use transport::common::*;
 pub use transport::*;
 
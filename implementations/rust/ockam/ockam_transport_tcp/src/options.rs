@@ -1,4 +1,5 @@
 use crate::workers::Addresses;
+use crate::{TcpClientTlsOptions, TcpServerTlsOptions};
 use ockam_core::compat::sync::Arc;
 use ockam_core::flow_control::{FlowControlId, FlowControlOutgoingAccessControl, FlowControls};
 use ockam_core::{Address, AllowAll, IncomingAccessControl, OutgoingAccessControl};
@@ -13,6 +14,7 @@ pub(crate) struct TcpConnectionAccessControl {
 pub struct TcpConnectionOptions {
     pub(super) consumer: Vec<FlowControlId>,
     pub(crate) flow_control_id: FlowControlId,
+    pub(crate) tls: Option<TcpClientTlsOptions>,
 }
 
 impl TcpConnectionOptions {
@@ -22,9 +24,17 @@ impl TcpConnectionOptions {
         Self {
             consumer: vec![],
             flow_control_id: FlowControls::generate_flow_control_id(),
+            tls: None,
         }
     }
 
+    /// Wrap the connection in TLS, using the given options
+    pub fn with_tls(mut self, tls: TcpClientTlsOptions) -> Self {
+        self.tls = Some(tls);
+
+        self
+    }
+
     /// Mark that this Connection is a Consumer for to the given [`FlowControlId`]
     pub fn as_consumer(mut self, id: &FlowControlId) -> Self {
         self.consumer.push(id.clone());
@@ -71,6 +81,7 @@ impl TcpConnectionOptions {
 #[derive(Debug)]
 pub struct TcpListenerOptions {
     pub(crate) flow_control_id: FlowControlId,
+    pub(crate) tls: Option<TcpServerTlsOptions>,
 }
 
 impl TcpListenerOptions {
@@ -81,9 +92,17 @@ impl TcpListenerOptions {
     pub fn new() -> Self {
         Self {
             flow_control_id: FlowControls::generate_flow_control_id(),
+            tls: None,
         }
     }
 
+    /// Only accept TLS connections, using the given options
+    pub fn with_tls(mut self, tls: TcpServerTlsOptions) -> Self {
+        self.tls = Some(tls);
+
+        self
+    }
+
     /// Getter for freshly generated [`FlowControlId`]
     pub fn spawner_flow_control_id(&self) -> FlowControlId {
         self.flow_control_id.clone()
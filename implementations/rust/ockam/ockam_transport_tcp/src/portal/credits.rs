@@ -0,0 +1,52 @@
+use ockam_core::compat::sync::{Arc, Mutex};
+use tokio::sync::Notify;
+
+/// Credits granted by the other side of a portal to send it payloads
+///
+/// Every payload read from the TCP stream consumes one credit. The other side grants
+/// new credits once it has written the payloads to its own TCP stream, so the reads
+/// pause when it falls behind. Credits are only enforced when both sides advertised
+/// [`PortalCapabilities::CREDITS`](crate::PortalCapabilities::CREDITS), so that portals
+/// keep working with a peer which doesn't send any credits.
+#[derive(Clone, Default)]
+pub(crate) struct PortalCredits {
+    state: Arc<Mutex<CreditsState>>,
+    notify: Arc<Notify>,
+}
+
+#[derive(Default)]
+struct CreditsState {
+    enabled: bool,
+    granted: u64,
+    sent: u64,
+}
+
+impl PortalCredits {
+    /// Enforce the credits: the reads pause until the other side grants some
+    pub(crate) fn enable(&self) {
+        self.state.lock().unwrap().enabled = true;
+    }
+
+    /// Wait for a credit to send one payload
+    pub(crate) async fn acquire(&self) {
+        loop {
+            {
+                let mut state = self.state.lock().unwrap();
+                if !state.enabled || state.sent < state.granted {
+                    state.sent += 1;
+                    return;
+                }
+            }
+            self.notify.notified().await;
+        }
+    }
+
+    /// Add the credits granted by the other side
+    pub(crate) fn grant(&self, credits: u32) {
+        {
+            let mut state = self.state.lock().unwrap();
+            state.granted += credits as u64;
+        }
+        self.notify.notify_one();
+    }
+}
@@ -1,12 +1,13 @@
 use crate::portal::addresses::{Addresses, PortalType};
-use crate::{portal::TcpPortalWorker, TcpInletOptions, TcpRegistry};
+use crate::portal::PortalStats;
+use crate::{portal::TcpPortalWorker, TcpInletInfo, TcpInletOptions, TcpRegistry};
 use ockam_core::compat::net::SocketAddr;
 use ockam_core::{async_trait, compat::boxed::Box};
 use ockam_core::{Address, Processor, Result, Route};
 use ockam_node::Context;
 use ockam_transport_core::TransportError;
 use tokio::net::TcpListener;
-use tracing::{debug, error};
+use tracing::{debug, error, warn};
 
 /// A TCP Portal Inlet listen processor
 ///
@@ -16,22 +17,28 @@ use tracing::{debug, error};
 pub(crate) struct TcpInletListenProcessor {
     registry: TcpRegistry,
     inner: TcpListener,
+    socket_addr: SocketAddr,
     outlet_listener_route: Route,
     options: TcpInletOptions,
+    stats: PortalStats,
 }
 
 impl TcpInletListenProcessor {
     pub fn new(
         registry: TcpRegistry,
         inner: TcpListener,
+        socket_addr: SocketAddr,
         outlet_listener_route: Route,
         options: TcpInletOptions,
     ) -> Self {
+        let stats = PortalStats::new(&options.limits);
         Self {
             registry,
             inner,
+            socket_addr,
             outlet_listener_route,
             options,
+            stats,
         }
     }
 
@@ -54,7 +61,7 @@ impl TcpInletListenProcessor {
             }
         };
         let socket_addr = inner.local_addr().map_err(TransportError::from)?;
-        let processor = Self::new(registry, inner, outlet_listener_route, options);
+        let processor = Self::new(registry, inner, socket_addr, outlet_listener_route, options);
 
         ctx.start_processor(processor_address.clone(), processor)
             .await?;
@@ -68,7 +75,12 @@ impl Processor for TcpInletListenProcessor {
     type Context = Context;
 
     async fn initialize(&mut self, ctx: &mut Self::Context) -> Result<()> {
-        self.registry.add_inlet_listener_processor(&ctx.address());
+        self.registry
+            .add_inlet_listener_processor(TcpInletInfo::new(
+                ctx.address(),
+                self.socket_addr,
+                self.stats.clone(),
+            ));
 
         Ok(())
     }
@@ -81,6 +93,20 @@ impl Processor for TcpInletListenProcessor {
     }
 
     async fn process(&mut self, ctx: &mut Self::Context) -> Result<bool> {
+        let (stream, peer) = self.inner.accept().await.map_err(TransportError::from)?;
+
+        // The stream is dropped right away if the connection is above the limits
+        let connection = match self.stats.open_connection(peer) {
+            Some(connection) => connection,
+            None => {
+                warn!(
+                    "Tcp Inlet rejected a connection from {} above its limits",
+                    peer
+                );
+                return Ok(true);
+            }
+        };
+
         let addresses = Addresses::generate(PortalType::Inlet);
         let outlet_listener_route = self.outlet_listener_route.clone();
 
@@ -90,7 +116,6 @@ impl Processor for TcpInletListenProcessor {
             outlet_listener_route.next()?,
         );
 
-        let (stream, peer) = self.inner.accept().await.map_err(TransportError::from)?;
         TcpPortalWorker::start_new_inlet(
             ctx,
             self.registry.clone(),
@@ -99,6 +124,8 @@ impl Processor for TcpInletListenProcessor {
             outlet_listener_route,
             addresses,
             self.options.incoming_access_control.clone(),
+            self.options.window_size,
+            connection,
         )
         .await?;
 
@@ -1,13 +1,21 @@
 mod addresses;
+mod credits;
 mod inlet_listener;
 pub mod options;
 mod outlet_listener;
 mod portal_message;
 mod portal_receiver;
 mod portal_worker;
+mod stats;
+mod target_pool;
+mod target_pool_processor;
 
+pub(crate) use credits::*;
 pub(crate) use inlet_listener::*;
 pub(crate) use outlet_listener::*;
 pub use portal_message::*;
 pub(crate) use portal_receiver::*;
 pub(crate) use portal_worker::*;
+pub(crate) use stats::*;
+pub(crate) use target_pool::*;
+pub(crate) use target_pool_processor::*;
@@ -1,12 +1,24 @@
 use crate::portal::addresses::Addresses;
+use crate::portal::PortalLimits;
+use core::fmt;
+use core::fmt::Formatter;
+use core::str::FromStr;
+use core::time::Duration;
 use ockam_core::compat::sync::Arc;
 use ockam_core::flow_control::{FlowControlId, FlowControls};
 use ockam_core::{Address, AllowAll, IncomingAccessControl};
+use ockam_transport_core::TransportError;
+
+/// Default number of payloads that the other side of a portal can send
+/// before waiting for them to be written to the TCP stream
+pub const DEFAULT_WINDOW_SIZE: u32 = 64;
 
 /// Trust Options for an Inlet
 #[derive(Debug)]
 pub struct TcpInletOptions {
     pub(super) incoming_access_control: Arc<dyn IncomingAccessControl>,
+    pub(super) window_size: u32,
+    pub(super) limits: PortalLimits,
 }
 
 impl TcpInletOptions {
@@ -14,6 +26,8 @@ impl TcpInletOptions {
     pub fn new() -> Self {
         Self {
             incoming_access_control: Arc::new(AllowAll),
+            window_size: DEFAULT_WINDOW_SIZE,
+            limits: PortalLimits::default(),
         }
     }
 
@@ -35,6 +49,32 @@ impl TcpInletOptions {
         self
     }
 
+    /// Set the number of payloads that the other side of the portal can send before
+    /// waiting for them to be written to the TCP stream. The minimum is 1
+    pub fn with_window_size(mut self, window_size: u32) -> Self {
+        self.window_size = window_size.max(1);
+        self
+    }
+
+    /// Set the maximum number of concurrent connections, new connections are rejected above it
+    pub fn with_max_connections(mut self, max_connections: usize) -> Self {
+        self.limits.max_connections = Some(max_connections);
+        self
+    }
+
+    /// Set the maximum number of new connections per second, new connections are rejected above it
+    pub fn with_max_connections_per_second(mut self, max_connections_per_second: u32) -> Self {
+        self.limits.max_connections_per_second = Some(max_connections_per_second);
+        self
+    }
+
+    /// Set the maximum number of bytes per second read from and written to all
+    /// the connections of the portal. Connections are slowed down above it
+    pub fn with_max_bytes_per_second(mut self, max_bytes_per_second: u64) -> Self {
+        self.limits.max_bytes_per_second = Some(max_bytes_per_second);
+        self
+    }
+
     pub(super) fn setup_flow_control(
         &self,
         flow_controls: &FlowControls,
@@ -57,11 +97,82 @@ impl Default for TcpInletOptions {
     }
 }
 
+/// Strategy used by an Outlet to choose the target of a new connection
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LoadBalancing {
+    /// Use each target in turn
+    #[default]
+    RoundRobin,
+    /// Use the target with the fewest active connections
+    LeastConnections,
+}
+
+impl fmt::Display for LoadBalancing {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadBalancing::RoundRobin => write!(f, "round-robin"),
+            LoadBalancing::LeastConnections => write!(f, "least-connections"),
+        }
+    }
+}
+
+impl FromStr for LoadBalancing {
+    type Err = ockam_core::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "round-robin" => Ok(LoadBalancing::RoundRobin),
+            "least-connections" => Ok(LoadBalancing::LeastConnections),
+            _ => Err(TransportError::Protocol.into()),
+        }
+    }
+}
+
+/// Active health checks of the targets of an Outlet
+///
+/// Each target is checked by opening a TCP connection to it. A target which fails
+/// `failure_threshold` consecutive checks is ejected until a check succeeds again
+#[derive(Clone, Debug)]
+pub struct HealthCheckOptions {
+    pub(crate) interval: Duration,
+    pub(crate) timeout: Duration,
+    pub(crate) failure_threshold: u32,
+}
+
+impl HealthCheckOptions {
+    /// Check the targets every `interval`
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            timeout: Duration::from_secs(2),
+            failure_threshold: 3,
+        }
+    }
+
+    /// Set the maximum duration of a connection attempt
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the number of consecutive failed checks before a target is ejected. The minimum is 1
+    pub fn with_failure_threshold(mut self, failure_threshold: u32) -> Self {
+        self.failure_threshold = failure_threshold.max(1);
+        self
+    }
+}
+
 /// Trust Options for an Outlet
 #[derive(Debug)]
 pub struct TcpOutletOptions {
     pub(super) consumer: Vec<FlowControlId>,
     pub(super) incoming_access_control: Arc<dyn IncomingAccessControl>,
+    pub(super) window_size: u32,
+    pub(super) limits: PortalLimits,
+    pub(super) targets: Vec<String>,
+    pub(super) load_balancing: LoadBalancing,
+    pub(super) health_check: Option<HealthCheckOptions>,
+    pub(super) dns_refresh_interval: Option<Duration>,
 }
 
 impl TcpOutletOptions {
@@ -70,6 +181,12 @@ impl TcpOutletOptions {
         Self {
             consumer: vec![],
             incoming_access_control: Arc::new(AllowAll),
+            window_size: DEFAULT_WINDOW_SIZE,
+            limits: PortalLimits::default(),
+            targets: vec![],
+            load_balancing: LoadBalancing::default(),
+            health_check: None,
+            dns_refresh_interval: None,
         }
     }
 
@@ -91,6 +208,58 @@ impl TcpOutletOptions {
         self
     }
 
+    /// Set the number of payloads that the other side of the portal can send before
+    /// waiting for them to be written to the TCP stream. The minimum is 1
+    pub fn with_window_size(mut self, window_size: u32) -> Self {
+        self.window_size = window_size.max(1);
+        self
+    }
+
+    /// Set the maximum number of concurrent connections, new connections are rejected above it
+    pub fn with_max_connections(mut self, max_connections: usize) -> Self {
+        self.limits.max_connections = Some(max_connections);
+        self
+    }
+
+    /// Set the maximum number of new connections per second, new connections are rejected above it
+    pub fn with_max_connections_per_second(mut self, max_connections_per_second: u32) -> Self {
+        self.limits.max_connections_per_second = Some(max_connections_per_second);
+        self
+    }
+
+    /// Set the maximum number of bytes per second read from and written to all
+    /// the connections of the portal. Connections are slowed down above it
+    pub fn with_max_bytes_per_second(mut self, max_bytes_per_second: u64) -> Self {
+        self.limits.max_bytes_per_second = Some(max_bytes_per_second);
+        self
+    }
+
+    /// Add targets to the Outlet, on top of the peer it was created with.
+    /// New connections are balanced between all the targets
+    pub fn with_targets(mut self, targets: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.targets.extend(targets.into_iter().map(|t| t.into()));
+        self
+    }
+
+    /// Set the strategy used to choose the target of a new connection
+    pub fn with_load_balancing(mut self, load_balancing: LoadBalancing) -> Self {
+        self.load_balancing = load_balancing;
+        self
+    }
+
+    /// Check the targets periodically and stop using the ones that fail
+    pub fn with_health_check(mut self, health_check: HealthCheckOptions) -> Self {
+        self.health_check = Some(health_check);
+        self
+    }
+
+    /// Resolve the targets hostnames again every `interval`. Every address
+    /// returned by the DNS resolution is then used as a target
+    pub fn with_dns_refresh_interval(mut self, interval: Duration) -> Self {
+        self.dns_refresh_interval = Some(interval);
+        self
+    }
+
     /// Mark that this Outlet listener is a Consumer for to the given [`FlowControlId`]
     /// Also, in this case spawned Outlets will be marked as Consumers with [`FlowControlId`]
     /// of the message that was used to create the Outlet
@@ -1,10 +1,10 @@
 use crate::portal::addresses::{Addresses, PortalType};
-use crate::{portal::TcpPortalWorker, PortalMessage, TcpOutletOptions, TcpRegistry};
-use ockam_core::{async_trait, Address, DenyAll, Result, Routed, Worker};
+use crate::portal::{PortalStats, TargetPool, TargetPoolProcessor};
+use crate::{portal::TcpPortalWorker, PortalMessage, TcpOutletInfo, TcpOutletOptions, TcpRegistry};
+use ockam_core::{async_trait, Address, AllowOnwardAddress, DenyAll, Result, Routed, Worker};
 use ockam_node::{Context, WorkerBuilder};
 use ockam_transport_core::TransportError;
-use std::net::SocketAddr;
-use tracing::debug;
+use tracing::{debug, warn};
 
 /// A TCP Portal Outlet listen worker
 ///
@@ -13,32 +13,47 @@ use tracing::debug;
 /// [`TcpTransport::create_outlet`](crate::TcpTransport::create_outlet).
 pub(crate) struct TcpOutletListenWorker {
     registry: TcpRegistry,
-    peer: SocketAddr,
+    pool: TargetPool,
+    pool_processor: Option<Address>,
     options: TcpOutletOptions,
+    stats: PortalStats,
 }
 
 impl TcpOutletListenWorker {
     /// Create a new `TcpOutletListenWorker`
-    fn new(registry: TcpRegistry, peer: SocketAddr, options: TcpOutletOptions) -> Self {
+    fn new(registry: TcpRegistry, pool: TargetPool, options: TcpOutletOptions) -> Self {
+        let stats = PortalStats::new(&options.limits);
         Self {
             registry,
-            peer,
+            pool,
+            pool_processor: None,
             options,
+            stats,
         }
     }
 
+    /// Start an Outlet listener connecting to `peer`, and to the additional targets of the options
     pub(crate) async fn start(
         ctx: &Context,
         registry: TcpRegistry,
         address: Address,
-        peer: SocketAddr,
+        peer: String,
         options: TcpOutletOptions,
     ) -> Result<()> {
         let access_control = options.incoming_access_control.clone();
 
+        let mut peers = vec![peer];
+        peers.extend(options.targets.iter().cloned());
+        let pool = TargetPool::create(
+            peers,
+            options.load_balancing,
+            options.dns_refresh_interval.is_some(),
+        )
+        .await?;
+
         options.setup_flow_control_for_outlet_listener(ctx.flow_controls(), &address);
 
-        let worker = Self::new(registry, peer, options);
+        let worker = Self::new(registry, pool, options);
         WorkerBuilder::new(worker)
             .with_address(address)
             .with_incoming_access_control_arc(access_control)
@@ -56,7 +71,23 @@ impl Worker for TcpOutletListenWorker {
     type Message = PortalMessage;
 
     async fn initialize(&mut self, ctx: &mut Self::Context) -> Result<()> {
-        self.registry.add_outlet_listener_worker(&ctx.address());
+        // The pool processor is started here, so that it is stopped along with the listener
+        let pool_processor = Address::random_tagged("TcpOutletListenWorker.target_pool");
+        self.pool_processor = TargetPoolProcessor::start(
+            ctx,
+            pool_processor.clone(),
+            self.pool.clone(),
+            self.options.health_check.clone(),
+            self.options.dns_refresh_interval,
+        )
+        .await?
+        .then_some(pool_processor);
+
+        self.registry.add_outlet_listener_worker(TcpOutletInfo::new(
+            ctx.address(),
+            self.pool.clone(),
+            self.stats.clone(),
+        ));
 
         Ok(())
     }
@@ -64,6 +95,10 @@ impl Worker for TcpOutletListenWorker {
     async fn shutdown(&mut self, ctx: &mut Self::Context) -> Result<()> {
         self.registry.remove_outlet_listener_worker(&ctx.address());
 
+        if let Some(pool_processor) = self.pool_processor.take() {
+            ctx.stop_processor(pool_processor).await?;
+        }
+
         Ok(())
     }
 
@@ -75,10 +110,36 @@ impl Worker for TcpOutletListenWorker {
         let return_route = msg.return_route();
         let src_addr = msg.src_addr();
 
-        if let PortalMessage::Ping = msg.body() {
-        } else {
-            return Err(TransportError::Protocol.into());
-        }
+        let capabilities = match msg.as_body() {
+            PortalMessage::Ping => msg.as_body().decode_capabilities(msg.payload()),
+            _ => return Err(TransportError::Protocol.into()),
+        };
+
+        let (peer, target_connection) = self.pool.select()?;
+
+        // Let the Inlet know that the connection is refused if it is above the limits
+        let connection = match self.stats.open_connection(peer) {
+            Some(connection) => connection,
+            None => {
+                warn!(
+                    "Tcp Outlet rejected a connection to {} above its limits",
+                    peer
+                );
+                // The listener can't send messages, the Disconnect is sent from a
+                // short-lived address which can only reply to the Inlet
+                let reject_ctx = ctx
+                    .new_detached(
+                        Address::random_tagged("TcpOutletListenWorker.reject"),
+                        DenyAll,
+                        AllowOnwardAddress(return_route.next()?.clone()),
+                    )
+                    .await?;
+                reject_ctx
+                    .send(return_route, PortalMessage::Disconnect)
+                    .await?;
+                return Ok(());
+            }
+        };
 
         let addresses = Addresses::generate(PortalType::Outlet);
 
@@ -88,14 +149,18 @@ impl Worker for TcpOutletListenWorker {
         TcpPortalWorker::start_new_outlet(
             ctx,
             self.registry.clone(),
-            self.peer,
+            peer,
+            target_connection,
+            connection,
             return_route.clone(),
             addresses.clone(),
             self.options.incoming_access_control.clone(),
+            self.options.window_size,
+            capabilities,
         )
         .await?;
 
-        debug!("Created Tcp Outlet at {}", addresses.remote);
+        debug!("Created Tcp Outlet at {} to {}", addresses.remote, peer);
 
         Ok(())
     }
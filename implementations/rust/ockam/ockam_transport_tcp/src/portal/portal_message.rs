@@ -1,4 +1,5 @@
-use ockam_core::Message;
+use ockam_core::compat::vec::Vec;
+use ockam_core::{Decodable, Encodable, Message, Result};
 use serde::{Deserialize, Serialize};
 
 /// A command message type for a Portal
@@ -13,6 +14,74 @@ pub enum PortalMessage {
     Disconnect,
     /// Message with binary payload
     Payload(Vec<u8>),
+    /// Number of additional payloads that the other side can send,
+    /// granted once the previous payloads are written to the TCP stream.
+    /// Only sent if the other side advertised [`PortalCapabilities::CREDITS`]
+    Credit(u32),
+    /// Message to indicate that the TCP stream on the sending side reached EOF.
+    /// No more payloads will be sent, the receiving side shuts down the write half
+    /// of its TCP stream while the other direction stays open.
+    /// Only sent if the other side advertised [`PortalCapabilities::READ_CLOSED`]
+    ReadClosed,
+}
+
+impl PortalMessage {
+    /// Encode a `Ping` or a `Pong` followed by the capabilities of this side of the portal
+    pub fn encode_with_capabilities(&self, capabilities: PortalCapabilities) -> Result<Vec<u8>> {
+        let mut encoded = self.encode()?;
+        encoded.extend(capabilities.encode()?);
+        Ok(encoded)
+    }
+
+    /// Read the capabilities following a `Ping` or a `Pong` in its encoded `payload`.
+    /// Older portals don't send any, they don't support any capability then
+    pub fn decode_capabilities(&self, payload: &[u8]) -> PortalCapabilities {
+        let message_len = match self.encode() {
+            Ok(encoded) => encoded.len(),
+            Err(_) => return PortalCapabilities::default(),
+        };
+        match payload.get(message_len..) {
+            Some(capabilities) if !capabilities.is_empty() => {
+                PortalCapabilities::decode(capabilities).unwrap_or_default()
+            }
+            _ => PortalCapabilities::default(),
+        }
+    }
+}
+
+/// Optional features of a portal, advertised after the `Ping` and `Pong` messages.
+///
+/// Older portals ignore the bytes following these messages, so a feature is only used
+/// once both sides advertised it.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PortalCapabilities(u32);
+
+impl PortalCapabilities {
+    /// The portal grants credits with [`PortalMessage::Credit`] to bound the payloads in flight
+    pub const CREDITS: PortalCapabilities = PortalCapabilities(1);
+
+    /// The portal closes only one direction of the connection with [`PortalMessage::ReadClosed`]
+    pub const READ_CLOSED: PortalCapabilities = PortalCapabilities(2);
+
+    /// Capabilities supported by this implementation
+    pub fn supported() -> Self {
+        Self(Self::CREDITS.0 | Self::READ_CLOSED.0)
+    }
+
+    /// Return true if all the given capabilities are supported
+    pub fn contains(&self, other: PortalCapabilities) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Capabilities supported by both sides
+    pub fn intersection(&self, other: PortalCapabilities) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    /// Remove some capabilities
+    pub fn without(&self, other: PortalCapabilities) -> Self {
+        Self(self.0 & !other.0)
+    }
 }
 
 /// An internal message type for a Portal
@@ -20,7 +89,34 @@ pub enum PortalMessage {
 pub enum PortalInternalMessage {
     /// Connection was dropped
     Disconnect,
+    /// Connection reached EOF, the peer won't write anymore
+    ReadClosed,
 }
 
 ///Maximum allowed size for a payload
 pub const MAX_PAYLOAD_SIZE: usize = 48 * 1024;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capabilities_after_ping() -> Result<()> {
+        let ping = PortalMessage::Ping.encode_with_capabilities(PortalCapabilities::CREDITS)?;
+
+        // older portals decode the Ping and ignore the capabilities
+        assert!(matches!(PortalMessage::decode(&ping)?, PortalMessage::Ping));
+        assert_eq!(
+            PortalMessage::Ping.decode_capabilities(&ping),
+            PortalCapabilities::CREDITS
+        );
+
+        // and don't send any
+        let ping = PortalMessage::Ping.encode()?;
+        assert_eq!(
+            PortalMessage::Ping.decode_capabilities(&ping),
+            PortalCapabilities::default()
+        );
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 use crate::portal::portal_message::MAX_PAYLOAD_SIZE;
-use crate::{PortalInternalMessage, PortalMessage, TcpRegistry};
+use crate::portal::{PortalConnection, PortalCredits};
+use crate::{PortalCapabilities, PortalInternalMessage, PortalMessage, TcpRegistry};
 use ockam_core::compat::vec::Vec;
 use ockam_core::{async_trait, Encodable, LocalMessage, Route, TransportMessage};
 use ockam_core::{route, Address, Processor, Result};
@@ -18,6 +19,9 @@ pub(crate) struct TcpPortalRecvProcessor {
     read_half: OwnedReadHalf,
     sender_address: Address,
     onward_route: Route,
+    credits: PortalCredits,
+    capabilities: PortalCapabilities,
+    connection: PortalConnection,
 }
 
 impl TcpPortalRecvProcessor {
@@ -27,6 +31,9 @@ impl TcpPortalRecvProcessor {
         read_half: OwnedReadHalf,
         sender_address: Address,
         onward_route: Route,
+        credits: PortalCredits,
+        capabilities: PortalCapabilities,
+        connection: PortalConnection,
     ) -> Self {
         Self {
             registry,
@@ -34,8 +41,44 @@ impl TcpPortalRecvProcessor {
             read_half,
             sender_address,
             onward_route,
+            credits,
+            capabilities,
+            connection,
         }
     }
+
+    /// Notify the Sender that no more data will be read.
+    /// The Sender notifies the other side of the portal about a disconnection
+    async fn notify_sender(&self, ctx: &Context, internal_msg: PortalInternalMessage) {
+        if let Err(err) = ctx
+            .send(route![self.sender_address.clone()], internal_msg)
+            .await
+        {
+            warn!(
+                "Error notifying Tcp Portal Sender about closed connection {}",
+                err
+            );
+        }
+    }
+
+    /// Notify the other side of the portal that the TCP stream reached EOF, after the
+    /// last payloads. Without the `ReadClosed` capability the whole portal is disconnected
+    async fn notify_read_closed(&self, ctx: &Context) -> Result<()> {
+        if !self.capabilities.contains(PortalCapabilities::READ_CLOSED) {
+            self.notify_sender(ctx, PortalInternalMessage::Disconnect)
+                .await;
+            return Ok(());
+        }
+
+        self.notify_sender(ctx, PortalInternalMessage::ReadClosed)
+            .await;
+        let msg = TransportMessage::v1(
+            self.onward_route.clone(),
+            self.sender_address.clone(),
+            PortalMessage::ReadClosed.encode()?,
+        );
+        ctx.forward(LocalMessage::new(msg, vec![])).await
+    }
 }
 
 #[async_trait]
@@ -58,41 +101,35 @@ impl Processor for TcpPortalRecvProcessor {
     async fn process(&mut self, ctx: &mut Context) -> Result<bool> {
         self.buf.clear();
 
+        // Stop reading from the stream until the other side catches up
+        self.credits.acquire().await;
+
         let _len = match self.read_half.read_buf(&mut self.buf).await {
             Ok(len) => len,
             Err(err) => {
                 error!("Tcp Portal connection read failed with error: {}", err);
+                self.notify_sender(ctx, PortalInternalMessage::Disconnect)
+                    .await;
                 return Ok(false);
             }
         };
 
         if self.buf.is_empty() {
-            // Notify Sender that connection was closed
-            if let Err(err) = ctx
-                .send(
-                    route![self.sender_address.clone()],
-                    PortalInternalMessage::Disconnect,
-                )
-                .await
-            {
-                warn!(
-                    "Error notifying Tcp Portal Sender about dropped connection {}",
-                    err
-                );
-            }
-
-            let msg = TransportMessage::v1(
-                self.onward_route.clone(),
-                self.sender_address.clone(),
-                PortalMessage::Disconnect.encode()?,
-            );
-            ctx.forward(LocalMessage::new(msg, vec![])).await?;
-
+            // The peer closed its write half, the other direction may still be open
+            self.notify_read_closed(ctx).await?;
             return Ok(false);
         }
 
+        self.connection.add_bytes_in(self.buf.len());
+        self.connection.throttle(self.buf.len()).await;
+
         // Loop just in case buf was extended (should not happen though)
-        for chunk in self.buf.chunks(MAX_PAYLOAD_SIZE) {
+        for (index, chunk) in self.buf.chunks(MAX_PAYLOAD_SIZE).enumerate() {
+            // The credit for the first payload was acquired before reading
+            if index > 0 {
+                self.credits.acquire().await;
+            }
+
             let msg = TransportMessage::v1(
                 self.onward_route.clone(),
                 self.sender_address.clone(),
@@ -1,10 +1,14 @@
 use crate::portal::addresses::{Addresses, PortalType};
-use crate::{portal::TcpPortalRecvProcessor, PortalInternalMessage, PortalMessage, TcpRegistry};
+use crate::portal::{PortalConnection, PortalCredits, TargetConnection};
+use crate::{
+    portal::TcpPortalRecvProcessor, PortalCapabilities, PortalInternalMessage, PortalMessage,
+    TcpRegistry,
+};
 use core::time::Duration;
 use ockam_core::compat::{boxed::Box, net::SocketAddr, sync::Arc};
 use ockam_core::{
     async_trait, AllowAll, AllowOnwardAddresses, AllowSourceAddress, Decodable, DenyAll,
-    IncomingAccessControl, Mailbox, Mailboxes,
+    IncomingAccessControl, Mailbox, Mailboxes, NeutralMessage,
 };
 use ockam_core::{Any, Result, Route, Routed, Worker};
 use ockam_node::{Context, ProcessorBuilder, WorkerBuilder};
@@ -44,10 +48,24 @@ pub(crate) struct TcpPortalWorker {
     remote_route: Option<Route>,
     is_disconnecting: bool,
     portal_type: PortalType,
+    credits: PortalCredits,
+    // capabilities supported by both sides, known once the Ping and Pong were exchanged
+    capabilities: PortalCapabilities,
+    window_size: u32,
+    // payloads written to the TCP stream since the last credits were granted
+    written_payloads: u32,
+    // the TCP stream reached EOF, we won't read from it anymore
+    read_closed: bool,
+    // the other side reached EOF, we shut down the write half of the TCP stream
+    write_closed: bool,
+    // counts this connection in the Outlet target pool while the worker is running
+    _target_connection: Option<TargetConnection>,
+    connection: PortalConnection,
 }
 
 impl TcpPortalWorker {
     /// Start a new `TcpPortalWorker` of type [`TypeName::Inlet`]
+    #[allow(clippy::too_many_arguments)]
     pub(super) async fn start_new_inlet(
         ctx: &Context,
         registry: TcpRegistry,
@@ -56,6 +74,8 @@ impl TcpPortalWorker {
         ping_route: Route,
         addresses: Addresses,
         access_control: Arc<dyn IncomingAccessControl>,
+        window_size: u32,
+        connection: PortalConnection,
     ) -> Result<()> {
         Self::start(
             ctx,
@@ -66,18 +86,27 @@ impl TcpPortalWorker {
             addresses,
             PortalType::Inlet,
             access_control,
+            window_size,
+            PortalCapabilities::default(),
+            None,
+            connection,
         )
         .await
     }
 
     /// Start a new `TcpPortalWorker` of type [`TypeName::Outlet`]
+    #[allow(clippy::too_many_arguments)]
     pub(super) async fn start_new_outlet(
         ctx: &Context,
         registry: TcpRegistry,
         peer: SocketAddr,
+        target_connection: TargetConnection,
+        connection: PortalConnection,
         pong_route: Route,
         addresses: Addresses,
         access_control: Arc<dyn IncomingAccessControl>,
+        window_size: u32,
+        inlet_capabilities: PortalCapabilities,
     ) -> Result<()> {
         Self::start(
             ctx,
@@ -88,6 +117,10 @@ impl TcpPortalWorker {
             addresses,
             PortalType::Outlet,
             access_control,
+            window_size,
+            inlet_capabilities.intersection(PortalCapabilities::supported()),
+            Some(target_connection),
+            connection,
         )
         .await
     }
@@ -103,6 +136,10 @@ impl TcpPortalWorker {
         addresses: Addresses,
         portal_type: PortalType,
         access_control: Arc<dyn IncomingAccessControl>,
+        window_size: u32,
+        capabilities: PortalCapabilities,
+        target_connection: Option<TargetConnection>,
+        connection: PortalConnection,
     ) -> Result<()> {
         info!(
             "Creating new {:?} at internal: {}, remote: {}",
@@ -129,6 +166,14 @@ impl TcpPortalWorker {
             remote_route: None,
             is_disconnecting: false,
             portal_type,
+            credits: PortalCredits::default(),
+            capabilities,
+            window_size,
+            written_payloads: 0,
+            read_closed: false,
+            write_closed: false,
+            _target_connection: target_connection,
+            connection,
         };
 
         let internal_mailbox = Mailbox::new(
@@ -173,6 +218,9 @@ impl TcpPortalWorker {
                 rx,
                 self.addresses.internal.clone(),
                 onward_route,
+                self.credits.clone(),
+                self.capabilities,
+                self.connection.clone(),
             );
 
             ProcessorBuilder::new(receiver)
@@ -272,11 +320,57 @@ impl TcpPortalWorker {
         Ok(())
     }
 
+    /// Stop the portal once both directions of the TCP stream are closed
+    async fn stop_if_closed(&mut self, ctx: &Context) -> Result<()> {
+        if !(self.read_closed && self.write_closed) {
+            return Ok(());
+        }
+
+        // Both sides already know that the connection is closed, the receiver stopped itself
+        self.is_disconnecting = true;
+        ctx.stop_worker(self.addresses.internal.clone()).await?;
+
+        info!(
+            "{:?} at: {} stopped after both directions were closed",
+            self.portal_type.str(),
+            self.addresses.internal
+        );
+
+        Ok(())
+    }
+
+    /// Shut down the write half of the TCP stream after the other side reached EOF
+    async fn close_write_half(&mut self, ctx: &Context) -> Result<()> {
+        if let Some(tx) = &mut self.write_half {
+            if let Err(err) = tx.shutdown().await {
+                warn!(
+                    "Failed to shut down the connection to peer {} with error: {}",
+                    self.peer, err
+                );
+                return self
+                    .start_disconnection(ctx, DisconnectionReason::FailedTx)
+                    .await;
+            }
+        } else {
+            return Err(TransportError::PortalInvalidState.into());
+        }
+
+        debug!(
+            "{:?} at: {} closed the write half of the connection",
+            self.portal_type.str(),
+            self.addresses.internal
+        );
+
+        self.write_closed = true;
+        self.stop_if_closed(ctx).await
+    }
+
     async fn handle_send_ping(&self, ctx: &Context, ping_route: Route) -> Result<State> {
         // Force creation of Outlet on the other side
+        let ping = PortalMessage::Ping.encode_with_capabilities(PortalCapabilities::supported())?;
         ctx.send_from_address(
             ping_route,
-            PortalMessage::Ping,
+            NeutralMessage::from(ping),
             self.addresses.remote.clone(),
         )
         .await?;
@@ -288,13 +382,22 @@ impl TcpPortalWorker {
 
     async fn handle_send_pong(&mut self, ctx: &Context, pong_route: Route) -> Result<State> {
         // Respond to Inlet
+        let pong = PortalMessage::Pong.encode_with_capabilities(PortalCapabilities::supported())?;
         ctx.send_from_address(
             pong_route.clone(),
-            PortalMessage::Pong,
+            NeutralMessage::from(pong),
             self.addresses.remote.clone(),
         )
         .await?;
 
+        // The Inlet sent its capabilities with the Ping, it knows from the Pong
+        // that the credits are enforced before it starts reading its TCP stream
+        if self.capabilities.contains(PortalCapabilities::CREDITS) {
+            self.credits.enable();
+            self.send_credits(ctx, &pong_route, self.window_size)
+                .await?;
+        }
+
         if self.write_half.is_none() {
             let stream = TcpStream::connect(self.peer)
                 .await
@@ -316,6 +419,35 @@ impl TcpPortalWorker {
         self.remote_route = Some(pong_route);
         Ok(State::Initialized)
     }
+
+    /// Grant credits to the other side to send more payloads
+    async fn send_credits(&self, ctx: &Context, route: &Route, credits: u32) -> Result<()> {
+        ctx.send_from_address(
+            route.clone(),
+            PortalMessage::Credit(credits),
+            self.addresses.remote.clone(),
+        )
+        .await
+    }
+
+    /// Count a payload written to the TCP stream, and grant new credits
+    /// to the other side once half of the window was written
+    async fn acknowledge_payload(&mut self, ctx: &Context) -> Result<()> {
+        self.written_payloads += 1;
+        if self.written_payloads < (self.window_size / 2).max(1) {
+            return Ok(());
+        }
+
+        let credits = core::mem::take(&mut self.written_payloads);
+        if !self.capabilities.contains(PortalCapabilities::CREDITS) {
+            return Ok(());
+        }
+        if let Some(remote_route) = &self.remote_route {
+            self.send_credits(ctx, remote_route, credits).await?;
+        }
+
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -345,6 +477,7 @@ impl Worker for TcpPortalWorker {
 
     async fn shutdown(&mut self, _ctx: &mut Self::Context) -> Result<()> {
         self.registry.remove_portal_worker(&self.addresses.remote);
+        self.connection.close();
 
         Ok(())
     }
@@ -375,17 +508,33 @@ impl Worker for TcpPortalWorker {
                     return Err(TransportError::PortalInvalidState.into());
                 }
 
-                let msg = PortalMessage::decode(msg.payload())?;
-
-                if let PortalMessage::Pong = msg {
-                } else {
-                    return Err(TransportError::Protocol.into());
+                match PortalMessage::decode(msg.payload())? {
+                    PortalMessage::Pong => {
+                        self.capabilities = PortalMessage::Pong
+                            .decode_capabilities(msg.payload())
+                            .intersection(PortalCapabilities::supported());
+                    }
+                    // The Outlet refused the connection
+                    PortalMessage::Disconnect => {
+                        return self
+                            .start_disconnection(ctx, DisconnectionReason::Remote)
+                            .await;
+                    }
+                    _ => return Err(TransportError::Protocol.into()),
                 }
 
+                // The Outlet grants its initial credits right after the Pong
+                if self.capabilities.contains(PortalCapabilities::CREDITS) {
+                    self.credits.enable();
+                }
                 self.start_receiver(ctx, return_route.clone()).await?;
 
                 debug!("Inlet at: {} received pong", self.addresses.internal);
 
+                if self.capabilities.contains(PortalCapabilities::CREDITS) {
+                    self.send_credits(ctx, &return_route, self.window_size)
+                        .await?;
+                }
                 self.remote_route = Some(return_route);
                 self.state = State::Initialized;
             }
@@ -409,6 +558,15 @@ impl Worker for TcpPortalWorker {
                             self.start_disconnection(ctx, DisconnectionReason::FailedRx)
                                 .await?;
                         }
+                        PortalInternalMessage::ReadClosed => {
+                            debug!(
+                                "Tcp stream reached EOF for {:?} at: {}",
+                                self.portal_type.str(),
+                                self.addresses.internal
+                            );
+                            self.read_closed = true;
+                            self.stop_if_closed(ctx).await?;
+                        }
                     }
                 } else {
                     trace!(
@@ -423,8 +581,12 @@ impl Worker for TcpPortalWorker {
                     match msg {
                         PortalMessage::Payload(payload) => {
                             if let Some(tx) = &mut self.write_half {
+                                self.connection.throttle(payload.len()).await;
                                 match tx.write_all(&payload).await {
-                                    Ok(()) => {}
+                                    Ok(()) => {
+                                        self.connection.add_bytes_out(payload.len());
+                                        self.acknowledge_payload(ctx).await?
+                                    }
                                     Err(err) => {
                                         warn!(
                                             "Failed to send message to peer {} with error: {}",
@@ -445,6 +607,8 @@ impl Worker for TcpPortalWorker {
                             self.start_disconnection(ctx, DisconnectionReason::Remote)
                                 .await?;
                         }
+                        PortalMessage::Credit(credits) => self.credits.grant(credits),
+                        PortalMessage::ReadClosed => self.close_write_half(ctx).await?,
                         PortalMessage::Ping | PortalMessage::Pong => {
                             return Err(TransportError::Protocol.into());
                         }
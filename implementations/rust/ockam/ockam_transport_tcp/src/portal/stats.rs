@@ -0,0 +1,229 @@
+use crate::{TcpPortalConnectionStats, TcpPortalStats};
+use core::sync::atomic::{AtomicU64, Ordering};
+use core::time::Duration;
+use ockam_core::compat::sync::{Arc, Mutex};
+use std::net::SocketAddr;
+use std::time::Instant;
+
+/// Limits of a portal, set with the Inlet and Outlet options
+#[derive(Clone, Debug, Default)]
+pub(crate) struct PortalLimits {
+    pub(crate) max_connections: Option<usize>,
+    pub(crate) max_connections_per_second: Option<u32>,
+    pub(crate) max_bytes_per_second: Option<u64>,
+}
+
+/// Counters and limits shared by all the connections of a portal
+///
+/// They are owned by the Inlet or Outlet listener, which admits the new connections,
+/// while the portal workers and receivers account for the bytes of each connection.
+#[derive(Debug, Clone)]
+pub(crate) struct PortalStats {
+    inner: Arc<PortalStatsInner>,
+}
+
+#[derive(Debug)]
+struct PortalStatsInner {
+    max_connections: Option<usize>,
+    connections_rate: Option<RateLimiter>,
+    bytes_rate: Option<RateLimiter>,
+    bytes_in: AtomicU64,
+    bytes_out: AtomicU64,
+    connections_opened: AtomicU64,
+    connections_closed: AtomicU64,
+    connections_rejected: AtomicU64,
+    connections: Mutex<Vec<Arc<ConnectionCounters>>>,
+}
+
+#[derive(Debug)]
+struct ConnectionCounters {
+    peer: SocketAddr,
+    bytes_in: AtomicU64,
+    bytes_out: AtomicU64,
+}
+
+impl PortalStats {
+    pub(crate) fn new(limits: &PortalLimits) -> Self {
+        Self {
+            inner: Arc::new(PortalStatsInner {
+                max_connections: limits.max_connections,
+                connections_rate: limits
+                    .max_connections_per_second
+                    .map(|rate| RateLimiter::new(rate as u64)),
+                bytes_rate: limits.max_bytes_per_second.map(RateLimiter::new),
+                bytes_in: Default::default(),
+                bytes_out: Default::default(),
+                connections_opened: Default::default(),
+                connections_closed: Default::default(),
+                connections_rejected: Default::default(),
+                connections: Default::default(),
+            }),
+        }
+    }
+
+    /// Admit a new connection from/to `peer` if the limits allow it. The connection
+    /// is counted until the returned [`PortalConnection`] is dropped
+    pub(crate) fn open_connection(&self, peer: SocketAddr) -> Option<PortalConnection> {
+        let mut connections = self.inner.connections.lock().unwrap();
+
+        let admitted = self
+            .inner
+            .max_connections
+            .map_or(true, |max| connections.len() < max)
+            && self
+                .inner
+                .connections_rate
+                .as_ref()
+                .map_or(true, |rate| rate.try_acquire(1));
+        if !admitted {
+            self.inner
+                .connections_rejected
+                .fetch_add(1, Ordering::Relaxed);
+            return None;
+        }
+
+        let counters = Arc::new(ConnectionCounters {
+            peer,
+            bytes_in: Default::default(),
+            bytes_out: Default::default(),
+        });
+        connections.push(counters.clone());
+        self.inner
+            .connections_opened
+            .fetch_add(1, Ordering::Relaxed);
+
+        Some(PortalConnection {
+            stats: self.clone(),
+            counters,
+        })
+    }
+
+    /// Current counters of the portal
+    pub(crate) fn snapshot(&self) -> TcpPortalStats {
+        let connections = self
+            .inner
+            .connections
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|c| {
+                TcpPortalConnectionStats::new(
+                    c.peer,
+                    c.bytes_in.load(Ordering::Relaxed),
+                    c.bytes_out.load(Ordering::Relaxed),
+                )
+            })
+            .collect();
+
+        TcpPortalStats::new(
+            self.inner.bytes_in.load(Ordering::Relaxed),
+            self.inner.bytes_out.load(Ordering::Relaxed),
+            self.inner.connections_opened.load(Ordering::Relaxed),
+            self.inner.connections_closed.load(Ordering::Relaxed),
+            self.inner.connections_rejected.load(Ordering::Relaxed),
+            connections,
+        )
+    }
+}
+
+/// A connection of a portal, used to account for its bytes
+#[derive(Debug, Clone)]
+pub(crate) struct PortalConnection {
+    stats: PortalStats,
+    counters: Arc<ConnectionCounters>,
+}
+
+impl PortalConnection {
+    /// Wait until the portal throughput limit allows to transfer `len` more bytes
+    pub(crate) async fn throttle(&self, len: usize) {
+        if let Some(rate) = &self.stats.inner.bytes_rate {
+            let wait = rate.reserve(len as u64);
+            if !wait.is_zero() {
+                tokio::time::sleep(wait).await;
+            }
+        }
+    }
+
+    /// Count bytes read from the TCP stream
+    pub(crate) fn add_bytes_in(&self, len: usize) {
+        self.counters
+            .bytes_in
+            .fetch_add(len as u64, Ordering::Relaxed);
+        self.stats
+            .inner
+            .bytes_in
+            .fetch_add(len as u64, Ordering::Relaxed);
+    }
+
+    /// Count bytes written to the TCP stream
+    pub(crate) fn add_bytes_out(&self, len: usize) {
+        self.counters
+            .bytes_out
+            .fetch_add(len as u64, Ordering::Relaxed);
+        self.stats
+            .inner
+            .bytes_out
+            .fetch_add(len as u64, Ordering::Relaxed);
+    }
+
+    /// Remove the connection from the portal once it is closed
+    pub(crate) fn close(&self) {
+        let mut connections = self.stats.inner.connections.lock().unwrap();
+        let len = connections.len();
+        connections.retain(|c| !Arc::ptr_eq(c, &self.counters));
+        if connections.len() < len {
+            self.stats
+                .inner
+                .connections_closed
+                .fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Token bucket allowing `rate` units per second, with bursts of up to one second
+#[derive(Debug)]
+struct RateLimiter {
+    rate: u64,
+    state: Mutex<(f64, Instant)>,
+}
+
+impl RateLimiter {
+    fn new(rate: u64) -> Self {
+        let rate = rate.max(1);
+        Self {
+            rate,
+            state: Mutex::new((rate as f64, Instant::now())),
+        }
+    }
+
+    fn refill(&self, state: &mut (f64, Instant)) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(state.1).as_secs_f64();
+        state.0 = (state.0 + elapsed * self.rate as f64).min(self.rate as f64);
+        state.1 = now;
+    }
+
+    /// Take `amount` units if they are available
+    fn try_acquire(&self, amount: u64) -> bool {
+        let mut state = self.state.lock().unwrap();
+        self.refill(&mut state);
+        if state.0 < amount as f64 {
+            return false;
+        }
+        state.0 -= amount as f64;
+        true
+    }
+
+    /// Take `amount` units, possibly more than available, and return how long
+    /// to wait until they are paid back
+    fn reserve(&self, amount: u64) -> Duration {
+        let mut state = self.state.lock().unwrap();
+        self.refill(&mut state);
+        state.0 -= amount as f64;
+        if state.0 >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-state.0 / self.rate as f64)
+        }
+    }
+}
@@ -0,0 +1,279 @@
+use crate::transport::common::resolve_peer_addresses;
+use crate::{LoadBalancing, TcpOutletTarget};
+use core::sync::atomic::{AtomicUsize, Ordering};
+use ockam_core::compat::sync::{Arc, Mutex};
+use ockam_core::Result;
+use ockam_transport_core::TransportError;
+use std::net::SocketAddr;
+use tracing::{info, warn};
+
+/// Targets of a TCP Outlet
+///
+/// The pool is shared between the Outlet listener, which chooses the target of
+/// each new connection, and the processor running the health checks and DNS refreshes.
+#[derive(Debug, Clone)]
+pub(crate) struct TargetPool {
+    peers: Arc<Vec<String>>,
+    load_balancing: LoadBalancing,
+    state: Arc<Mutex<PoolState>>,
+}
+
+#[derive(Debug)]
+struct PoolState {
+    targets: Vec<Target>,
+    next: usize,
+}
+
+#[derive(Debug)]
+struct Target {
+    address: SocketAddr,
+    healthy: bool,
+    failures: u32,
+    connections: Arc<AtomicUsize>,
+}
+
+impl Target {
+    fn new(address: SocketAddr) -> Self {
+        Self {
+            address,
+            healthy: true,
+            failures: 0,
+            connections: Default::default(),
+        }
+    }
+}
+
+/// A portal connection to a target, counted until it is dropped
+pub(crate) struct TargetConnection {
+    connections: Arc<AtomicUsize>,
+}
+
+impl Drop for TargetConnection {
+    fn drop(&mut self) {
+        self.connections.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl TargetPool {
+    /// Resolve the peers of the pool. If `all_addresses` is false each peer is resolved to a single
+    /// address, otherwise every address returned by the DNS is a target
+    pub(crate) async fn create(
+        peers: Vec<String>,
+        load_balancing: LoadBalancing,
+        all_addresses: bool,
+    ) -> Result<Self> {
+        let mut addresses = vec![];
+        for peer in &peers {
+            let resolved = resolve_peer_addresses(peer).await?;
+            if all_addresses {
+                addresses.extend(resolved);
+            } else {
+                // Prefer ip4, as `resolve_peer` does
+                let address = resolved
+                    .iter()
+                    .find(|x| x.is_ipv4())
+                    .or_else(|| resolved.first())
+                    .ok_or(TransportError::InvalidAddress)?;
+                addresses.push(*address);
+            }
+        }
+
+        if addresses.is_empty() {
+            return Err(TransportError::InvalidAddress.into());
+        }
+        addresses.sort();
+        addresses.dedup();
+
+        let state = PoolState {
+            targets: addresses.into_iter().map(Target::new).collect(),
+            next: 0,
+        };
+
+        Ok(Self {
+            peers: Arc::new(peers),
+            load_balancing,
+            state: Arc::new(Mutex::new(state)),
+        })
+    }
+
+    /// Choose the target of a new connection. Ejected targets are skipped, unless all
+    /// the targets were ejected, in which case they are all used
+    pub(crate) fn select(&self) -> Result<(SocketAddr, TargetConnection)> {
+        let mut state = self.state.lock().unwrap();
+
+        let mut candidates: Vec<usize> = (0..state.targets.len())
+            .filter(|&i| state.targets[i].healthy)
+            .collect();
+        if candidates.is_empty() {
+            warn!("All the targets of the outlet are unhealthy, using them all");
+            candidates = (0..state.targets.len()).collect();
+        }
+        if candidates.is_empty() {
+            return Err(TransportError::PeerNotFound.into());
+        }
+
+        // Start from a different candidate each time, so that ties are balanced as well
+        let start = state.next % candidates.len();
+        state.next = state.next.wrapping_add(1);
+        candidates.rotate_left(start);
+
+        let index = match self.load_balancing {
+            LoadBalancing::RoundRobin => candidates[0],
+            LoadBalancing::LeastConnections => candidates
+                .into_iter()
+                .min_by_key(|&i| state.targets[i].connections.load(Ordering::Relaxed))
+                .unwrap_or_default(),
+        };
+
+        let target = &state.targets[index];
+        target.connections.fetch_add(1, Ordering::Relaxed);
+
+        Ok((
+            target.address,
+            TargetConnection {
+                connections: target.connections.clone(),
+            },
+        ))
+    }
+
+    /// Resolve the peers again. Known targets keep their state, and the previous
+    /// targets are kept if the resolution doesn't return any address
+    pub(crate) async fn refresh(&self) {
+        let mut addresses = vec![];
+        for peer in self.peers.iter() {
+            match resolve_peer_addresses(peer).await {
+                Ok(resolved) => addresses.extend(resolved),
+                Err(err) => warn!("Failed to resolve the outlet target {}: {}", peer, err),
+            }
+        }
+        addresses.sort();
+        addresses.dedup();
+        if addresses.is_empty() {
+            return;
+        }
+
+        let mut state = self.state.lock().unwrap();
+        let mut previous = core::mem::take(&mut state.targets);
+        for address in addresses {
+            match previous.iter().position(|t| t.address == address) {
+                Some(i) => state.targets.push(previous.swap_remove(i)),
+                None => {
+                    info!("Added the outlet target {}", address);
+                    state.targets.push(Target::new(address))
+                }
+            }
+        }
+        for target in previous {
+            info!("Removed the outlet target {}", target.address);
+        }
+    }
+
+    /// Addresses of all the targets
+    pub(crate) fn addresses(&self) -> Vec<SocketAddr> {
+        let state = self.state.lock().unwrap();
+        state.targets.iter().map(|t| t.address).collect()
+    }
+
+    /// Record the result of a health check. A target is ejected after `failure_threshold`
+    /// consecutive failures, and used again after a successful check
+    pub(crate) fn report(&self, address: SocketAddr, success: bool, failure_threshold: u32) {
+        let mut state = self.state.lock().unwrap();
+        let target = match state.targets.iter_mut().find(|t| t.address == address) {
+            Some(target) => target,
+            None => return,
+        };
+
+        if success {
+            if !target.healthy {
+                info!("The outlet target {} is healthy again", address);
+            }
+            target.failures = 0;
+            target.healthy = true;
+        } else {
+            target.failures = target.failures.saturating_add(1);
+            if target.healthy && target.failures >= failure_threshold {
+                warn!(
+                    "Ejecting the outlet target {} after {} failed health checks",
+                    address, target.failures
+                );
+                target.healthy = false;
+            }
+        }
+    }
+
+    /// Current status of the targets
+    pub(crate) fn targets(&self) -> Vec<TcpOutletTarget> {
+        let state = self.state.lock().unwrap();
+        state
+            .targets
+            .iter()
+            .map(|t| {
+                TcpOutletTarget::new(t.address, t.healthy, t.connections.load(Ordering::Relaxed))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::portal::TargetPool;
+    use crate::LoadBalancing;
+    use ockam_core::Result;
+
+    async fn pool(load_balancing: LoadBalancing) -> Result<TargetPool> {
+        TargetPool::create(
+            vec!["127.0.0.1:1001".into(), "127.0.0.1:1002".into()],
+            load_balancing,
+            false,
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_round_robin() -> Result<()> {
+        let pool = pool(LoadBalancing::RoundRobin).await?;
+
+        let (first, _c1) = pool.select()?;
+        let (second, _c2) = pool.select()?;
+        let (third, _c3) = pool.select()?;
+        assert_ne!(first, second);
+        assert_eq!(first, third);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_least_connections() -> Result<()> {
+        let pool = pool(LoadBalancing::LeastConnections).await?;
+
+        let (first, c1) = pool.select()?;
+        let (second, _c2) = pool.select()?;
+        assert_ne!(first, second);
+
+        // The first target has no connections anymore
+        drop(c1);
+        let (third, _c3) = pool.select()?;
+        assert_eq!(first, third);
+
+        let targets = pool.targets();
+        assert!(targets.iter().all(|t| t.connections() == 1));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_ejection() -> Result<()> {
+        let pool = pool(LoadBalancing::RoundRobin).await?;
+        let failing = pool.addresses()[0];
+
+        pool.report(failing, false, 2);
+        assert!(pool.targets().iter().all(|t| t.is_healthy()));
+
+        pool.report(failing, false, 2);
+        for _ in 0..4 {
+            assert_ne!(pool.select()?.0, failing);
+        }
+
+        pool.report(failing, true, 2);
+        assert!(pool.targets().iter().all(|t| t.is_healthy()));
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+use crate::portal::TargetPool;
+use crate::HealthCheckOptions;
+use core::time::Duration;
+use ockam_core::{async_trait, Address, DenyAll, Processor, Result};
+use ockam_node::{Context, ProcessorBuilder};
+use std::time::Instant;
+use tokio::net::TcpStream;
+use tracing::debug;
+
+/// A TCP Outlet target pool processor
+///
+/// Runs the active health checks of the targets of an Outlet and refreshes
+/// their DNS resolution. It is started by `TcpOutletListenWorker` when one
+/// of them is configured and stopped along with it.
+pub(crate) struct TargetPoolProcessor {
+    pool: TargetPool,
+    health_check: Option<HealthCheckOptions>,
+    dns_refresh_interval: Option<Duration>,
+    next_health_check: Instant,
+    next_dns_refresh: Instant,
+}
+
+impl TargetPoolProcessor {
+    /// Start a new `TargetPoolProcessor` if health checks or DNS refreshes are configured
+    pub(crate) async fn start(
+        ctx: &Context,
+        address: Address,
+        pool: TargetPool,
+        health_check: Option<HealthCheckOptions>,
+        dns_refresh_interval: Option<Duration>,
+    ) -> Result<bool> {
+        if health_check.is_none() && dns_refresh_interval.is_none() {
+            return Ok(false);
+        }
+
+        let now = Instant::now();
+        let processor = Self {
+            pool,
+            health_check,
+            dns_refresh_interval,
+            next_health_check: now,
+            next_dns_refresh: now,
+        };
+
+        ProcessorBuilder::new(processor)
+            .with_address(address)
+            .with_incoming_access_control(DenyAll)
+            .with_outgoing_access_control(DenyAll)
+            .start(ctx)
+            .await?;
+
+        Ok(true)
+    }
+
+    async fn check_targets(&self, health_check: &HealthCheckOptions) {
+        for address in self.pool.addresses() {
+            let success = matches!(
+                tokio::time::timeout(health_check.timeout, TcpStream::connect(address)).await,
+                Ok(Ok(_))
+            );
+            debug!(
+                "Health check of the outlet target {}: {}",
+                address,
+                if success { "success" } else { "failure" }
+            );
+            self.pool
+                .report(address, success, health_check.failure_threshold);
+        }
+    }
+}
+
+#[async_trait]
+impl Processor for TargetPoolProcessor {
+    type Context = Context;
+
+    async fn process(&mut self, ctx: &mut Self::Context) -> Result<bool> {
+        let next = match (&self.health_check, self.dns_refresh_interval) {
+            (Some(_), Some(_)) => self.next_health_check.min(self.next_dns_refresh),
+            (Some(_), None) => self.next_health_check,
+            (None, _) => self.next_dns_refresh,
+        };
+        ctx.sleep(next.saturating_duration_since(Instant::now()))
+            .await;
+
+        let now = Instant::now();
+        if let Some(interval) = self.dns_refresh_interval {
+            if now >= self.next_dns_refresh {
+                self.pool.refresh().await;
+                self.next_dns_refresh = now + interval;
+            }
+        }
+        if let Some(health_check) = &self.health_check {
+            if now >= self.next_health_check {
+                self.check_targets(health_check).await;
+                self.next_health_check = Instant::now() + health_check.interval;
+            }
+        }
+
+        Ok(true)
+    }
+}
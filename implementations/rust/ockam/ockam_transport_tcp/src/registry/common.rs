@@ -1,3 +1,4 @@
+use crate::portal::{PortalStats, TargetPool};
 use core::fmt;
 use core::fmt::Formatter;
 use ockam_core::flow_control::FlowControlId;
@@ -157,3 +158,186 @@ impl TcpListenerInfo {
         &self.flow_control_id
     }
 }
+
+/// Status of a target of a Tcp Outlet
+#[derive(Debug, Clone)]
+pub struct TcpOutletTarget {
+    socket_address: SocketAddr,
+    healthy: bool,
+    connections: usize,
+}
+
+impl TcpOutletTarget {
+    /// Constructor
+    pub fn new(socket_address: SocketAddr, healthy: bool, connections: usize) -> Self {
+        Self {
+            socket_address,
+            healthy,
+            connections,
+        }
+    }
+
+    /// Corresponding socket address
+    pub fn socket_address(&self) -> SocketAddr {
+        self.socket_address
+    }
+    /// False if the target was ejected after failing its health checks
+    pub fn is_healthy(&self) -> bool {
+        self.healthy
+    }
+    /// Number of active portal connections to the target
+    pub fn connections(&self) -> usize {
+        self.connections
+    }
+}
+
+/// Information about specific Tcp Outlet
+#[derive(Debug, Clone)]
+pub struct TcpOutletInfo {
+    address: Address,
+    pool: TargetPool,
+    stats: PortalStats,
+}
+
+impl TcpOutletInfo {
+    pub(crate) fn new(address: Address, pool: TargetPool, stats: PortalStats) -> Self {
+        Self {
+            address,
+            pool,
+            stats,
+        }
+    }
+
+    /// Address of the Outlet listener Worker
+    pub fn address(&self) -> &Address {
+        &self.address
+    }
+    /// Current status of the Outlet targets
+    pub fn targets(&self) -> Vec<TcpOutletTarget> {
+        self.pool.targets()
+    }
+    /// Current counters of the Outlet
+    pub fn stats(&self) -> TcpPortalStats {
+        self.stats.snapshot()
+    }
+}
+
+/// Counters of a Tcp portal connection
+#[derive(Debug, Clone)]
+pub struct TcpPortalConnectionStats {
+    peer: SocketAddr,
+    bytes_in: u64,
+    bytes_out: u64,
+}
+
+impl TcpPortalConnectionStats {
+    /// Constructor
+    pub fn new(peer: SocketAddr, bytes_in: u64, bytes_out: u64) -> Self {
+        Self {
+            peer,
+            bytes_in,
+            bytes_out,
+        }
+    }
+
+    /// Socket address of the peer of the connection
+    pub fn peer(&self) -> SocketAddr {
+        self.peer
+    }
+    /// Bytes read from the TCP connection
+    pub fn bytes_in(&self) -> u64 {
+        self.bytes_in
+    }
+    /// Bytes written to the TCP connection
+    pub fn bytes_out(&self) -> u64 {
+        self.bytes_out
+    }
+}
+
+/// Counters of a Tcp Inlet or Outlet, for all its connections
+#[derive(Debug, Clone)]
+pub struct TcpPortalStats {
+    bytes_in: u64,
+    bytes_out: u64,
+    connections_opened: u64,
+    connections_closed: u64,
+    connections_rejected: u64,
+    connections: Vec<TcpPortalConnectionStats>,
+}
+
+impl TcpPortalStats {
+    /// Constructor
+    pub fn new(
+        bytes_in: u64,
+        bytes_out: u64,
+        connections_opened: u64,
+        connections_closed: u64,
+        connections_rejected: u64,
+        connections: Vec<TcpPortalConnectionStats>,
+    ) -> Self {
+        Self {
+            bytes_in,
+            bytes_out,
+            connections_opened,
+            connections_closed,
+            connections_rejected,
+            connections,
+        }
+    }
+
+    /// Bytes read from the TCP connections
+    pub fn bytes_in(&self) -> u64 {
+        self.bytes_in
+    }
+    /// Bytes written to the TCP connections
+    pub fn bytes_out(&self) -> u64 {
+        self.bytes_out
+    }
+    /// Number of connections opened since the portal was created
+    pub fn connections_opened(&self) -> u64 {
+        self.connections_opened
+    }
+    /// Number of connections closed since the portal was created
+    pub fn connections_closed(&self) -> u64 {
+        self.connections_closed
+    }
+    /// Number of connections rejected because of the portal limits
+    pub fn connections_rejected(&self) -> u64 {
+        self.connections_rejected
+    }
+    /// Counters of the active connections
+    pub fn connections(&self) -> &[TcpPortalConnectionStats] {
+        &self.connections
+    }
+}
+
+/// Information about specific Tcp Inlet
+#[derive(Debug, Clone)]
+pub struct TcpInletInfo {
+    address: Address,
+    socket_address: SocketAddr,
+    stats: PortalStats,
+}
+
+impl TcpInletInfo {
+    pub(crate) fn new(address: Address, socket_address: SocketAddr, stats: PortalStats) -> Self {
+        Self {
+            address,
+            socket_address,
+            stats,
+        }
+    }
+
+    /// Address of the Inlet listener Processor
+    pub fn address(&self) -> &Address {
+        &self.address
+    }
+    /// Socket address the Inlet listens on
+    pub fn socket_address(&self) -> SocketAddr {
+        self.socket_address
+    }
+    /// Current counters of the Inlet
+    pub fn stats(&self) -> TcpPortalStats {
+        self.stats.snapshot()
+    }
+}
@@ -1,4 +1,6 @@
-use crate::{TcpListenerInfo, TcpReceiverInfo, TcpRegistry, TcpSenderInfo};
+use crate::{
+    TcpInletInfo, TcpListenerInfo, TcpOutletInfo, TcpReceiverInfo, TcpRegistry, TcpSenderInfo,
+};
 use ockam_core::Address;
 
 impl TcpRegistry {
@@ -22,9 +24,9 @@ impl TcpRegistry {
             lock.remove_portal_receiver_processor(addr);
         }
     }
-    pub(crate) fn add_inlet_listener_processor(&self, addr: &Address) {
+    pub(crate) fn add_inlet_listener_processor(&self, info: TcpInletInfo) {
         if let Ok(mut lock) = self.registry.write() {
-            lock.add_inlet_listener_processor(addr);
+            lock.add_inlet_listener_processor(info);
         }
     }
     pub(crate) fn remove_inlet_listener_processor(&self, addr: &Address) {
@@ -32,9 +34,9 @@ impl TcpRegistry {
             lock.remove_inlet_listener_processor(addr);
         }
     }
-    pub(crate) fn add_outlet_listener_worker(&self, addr: &Address) {
+    pub(crate) fn add_outlet_listener_worker(&self, info: TcpOutletInfo) {
         if let Ok(mut lock) = self.registry.write() {
-            lock.add_outlet_listener_worker(addr);
+            lock.add_outlet_listener_worker(info);
         }
     }
     pub(crate) fn remove_outlet_listener_worker(&self, addr: &Address) {
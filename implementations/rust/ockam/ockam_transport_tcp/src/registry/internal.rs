@@ -1,12 +1,12 @@
-use crate::{TcpListenerInfo, TcpReceiverInfo, TcpSenderInfo};
+use crate::{TcpInletInfo, TcpListenerInfo, TcpOutletInfo, TcpReceiverInfo, TcpSenderInfo};
 use ockam_core::Address;
 
 #[derive(Default)]
 pub(super) struct InternalRegistry {
     pub(super) portal_workers: Vec<Address>,
     pub(super) portal_receiver_processors: Vec<Address>,
-    pub(super) inlet_listener_processors: Vec<Address>,
-    pub(super) outlet_listener_workers: Vec<Address>,
+    pub(super) inlet_listener_processors: Vec<TcpInletInfo>,
+    pub(super) outlet_listener_workers: Vec<TcpOutletInfo>,
     pub(super) listener_processors: Vec<TcpListenerInfo>,
     pub(super) sender_workers: Vec<TcpSenderInfo>,
     pub(super) receiver_processors: Vec<TcpReceiverInfo>,
@@ -25,17 +25,18 @@ impl InternalRegistry {
     pub(super) fn remove_portal_receiver_processor(&mut self, addr: &Address) {
         self.portal_receiver_processors.retain(|x| x != addr);
     }
-    pub(super) fn add_inlet_listener_processor(&mut self, addr: &Address) {
-        self.inlet_listener_processors.push(addr.clone())
+    pub(super) fn add_inlet_listener_processor(&mut self, info: TcpInletInfo) {
+        self.inlet_listener_processors.push(info)
     }
     pub(super) fn remove_inlet_listener_processor(&mut self, addr: &Address) {
-        self.inlet_listener_processors.retain(|x| x != addr);
+        self.inlet_listener_processors
+            .retain(|x| x.address() != addr);
     }
-    pub(super) fn add_outlet_listener_worker(&mut self, addr: &Address) {
-        self.outlet_listener_workers.push(addr.clone())
+    pub(super) fn add_outlet_listener_worker(&mut self, info: TcpOutletInfo) {
+        self.outlet_listener_workers.push(info)
     }
     pub(super) fn remove_outlet_listener_worker(&mut self, addr: &Address) {
-        self.outlet_listener_workers.retain(|x| x != addr);
+        self.outlet_listener_workers.retain(|x| x.address() != addr);
     }
     pub(super) fn add_listener_processor(&mut self, info: TcpListenerInfo) {
         self.listener_processors.push(info)
@@ -1,5 +1,5 @@
 use crate::registry::internal::InternalRegistry;
-use crate::{TcpListenerInfo, TcpReceiverInfo, TcpSenderInfo};
+use crate::{TcpInletInfo, TcpListenerInfo, TcpOutletInfo, TcpReceiverInfo, TcpSenderInfo};
 use ockam_core::compat::sync::{Arc, RwLock};
 
 /// Registry of all active workers and processors in TCP Transport to ease their lifecycle management
@@ -23,4 +23,22 @@ impl TcpRegistry {
     pub fn get_all_listeners(&self) -> Vec<TcpListenerInfo> {
         self.registry.read().unwrap().listener_processors.clone()
     }
+
+    /// Return information about all active inlets
+    pub fn get_all_inlets(&self) -> Vec<TcpInletInfo> {
+        self.registry
+            .read()
+            .unwrap()
+            .inlet_listener_processors
+            .clone()
+    }
+
+    /// Return information about all active outlets
+    pub fn get_all_outlets(&self) -> Vec<TcpOutletInfo> {
+        self.registry
+            .read()
+            .unwrap()
+            .outlet_listener_workers
+            .clone()
+    }
 }
@@ -0,0 +1,216 @@
+use core::time::Duration;
+use ockam_core::compat::sync::Arc;
+use ockam_core::Result;
+use ockam_transport_core::tls::{
+    native_root_certificates, read_certificates, read_private_key, read_root_certificates,
+    tls_error,
+};
+use ockam_transport_core::TransportError;
+use rustls::server::AllowAnyAuthenticatedClient;
+use rustls::{ClientConfig, ServerConfig, ServerName};
+use std::path::PathBuf;
+use tokio::net::TcpStream;
+use tokio_rustls::{client, server, TlsAcceptor, TlsConnector};
+use tracing::{debug, warn};
+
+/// Maximum duration of a TLS handshake, after which the connection is dropped
+pub const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// TLS options of an outgoing TCP connection
+///
+/// By default, the server certificate is verified with the root certificates of the platform,
+/// for the host name used to connect.
+#[derive(Debug, Clone, Default)]
+pub struct TcpClientTlsOptions {
+    server_name: Option<String>,
+    ca_bundle: Option<PathBuf>,
+    client_certificate: Option<(PathBuf, PathBuf)>,
+}
+
+impl TcpClientTlsOptions {
+    /// Verify the server with the root certificates of the platform
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Verify the server certificate for the given name, instead of the host name used to connect
+    pub fn with_server_name(mut self, server_name: impl Into<String>) -> Self {
+        self.server_name = Some(server_name.into());
+        self
+    }
+
+    /// Verify the server with the certificates of the given PEM file instead of
+    /// the root certificates of the platform
+    pub fn with_ca_bundle(mut self, path: impl Into<PathBuf>) -> Self {
+        self.ca_bundle = Some(path.into());
+        self
+    }
+
+    /// Authenticate to the server with the given PEM certificate chain and private key
+    pub fn with_client_certificate(
+        mut self,
+        certificate_chain: impl Into<PathBuf>,
+        private_key: impl Into<PathBuf>,
+    ) -> Self {
+        self.client_certificate = Some((certificate_chain.into(), private_key.into()));
+        self
+    }
+}
+
+impl TcpClientTlsOptions {
+    /// Perform the TLS handshake over a connection opened to `peer`, a `host:port` string
+    pub(crate) async fn connect(
+        &self,
+        peer: &str,
+        stream: TcpStream,
+    ) -> Result<client::TlsStream<TcpStream>> {
+        let server_name = match &self.server_name {
+            Some(server_name) => server_name.as_str(),
+            None => host(peer),
+        };
+        let server_name = ServerName::try_from(server_name).map_err(|_| {
+            warn!(%server_name, "Invalid TLS server name");
+            TransportError::InvalidAddress
+        })?;
+
+        let connector = TlsConnector::from(self.client_config()?);
+        debug!(%peer, "Starting TLS handshake");
+        match tokio::time::timeout(
+            TLS_HANDSHAKE_TIMEOUT,
+            connector.connect(server_name, stream),
+        )
+        .await
+        {
+            Ok(Ok(stream)) => Ok(stream),
+            Ok(Err(e)) => {
+                warn!(%peer, err = %e, "TLS handshake failed");
+                Err(TransportError::from(e).into())
+            }
+            Err(_) => {
+                warn!(%peer, "TLS handshake timed out");
+                Err(TransportError::ConnectionDrop.into())
+            }
+        }
+    }
+
+    fn client_config(&self) -> Result<Arc<ClientConfig>> {
+        let roots = match &self.ca_bundle {
+            Some(path) => read_root_certificates(path)?,
+            None => native_root_certificates()?,
+        };
+
+        let builder = ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(roots);
+        let config = match &self.client_certificate {
+            Some((certificate_chain, private_key)) => builder
+                .with_client_auth_cert(
+                    read_certificates(certificate_chain)?,
+                    read_private_key(private_key)?,
+                )
+                .map_err(tls_error)?,
+            None => builder.with_no_client_auth(),
+        };
+
+        Ok(Arc::new(config))
+    }
+}
+
+/// TLS options of a TCP listener
+#[derive(Debug, Clone)]
+pub struct TcpServerTlsOptions {
+    certificate_chain: PathBuf,
+    private_key: PathBuf,
+    client_ca_bundle: Option<PathBuf>,
+}
+
+impl TcpServerTlsOptions {
+    /// Present the given PEM certificate chain, signed with the given PEM private key
+    pub fn new(certificate_chain: impl Into<PathBuf>, private_key: impl Into<PathBuf>) -> Self {
+        Self {
+            certificate_chain: certificate_chain.into(),
+            private_key: private_key.into(),
+            client_ca_bundle: None,
+        }
+    }
+
+    /// Only accept the clients presenting a certificate signed by one of the
+    /// certificates of the given PEM file
+    pub fn with_client_ca_bundle(mut self, path: impl Into<PathBuf>) -> Self {
+        self.client_ca_bundle = Some(path.into());
+        self
+    }
+}
+
+impl TcpServerTlsOptions {
+    /// Load the certificates, so that invalid options are reported when the listener starts
+    pub(crate) fn acceptor(&self) -> Result<TcpTlsAcceptor> {
+        let builder = ServerConfig::builder().with_safe_defaults();
+        let builder = match &self.client_ca_bundle {
+            Some(path) => builder.with_client_cert_verifier(
+                AllowAnyAuthenticatedClient::new(read_root_certificates(path)?).boxed(),
+            ),
+            None => builder.with_no_client_auth(),
+        };
+        let config = builder
+            .with_single_cert(
+                read_certificates(&self.certificate_chain)?,
+                read_private_key(&self.private_key)?,
+            )
+            .map_err(tls_error)?;
+
+        Ok(TcpTlsAcceptor(TlsAcceptor::from(Arc::new(config))))
+    }
+}
+
+/// Performs the TLS handshake of the connections accepted by a listener
+#[derive(Clone)]
+pub(crate) struct TcpTlsAcceptor(TlsAcceptor);
+
+impl TcpTlsAcceptor {
+    pub(crate) async fn accept(&self, stream: TcpStream) -> Result<server::TlsStream<TcpStream>> {
+        match tokio::time::timeout(TLS_HANDSHAKE_TIMEOUT, self.0.accept(stream)).await {
+            Ok(Ok(stream)) => Ok(stream),
+            Ok(Err(e)) => Err(TransportError::from(e).into()),
+            Err(_) => Err(TransportError::ConnectionDrop.into()),
+        }
+    }
+}
+
+/// Host part of a `host:port` string
+fn host(peer: &str) -> &str {
+    peer.rsplit_once(':')
+        .map_or(peer, |(host, _)| host)
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_host() {
+        assert_eq!(host("relay.ockam.io:443"), "relay.ockam.io");
+        assert_eq!(host("127.0.0.1:443"), "127.0.0.1");
+        assert_eq!(host("[::1]:443"), "::1");
+    }
+
+    #[test]
+    fn test_invalid_certificate_files() {
+        let options = TcpServerTlsOptions::new("missing.pem", "missing.key");
+        assert!(options.acceptor().is_err());
+
+        let options = TcpServerTlsOptions::new(
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/certs/server.key"),
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/certs/server.key"),
+        );
+        assert!(options.acceptor().is_err());
+
+        let options = TcpServerTlsOptions::new(
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/certs/server.pem"),
+            concat!(env!("CARGO_MANIFEST_DIR"), "/tests/certs/server.key"),
+        );
+        assert!(options.acceptor().is_ok());
+    }
+}
@@ -143,6 +143,19 @@ pub fn resolve_peer(peer: String) -> Result<SocketAddr> {
     Err(TransportError::InvalidAddress.into())
 }
 
+/// Resolve the given peer to all the [`SocketAddr`](std::net::SocketAddr)es returned by the DNS,
+/// without blocking the runtime
+pub(crate) async fn resolve_peer_addresses(peer: &str) -> Result<Vec<SocketAddr>> {
+    if let Ok(p) = parse_socket_addr(peer) {
+        return Ok(vec![p]);
+    }
+
+    match tokio::net::lookup_host(peer).await {
+        Ok(iter) => Ok(iter.collect()),
+        Err(_) => Err(TransportError::InvalidAddress.into()),
+    }
+}
+
 pub(super) fn parse_socket_addr(s: &str) -> Result<SocketAddr> {
     Ok(s.parse().map_err(|_| TransportError::InvalidAddress)?)
 }
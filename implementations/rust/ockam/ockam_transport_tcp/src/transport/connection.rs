@@ -1,11 +1,14 @@
 use crate::transport::common::{resolve_peer, TcpConnection};
-use crate::workers::{Addresses, TcpRecvProcessor, TcpSendWorker};
+use crate::workers::{split_stream, split_tcp_stream, Addresses, TcpRecvProcessor, TcpSendWorker};
 use crate::{TcpConnectionMode, TcpConnectionOptions, TcpTransport};
 use ockam_core::{Address, Result};
 
 impl TcpTransport {
     /// Establish an outgoing TCP connection.
     ///
+    /// The connection is wrapped in TLS when the options contain
+    /// [`TcpClientTlsOptions`](crate::TcpClientTlsOptions).
+    ///
     /// ```rust
     /// use ockam_transport_tcp::{TcpConnectionOptions, TcpListenerOptions, TcpTransport};
     /// # use ockam_node::Context;
@@ -22,9 +25,14 @@ impl TcpTransport {
         options: TcpConnectionOptions,
     ) -> Result<TcpConnection> {
         // Resolve peer address
-        let socket = resolve_peer(peer.into())?;
+        let peer = peer.into();
+        let socket = resolve_peer(peer.clone())?;
 
-        let (read_half, write_half) = TcpSendWorker::connect(socket).await?;
+        let stream = TcpSendWorker::connect(socket).await?;
+        let (read_half, write_half) = match &options.tls {
+            Some(tls) => split_stream(tls.connect(&peer, stream).await?),
+            None => split_tcp_stream(stream),
+        };
 
         let mode = TcpConnectionMode::Outgoing;
         let addresses = Addresses::generate(mode);
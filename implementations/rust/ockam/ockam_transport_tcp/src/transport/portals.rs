@@ -1,5 +1,5 @@
 use crate::portal::TcpInletListenProcessor;
-use crate::transport::common::{parse_socket_addr, resolve_peer};
+use crate::transport::common::parse_socket_addr;
 use crate::{portal::TcpOutletListenWorker, TcpInletOptions, TcpOutletOptions, TcpTransport};
 use ockam_core::compat::net::SocketAddr;
 use ockam_core::{Address, Result, Route};
@@ -65,6 +65,10 @@ impl TcpTransport {
     /// to Inlet using return route.
     /// Pair of corresponding Inlet and Outlet is called Portal.
     ///
+    /// Additional targets can be set with [`TcpOutletOptions::with_targets`], in which case
+    /// each new connection goes to one of the targets, chosen with the [`LoadBalancing`](crate::LoadBalancing) strategy
+    /// of the options.
+    ///
     /// ```rust
     /// use ockam_transport_tcp::{TcpOutletOptions, TcpTransport};
     /// # use ockam_node::Context;
@@ -82,13 +86,11 @@ impl TcpTransport {
         peer: impl Into<String>,
         options: TcpOutletOptions,
     ) -> Result<()> {
-        // Resolve peer address
-        let peer_addr = resolve_peer(peer.into())?;
         TcpOutletListenWorker::start(
             &self.ctx,
             self.registry.clone(),
             address.into(),
-            peer_addr,
+            peer.into(),
             options,
         )
         .await?;
@@ -103,8 +105,14 @@ impl TcpTransport {
         peer: SocketAddr,
         options: TcpOutletOptions,
     ) -> Result<()> {
-        TcpOutletListenWorker::start(&self.ctx, self.registry.clone(), address, peer, options)
-            .await?;
+        TcpOutletListenWorker::start(
+            &self.ctx,
+            self.registry.clone(),
+            address,
+            peer.to_string(),
+            options,
+        )
+        .await?;
 
         Ok(())
     }
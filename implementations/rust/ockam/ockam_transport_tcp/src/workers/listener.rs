@@ -1,22 +1,32 @@
-use crate::workers::{Addresses, TcpRecvProcessor};
-use crate::{TcpConnectionMode, TcpListenerInfo, TcpListenerOptions, TcpRegistry, TcpSendWorker};
+use crate::workers::{split_stream, split_tcp_stream, Addresses, TcpRecvProcessor};
+use crate::{
+    TcpConnectionMode, TcpListenerInfo, TcpListenerOptions, TcpRegistry, TcpSendWorker,
+    TcpTlsAcceptor,
+};
 use ockam_core::{async_trait, compat::net::SocketAddr};
 use ockam_core::{Address, Processor, Result};
 use ockam_node::Context;
 use ockam_transport_core::TransportError;
-use tokio::net::TcpListener;
-use tracing::debug;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinSet;
+use tokio_rustls::server::TlsStream;
+use tracing::{debug, warn};
 
 /// A TCP Listen processor
 ///
 /// TCP listen processors are created by `TcpTransport`
 /// after a call is made to
 /// [`TcpTransport::listen`](crate::TcpTransport::listen).
+///
+/// TLS handshakes run in separate tasks, so that a slow client doesn't
+/// block the other ones.
 pub(crate) struct TcpListenProcessor {
     registry: TcpRegistry,
     inner: TcpListener,
     socket_address: SocketAddr,
     options: TcpListenerOptions,
+    tls_acceptor: Option<TcpTlsAcceptor>,
+    tls_handshakes: JoinSet<Option<(TlsStream<TcpStream>, SocketAddr)>>,
 }
 
 impl TcpListenProcessor {
@@ -27,6 +37,10 @@ impl TcpListenProcessor {
         options: TcpListenerOptions,
     ) -> Result<(SocketAddr, Address)> {
         debug!("Binding TcpListener to {}", addr);
+        let tls_acceptor = match &options.tls {
+            Some(tls) => Some(tls.acceptor()?),
+            None => None,
+        };
         let inner = TcpListener::bind(addr)
             .await
             .map_err(TransportError::from)?;
@@ -40,6 +54,8 @@ impl TcpListenProcessor {
             inner,
             socket_address: saddr,
             options,
+            tls_acceptor,
+            tls_handshakes: JoinSet::new(),
         };
 
         ctx.start_processor(address.clone(), processor).await?;
@@ -73,9 +89,28 @@ impl Processor for TcpListenProcessor {
     async fn process(&mut self, ctx: &mut Self::Context) -> Result<bool> {
         debug!("Waiting for incoming TCP connection...");
 
-        // Wait for an incoming connection
-        let (stream, peer) = self.inner.accept().await.map_err(TransportError::from)?;
-        debug!("TCP connection accepted");
+        let ((read_half, write_half), peer) = tokio::select! {
+            accepted = self.inner.accept() => {
+                // Wait for an incoming connection
+                let (stream, peer) = accepted.map_err(TransportError::from)?;
+                debug!("TCP connection accepted");
+
+                match &self.tls_acceptor {
+                    Some(tls_acceptor) => {
+                        self.tls_handshakes
+                            .spawn(tls_handshake(tls_acceptor.clone(), stream, peer));
+                        return Ok(true);
+                    }
+                    None => (split_tcp_stream(stream), peer),
+                }
+            }
+            Some(handshake) = self.tls_handshakes.join_next(), if !self.tls_handshakes.is_empty() => {
+                match handshake {
+                    Ok(Some((stream, peer))) => (split_stream(stream), peer),
+                    _ => return Ok(true),
+                }
+            }
+        };
 
         let mode = TcpConnectionMode::Incoming;
         let addresses = Addresses::generate(mode);
@@ -87,8 +122,6 @@ impl Processor for TcpListenProcessor {
             .options
             .create_access_control(ctx.flow_controls(), receiver_flow_control_id.clone());
 
-        let (read_half, write_half) = stream.into_split();
-
         // Worker to receive messages from the Node and send them over the wire
         TcpSendWorker::start(
             ctx,
@@ -118,3 +151,19 @@ impl Processor for TcpListenProcessor {
         Ok(true)
     }
 }
+
+/// Perform the TLS handshake of an accepted connection. A failed handshake only
+/// concerns this client, and is logged
+async fn tls_handshake(
+    tls_acceptor: TcpTlsAcceptor,
+    stream: TcpStream,
+    peer: SocketAddr,
+) -> Option<(TlsStream<TcpStream>, SocketAddr)> {
+    match tls_acceptor.accept(stream).await {
+        Ok(stream) => Some((stream, peer)),
+        Err(e) => {
+            warn!(%peer, err = %e, "TLS handshake failed");
+            None
+        }
+    }
+}
@@ -2,8 +2,30 @@ mod addresses;
 mod listener;
 mod receiver;
 mod sender;
+mod stream;
 
 pub(crate) use addresses::*;
 pub(crate) use listener::*;
 pub(crate) use receiver::*;
 pub(crate) use sender::*;
+pub(crate) use stream::*;
+
+use ockam_node::metrics::{registry, Counter};
+
+/// Counter of the bytes sent to TCP peers
+pub(crate) fn bytes_sent_counter() -> Counter {
+    registry().counter(
+        "ockam_transport_tcp_bytes_sent_total",
+        "Number of bytes sent to TCP peers",
+        vec![],
+    )
+}
+
+/// Counter of the bytes received from TCP peers
+pub(crate) fn bytes_received_counter() -> Counter {
+    registry().counter(
+        "ockam_transport_tcp_bytes_received_total",
+        "Number of bytes received from TCP peers",
+        vec![],
+    )
+}
@@ -1,4 +1,4 @@
-use crate::workers::Addresses;
+use crate::workers::{bytes_received_counter, Addresses, TcpReadHalf};
 use crate::{TcpConnectionMode, TcpReceiverInfo, TcpRegistry, TcpSendWorkerMsg};
 use ockam_core::compat::net::SocketAddr;
 use ockam_core::compat::sync::Arc;
@@ -7,9 +7,10 @@ use ockam_core::{
     async_trait, AllowOnwardAddress, DenyAll, Mailbox, Mailboxes, OutgoingAccessControl,
 };
 use ockam_core::{Decodable, LocalMessage, Processor, Result, TransportMessage};
+use ockam_node::metrics::Counter;
 use ockam_node::{Context, ProcessorBuilder};
 use ockam_transport_core::TransportError;
-use tokio::{io::AsyncReadExt, net::tcp::OwnedReadHalf};
+use tokio::io::AsyncReadExt;
 use tracing::{error, info, trace};
 
 /// A TCP receiving message processor
@@ -22,18 +23,19 @@ use tracing::{error, info, trace};
 /// the node message system.
 pub(crate) struct TcpRecvProcessor {
     registry: TcpRegistry,
-    read_half: OwnedReadHalf,
+    read_half: TcpReadHalf,
     socket_address: SocketAddr,
     addresses: Addresses,
     mode: TcpConnectionMode,
     flow_control_id: FlowControlId,
+    bytes_received: Counter,
 }
 
 impl TcpRecvProcessor {
     /// Create a new `TcpRecvProcessor`
     fn new(
         registry: TcpRegistry,
-        read_half: OwnedReadHalf,
+        read_half: TcpReadHalf,
         socket_address: SocketAddr,
         addresses: Addresses,
         mode: TcpConnectionMode,
@@ -46,6 +48,7 @@ impl TcpRecvProcessor {
             addresses,
             mode,
             flow_control_id,
+            bytes_received: bytes_received_counter(),
         }
     }
 
@@ -53,7 +56,7 @@ impl TcpRecvProcessor {
     pub async fn start(
         ctx: &Context,
         registry: TcpRegistry,
-        read_half: OwnedReadHalf,
+        read_half: TcpReadHalf,
         addresses: &Addresses,
         socket_address: SocketAddr,
         mode: TcpConnectionMode,
@@ -161,6 +164,8 @@ impl Processor for TcpRecvProcessor {
                 return Ok(true);
             }
         }
+        // Count the length header as well
+        self.bytes_received.inc_by(len as u64 + 2);
 
         // Deserialize the message now
         let mut msg = TransportMessage::decode(&buf).map_err(|_| TransportError::RecvBadMessage)?;
@@ -1,4 +1,4 @@
-use crate::workers::Addresses;
+use crate::workers::{bytes_sent_counter, Addresses, TcpWriteHalf};
 use crate::{TcpConnectionMode, TcpRegistry, TcpSenderInfo};
 use cfg_if::cfg_if;
 use core::time::Duration;
@@ -12,12 +12,12 @@ use ockam_core::{
     Any, Decodable, Encodable, Mailbox, Mailboxes, Message, Result, Routed, TransportMessage,
     Worker,
 };
+use ockam_node::metrics::Counter;
 use ockam_node::{Context, WorkerBuilder};
 use ockam_transport_core::TransportError;
 use serde::{Deserialize, Serialize};
 use socket2::{SockRef, TcpKeepalive};
 use tokio::io::AsyncWriteExt;
-use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
 use tokio::net::TcpStream;
 use tracing::{debug, info, trace, warn};
 
@@ -36,19 +36,20 @@ pub(crate) enum TcpSendWorkerMsg {
 /// to dispatch to a remote peer.
 pub(crate) struct TcpSendWorker {
     registry: TcpRegistry,
-    write_half: OwnedWriteHalf,
+    write_half: TcpWriteHalf,
     socket_address: SocketAddr,
     addresses: Addresses,
     mode: TcpConnectionMode,
     receiver_flow_control_id: FlowControlId,
     rx_should_be_stopped: bool,
+    bytes_sent: Counter,
 }
 
 impl TcpSendWorker {
     /// Create a new `TcpSendWorker`
     fn new(
         registry: TcpRegistry,
-        write_half: OwnedWriteHalf,
+        write_half: TcpWriteHalf,
         socket_address: SocketAddr,
         addresses: Addresses,
         mode: TcpConnectionMode,
@@ -62,6 +63,7 @@ impl TcpSendWorker {
             receiver_flow_control_id,
             mode,
             rx_should_be_stopped: true,
+            bytes_sent: bytes_sent_counter(),
         }
     }
 }
@@ -73,7 +75,7 @@ impl TcpSendWorker {
     pub(crate) async fn start(
         ctx: &Context,
         registry: TcpRegistry,
-        write_half: OwnedWriteHalf,
+        write_half: TcpWriteHalf,
         addresses: &Addresses,
         socket_address: SocketAddr,
         mode: TcpConnectionMode,
@@ -119,9 +121,7 @@ impl TcpSendWorker {
         Ok(())
     }
 
-    pub(crate) async fn connect(
-        socket_address: SocketAddr,
-    ) -> Result<(OwnedReadHalf, OwnedWriteHalf)> {
+    pub(crate) async fn connect(socket_address: SocketAddr) -> Result<TcpStream> {
         debug!(addr = %socket_address, "Connecting");
         let connection = match TcpStream::connect(socket_address).await {
             Ok(c) => {
@@ -147,7 +147,7 @@ impl TcpSendWorker {
         let socket = SockRef::from(&connection);
         socket.set_tcp_keepalive(&keepalive).unwrap();
 
-        Ok(connection.into_split())
+        Ok(connection)
     }
 }
 
@@ -216,12 +216,16 @@ impl Worker for TcpSendWorker {
             // Create a message buffer with prepended length
             let msg = prepare_message(msg)?;
 
-            if self.write_half.write_all(msg.as_slice()).await.is_err() {
+            // Flushing is needed to send the data buffered by a TLS stream
+            if self.write_half.write_all(msg.as_slice()).await.is_err()
+                || self.write_half.flush().await.is_err()
+            {
                 warn!("Failed to send message to peer {}", self.socket_address);
                 self.stop(ctx).await?;
 
                 return Ok(());
             }
+            self.bytes_sent.inc_by(msg.len() as u64);
         }
 
         Ok(())
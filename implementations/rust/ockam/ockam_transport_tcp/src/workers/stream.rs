@@ -0,0 +1,23 @@
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpStream;
+
+/// Read half of a TCP connection, which may be wrapped in TLS
+pub(crate) type TcpReadHalf = Box<dyn AsyncRead + Send + Sync + Unpin>;
+
+/// Write half of a TCP connection, which may be wrapped in TLS
+pub(crate) type TcpWriteHalf = Box<dyn AsyncWrite + Send + Sync + Unpin>;
+
+/// Split a plain TCP stream, without locking between the two halves
+pub(crate) fn split_tcp_stream(stream: TcpStream) -> (TcpReadHalf, TcpWriteHalf) {
+    let (read_half, write_half) = stream.into_split();
+    (Box::new(read_half), Box::new(write_half))
+}
+
+/// Split a stream wrapping a TCP stream, for instance a TLS stream
+pub(crate) fn split_stream<S>(stream: S) -> (TcpReadHalf, TcpWriteHalf)
+where
+    S: AsyncRead + AsyncWrite + Send + Sync + 'static,
+{
+    let (read_half, write_half) = tokio::io::split(stream);
+    (Box::new(read_half), Box::new(write_half))
+}
@@ -238,3 +238,151 @@ async fn portal__tcp_connection_with_invalid_message_flow__should_not_succeed(
 
     Ok(())
 }
+
+#[allow(non_snake_case)]
+#[ockam_macros::test(timeout = 15000)]
+async fn portal__large_payload_with_small_window__should_succeed(ctx: &mut Context) -> Result<()> {
+    let payload: Vec<u8> = (0..1024 * 1024).map(|_| random::<u8>()).collect();
+    let expected = payload.clone();
+
+    let tcp = TcpTransport::create(ctx).await?;
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let bind_address = listener.local_addr().unwrap().to_string();
+    tcp.create_outlet(
+        "outlet",
+        bind_address,
+        TcpOutletOptions::new().with_window_size(2),
+    )
+    .await?;
+
+    let (inlet_addr, _) = tcp
+        .create_inlet(
+            "127.0.0.1:0",
+            route!["outlet"],
+            TcpInletOptions::new().with_window_size(2),
+        )
+        .await?;
+
+    let handle = tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+
+        let mut received = vec![0u8; expected.len()];
+        stream.read_exact(&mut received).await.unwrap();
+        assert_eq!(received, expected);
+    });
+
+    let mut stream = TcpStream::connect(inlet_addr).await.unwrap();
+    stream.write_all(&payload).await.unwrap();
+
+    let res = handle.await;
+    assert!(res.is_ok());
+
+    if let Err(e) = ctx.stop().await {
+        println!("Unclean stop: {}", e)
+    }
+
+    Ok(())
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test(timeout = 5000)]
+async fn portal__half_close__should_keep_other_direction_open(ctx: &mut Context) -> Result<()> {
+    let payload1 = generate_binary();
+    let payload2 = generate_binary();
+
+    let (inlet_addr, listener) = setup(ctx).await?;
+
+    let handle = tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+
+        // Read the whole request until the client closes its write half
+        let mut request = vec![];
+        stream.read_to_end(&mut request).await.unwrap();
+        assert_eq!(request, payload1);
+
+        // Then respond on the direction that is still open
+        write_binary(&mut stream, payload2).await;
+    });
+
+    // Wait till listener is up
+    tokio::time::sleep(Duration::from_millis(250)).await;
+
+    let mut stream = TcpStream::connect(inlet_addr).await.unwrap();
+    write_binary(&mut stream, payload1).await;
+    stream.shutdown().await.unwrap();
+
+    let mut response = vec![];
+    stream.read_to_end(&mut response).await.unwrap();
+    assert_eq!(response, payload2);
+
+    let res = handle.await;
+    assert!(res.is_ok());
+
+    if let Err(e) = ctx.stop().await {
+        println!("Unclean stop: {}", e)
+    }
+
+    Ok(())
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test(timeout = 5000)]
+async fn portal__max_connections__should_reject_and_count(ctx: &mut Context) -> Result<()> {
+    let payload1 = generate_binary();
+    let payload2 = generate_binary();
+
+    let tcp = TcpTransport::create(ctx).await?;
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let bind_address = listener.local_addr().unwrap().to_string();
+    tcp.create_outlet("outlet", bind_address, TcpOutletOptions::new())
+        .await?;
+
+    let (inlet_addr, inlet_processor) = tcp
+        .create_inlet(
+            "127.0.0.1:0",
+            route!["outlet"],
+            TcpInletOptions::new().with_max_connections(1),
+        )
+        .await?;
+
+    let handle = tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+
+        read_assert_binary(&mut stream, payload1).await;
+        write_binary(&mut stream, payload2).await;
+        stream
+    });
+
+    let mut stream = TcpStream::connect(inlet_addr).await.unwrap();
+    write_binary(&mut stream, payload1).await;
+    read_assert_binary(&mut stream, payload2).await;
+
+    // The second connection is closed right away
+    let mut rejected = TcpStream::connect(inlet_addr).await.unwrap();
+    let mut buf = [0u8; LENGTH];
+    assert_eq!(rejected.read(&mut buf).await.unwrap(), 0);
+
+    let inlet = tcp
+        .registry()
+        .get_all_inlets()
+        .into_iter()
+        .find(|i| i.address() == &inlet_processor)
+        .unwrap();
+    let stats = inlet.stats();
+    assert_eq!(stats.bytes_in(), LENGTH as u64);
+    assert_eq!(stats.bytes_out(), LENGTH as u64);
+    assert_eq!(stats.connections_opened(), 1);
+    assert_eq!(stats.connections_rejected(), 1);
+    assert_eq!(stats.connections().len(), 1);
+
+    let res = handle.await;
+    assert!(res.is_ok());
+
+    if let Err(e) = ctx.stop().await {
+        println!("Unclean stop: {}", e)
+    }
+
+    Ok(())
+}
@@ -1,7 +1,10 @@
 use ockam_core::compat::rand::{self, Rng};
 use ockam_core::{route, Result, Routed, Worker};
 use ockam_node::Context;
-use ockam_transport_tcp::{TcpConnectionOptions, TcpListenerOptions, TcpTransport};
+use ockam_transport_tcp::{
+    TcpClientTlsOptions, TcpConnectionOptions, TcpListenerOptions, TcpServerTlsOptions,
+    TcpTransport,
+};
 
 pub struct Echoer;
 
@@ -52,3 +55,80 @@ async fn send_receive(ctx: &mut Context) -> Result<()> {
 
     Ok(())
 }
+
+const CERTS_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/certs");
+
+fn server_tls_options() -> TcpServerTlsOptions {
+    TcpServerTlsOptions::new(
+        format!("{CERTS_DIR}/server.pem"),
+        format!("{CERTS_DIR}/server.key"),
+    )
+}
+
+#[ockam_macros::test]
+async fn send_receive_tls(ctx: &mut Context) -> Result<()> {
+    let options = TcpListenerOptions::new().with_tls(server_tls_options());
+    ctx.flow_controls()
+        .add_consumer("echoer", &options.spawner_flow_control_id());
+    ctx.start_worker("echoer", Echoer).await?;
+
+    let transport = TcpTransport::create(ctx).await?;
+    let listener = transport.listen("127.0.0.1:0", options).await?;
+
+    let tls = TcpClientTlsOptions::new().with_ca_bundle(format!("{CERTS_DIR}/ca.pem"));
+    let addr = transport
+        .connect(
+            format!("localhost:{}", listener.socket_address().port()),
+            TcpConnectionOptions::new().with_tls(tls),
+        )
+        .await?
+        .sender_address()
+        .clone();
+
+    let msg = "Hello over TLS".to_string();
+    let reply = ctx
+        .send_and_receive::<String>(route![addr, "echoer"], msg.clone())
+        .await?;
+    assert_eq!(reply, msg, "Should receive the same message");
+
+    if let Err(e) = ctx.stop().await {
+        println!("Unclean stop: {}", e)
+    }
+
+    Ok(())
+}
+
+#[ockam_macros::test]
+async fn tls_rejects_untrusted_server(ctx: &mut Context) -> Result<()> {
+    let transport = TcpTransport::create(ctx).await?;
+    let listener = transport
+        .listen(
+            "127.0.0.1:0",
+            TcpListenerOptions::new().with_tls(server_tls_options()),
+        )
+        .await?;
+
+    // The test certificates are not signed by a root certificate of the platform
+    let res = transport
+        .connect(
+            listener.socket_string(),
+            TcpConnectionOptions::new().with_tls(TcpClientTlsOptions::new()),
+        )
+        .await;
+    assert!(res.is_err());
+
+    // The listener keeps accepting connections after a failed handshake
+    let tls = TcpClientTlsOptions::new().with_ca_bundle(format!("{CERTS_DIR}/ca.pem"));
+    transport
+        .connect(
+            listener.socket_string(),
+            TcpConnectionOptions::new().with_tls(tls),
+        )
+        .await?;
+
+    if let Err(e) = ctx.stop().await {
+        println!("Unclean stop: {}", e)
+    }
+
+    Ok(())
+}
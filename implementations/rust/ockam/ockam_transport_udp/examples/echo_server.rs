@@ -1,12 +1,15 @@
 use ockam_core::{Result, Routed, Worker};
 use ockam_node::Context;
-use ockam_transport_udp::UdpTransport;
+use ockam_transport_udp::{UdpListenerOptions, UdpTransport};
 use tracing::debug;
 
 #[ockam_macros::node]
 async fn main(ctx: Context) -> Result<()> {
     let udp = UdpTransport::create(&ctx).await?;
-    udp.listen("127.0.0.1:8000").await?;
+    let options = UdpListenerOptions::new();
+    ctx.flow_controls()
+        .add_consumer("echoer", &options.flow_control_id());
+    udp.listen("127.0.0.1:8000", options).await?;
     ctx.start_worker("echoer", Echoer).await?;
     Ok(())
 }
@@ -1,6 +1,6 @@
 use ockam_core::Result;
 use ockam_node::Context;
-use ockam_transport_udp::{UdpRendezvousService, UdpTransport};
+use ockam_transport_udp::{UdpListenerOptions, UdpRendezvousService, UdpTransport};
 use tracing::debug;
 
 #[ockam_macros::node]
@@ -14,7 +14,10 @@ async fn main(ctx: Context) -> Result<()> {
     UdpRendezvousService::start(&ctx, "rendezvous").await?;
 
     let udp = UdpTransport::create(&ctx).await?;
-    udp.listen(addr).await?;
+    let options = UdpListenerOptions::new();
+    ctx.flow_controls()
+        .add_consumer("rendezvous", &options.flow_control_id());
+    udp.listen(addr, options).await?;
 
     // Don't stop context/node. Run forever.
     Ok(())
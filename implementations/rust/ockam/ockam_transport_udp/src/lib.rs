@@ -10,11 +10,19 @@
 use ockam_core::TransportType;
 
 pub use hole_puncher::{PunchError, UdpHolePuncher};
+pub use options::{
+    UdpListenerOptions, UdpSocketOptions, DEFAULT_MAX_RETRANSMISSIONS, DEFAULT_REASSEMBLY_TIMEOUT,
+    DEFAULT_RETRANSMISSION_TIMEOUT,
+};
+pub use portal::options::{UdpInletOptions, UdpOutletOptions, DEFAULT_IDLE_TIMEOUT};
+pub use portal::{UdpPortalMessage, MAX_DATAGRAM_SIZE};
 pub use rendezvous_service::UdpRendezvousService;
 pub use transport::UdpTransport;
 pub use transport::UdpTransportExtension;
 
 mod hole_puncher;
+mod options;
+mod portal;
 mod rendezvous_service;
 mod router;
 mod transport;
@@ -0,0 +1,135 @@
+use core::time::Duration;
+use ockam_core::compat::sync::Arc;
+use ockam_core::flow_control::{FlowControlId, FlowControlOutgoingAccessControl, FlowControls};
+use ockam_core::{Address, OutgoingAccessControl};
+use serde::{Deserialize, Serialize};
+
+/// Default duration after which a partially received message is dropped
+pub const DEFAULT_REASSEMBLY_TIMEOUT: Duration = Duration::from_secs(5);
+/// Default duration after which an unacknowledged fragment is sent again
+pub const DEFAULT_RETRANSMISSION_TIMEOUT: Duration = Duration::from_millis(500);
+/// Default number of retransmissions of a fragment before giving up
+pub const DEFAULT_MAX_RETRANSMISSIONS: u32 = 5;
+
+/// Delivery options of a UDP socket
+///
+/// Messages which don't fit in a single datagram are split into fragments, and reassembled
+/// by the receiver. In reliable mode, the receiver acknowledges every fragment and the sender
+/// retransmits the fragments which are not acknowledged in time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UdpSocketOptions {
+    pub(crate) reliable: bool,
+    pub(crate) reassembly_timeout: Duration,
+    pub(crate) retransmission_timeout: Duration,
+    pub(crate) max_retransmissions: u32,
+}
+
+impl UdpSocketOptions {
+    #[allow(clippy::new_without_default)]
+    /// Unreliable delivery, with the default reassembly timeout
+    pub fn new() -> Self {
+        Self {
+            reliable: false,
+            reassembly_timeout: DEFAULT_REASSEMBLY_TIMEOUT,
+            retransmission_timeout: DEFAULT_RETRANSMISSION_TIMEOUT,
+            max_retransmissions: DEFAULT_MAX_RETRANSMISSIONS,
+        }
+    }
+
+    /// Acknowledge and retransmit the fragments of the messages sent by this socket
+    pub fn with_reliable_delivery(mut self) -> Self {
+        self.reliable = true;
+        self
+    }
+
+    /// Drop the messages which are not fully received after the given duration
+    pub fn with_reassembly_timeout(mut self, timeout: Duration) -> Self {
+        self.reassembly_timeout = timeout;
+        self
+    }
+
+    /// Send a fragment again if it is not acknowledged after the given duration
+    pub fn with_retransmission_timeout(mut self, timeout: Duration) -> Self {
+        self.retransmission_timeout = timeout;
+        self
+    }
+
+    /// Stop retransmitting a fragment after the given number of retransmissions
+    pub fn with_max_retransmissions(mut self, max_retransmissions: u32) -> Self {
+        self.max_retransmissions = max_retransmissions;
+        self
+    }
+}
+
+/// Trust Options for a UDP listener
+///
+/// A UDP socket doesn't have connections, so the socket receiver itself is a Producer and
+/// its sender a Consumer, like the receiver and sender of a TCP connection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UdpListenerOptions {
+    pub(crate) consumer: Vec<FlowControlId>,
+    pub(crate) flow_control_id: FlowControlId,
+    pub(crate) socket: UdpSocketOptions,
+}
+
+impl UdpListenerOptions {
+    #[allow(clippy::new_without_default)]
+    /// Mark this Udp Receiver as a Producer with a random [`FlowControlId`]
+    pub fn new() -> Self {
+        Self {
+            consumer: vec![],
+            flow_control_id: FlowControls::generate_flow_control_id(),
+            socket: UdpSocketOptions::new(),
+        }
+    }
+
+    /// Mark that this Udp Sender is a Consumer for to the given [`FlowControlId`]
+    pub fn as_consumer(mut self, id: &FlowControlId) -> Self {
+        self.consumer.push(id.clone());
+
+        self
+    }
+
+    /// Set the delivery options of the listening socket
+    pub fn with_socket_options(mut self, socket: UdpSocketOptions) -> Self {
+        self.socket = socket;
+
+        self
+    }
+
+    /// Getter for freshly generated [`FlowControlId`]
+    pub fn flow_control_id(&self) -> FlowControlId {
+        self.flow_control_id.clone()
+    }
+}
+
+impl UdpListenerOptions {
+    pub(crate) fn setup_flow_control(
+        &self,
+        flow_controls: &FlowControls,
+        receiver_address: &Address,
+        sender_address: &Address,
+    ) {
+        flow_controls.add_producer(
+            receiver_address.clone(),
+            &self.flow_control_id,
+            None,
+            vec![sender_address.clone()],
+        );
+
+        for id in &self.consumer {
+            flow_controls.add_consumer(sender_address.clone(), id);
+        }
+    }
+
+    pub(crate) fn create_receiver_access_control(
+        &self,
+        flow_controls: &FlowControls,
+    ) -> Arc<dyn OutgoingAccessControl> {
+        Arc::new(FlowControlOutgoingAccessControl::new(
+            flow_controls,
+            self.flow_control_id.clone(),
+            None,
+        ))
+    }
+}
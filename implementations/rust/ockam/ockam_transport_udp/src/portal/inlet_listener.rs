@@ -0,0 +1,175 @@
+use crate::portal::addresses::{Addresses, PortalType};
+use crate::portal::{UdpPortalInternalMessage, UdpPortalWorker, MAX_DATAGRAM_SIZE};
+use crate::UdpInletOptions;
+use ockam_core::{
+    async_trait, Address, DenyAll, OutgoingAccessControl, Processor, RelayMessage, Result, Route,
+};
+use ockam_node::{Context, ProcessorBuilder};
+use ockam_transport_core::TransportError;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::net::UdpSocket;
+use tracing::{debug, error, warn};
+
+/// Flows of an Inlet, indexed by the address of the UDP client
+pub(crate) type InletFlows = Arc<Mutex<HashMap<SocketAddr, Address>>>;
+
+/// Only allow the Inlet listener to send datagrams to its current flows
+#[derive(Debug)]
+struct AllowInletFlows(InletFlows);
+
+#[async_trait]
+impl OutgoingAccessControl for AllowInletFlows {
+    async fn is_authorized(&self, relay_msg: &RelayMessage) -> Result<bool> {
+        let next = relay_msg.onward_route().next()?;
+        if self.0.lock().unwrap().values().any(|flow| flow == next) {
+            return ockam_core::allow();
+        }
+
+        ockam_core::deny()
+    }
+}
+
+/// A UDP Portal Inlet listen processor
+///
+/// UDP Portal Inlet listen processors are created by `UdpTransport`
+/// after a call is made to
+/// [`UdpTransport::create_inlet`](crate::UdpTransport::create_inlet).
+///
+/// Every new UDP client gets its own [`UdpPortalWorker`], which is stopped
+/// once the client stops sending and receiving datagrams.
+pub(crate) struct UdpInletListenProcessor {
+    socket: Arc<UdpSocket>,
+    outlet_listener_route: Route,
+    options: UdpInletOptions,
+    flows: InletFlows,
+    buffer: Vec<u8>,
+}
+
+impl UdpInletListenProcessor {
+    fn new(socket: UdpSocket, outlet_listener_route: Route, options: UdpInletOptions) -> Self {
+        Self {
+            socket: Arc::new(socket),
+            outlet_listener_route,
+            options,
+            flows: Default::default(),
+            buffer: vec![0u8; MAX_DATAGRAM_SIZE],
+        }
+    }
+
+    /// Start a new `UdpInletListenProcessor`
+    pub(crate) async fn start(
+        ctx: &Context,
+        outlet_listener_route: Route,
+        addr: SocketAddr,
+        options: UdpInletOptions,
+    ) -> Result<(SocketAddr, Address)> {
+        let processor_address = Address::random_tagged("UdpInletListenProcessor");
+
+        debug!("Binding UdpInletListenProcessor to {}", addr);
+        let socket = match UdpSocket::bind(addr).await {
+            Ok(socket) => socket,
+            Err(err) => {
+                error!(%addr, %err, "could not bind to address");
+                return Err(TransportError::from(err).into());
+            }
+        };
+        let socket_addr = socket.local_addr().map_err(TransportError::from)?;
+        let processor = Self::new(socket, outlet_listener_route, options);
+        let outgoing_access_control = AllowInletFlows(processor.flows.clone());
+
+        ProcessorBuilder::new(processor)
+            .with_address(processor_address.clone())
+            .with_incoming_access_control(DenyAll)
+            .with_outgoing_access_control(outgoing_access_control)
+            .start(ctx)
+            .await?;
+
+        Ok((socket_addr, processor_address))
+    }
+
+    /// Start a new flow for a client sending its first datagram
+    async fn start_flow(&self, ctx: &Context, peer: SocketAddr) -> Result<Address> {
+        let addresses = Addresses::generate(PortalType::Inlet);
+        let outlet_listener_route = self.outlet_listener_route.clone();
+
+        self.options.setup_flow_control(
+            ctx.flow_controls(),
+            &addresses,
+            outlet_listener_route.next()?,
+        );
+
+        UdpPortalWorker::start_new_inlet(
+            ctx,
+            self.socket.clone(),
+            peer,
+            outlet_listener_route,
+            addresses.clone(),
+            self.options.incoming_access_control.clone(),
+            self.options.idle_timeout,
+            self.flows.clone(),
+        )
+        .await?;
+
+        self.flows
+            .lock()
+            .unwrap()
+            .insert(peer, addresses.internal.clone());
+
+        debug!(
+            "Created Udp Inlet flow for {} at {}",
+            peer, addresses.remote
+        );
+
+        Ok(addresses.internal)
+    }
+}
+
+#[async_trait]
+impl Processor for UdpInletListenProcessor {
+    type Context = Context;
+
+    async fn shutdown(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        // The flows share the socket of the Inlet, they can't outlive it
+        let flows: Vec<Address> = self.flows.lock().unwrap().values().cloned().collect();
+        for internal in flows {
+            let _ = ctx.stop_worker(internal).await;
+        }
+
+        Ok(())
+    }
+
+    async fn process(&mut self, ctx: &mut Self::Context) -> Result<bool> {
+        let (len, peer) = match self.socket.recv_from(&mut self.buffer).await {
+            Ok(res) => res,
+            Err(err) => {
+                warn!(%err, "Failed to receive a datagram, will wait for next datagram");
+                return Ok(true);
+            }
+        };
+        let datagram = self.buffer[..len].to_vec();
+
+        let flow = self.flows.lock().unwrap().get(&peer).cloned();
+        let internal = match flow {
+            Some(internal) => internal,
+            None => match self.start_flow(ctx, peer).await {
+                Ok(internal) => internal,
+                Err(err) => {
+                    warn!(%peer, %err, "Failed to start a flow, dropping the datagram");
+                    return Ok(true);
+                }
+            },
+        };
+
+        // The flow may have expired in the meantime, the datagram is lost in that case
+        if let Err(err) = ctx
+            .send(internal, UdpPortalInternalMessage::Datagram(datagram))
+            .await
+        {
+            debug!(%peer, %err, "Failed to forward a datagram to its flow");
+        }
+
+        Ok(true)
+    }
+}
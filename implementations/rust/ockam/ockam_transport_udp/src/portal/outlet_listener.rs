@@ -0,0 +1,113 @@
+use crate::portal::addresses::{Addresses, PortalType};
+use crate::portal::{UdpPortalMessage, UdpPortalWorker};
+use crate::UdpOutletOptions;
+use ockam_core::{async_trait, Address, DenyAll, Result, Routed, Worker};
+use ockam_node::{Context, WorkerBuilder};
+use ockam_transport_core::TransportError;
+use std::collections::HashSet;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tracing::debug;
+
+/// Flows of an Outlet, indexed by their internal address
+pub(crate) type OutletFlows = Arc<Mutex<HashSet<Address>>>;
+
+/// A UDP Portal Outlet listen worker
+///
+/// UDP Portal Outlet listen workers are created by `UdpTransport`
+/// after a call is made to
+/// [`UdpTransport::create_outlet`](crate::UdpTransport::create_outlet).
+///
+/// Every Inlet flow gets its own [`UdpPortalWorker`] sending datagrams to the
+/// target from a dedicated local socket. The flows are stopped with the listener.
+pub(crate) struct UdpOutletListenWorker {
+    peer: SocketAddr,
+    options: UdpOutletOptions,
+    flows: OutletFlows,
+}
+
+impl UdpOutletListenWorker {
+    /// Create a new `UdpOutletListenWorker`
+    fn new(peer: SocketAddr, options: UdpOutletOptions) -> Self {
+        Self {
+            peer,
+            options,
+            flows: Default::default(),
+        }
+    }
+
+    pub(crate) async fn start(
+        ctx: &Context,
+        address: Address,
+        peer: SocketAddr,
+        options: UdpOutletOptions,
+    ) -> Result<()> {
+        let access_control = options.incoming_access_control.clone();
+
+        options.setup_flow_control_for_outlet_listener(ctx.flow_controls(), &address);
+
+        let worker = Self::new(peer, options);
+        WorkerBuilder::new(worker)
+            .with_address(address)
+            .with_incoming_access_control_arc(access_control)
+            .with_outgoing_access_control(DenyAll)
+            .start(ctx)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Worker for UdpOutletListenWorker {
+    type Context = Context;
+    type Message = UdpPortalMessage;
+
+    async fn shutdown(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        let flows: Vec<Address> = self.flows.lock().unwrap().iter().cloned().collect();
+        for internal in flows {
+            let _ = ctx.stop_worker(internal).await;
+        }
+
+        Ok(())
+    }
+
+    async fn handle_message(
+        &mut self,
+        ctx: &mut Self::Context,
+        msg: Routed<Self::Message>,
+    ) -> Result<()> {
+        let return_route = msg.return_route();
+        let src_addr = msg.src_addr();
+
+        if let UdpPortalMessage::Ping = msg.body() {
+        } else {
+            return Err(TransportError::Protocol.into());
+        }
+
+        let addresses = Addresses::generate(PortalType::Outlet);
+
+        self.options
+            .setup_flow_control_for_outlet(ctx.flow_controls(), &addresses, &src_addr);
+
+        UdpPortalWorker::start_new_outlet(
+            ctx,
+            self.peer,
+            return_route,
+            addresses.clone(),
+            self.options.incoming_access_control.clone(),
+            self.options.idle_timeout,
+            self.flows.clone(),
+        )
+        .await?;
+
+        self.flows
+            .lock()
+            .unwrap()
+            .insert(addresses.internal.clone());
+
+        debug!("Created Udp Outlet flow at {}", addresses.remote);
+
+        Ok(())
+    }
+}
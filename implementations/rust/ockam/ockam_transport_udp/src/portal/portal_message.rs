@@ -0,0 +1,27 @@
+use ockam_core::Message;
+use serde::{Deserialize, Serialize};
+
+/// A command message type for a UDP Portal
+#[derive(Serialize, Deserialize, Message, Debug)]
+pub enum UdpPortalMessage {
+    /// First message that an Inlet flow sends to the Outlet
+    Ping,
+    /// First message that an Outlet flow sends to the Inlet
+    Pong,
+    /// Message to indicate that the flow expired on the other side
+    Disconnect,
+    /// Message with a single datagram
+    Datagram(Vec<u8>),
+}
+
+/// An internal message type for a UDP Portal
+#[derive(Serialize, Deserialize, Message, Clone)]
+pub(crate) enum UdpPortalInternalMessage {
+    /// Datagram received on the local UDP socket
+    Datagram(Vec<u8>),
+    /// Check if the flow is still used
+    IdleCheck,
+}
+
+/// Maximum size of a datagram received by a Portal
+pub const MAX_DATAGRAM_SIZE: usize = 65535;
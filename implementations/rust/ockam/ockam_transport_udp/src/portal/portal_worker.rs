@@ -0,0 +1,451 @@
+use crate::portal::addresses::{Addresses, PortalType};
+use crate::portal::{
+    InletFlows, OutletFlows, UdpPortalInternalMessage, UdpPortalMessage, UdpPortalRecvProcessor,
+};
+use core::time::Duration;
+use ockam_core::{
+    async_trait, AllowAll, AllowOnwardAddress, AllowSourceAddresses, Decodable, DenyAll,
+    IncomingAccessControl, Mailbox, Mailboxes,
+};
+use ockam_core::{Address, Any, Result, Route, Routed, Worker};
+use ockam_node::{Context, DelayedEvent, ProcessorBuilder, WorkerBuilder};
+use ockam_transport_core::TransportError;
+use std::collections::VecDeque;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::Arc;
+use tokio::net::UdpSocket;
+use tracing::{debug, info, trace, warn};
+
+/// Maximum number of datagrams kept by an Inlet flow while waiting for the Outlet flow
+const MAX_PENDING_DATAGRAMS: usize = 64;
+
+/// Enumerate all `UdpPortalWorker` states
+///
+/// Possible state transitions are:
+///
+/// `Outlet`: `SendPong` -> `Initialized`
+/// `Inlet`: `SendPing` -> `ReceivePong` -> `Initialized`
+#[derive(Clone)]
+enum State {
+    SendPing { ping_route: Route },
+    SendPong { pong_route: Route },
+    ReceivePong,
+    Initialized,
+}
+
+/// A UDP Portal worker
+///
+/// A UDP Portal worker manages a single flow: the datagrams exchanged between
+/// one UDP client of the Inlet and the target of the Outlet.
+/// Inlet flows are created by
+/// [`UdpInletListenProcessor`](crate::portal::UdpInletListenProcessor) when a new
+/// client sends its first datagram, Outlet flows are created by
+/// [`UdpOutletListenWorker`](crate::portal::UdpOutletListenWorker) when an Inlet
+/// flow pings it. A flow is closed on both sides once it stays idle for the
+/// configured idle timeout.
+pub(crate) struct UdpPortalWorker {
+    state: State,
+    socket: Option<Arc<UdpSocket>>,
+    peer: SocketAddr,
+    addresses: Addresses,
+    remote_route: Option<Route>,
+    portal_type: PortalType,
+    pending: VecDeque<Vec<u8>>,
+    idle_timer: DelayedEvent<UdpPortalInternalMessage>,
+    idle_timeout: Duration,
+    // set every time a datagram goes through the flow, to detect idle flows
+    activity: bool,
+    inlet_flows: Option<InletFlows>,
+    outlet_flows: Option<OutletFlows>,
+    is_stopping: bool,
+}
+
+impl UdpPortalWorker {
+    /// Start a new `UdpPortalWorker` of type [`PortalType::Inlet`]
+    #[allow(clippy::too_many_arguments)]
+    pub(super) async fn start_new_inlet(
+        ctx: &Context,
+        socket: Arc<UdpSocket>,
+        peer: SocketAddr,
+        ping_route: Route,
+        addresses: Addresses,
+        access_control: Arc<dyn IncomingAccessControl>,
+        idle_timeout: Duration,
+        inlet_flows: InletFlows,
+    ) -> Result<()> {
+        // Datagrams are forwarded by the Inlet listener processor
+        let datagrams_source = ctx.address();
+        Self::start(
+            ctx,
+            peer,
+            State::SendPing { ping_route },
+            Some(socket),
+            addresses,
+            PortalType::Inlet,
+            access_control,
+            idle_timeout,
+            datagrams_source,
+            Some(inlet_flows),
+            None,
+        )
+        .await
+    }
+
+    /// Start a new `UdpPortalWorker` of type [`PortalType::Outlet`]
+    #[allow(clippy::too_many_arguments)]
+    pub(super) async fn start_new_outlet(
+        ctx: &Context,
+        peer: SocketAddr,
+        pong_route: Route,
+        addresses: Addresses,
+        access_control: Arc<dyn IncomingAccessControl>,
+        idle_timeout: Duration,
+        outlet_flows: OutletFlows,
+    ) -> Result<()> {
+        let datagrams_source = addresses.receiver.clone();
+        Self::start(
+            ctx,
+            peer,
+            State::SendPong { pong_route },
+            None,
+            addresses,
+            PortalType::Outlet,
+            access_control,
+            idle_timeout,
+            datagrams_source,
+            None,
+            Some(outlet_flows),
+        )
+        .await
+    }
+
+    /// Start a new `UdpPortalWorker`
+    #[allow(clippy::too_many_arguments)]
+    async fn start(
+        ctx: &Context,
+        peer: SocketAddr,
+        state: State,
+        socket: Option<Arc<UdpSocket>>,
+        addresses: Addresses,
+        portal_type: PortalType,
+        access_control: Arc<dyn IncomingAccessControl>,
+        idle_timeout: Duration,
+        datagrams_source: Address,
+        inlet_flows: Option<InletFlows>,
+        outlet_flows: Option<OutletFlows>,
+    ) -> Result<()> {
+        info!(
+            "Creating new Udp {:?} flow at internal: {}, remote: {}",
+            portal_type.str(),
+            addresses.internal,
+            addresses.remote
+        );
+
+        let idle_timer = DelayedEvent::create(
+            ctx,
+            addresses.internal.clone(),
+            UdpPortalInternalMessage::IdleCheck,
+        )
+        .await?;
+
+        let internal_mailbox = Mailbox::new(
+            addresses.internal.clone(),
+            Arc::new(AllowSourceAddresses(vec![
+                datagrams_source,
+                idle_timer.address(),
+            ])),
+            Arc::new(DenyAll),
+        );
+
+        let remote_mailbox = Mailbox::new(
+            addresses.remote.clone(),
+            access_control,
+            Arc::new(AllowAll), // FIXME: @ac Allow to respond anywhere using return_route
+        );
+
+        let worker = Self {
+            state,
+            socket,
+            peer,
+            addresses,
+            remote_route: None,
+            portal_type,
+            pending: VecDeque::new(),
+            idle_timer,
+            idle_timeout,
+            activity: false,
+            inlet_flows,
+            outlet_flows,
+            is_stopping: false,
+        };
+
+        WorkerBuilder::new(worker)
+            .with_mailboxes(Mailboxes::new(internal_mailbox, vec![remote_mailbox]))
+            .start(ctx)
+            .await?;
+
+        Ok(())
+    }
+}
+
+impl UdpPortalWorker {
+    fn clone_state(&self) -> State {
+        self.state.clone()
+    }
+
+    async fn handle_send_ping(&self, ctx: &Context, ping_route: Route) -> Result<State> {
+        // Force creation of an Outlet flow on the other side
+        ctx.send_from_address(
+            ping_route,
+            UdpPortalMessage::Ping,
+            self.addresses.remote.clone(),
+        )
+        .await?;
+
+        debug!("Udp Inlet flow at: {} sent ping", self.addresses.internal);
+
+        Ok(State::ReceivePong)
+    }
+
+    async fn handle_send_pong(&mut self, ctx: &Context, pong_route: Route) -> Result<State> {
+        // Each Outlet flow uses its own socket so that the target can tell the clients apart
+        let bind_addr = if self.peer.is_ipv4() {
+            SocketAddr::from((Ipv4Addr::UNSPECIFIED, 0))
+        } else {
+            SocketAddr::from((Ipv6Addr::UNSPECIFIED, 0))
+        };
+        let socket = Arc::new(
+            UdpSocket::bind(bind_addr)
+                .await
+                .map_err(TransportError::from)?,
+        );
+
+        let receiver =
+            UdpPortalRecvProcessor::new(socket.clone(), self.peer, self.addresses.internal.clone());
+        ProcessorBuilder::new(receiver)
+            .with_address(self.addresses.receiver.clone())
+            .with_incoming_access_control(DenyAll)
+            .with_outgoing_access_control(AllowOnwardAddress(self.addresses.internal.clone()))
+            .start(ctx)
+            .await?;
+        self.socket = Some(socket);
+
+        // Respond to the Inlet flow
+        ctx.send_from_address(
+            pong_route.clone(),
+            UdpPortalMessage::Pong,
+            self.addresses.remote.clone(),
+        )
+        .await?;
+
+        debug!("Udp Outlet flow at: {} sent pong", self.addresses.internal);
+
+        self.remote_route = Some(pong_route);
+        Ok(State::Initialized)
+    }
+
+    /// Send a datagram received on the local socket to the other side of the portal
+    async fn send_to_remote(&self, ctx: &Context, datagram: Vec<u8>) -> Result<()> {
+        match &self.remote_route {
+            Some(remote_route) => {
+                ctx.send_from_address(
+                    remote_route.clone(),
+                    UdpPortalMessage::Datagram(datagram),
+                    self.addresses.remote.clone(),
+                )
+                .await
+            }
+            None => Err(TransportError::PortalInvalidState.into()),
+        }
+    }
+
+    /// Send a datagram received from the other side of the portal to the local peer
+    async fn send_to_peer(&self, datagram: &[u8]) -> Result<()> {
+        let socket = self
+            .socket
+            .as_ref()
+            .ok_or(TransportError::PortalInvalidState)?;
+
+        // Sending errors are not fatal for a datagram flow, the datagram is simply lost
+        if let Err(err) = socket.send_to(datagram, self.peer).await {
+            warn!(
+                "Failed to send datagram to peer {} with error: {}",
+                self.peer, err
+            );
+        }
+
+        Ok(())
+    }
+
+    async fn handle_idle_check(&mut self, ctx: &Context) -> Result<()> {
+        if self.activity {
+            self.activity = false;
+            return self.idle_timer.schedule(self.idle_timeout).await;
+        }
+
+        info!(
+            "Udp {:?} flow at: {} is idle, closing it",
+            self.portal_type.str(),
+            self.addresses.internal
+        );
+        self.stop(ctx, true).await
+    }
+
+    /// Stop the flow, its receiver is stopped on shutdown for an Outlet flow
+    async fn stop(&mut self, ctx: &Context, notify_remote: bool) -> Result<()> {
+        self.is_stopping = true;
+
+        if notify_remote {
+            if let Some(remote_route) = self.remote_route.take() {
+                // The other side may already be gone
+                let _ = ctx
+                    .send_from_address(
+                        remote_route,
+                        UdpPortalMessage::Disconnect,
+                        self.addresses.remote.clone(),
+                    )
+                    .await;
+            }
+        }
+
+        ctx.stop_worker(self.addresses.internal.clone()).await
+    }
+}
+
+#[async_trait]
+impl Worker for UdpPortalWorker {
+    type Context = Context;
+    type Message = Any;
+
+    async fn initialize(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        let state = self.clone_state();
+
+        match state {
+            State::SendPing { ping_route } => {
+                self.state = self.handle_send_ping(ctx, ping_route).await?;
+            }
+            State::SendPong { pong_route } => {
+                self.state = self.handle_send_pong(ctx, pong_route).await?;
+            }
+            State::ReceivePong | State::Initialized => {
+                return Err(TransportError::PortalInvalidState.into())
+            }
+        }
+
+        self.idle_timer.schedule(self.idle_timeout).await
+    }
+
+    async fn shutdown(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        self.idle_timer.cancel();
+
+        if let Some(inlet_flows) = &self.inlet_flows {
+            let mut inlet_flows = inlet_flows.lock().unwrap();
+            if inlet_flows.get(&self.peer) == Some(&self.addresses.internal) {
+                inlet_flows.remove(&self.peer);
+            }
+        }
+
+        if let Some(outlet_flows) = &self.outlet_flows {
+            outlet_flows
+                .lock()
+                .unwrap()
+                .remove(&self.addresses.internal);
+        }
+
+        // The flow may also be stopped by its Outlet listener
+        if let PortalType::Outlet = self.portal_type {
+            let _ = ctx.stop_processor(self.addresses.receiver.clone()).await;
+        }
+
+        Ok(())
+    }
+
+    async fn handle_message(&mut self, ctx: &mut Context, msg: Routed<Any>) -> Result<()> {
+        if self.is_stopping {
+            return Ok(());
+        }
+
+        // Remove our own address from the route so the other end
+        // knows what to do with the incoming message
+        let mut onward_route = msg.onward_route();
+        let recipient = onward_route.step()?;
+
+        let return_route = msg.return_route();
+
+        if onward_route.next().is_ok() {
+            return Err(TransportError::UnknownRoute.into());
+        }
+
+        if recipient == self.addresses.internal {
+            match UdpPortalInternalMessage::decode(msg.payload())? {
+                UdpPortalInternalMessage::Datagram(datagram) => {
+                    trace!(
+                        "Udp {:?} flow at: {} received local datagram",
+                        self.portal_type.str(),
+                        self.addresses.internal
+                    );
+                    self.activity = true;
+
+                    match self.state {
+                        State::ReceivePong => {
+                            if self.pending.len() == MAX_PENDING_DATAGRAMS {
+                                warn!(
+                                    "Udp Inlet flow at: {} is not connected yet, dropping a datagram",
+                                    self.addresses.internal
+                                );
+                            } else {
+                                self.pending.push_back(datagram);
+                            }
+                        }
+                        State::Initialized => self.send_to_remote(ctx, datagram).await?,
+                        State::SendPing { .. } | State::SendPong { .. } => {
+                            return Err(TransportError::PortalInvalidState.into())
+                        }
+                    }
+                }
+                UdpPortalInternalMessage::IdleCheck => self.handle_idle_check(ctx).await?,
+            }
+
+            return Ok(());
+        }
+
+        trace!(
+            "Udp {:?} flow at: {} received remote message",
+            self.portal_type.str(),
+            self.addresses.internal
+        );
+
+        let state = self.clone_state();
+
+        match (state, UdpPortalMessage::decode(msg.payload())?) {
+            (State::ReceivePong, UdpPortalMessage::Pong) => {
+                debug!(
+                    "Udp Inlet flow at: {} received pong",
+                    self.addresses.internal
+                );
+
+                self.remote_route = Some(return_route);
+                self.state = State::Initialized;
+
+                while let Some(datagram) = self.pending.pop_front() {
+                    self.send_to_remote(ctx, datagram).await?;
+                }
+            }
+            (State::Initialized, UdpPortalMessage::Datagram(datagram)) => {
+                self.activity = true;
+                self.send_to_peer(&datagram).await?;
+            }
+            (_, UdpPortalMessage::Disconnect) => {
+                info!(
+                    "Udp {:?} flow at: {} was closed by the other side",
+                    self.portal_type.str(),
+                    self.addresses.internal
+                );
+                self.stop(ctx, false).await?;
+            }
+            _ => return Err(TransportError::Protocol.into()),
+        }
+
+        Ok(())
+    }
+}
@@ -19,7 +19,7 @@ use tracing::{debug, trace, warn};
 /// # Example
 ///
 /// ```rust
-/// use ockam_transport_udp::{UdpTransport, UdpRendezvousService};
+/// use ockam_transport_udp::{UdpListenerOptions, UdpTransport, UdpRendezvousService};
 /// # use ockam_node::Context;
 /// # use ockam_core::Result;
 /// # async fn test(ctx: Context) -> Result<()> {
@@ -27,7 +27,9 @@ use tracing::{debug, trace, warn};
 /// // Start a Rendezvous service with address 'my_rendezvous' and listen on UDP port 4000
 /// UdpRendezvousService::start(&ctx, "my_rendezvous").await?;
 /// let udp = UdpTransport::create(&ctx).await?;
-/// udp.listen("0.0.0.0:4000").await?;
+/// let options = UdpListenerOptions::new();
+/// ctx.flow_controls().add_consumer("my_rendezvous", &options.flow_control_id());
+/// udp.listen("0.0.0.0:4000", options).await?;
 /// # Ok(()) }
 /// ```
 pub struct UdpRendezvousService;
@@ -143,7 +145,7 @@ impl Worker for RendezvousWorker {
 mod tests {
     use super::RendezvousWorker;
     use crate::rendezvous_service::{RendezvousRequest, RendezvousResponse};
-    use crate::{UdpRendezvousService, UdpTransport, UDP};
+    use crate::{UdpListenerOptions, UdpRendezvousService, UdpTransport, UDP};
     use ockam_core::errcode::Origin;
     use ockam_core::{route, Error, Result, Route, Routed, TransportType, Worker};
     use ockam_node::Context;
@@ -240,7 +242,12 @@ mod tests {
         let rendezvous_route = route![(UDP, bind_addr.to_string()), "rendezvous"];
         ctx.start_worker("echo", EchoUDPAddress).await?;
         let route_echo = route![(UDP, bind_addr.to_string()), "echo"];
-        transport.listen(bind_addr.to_string()).await?;
+        let options = UdpListenerOptions::new();
+        ctx.flow_controls()
+            .add_consumer("rendezvous", &options.flow_control_id());
+        ctx.flow_controls()
+            .add_consumer("echo", &options.flow_control_id());
+        transport.listen(bind_addr.to_string(), options).await?;
 
         // Use echo service to find out our UDP sending address
         let send_addr: String = ctx.send_and_receive(route_echo, String::new()).await?;
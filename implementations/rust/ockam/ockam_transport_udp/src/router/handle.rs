@@ -1,4 +1,5 @@
 use crate::router::messages::{UdpRouterRequest, UdpRouterResponse};
+use crate::UdpListenerOptions;
 use ockam_core::{Address, AllowAll, Result};
 use ockam_node::Context;
 use std::net::SocketAddr;
@@ -31,8 +32,11 @@ impl UdpRouterHandle {
 
     /// Request router start listening on a local UDP port
     /// so the local node can act as a server to other nodes
-    pub async fn listen(&self, local_addr: SocketAddr) -> Result<()> {
-        let msg = UdpRouterRequest::Listen { local_addr };
+    pub async fn listen(&self, local_addr: SocketAddr, options: UdpListenerOptions) -> Result<()> {
+        let msg = UdpRouterRequest::Listen {
+            local_addr,
+            options,
+        };
         let UdpRouterResponse::Listen(res) = self
             .ctx
             .send_and_receive(self.api_addr.clone(), msg)
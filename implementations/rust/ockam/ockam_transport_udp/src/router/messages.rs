@@ -1,3 +1,4 @@
+use crate::UdpListenerOptions;
 use ockam_core::{Message, Result};
 use serde::{Deserialize, Serialize};
 use std::net::SocketAddr;
@@ -6,7 +7,10 @@ use std::net::SocketAddr;
 pub enum UdpRouterRequest {
     /// Listen on a local UDP port so the local node can
     /// act as a server to other nodes
-    Listen { local_addr: SocketAddr },
+    Listen {
+        local_addr: SocketAddr,
+        options: UdpListenerOptions,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug, Message)]
@@ -1,23 +1,23 @@
 use crate::router::messages::{UdpRouterRequest, UdpRouterResponse};
 use crate::router::UdpRouterHandle;
-use crate::workers::{TransportMessageCodec, UdpListenProcessor, UdpSendWorker};
-use futures_util::StreamExt;
+use crate::workers::{UdpListenProcessor, UdpRetransmitProcessor, UdpSendWorker, UnackedFragments};
+use crate::{UdpListenerOptions, UdpSocketOptions};
 use ockam_core::{
     async_trait, Address, AllowAll, Any, Decodable, DenyAll, LocalMessage, Mailbox, Mailboxes,
     Result, Routed, Worker,
 };
 use ockam_node::{Context, WorkerBuilder};
 use ockam_transport_core::TransportError;
-use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs};
 use std::sync::Arc;
 use tokio::net::UdpSocket;
-use tokio_util::udp::UdpFramed;
-use tracing::{debug, error, trace};
+use tracing::{debug, trace};
 
 /// The router for the UDP transport
 ///
-/// The router opens a single 'client' local socket for messages which were
-/// initiaited by an entity within the local node.
+/// The router opens a 'client' local socket for messages which were
+/// initiaited by an entity within the local node. The IPv6 'client' socket
+/// is only opened once a message is sent to an IPv6 peer.
 ///
 /// The router opens a 'server' local socket whenever a user calls
 /// [`listen()`](crate::UdpTransport::listen) on the transport.
@@ -31,18 +31,28 @@ use tracing::{debug, error, trace};
 /// sender. 'server' messages bypass the router as listeners inject the
 /// sender's address into the return route of received messages.
 ///
-/// This transport only supports IPv4.
+/// The messages received on the 'client' socket can only reach the workers
+/// which sent messages through it, since they are the replies to these messages.
+/// The messages received on a 'server' socket can only reach the Consumers
+/// given with the [`UdpListenerOptions`].
 pub(crate) struct UdpRouter {
     ctx: Context,
     main_addr: Address,
     api_addr: Address,
-    /// Sender for 'client' messages
+    /// Sender for 'client' messages to IPv4 peers
     client_sender: Address,
+    /// Sender for 'client' messages to IPv6 peers
+    client_sender_v6: Option<Address>,
+    /// Options of the 'client' sockets
+    client_options: UdpListenerOptions,
 }
 
 impl UdpRouter {
     /// Create and register a new UDP router with the node context
-    pub(crate) async fn register(ctx: &Context) -> Result<UdpRouterHandle> {
+    pub(crate) async fn register(
+        ctx: &Context,
+        client_socket_options: UdpSocketOptions,
+    ) -> Result<UdpRouterHandle> {
         // This context is only used to start workers, doesn't need to send nor receive messages
         let child_ctx = ctx
             .new_detached(
@@ -59,9 +69,11 @@ impl UdpRouter {
         let handle = UdpRouterHandle::try_new(&child_ctx, &api_addr).await?;
 
         // Create sender, listener pair for 'client' messages
+        let client_options = UdpListenerOptions::new().with_socket_options(client_socket_options);
         let client_sender = Self::create_sender_listener(
             &child_ctx,
-            SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), 0),
+            SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0),
+            client_options.clone(),
         )
         .await?;
 
@@ -70,6 +82,8 @@ impl UdpRouter {
             main_addr: main_addr.clone(),
             api_addr: api_addr.clone(),
             client_sender,
+            client_sender_v6: None,
+            client_options,
         };
 
         let main_mailbox = Mailbox::new(
@@ -95,40 +109,102 @@ impl UdpRouter {
 
     /// Handle the routing of 'client' messages
     async fn handle_route(&mut self, ctx: &Context, mut msg: LocalMessage) -> Result<()> {
+        // Allow the replies to reach the sender of the message
+        if let Ok(sender) = msg.transport().return_route.next() {
+            ctx.flow_controls()
+                .add_consumer(sender.clone(), &self.client_options.flow_control_id());
+        }
+
         // Forward message to sender for 'client' messages
-        let addr = self.client_sender.clone();
+        let addr = if Self::is_ipv6_only(msg.transport().onward_route.next()?) {
+            self.client_sender_v6().await?
+        } else {
+            self.client_sender.clone()
+        };
         msg.transport_mut().onward_route.modify().prepend(addr);
         ctx.forward(msg).await
     }
 
+    /// Return true if the peer only resolves to IPv6 addresses
+    fn is_ipv6_only(peer: &Address) -> bool {
+        match peer.address().to_socket_addrs() {
+            Ok(addrs) => {
+                let addrs: Vec<SocketAddr> = addrs.collect();
+                !addrs.is_empty() && addrs.iter().all(SocketAddr::is_ipv6)
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Return the sender of the IPv6 'client' socket, opening the socket if needed
+    async fn client_sender_v6(&mut self) -> Result<Address> {
+        if let Some(sender) = &self.client_sender_v6 {
+            return Ok(sender.clone());
+        }
+
+        let sender = Self::create_sender_listener(
+            &self.ctx,
+            SocketAddr::new(IpAddr::V6(Ipv6Addr::UNSPECIFIED), 0),
+            self.client_options.clone(),
+        )
+        .await?;
+        self.client_sender_v6 = Some(sender.clone());
+
+        Ok(sender)
+    }
+
     /// Create a sender, listener pair for the given socket address.
     ///
     /// Returns the address of the created sender.
-    async fn create_sender_listener(ctx: &Context, local_addr: SocketAddr) -> Result<Address> {
-        // This transport only supports IPv4
-        if !local_addr.is_ipv4() {
-            error!(local_addr = %local_addr, "This transport only supprts IPv4");
-            return Err(TransportError::InvalidAddress.into());
-        }
-
-        // Bind new socket
+    async fn create_sender_listener(
+        ctx: &Context,
+        local_addr: SocketAddr,
+        options: UdpListenerOptions,
+    ) -> Result<Address> {
+        // Bind new socket, shared by the sender and the listener
         let socket = UdpSocket::bind(local_addr)
             .await
             .map_err(|_| TransportError::InvalidAddress)?;
-
-        // Split socket into sink and stream
-        let (sink, stream) = UdpFramed::new(socket, TransportMessageCodec).split();
+        let socket = Arc::new(socket);
 
         debug!("Creating new sender and listener for {}", local_addr);
 
-        // Create sender
         let sender_addr = Address::random_tagged("UdpSendWorker");
-        let sender = UdpSendWorker::new(sink);
+        let listener_addr = Address::random_tagged("UdpListenProcessor");
+        options.setup_flow_control(ctx.flow_controls(), &listener_addr, &sender_addr);
+        let listener_outgoing_access_control =
+            options.create_receiver_access_control(ctx.flow_controls());
+
+        let unacked = UnackedFragments::default();
+
+        // Create sender
+        let sender = UdpSendWorker::new(socket.clone(), options.socket.reliable, unacked.clone());
         // FIXME: @ac
         ctx.start_worker(sender_addr.clone(), sender).await?;
 
         // Create listener
-        UdpListenProcessor::start(ctx, stream, sender_addr.clone()).await?;
+        UdpListenProcessor::start(
+            ctx,
+            listener_addr,
+            socket.clone(),
+            sender_addr.clone(),
+            &options.socket,
+            unacked.clone(),
+            listener_outgoing_access_control,
+        )
+        .await?;
+
+        // Retransmit the fragments which are not acknowledged
+        if options.socket.reliable {
+            UdpRetransmitProcessor::start(
+                ctx,
+                socket,
+                unacked,
+                options.socket.retransmission_timeout,
+                options.socket.max_retransmissions,
+            )
+            .await?;
+        }
 
         Ok(sender_addr)
     }
@@ -162,8 +238,11 @@ impl Worker for UdpRouter {
             let msg = UdpRouterRequest::decode(msg.payload())?;
             trace!("handle_message() API_ADDR: msg = {:?}", msg);
             match msg {
-                UdpRouterRequest::Listen { local_addr } => {
-                    let res = Self::create_sender_listener(&self.ctx, local_addr).await;
+                UdpRouterRequest::Listen {
+                    local_addr,
+                    options,
+                } => {
+                    let res = Self::create_sender_listener(&self.ctx, local_addr, options).await;
                     let res = res.map(|_| ());
                     ctx.send_from_address(return_route, UdpRouterResponse::Listen(res), msg_addr)
                         .await?;
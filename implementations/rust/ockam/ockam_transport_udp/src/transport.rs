@@ -1,31 +1,165 @@
+use crate::portal::{UdpInletListenProcessor, UdpOutletListenWorker};
 use crate::router::{UdpRouter, UdpRouterHandle};
-use ockam_core::{async_trait, Result};
+use crate::{UdpInletOptions, UdpListenerOptions, UdpOutletOptions, UdpSocketOptions};
+use ockam_core::{async_trait, Address, AsyncTryClone, Result, Route};
 use ockam_node::{Context, HasContext};
 use ockam_transport_core::TransportError;
+use std::net::{SocketAddr, ToSocketAddrs};
 
 /// High level management interface for UDP transport
 ///
 /// A node will have, at most, one UDP transport running.
 ///
-/// This transport only supports IPv4.
+/// The transport supports IPv4 and IPv6 peers and listeners. Messages larger than a
+/// datagram are fragmented and reassembled, up to 300 KB. The delivery of the
+/// fragments can be made reliable with [`UdpSocketOptions::with_reliable_delivery`].
 pub struct UdpTransport {
+    ctx: Context,
     router_handle: UdpRouterHandle,
 }
 
 impl UdpTransport {
     /// Create a new UDP transport for the current node
     pub async fn create(ctx: &Context) -> Result<UdpTransport> {
-        let router_handle = UdpRouter::register(ctx).await?;
-        Ok(Self { router_handle })
+        Self::create_with_options(ctx, UdpSocketOptions::new()).await
+    }
+
+    /// Create a new UDP transport for the current node, with the given delivery
+    /// options for the sockets used to send messages to other nodes
+    ///
+    /// ```rust
+    /// use ockam_transport_udp::{UdpSocketOptions, UdpTransport};
+    /// # use ockam_node::Context;
+    /// # use ockam_core::Result;
+    /// # async fn test(ctx: Context) -> Result<()> {
+    /// let udp = UdpTransport::create_with_options(
+    ///     &ctx,
+    ///     UdpSocketOptions::new().with_reliable_delivery(),
+    /// )
+    /// .await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn create_with_options(
+        ctx: &Context,
+        client_socket_options: UdpSocketOptions,
+    ) -> Result<UdpTransport> {
+        let router_handle = UdpRouter::register(ctx, client_socket_options).await?;
+        Ok(Self {
+            ctx: ctx.async_try_clone().await?,
+            router_handle,
+        })
     }
 
     /// Start listening to incoming datagrams on a specified local address
-    pub async fn listen<S: AsRef<str>>(&self, bind_addr: S) -> Result<()> {
+    ///
+    /// The received messages can only reach the Consumers of the
+    /// [`FlowControlId`](ockam_core::flow_control::FlowControlId) of the options.
+    ///
+    /// ```rust
+    /// use ockam_transport_udp::{UdpListenerOptions, UdpTransport};
+    /// # use ockam_node::Context;
+    /// # use ockam_core::Result;
+    /// # async fn test(ctx: Context) -> Result<()> {
+    /// let udp = UdpTransport::create(&ctx).await?;
+    /// let options = UdpListenerOptions::new();
+    /// ctx.flow_controls().add_consumer("echoer", &options.flow_control_id());
+    /// udp.listen("127.0.0.1:8000", options).await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn listen<S: AsRef<str>>(
+        &self,
+        bind_addr: S,
+        options: UdpListenerOptions,
+    ) -> Result<()> {
         let bind_addr = bind_addr
             .as_ref()
             .parse()
             .map_err(|_| TransportError::InvalidAddress)?;
-        self.router_handle.listen(bind_addr).await
+        self.router_handle.listen(bind_addr, options).await
+    }
+}
+
+impl UdpTransport {
+    /// Create a UDP Inlet that listens on bind_addr and forwards the datagrams of each UDP
+    /// client to the Outlet using outlet_route. Every client gets its own flow, with its own
+    /// socket on the Outlet side, which is closed once the client stays idle.
+    /// Pair of corresponding Inlet and Outlet is called Portal.
+    ///
+    /// ```rust
+    /// use ockam_transport_udp::{UdpInletOptions, UdpTransport};
+    /// # use ockam_node::Context;
+    /// # use ockam_core::{Result, route};
+    /// # async fn test(ctx: Context) -> Result<()> {
+    /// let route_path = route!["outlet"];
+    ///
+    /// let udp = UdpTransport::create(&ctx).await?;
+    /// udp.create_inlet("127.0.0.1:5000", route_path, UdpInletOptions::new()).await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn create_inlet(
+        &self,
+        bind_addr: impl Into<String>,
+        outlet_route: impl Into<Route>,
+        options: UdpInletOptions,
+    ) -> Result<(SocketAddr, Address)> {
+        let bind_addr = bind_addr
+            .into()
+            .parse()
+            .map_err(|_| TransportError::InvalidAddress)?;
+        UdpInletListenProcessor::start(&self.ctx, outlet_route.into(), bind_addr, options).await
+    }
+
+    /// Stop inlet at addr
+    pub async fn stop_inlet(&self, addr: impl Into<Address>) -> Result<()> {
+        self.ctx.stop_processor(addr).await?;
+
+        Ok(())
+    }
+
+    /// Create a UDP Outlet Listener at address, that sends the datagrams received from an Inlet
+    /// to peer and sends the datagrams received from peer back to the Inlet.
+    /// Pair of corresponding Inlet and Outlet is called Portal.
+    ///
+    /// ```rust
+    /// use ockam_transport_udp::{UdpOutletOptions, UdpTransport};
+    /// # use ockam_node::Context;
+    /// # use ockam_core::Result;
+    /// # async fn test(ctx: Context) -> Result<()> {
+    ///
+    /// let udp = UdpTransport::create(&ctx).await?;
+    /// udp.create_outlet("outlet", "localhost:9000", UdpOutletOptions::new()).await?;
+    /// # udp.stop_outlet("outlet").await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn create_outlet(
+        &self,
+        address: impl Into<Address>,
+        peer: impl Into<String>,
+        options: UdpOutletOptions,
+    ) -> Result<()> {
+        let peer = peer
+            .into()
+            .to_socket_addrs()
+            .map_err(|_| TransportError::InvalidAddress)?
+            .next()
+            .ok_or(TransportError::InvalidAddress)?;
+        self.create_udp_outlet(address.into(), peer, options).await
+    }
+
+    /// Create a UDP Outlet Listener at address, that sends datagrams to peer
+    pub async fn create_udp_outlet(
+        &self,
+        address: Address,
+        peer: SocketAddr,
+        options: UdpOutletOptions,
+    ) -> Result<()> {
+        UdpOutletListenWorker::start(&self.ctx, address, peer, options).await
+    }
+
+    /// Stop outlet at addr
+    pub async fn stop_outlet(&self, addr: impl Into<Address>) -> Result<()> {
+        self.ctx.stop_worker(addr).await?;
+        Ok(())
     }
 }
 
@@ -1,10 +1,12 @@
-use super::TransportMessageCodec;
-use crate::UDP;
-use futures_util::stream::SplitStream;
-use futures_util::StreamExt;
-use ockam_core::{async_trait, route, Address, AllowAll, LocalMessage, Processor, Result};
-use ockam_node::Context;
-use tokio_util::udp::UdpFramed;
+use super::{Reassembler, UdpPacket, UnackedFragments, MAX_PACKET_SIZE};
+use crate::{UdpSocketOptions, UDP};
+use ockam_core::compat::sync::Arc;
+use ockam_core::{
+    async_trait, route, Address, Decodable, DenyAll, LocalMessage, OutgoingAccessControl,
+    Processor, Result, TransportMessage,
+};
+use ockam_node::{Context, ProcessorBuilder};
+use tokio::net::UdpSocket;
 use tracing::{debug, warn};
 
 /// A listener for the UDP transport
@@ -12,30 +14,49 @@ use tracing::{debug, warn};
 /// This processor handles the reception of messages on a
 /// local socket. See [`UdpRouter`](crate::router::UdpRouter) for more details.
 ///
+/// The fragments received on the socket are reassembled into messages. The fragments
+/// sent in reliable mode are acknowledged, and the acknowledgments of our own fragments
+/// are passed to the paired sender's [`UnackedFragments`].
+///
 /// When a message is received, the address of the paired sender
 /// ([`UdpSendWorker`](crate::workers::UdpSendWorker)) is injected into the message's
 /// return route so that replies are sent to the sender.
 pub(crate) struct UdpListenProcessor {
-    /// The read half of the udnerlying UDP socket.
-    stream: SplitStream<UdpFramed<TransportMessageCodec>>,
+    /// The underlying UDP socket, shared with the sender.
+    socket: Arc<UdpSocket>,
     /// Address of our sender counterpart
     sender_addr: Address,
+    /// Messages being reassembled
+    reassembler: Reassembler,
+    /// Fragments sent by our sender counterpart, waiting for an acknowledgment
+    unacked: UnackedFragments,
+    /// Receive buffer, one byte larger than the largest valid datagram to detect truncation
+    buffer: Vec<u8>,
 }
 
 impl UdpListenProcessor {
     pub(crate) async fn start(
         ctx: &Context,
-        stream: SplitStream<UdpFramed<TransportMessageCodec>>,
+        address: Address,
+        socket: Arc<UdpSocket>,
         sender_addr: Address,
+        socket_options: &UdpSocketOptions,
+        unacked: UnackedFragments,
+        outgoing_access_control: Arc<dyn OutgoingAccessControl>,
     ) -> Result<()> {
         let processor = Self {
-            stream,
+            socket,
             sender_addr,
+            reassembler: Reassembler::new(socket_options.reassembly_timeout),
+            unacked,
+            buffer: vec![0; MAX_PACKET_SIZE + 1],
         };
-        let addr = Address::random_tagged("UdpListenProcessor");
 
-        // FIXME: @ac
-        ctx.start_processor_with_access_control(addr.clone(), processor, AllowAll, AllowAll)
+        ProcessorBuilder::new(processor)
+            .with_address(address)
+            .with_incoming_access_control(DenyAll)
+            .with_outgoing_access_control_arc(outgoing_access_control)
+            .start(ctx)
             .await?;
 
         Ok(())
@@ -52,19 +73,48 @@ impl Processor for UdpListenProcessor {
 
     async fn process(&mut self, ctx: &mut Self::Context) -> Result<bool> {
         debug!("Waiting for incoming UDP datagram...");
-        let (mut msg, addr) = match self.stream.next().await {
-            Some(res) => match res {
-                Ok((msg, addr)) => (msg, addr),
-                Err(e) => {
-                    warn!(
-                        "Failed to read message, will wait for next message: {:?}",
-                        e
-                    );
-                    return Ok(true);
-                }
-            },
-            None => {
-                debug!("No message read, will wait for next message.");
+        let (len, addr) = match self.socket.recv_from(&mut self.buffer).await {
+            Ok(res) => res,
+            Err(e) => {
+                warn!(
+                    "Failed to read datagram, will wait for next datagram: {:?}",
+                    e
+                );
+                return Ok(true);
+            }
+        };
+
+        let fragment = match UdpPacket::decode(&self.buffer[..len]) {
+            Ok(UdpPacket::Fragment(fragment)) => fragment,
+            Ok(UdpPacket::Ack { message_id, index }) => {
+                self.unacked.acknowledge(addr, message_id, index);
+                return Ok(true);
+            }
+            Err(e) => {
+                warn!("Dropping invalid datagram from {}: {}", addr, e);
+                return Ok(true);
+            }
+        };
+
+        if fragment.reliable {
+            let ack = UdpPacket::Ack {
+                message_id: fragment.message_id,
+                index: fragment.index,
+            };
+            if let Err(e) = self.socket.send_to(&ack.encode(), addr).await {
+                warn!("Failed to acknowledge fragment to {}: {:?}", addr, e);
+            }
+        }
+
+        let message = match self.reassembler.insert(addr, fragment) {
+            Some(message) => message,
+            None => return Ok(true),
+        };
+
+        let mut msg = match TransportMessage::decode(&message) {
+            Ok(msg) => msg,
+            Err(e) => {
+                warn!("Dropping invalid message from {}: {}", addr, e);
                 return Ok(true);
             }
         };
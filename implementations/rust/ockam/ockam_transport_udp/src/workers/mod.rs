@@ -1,9 +1,13 @@
 // TODO: Would it be logical to move this `workers` directory into the `router` directory?
 
-pub(crate) use codec::*;
 pub(crate) use listener::*;
+pub(crate) use packet::*;
+pub(crate) use reassembler::*;
+pub(crate) use retransmitter::*;
 pub(crate) use sender::*;
 
-mod codec;
 mod listener;
+mod packet;
+mod reassembler;
+mod retransmitter;
 mod sender;
@@ -0,0 +1,206 @@
+use bytes::{Buf, BufMut};
+use ockam_transport_core::TransportError;
+
+/// Maximum size of the payload of a fragment. Along with the packet, UDP and IP
+/// headers, a fragment fits in the minimum IPv6 MTU of 1280 bytes
+pub(crate) const MAX_FRAGMENT_SIZE: usize = 1200;
+
+/// Maximum number of fragments of a message
+pub(crate) const MAX_FRAGMENTS: usize = 256;
+
+/// Maximum size of an encoded [`TransportMessage`](ockam_core::TransportMessage)
+/// sent over the UDP transport
+pub(crate) const MAX_MESSAGE_SIZE: usize = MAX_FRAGMENT_SIZE * MAX_FRAGMENTS;
+
+/// Size of the largest datagram, used to allocate the receive buffers
+pub(crate) const MAX_PACKET_SIZE: usize = FRAGMENT_HEADER_SIZE + MAX_FRAGMENT_SIZE;
+
+const FRAGMENT_HEADER_SIZE: usize = 11;
+const ACK_HEADER_SIZE: usize = 9;
+
+/// Version of the packet format, sent as the first byte of every datagram
+const PACKET_VERSION: u8 = 1;
+
+const KIND_FRAGMENT: u8 = 1;
+const KIND_ACK: u8 = 2;
+
+const FLAG_RELIABLE: u8 = 0b0000_0001;
+
+/// A datagram of the UDP transport
+///
+/// Each [`TransportMessage`](ockam_core::TransportMessage) is encoded and sent as
+/// one or more fragments, which are reassembled by the receiver. In reliable mode
+/// every fragment is acknowledged and retransmitted until it is. Datagrams start
+/// with a version byte, so that the format can evolve.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum UdpPacket {
+    Fragment(Fragment),
+    Ack { message_id: u32, index: u16 },
+}
+
+/// A fragment of an encoded [`TransportMessage`](ockam_core::TransportMessage)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Fragment {
+    pub(crate) message_id: u32,
+    pub(crate) index: u16,
+    pub(crate) count: u16,
+    pub(crate) reliable: bool,
+    pub(crate) payload: Vec<u8>,
+}
+
+impl UdpPacket {
+    pub(crate) fn encode(&self) -> Vec<u8> {
+        match self {
+            UdpPacket::Fragment(fragment) => {
+                let mut buf = Vec::with_capacity(FRAGMENT_HEADER_SIZE + fragment.payload.len());
+                buf.put_u8(PACKET_VERSION);
+                buf.put_u8(KIND_FRAGMENT);
+                buf.put_u8(if fragment.reliable { FLAG_RELIABLE } else { 0 });
+                buf.put_u32(fragment.message_id);
+                buf.put_u16(fragment.index);
+                buf.put_u16(fragment.count);
+                buf.put_slice(&fragment.payload);
+                buf
+            }
+            UdpPacket::Ack { message_id, index } => {
+                let mut buf = Vec::with_capacity(ACK_HEADER_SIZE);
+                buf.put_u8(PACKET_VERSION);
+                buf.put_u8(KIND_ACK);
+                buf.put_u8(0);
+                buf.put_u32(*message_id);
+                buf.put_u16(*index);
+                buf
+            }
+        }
+    }
+
+    pub(crate) fn decode(mut buf: &[u8]) -> Result<Self, TransportError> {
+        if buf.len() < ACK_HEADER_SIZE {
+            return Err(TransportError::RecvBadMessage);
+        }
+
+        if buf.get_u8() != PACKET_VERSION {
+            return Err(TransportError::Protocol);
+        }
+        let kind = buf.get_u8();
+        let flags = buf.get_u8();
+        let message_id = buf.get_u32();
+        let index = buf.get_u16();
+
+        match kind {
+            KIND_ACK => Ok(UdpPacket::Ack { message_id, index }),
+            KIND_FRAGMENT => {
+                if buf.len() < FRAGMENT_HEADER_SIZE - ACK_HEADER_SIZE {
+                    return Err(TransportError::RecvBadMessage);
+                }
+                let count = buf.get_u16();
+                if count == 0 || index >= count || count as usize > MAX_FRAGMENTS {
+                    return Err(TransportError::Protocol);
+                }
+                if buf.len() > MAX_FRAGMENT_SIZE {
+                    return Err(TransportError::AttackAttmept);
+                }
+
+                Ok(UdpPacket::Fragment(Fragment {
+                    message_id,
+                    index,
+                    count,
+                    reliable: flags & FLAG_RELIABLE != 0,
+                    payload: buf.to_vec(),
+                }))
+            }
+            _ => Err(TransportError::Protocol),
+        }
+    }
+}
+
+/// Split an encoded message into fragments
+pub(crate) fn fragment(
+    message_id: u32,
+    message: &[u8],
+    reliable: bool,
+) -> Result<Vec<Fragment>, TransportError> {
+    if message.len() > MAX_MESSAGE_SIZE {
+        return Err(TransportError::Capacity);
+    }
+
+    // An empty message still needs one fragment
+    let count = ((message.len() + MAX_FRAGMENT_SIZE - 1) / MAX_FRAGMENT_SIZE).max(1) as u16;
+    let fragments = (0..count)
+        .map(|index| {
+            let start = index as usize * MAX_FRAGMENT_SIZE;
+            let end = message.len().min(start + MAX_FRAGMENT_SIZE);
+            Fragment {
+                message_id,
+                index,
+                count,
+                reliable,
+                payload: message[start..end].to_vec(),
+            }
+        })
+        .collect();
+
+    Ok(fragments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode() {
+        let fragment = UdpPacket::Fragment(Fragment {
+            message_id: 42,
+            index: 1,
+            count: 3,
+            reliable: true,
+            payload: vec![1, 2, 3],
+        });
+        assert_eq!(UdpPacket::decode(&fragment.encode()).unwrap(), fragment);
+
+        let ack = UdpPacket::Ack {
+            message_id: 42,
+            index: 1,
+        };
+        assert_eq!(UdpPacket::decode(&ack.encode()).unwrap(), ack);
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        assert!(UdpPacket::decode(&[PACKET_VERSION, KIND_FRAGMENT, 0, 0]).is_err());
+
+        let mut fragment = UdpPacket::Fragment(Fragment {
+            message_id: 1,
+            index: 3,
+            count: 3,
+            reliable: false,
+            payload: vec![],
+        })
+        .encode();
+        assert!(UdpPacket::decode(&fragment).is_err());
+
+        fragment[1] = 7;
+        assert!(UdpPacket::decode(&fragment).is_err());
+
+        let mut ack = UdpPacket::Ack {
+            message_id: 1,
+            index: 0,
+        }
+        .encode();
+        assert!(UdpPacket::decode(&ack).is_ok());
+        ack[0] = PACKET_VERSION + 1;
+        assert!(UdpPacket::decode(&ack).is_err());
+    }
+
+    #[test]
+    fn test_fragment() {
+        let message = vec![7u8; 2 * MAX_FRAGMENT_SIZE + 1];
+        let fragments = fragment(1, &message, false).unwrap();
+        assert_eq!(fragments.len(), 3);
+        assert!(fragments.iter().all(|f| f.count == 3));
+        assert_eq!(fragments[2].payload.len(), 1);
+
+        assert_eq!(fragment(1, &[], false).unwrap().len(), 1);
+        assert!(fragment(1, &vec![0; MAX_MESSAGE_SIZE + 1], false).is_err());
+    }
+}
@@ -0,0 +1,210 @@
+use super::Fragment;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+/// Maximum number of messages being reassembled at the same time on a socket
+const MAX_PENDING_MESSAGES: usize = 1024;
+
+/// Maximum number of completed messages remembered on a socket
+const MAX_COMPLETED_MESSAGES: usize = 4096;
+
+/// Reassembles the fragments received on a socket into messages
+///
+/// A message which doesn't receive all its fragments before the reassembly timeout
+/// is dropped. Completed messages which can receive fragments again, the ones with
+/// several fragments or sent in reliable mode, are remembered for the same duration
+/// so that retransmitted fragments don't deliver a message twice.
+pub(crate) struct Reassembler {
+    timeout: Duration,
+    pending: BTreeMap<(SocketAddr, u32), PendingMessage>,
+    completed: CompletedMessages,
+}
+
+/// Completed messages, in the order of their completion
+#[derive(Default)]
+struct CompletedMessages {
+    keys: BTreeSet<(SocketAddr, u32)>,
+    order: VecDeque<((SocketAddr, u32), Instant)>,
+}
+
+impl CompletedMessages {
+    fn contains(&self, key: &(SocketAddr, u32)) -> bool {
+        self.keys.contains(key)
+    }
+
+    /// Remember a completed message, forgetting the oldest one if there are too many
+    fn insert(&mut self, key: (SocketAddr, u32), now: Instant) {
+        if self.order.len() >= MAX_COMPLETED_MESSAGES {
+            if let Some((oldest, _)) = self.order.pop_front() {
+                self.keys.remove(&oldest);
+            }
+        }
+        if self.keys.insert(key) {
+            self.order.push_back((key, now));
+        }
+    }
+
+    fn remove_expired(&mut self, now: Instant, timeout: Duration) {
+        while let Some((key, completed_at)) = self.order.front() {
+            if now.duration_since(*completed_at) <= timeout {
+                break;
+            }
+            self.keys.remove(key);
+            self.order.pop_front();
+        }
+    }
+}
+
+struct PendingMessage {
+    started_at: Instant,
+    fragments: Vec<Option<Vec<u8>>>,
+    received: usize,
+}
+
+impl Reassembler {
+    pub(crate) fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            pending: BTreeMap::new(),
+            completed: Default::default(),
+        }
+    }
+
+    /// Add a fragment received from `peer`, return the message once all its
+    /// fragments were received
+    pub(crate) fn insert(&mut self, peer: SocketAddr, fragment: Fragment) -> Option<Vec<u8>> {
+        let now = Instant::now();
+        self.remove_expired(now);
+
+        let key = (peer, fragment.message_id);
+        if self.completed.contains(&key) {
+            debug!(%peer, message_id = fragment.message_id, "Dropping duplicate fragment");
+            return None;
+        }
+
+        // Fast path for the messages sent in a single datagram. They are only
+        // retransmitted in reliable mode
+        if fragment.count == 1 {
+            if fragment.reliable {
+                self.completed.insert(key, now);
+            }
+            return Some(fragment.payload);
+        }
+
+        if !self.pending.contains_key(&key) && self.pending.len() >= MAX_PENDING_MESSAGES {
+            warn!(%peer, "Too many messages being reassembled, dropping the oldest one");
+            if let Some(oldest) = self
+                .pending
+                .iter()
+                .min_by_key(|(_, m)| m.started_at)
+                .map(|(k, _)| *k)
+            {
+                self.pending.remove(&oldest);
+            }
+        }
+
+        let message = self.pending.entry(key).or_insert_with(|| PendingMessage {
+            started_at: now,
+            fragments: vec![None; fragment.count as usize],
+            received: 0,
+        });
+
+        if message.fragments.len() != fragment.count as usize {
+            warn!(%peer, message_id = fragment.message_id, "Inconsistent fragment count");
+            return None;
+        }
+
+        let slot = &mut message.fragments[fragment.index as usize];
+        if slot.is_none() {
+            *slot = Some(fragment.payload);
+            message.received += 1;
+        }
+
+        if message.received < message.fragments.len() {
+            return None;
+        }
+
+        let message = self.pending.remove(&key)?;
+        self.completed.insert(key, now);
+        Some(message.fragments.into_iter().flatten().flatten().collect())
+    }
+
+    fn remove_expired(&mut self, now: Instant) {
+        let timeout = self.timeout;
+        self.pending.retain(|(peer, message_id), m| {
+            let expired = now.duration_since(m.started_at) > timeout;
+            if expired {
+                warn!(%peer, message_id, "Reassembly timeout, dropping the message");
+            }
+            !expired
+        });
+        self.completed.remove_expired(now, timeout);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workers::fragment;
+
+    fn peer() -> SocketAddr {
+        "127.0.0.1:4000".parse().unwrap()
+    }
+
+    #[test]
+    fn test_reassemble_out_of_order() {
+        let message: Vec<u8> = (0..3000).map(|i| i as u8).collect();
+        let mut fragments = fragment(1, &message, false).unwrap();
+        fragments.reverse();
+
+        let mut reassembler = Reassembler::new(Duration::from_secs(5));
+        let last = fragments.pop().unwrap();
+        for f in fragments {
+            assert!(reassembler.insert(peer(), f).is_none());
+        }
+        assert_eq!(reassembler.insert(peer(), last), Some(message));
+    }
+
+    #[test]
+    fn test_duplicates_are_dropped() {
+        let fragments = fragment(1, &[1, 2, 3], true).unwrap();
+
+        let mut reassembler = Reassembler::new(Duration::from_secs(5));
+        assert!(reassembler.insert(peer(), fragments[0].clone()).is_some());
+        assert!(reassembler.insert(peer(), fragments[0].clone()).is_none());
+    }
+
+    #[test]
+    fn test_completed_messages_are_bounded() {
+        let mut reassembler = Reassembler::new(Duration::from_secs(5));
+
+        // Unreliable messages sent in a single datagram are not remembered
+        for message_id in 0..10 {
+            let fragments = fragment(message_id, &[1, 2, 3], false).unwrap();
+            assert!(reassembler.insert(peer(), fragments[0].clone()).is_some());
+        }
+        assert_eq!(reassembler.completed.order.len(), 0);
+
+        for message_id in 0..MAX_COMPLETED_MESSAGES as u32 + 1 {
+            let fragments = fragment(message_id, &[1, 2, 3], true).unwrap();
+            assert!(reassembler.insert(peer(), fragments[0].clone()).is_some());
+        }
+        assert_eq!(reassembler.completed.order.len(), MAX_COMPLETED_MESSAGES);
+        assert!(!reassembler.completed.contains(&(peer(), 0)));
+        assert!(reassembler.completed.contains(&(peer(), 1)));
+    }
+
+    #[test]
+    fn test_timeout() {
+        let message = vec![0u8; 3000];
+        let fragments = fragment(1, &message, false).unwrap();
+
+        let mut reassembler = Reassembler::new(Duration::ZERO);
+        assert!(reassembler.insert(peer(), fragments[0].clone()).is_none());
+        std::thread::sleep(Duration::from_millis(1));
+        assert!(reassembler.insert(peer(), fragments[1].clone()).is_none());
+        assert!(reassembler.insert(peer(), fragments[2].clone()).is_none());
+    }
+}
@@ -0,0 +1,127 @@
+use super::Fragment;
+use ockam_core::compat::sync::{Arc, Mutex};
+use ockam_core::{async_trait, Address, DenyAll, Processor, Result};
+use ockam_node::{Context, ProcessorBuilder};
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+use tokio::net::UdpSocket;
+use tracing::{trace, warn};
+
+const MIN_CHECK_INTERVAL: Duration = Duration::from_millis(10);
+
+/// A fragment is identified by its peer, message id and index
+type FragmentKey = (SocketAddr, u32, u16);
+
+/// Fragments sent in reliable mode which were not acknowledged yet
+///
+/// They are shared by the sender, which adds the fragments it sends, the listener,
+/// which removes the acknowledged ones, and the [`UdpRetransmitProcessor`].
+#[derive(Clone, Default)]
+pub(crate) struct UnackedFragments {
+    inner: Arc<Mutex<BTreeMap<FragmentKey, UnackedFragment>>>,
+}
+
+struct UnackedFragment {
+    datagram: Vec<u8>,
+    sent_at: Instant,
+    retransmissions: u32,
+}
+
+impl UnackedFragments {
+    pub(crate) fn add(&self, peer: SocketAddr, fragment: &Fragment, datagram: Vec<u8>) {
+        self.inner.lock().unwrap().insert(
+            (peer, fragment.message_id, fragment.index),
+            UnackedFragment {
+                datagram,
+                sent_at: Instant::now(),
+                retransmissions: 0,
+            },
+        );
+    }
+
+    pub(crate) fn acknowledge(&self, peer: SocketAddr, message_id: u32, index: u16) {
+        self.inner
+            .lock()
+            .unwrap()
+            .remove(&(peer, message_id, index));
+    }
+
+    /// Return the datagrams to send again, and drop the fragments which were
+    /// retransmitted `max_retransmissions` times
+    fn due(&self, timeout: Duration, max_retransmissions: u32) -> Vec<(SocketAddr, Vec<u8>)> {
+        let now = Instant::now();
+        let mut due = vec![];
+        self.inner
+            .lock()
+            .unwrap()
+            .retain(|(peer, message_id, index), fragment| {
+                if now.duration_since(fragment.sent_at) < timeout {
+                    return true;
+                }
+                if fragment.retransmissions >= max_retransmissions {
+                    warn!(%peer, message_id, index, "Fragment was never acknowledged, dropping it");
+                    return false;
+                }
+                fragment.retransmissions += 1;
+                fragment.sent_at = now;
+                due.push((*peer, fragment.datagram.clone()));
+                true
+            });
+        due
+    }
+}
+
+/// Retransmits the fragments of a socket which are not acknowledged in time
+pub(crate) struct UdpRetransmitProcessor {
+    socket: Arc<UdpSocket>,
+    unacked: UnackedFragments,
+    timeout: Duration,
+    max_retransmissions: u32,
+}
+
+impl UdpRetransmitProcessor {
+    pub(crate) async fn start(
+        ctx: &Context,
+        socket: Arc<UdpSocket>,
+        unacked: UnackedFragments,
+        timeout: Duration,
+        max_retransmissions: u32,
+    ) -> Result<()> {
+        let processor = Self {
+            socket,
+            unacked,
+            timeout,
+            max_retransmissions,
+        };
+
+        ProcessorBuilder::new(processor)
+            .with_address(Address::random_tagged("UdpRetransmitProcessor"))
+            .with_incoming_access_control(DenyAll)
+            .with_outgoing_access_control(DenyAll)
+            .start(ctx)
+            .await
+    }
+}
+
+#[async_trait]
+impl Processor for UdpRetransmitProcessor {
+    type Context = Context;
+
+    async fn initialize(&mut self, ctx: &mut Context) -> Result<()> {
+        ctx.set_cluster(crate::CLUSTER_NAME).await
+    }
+
+    async fn process(&mut self, _ctx: &mut Context) -> Result<bool> {
+        tokio::time::sleep((self.timeout / 2).max(MIN_CHECK_INTERVAL)).await;
+
+        for (peer, datagram) in self.unacked.due(self.timeout, self.max_retransmissions) {
+            trace!(%peer, "Retransmitting fragment");
+            if let Err(e) = self.socket.send_to(&datagram, peer).await {
+                warn!(%peer, "Failed to retransmit fragment: {}", e);
+            }
+        }
+
+        Ok(true)
+    }
+}
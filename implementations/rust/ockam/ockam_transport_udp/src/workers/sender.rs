@@ -1,28 +1,39 @@
-use super::TransportMessageCodec;
+use super::{fragment, UdpPacket, UnackedFragments};
 use crate::UDP;
-use futures_util::{stream::SplitSink, SinkExt};
-use ockam_core::{async_trait, Any, Result, Routed, TransportMessage, Worker};
+use ockam_core::compat::sync::Arc;
+use ockam_core::{async_trait, Any, Encodable, Result, Routed, Worker};
 use ockam_node::Context;
 use ockam_transport_core::TransportError;
 use std::net::{SocketAddr, ToSocketAddrs};
-use tokio_util::udp::UdpFramed;
+use tokio::net::UdpSocket;
 use tracing::{error, trace, warn};
 
 /// A sender for the UDP transport
 ///
 /// This worker handles the sending of messages on a
 /// local socket. See [`UdpRouter`](crate::router::UdpRouter) for more details.
+///
+/// Each message is split into fragments, see [`UdpPacket`](crate::workers::UdpPacket).
 pub(crate) struct UdpSendWorker {
-    /// The read half of the udnerlying UDP socket.
-    sink: SplitSink<UdpFramed<TransportMessageCodec>, (TransportMessage, SocketAddr)>,
+    /// The underlying UDP socket, shared with the listener.
+    socket: Arc<UdpSocket>,
+    /// Whether the fragments must be acknowledged by the peer
+    reliable: bool,
+    /// Fragments waiting for an acknowledgment
+    unacked: UnackedFragments,
+    /// Identifier of the next message sent on the socket
+    next_message_id: u32,
 }
 
 impl UdpSendWorker {
     /// Create a new `UdpSendWorker`
-    pub(crate) fn new(
-        sink: SplitSink<UdpFramed<TransportMessageCodec>, (TransportMessage, SocketAddr)>,
-    ) -> Self {
-        Self { sink }
+    pub(crate) fn new(socket: Arc<UdpSocket>, reliable: bool, unacked: UnackedFragments) -> Self {
+        Self {
+            socket,
+            reliable,
+            unacked,
+            next_message_id: rand::random(),
+        }
     }
 }
 
@@ -42,7 +53,7 @@ impl Worker for UdpSendWorker {
 
         trace!("Sending message to {:?}", msg.onward_route);
 
-        // Resolve peer address to IPv4 SocketAddr(s).
+        // Resolve peer address to SocketAddr(s) of the socket's address family
         let peer_addr = msg.onward_route.step()?;
 
         if peer_addr.transport_type() != UDP {
@@ -51,38 +62,54 @@ impl Worker for UdpSendWorker {
             return Err(TransportError::UnknownRoute.into());
         }
 
+        let is_ipv4 = self
+            .socket
+            .local_addr()
+            .map_err(TransportError::from)?
+            .is_ipv4();
         let peer_addr = peer_addr.address();
         let peer_addrs = peer_addr
             .to_socket_addrs()
             .map_err(|_| TransportError::InvalidAddress)?;
-        let peer_addrs: Vec<_> = peer_addrs.filter(SocketAddr::is_ipv4).collect();
+        let peer_addrs: Vec<_> = peer_addrs.filter(|a| a.is_ipv4() == is_ipv4).collect();
 
         // Try to send to first SocketAddr
-        let addr = match peer_addrs.first() {
+        let addr: SocketAddr = match peer_addrs.first() {
             Some(a) => *a,
             None => {
-                warn!("No IPv4 address resolved for peer {:?}", peer_addr);
+                warn!(
+                    "No address of the socket's address family resolved for peer {:?}",
+                    peer_addr
+                );
                 return Err(TransportError::UnknownRoute.into());
             }
         };
 
-        // Error on conditions that _might_ put the sink
+        // Error on conditions that _might_ put the socket
         // into an error state
         if addr.port() == 0 {
             warn!(peer_addr = %peer_addr, "Will not send to address");
             return Err(TransportError::InvalidAddress.into());
         }
 
-        // Send
-        match self.sink.send((msg.clone(), addr)).await {
-            Ok(()) => {
-                trace!("Successful send to {}", addr);
-                Ok(())
+        let message_id = self.next_message_id;
+        self.next_message_id = self.next_message_id.wrapping_add(1);
+
+        let encoded = msg.encode().map_err(|_| TransportError::SendBadMessage)?;
+        for fragment in fragment(message_id, &encoded, self.reliable)? {
+            let datagram = UdpPacket::Fragment(fragment.clone()).encode();
+            if self.reliable {
+                self.unacked.add(addr, &fragment, datagram.clone());
             }
-            Err(e) => {
+
+            // Send
+            if let Err(e) = self.socket.send_to(&datagram, addr).await {
                 error!("Failed send to {}: {:?}", addr, e);
-                Err(e.into())
+                return Err(TransportError::from(e).into());
             }
         }
+
+        trace!("Successful send to {}", addr);
+        Ok(())
     }
 }
@@ -0,0 +1,108 @@
+use std::time::Duration;
+
+use tokio::net::UdpSocket;
+
+use ockam_core::compat::rand::random;
+use ockam_core::{route, Result};
+use ockam_node::Context;
+use ockam_transport_udp::{UdpInletOptions, UdpOutletOptions, UdpTransport};
+
+const LENGTH: usize = 32;
+
+async fn setup(ctx: &Context, idle_timeout: Duration) -> Result<(UdpTransport, String, UdpSocket)> {
+    let udp = UdpTransport::create(ctx).await?;
+
+    let target = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let target_address = target.local_addr().unwrap().to_string();
+    udp.create_outlet(
+        "outlet",
+        target_address,
+        UdpOutletOptions::new().with_idle_timeout(idle_timeout),
+    )
+    .await?;
+
+    let (inlet_saddr, _) = udp
+        .create_inlet(
+            "127.0.0.1:0",
+            route!["outlet"],
+            UdpInletOptions::new().with_idle_timeout(idle_timeout),
+        )
+        .await?;
+
+    Ok((udp, inlet_saddr.to_string(), target))
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test(timeout = 5000)]
+async fn portal__datagrams_from_two_clients__should_be_kept_apart(ctx: &mut Context) -> Result<()> {
+    let (_udp, inlet_addr, target) = setup(ctx, Duration::from_secs(10)).await?;
+
+    let client1 = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let client2 = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let mut buffer = [0u8; LENGTH];
+
+    for client in [&client1, &client2] {
+        let request: [u8; LENGTH] = random();
+        client.send_to(&request, &inlet_addr).await.unwrap();
+
+        let (len, flow_addr) = target.recv_from(&mut buffer).await.unwrap();
+        assert_eq!(&buffer[..len], &request);
+
+        // The target replies to the socket of the flow, the reply only reaches that client
+        let response: [u8; LENGTH] = random();
+        target.send_to(&response, flow_addr).await.unwrap();
+
+        let (len, from) = client.recv_from(&mut buffer).await.unwrap();
+        assert_eq!(&buffer[..len], &response);
+        assert_eq!(from.to_string(), inlet_addr);
+    }
+
+    ctx.stop().await
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test(timeout = 5000)]
+async fn portal__idle_flow__should_be_closed(ctx: &mut Context) -> Result<()> {
+    let (_udp, inlet_addr, target) = setup(ctx, Duration::from_millis(200)).await?;
+
+    let client = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let mut buffer = [0u8; LENGTH];
+
+    let request: [u8; LENGTH] = random();
+    client.send_to(&request, &inlet_addr).await.unwrap();
+    let (_, first_flow_addr) = target.recv_from(&mut buffer).await.unwrap();
+
+    // Wait for the flow to expire, the next datagram of the client starts a new flow
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    client.send_to(&request, &inlet_addr).await.unwrap();
+    let (len, second_flow_addr) = target.recv_from(&mut buffer).await.unwrap();
+    assert_eq!(&buffer[..len], &request);
+    assert_ne!(first_flow_addr, second_flow_addr);
+
+    ctx.stop().await
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test(timeout = 5000)]
+async fn portal__stopped_outlet__should_stop_its_flows(ctx: &mut Context) -> Result<()> {
+    let (udp, inlet_addr, target) = setup(ctx, Duration::from_secs(10)).await?;
+
+    let client = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let mut buffer = [0u8; LENGTH];
+
+    let request: [u8; LENGTH] = random();
+    client.send_to(&request, &inlet_addr).await.unwrap();
+    let (_, flow_addr) = target.recv_from(&mut buffer).await.unwrap();
+
+    udp.stop_outlet("outlet").await?;
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    // The socket of the flow is closed, the reply doesn't reach the client anymore
+    let response: [u8; LENGTH] = random();
+    target.send_to(&response, flow_addr).await.unwrap();
+    let res = tokio::time::timeout(Duration::from_millis(500), client.recv_from(&mut buffer)).await;
+    assert!(res.is_err());
+
+    ctx.stop().await
+}
@@ -1,7 +1,7 @@
 use ockam_core::compat::rand::{self, Rng};
 use ockam_core::{route, Address, AllowAll, Result, Routed, Worker};
 use ockam_node::{Context, MessageReceiveOptions, MessageSendReceiveOptions};
-use ockam_transport_udp::{UdpTransport, UDP};
+use ockam_transport_udp::{UdpListenerOptions, UdpSocketOptions, UdpTransport, UDP};
 use std::net::SocketAddr;
 use std::time::Duration;
 use tracing::{debug, error, trace};
@@ -24,7 +24,9 @@ async fn reply_from_correct_server_port(ctx: &mut Context) -> Result<()> {
     // Listener
     {
         ctx.start_worker("echoer", Echoer::new()).await?;
-        transport.listen(bind_addr.to_string()).await?;
+        transport
+            .listen(bind_addr.to_string(), echoer_listener_options(ctx))
+            .await?;
     };
 
     // Sender
@@ -61,8 +63,7 @@ async fn reply_from_correct_server_port(ctx: &mut Context) -> Result<()> {
 /// The transport should still allow sending of messages
 /// even after a send socket error.
 ///
-/// Examples of errors are when an IPv4 socket is asked to send to
-/// an IPv6 address, or when we ask an IPv4 socket to send to port 0.
+/// An example of error is when we ask a socket to send to port 0.
 #[ockam_macros::test]
 async fn recover_from_sender_error(ctx: &mut Context) -> Result<()> {
     // Find an available port
@@ -80,7 +81,9 @@ async fn recover_from_sender_error(ctx: &mut Context) -> Result<()> {
 
     // Listener
     ctx.start_worker("echoer", Echoer::new()).await?;
-    transport.listen(addr_ok.clone()).await?;
+    transport
+        .listen(addr_ok.clone(), echoer_listener_options(ctx))
+        .await?;
 
     // Send message to try and cause a socket send error
     let r = route![(UDP, addr_nok), "echoer"];
@@ -125,7 +128,9 @@ async fn send_from_same_client_port(ctx: &mut Context) -> Result<()> {
     // Note: it is the Echoer which is checking the UDP ports for this test
     ctx.start_worker("echoer", Echoer::new()).await?;
     for addr in &bind_addrs {
-        transport.listen(addr.to_string()).await?;
+        transport
+            .listen(addr.to_string(), echoer_listener_options(ctx))
+            .await?;
     }
 
     // Send messages
@@ -163,7 +168,9 @@ async fn send_receive(ctx: &mut Context) -> Result<()> {
     // Listener
     {
         ctx.start_worker("echoer", Echoer::new()).await?;
-        transport.listen(bind_addr.clone()).await?;
+        transport
+            .listen(bind_addr.clone(), echoer_listener_options(ctx))
+            .await?;
     };
 
     // Sender
@@ -192,6 +199,150 @@ async fn send_receive(ctx: &mut Context) -> Result<()> {
     Ok(())
 }
 
+/// Messages larger than a datagram should be fragmented and reassembled
+#[ockam_macros::test]
+async fn send_receive_large_message(ctx: &mut Context) -> Result<()> {
+    let bind_addr = utils::available_local_ports(1)
+        .await?
+        .first()
+        .unwrap()
+        .to_string();
+
+    let transport = UdpTransport::create(ctx).await?;
+    ctx.start_worker("echoer", Echoer::new()).await?;
+    transport
+        .listen(bind_addr.clone(), echoer_listener_options(ctx))
+        .await?;
+
+    let msg = random_string(100_000);
+    let r = route![(UDP, bind_addr), "echoer"];
+    let reply = ctx
+        .send_and_receive_extended::<String>(
+            r,
+            msg.clone(),
+            MessageSendReceiveOptions::new().with_timeout(TIMEOUT),
+        )
+        .await?
+        .body();
+    assert_eq!(reply, msg, "Should receive the same message");
+
+    ctx.stop().await?;
+    Ok(())
+}
+
+/// Messages sent in reliable mode should be acknowledged and delivered
+#[ockam_macros::test]
+async fn send_receive_reliable(ctx: &mut Context) -> Result<()> {
+    let bind_addr = utils::available_local_ports(1)
+        .await?
+        .first()
+        .unwrap()
+        .to_string();
+
+    let transport =
+        UdpTransport::create_with_options(ctx, UdpSocketOptions::new().with_reliable_delivery())
+            .await?;
+    ctx.start_worker("echoer", Echoer::new()).await?;
+    transport
+        .listen(
+            bind_addr.clone(),
+            echoer_listener_options(ctx)
+                .with_socket_options(UdpSocketOptions::new().with_reliable_delivery()),
+        )
+        .await?;
+
+    for size in [16, 10_000] {
+        let msg = random_string(size);
+        let r = route![(UDP, bind_addr.clone()), "echoer"];
+        let reply = ctx
+            .send_and_receive_extended::<String>(
+                r,
+                msg.clone(),
+                MessageSendReceiveOptions::new().with_timeout(TIMEOUT),
+            )
+            .await?
+            .body();
+        assert_eq!(reply, msg, "Should receive the same message");
+    }
+
+    ctx.stop().await?;
+    Ok(())
+}
+
+#[ockam_macros::test]
+async fn send_receive_ipv6(ctx: &mut Context) -> Result<()> {
+    let bind_addr = utils::available_local_ipv6_port().await?.to_string();
+    debug!("bind_addr = {:?}", bind_addr);
+
+    let transport = UdpTransport::create(ctx).await?;
+    ctx.start_worker("echoer", Echoer::new()).await?;
+    transport
+        .listen(bind_addr.clone(), echoer_listener_options(ctx))
+        .await?;
+
+    let msg = random_string(256);
+    let r = route![(UDP, bind_addr), "echoer"];
+    let reply = ctx
+        .send_and_receive_extended::<String>(
+            r,
+            msg.clone(),
+            MessageSendReceiveOptions::new().with_timeout(TIMEOUT),
+        )
+        .await?
+        .body();
+    assert_eq!(reply, msg, "Should receive the same message");
+
+    ctx.stop().await?;
+    Ok(())
+}
+
+/// Messages received by the listener should only reach its Consumers
+#[ockam_macros::test]
+async fn listener_should_not_forward_to_non_consumers(ctx: &mut Context) -> Result<()> {
+    let bind_addr = utils::available_local_ports(1)
+        .await?
+        .first()
+        .unwrap()
+        .to_string();
+
+    let transport = UdpTransport::create(ctx).await?;
+
+    // The echoer is not a Consumer of the listener
+    ctx.start_worker("echoer", Echoer::new()).await?;
+    transport
+        .listen(bind_addr.clone(), UdpListenerOptions::new())
+        .await?;
+
+    let r = route![(UDP, bind_addr), "echoer"];
+    let res: Result<Routed<String>> = ctx
+        .send_and_receive_extended(
+            r,
+            String::from("Hola"),
+            MessageSendReceiveOptions::new().with_timeout(Duration::from_millis(500)),
+        )
+        .await;
+    assert!(res.is_err(), "The echoer should not receive the message");
+
+    ctx.stop().await?;
+    Ok(())
+}
+
+fn random_string(size: usize) -> String {
+    rand::thread_rng()
+        .sample_iter(&rand::distributions::Alphanumeric)
+        .take(size)
+        .map(char::from)
+        .collect()
+}
+
+/// Listener options allowing the received messages to reach the echoer
+fn echoer_listener_options(ctx: &Context) -> UdpListenerOptions {
+    let options = UdpListenerOptions::new();
+    ctx.flow_controls()
+        .add_consumer("echoer", &options.flow_control_id());
+    options
+}
+
 pub struct Echoer {
     prev_src_addr: Option<String>,
 }
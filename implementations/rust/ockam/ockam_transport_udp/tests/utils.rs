@@ -26,3 +26,12 @@ pub async fn available_local_ports(count: usize) -> Result<Vec<SocketAddr>> {
 
     Ok(addrs)
 }
+
+/// Helper function. Try to find an available local IPv6 UDP port.
+pub async fn available_local_ipv6_port() -> Result<SocketAddr> {
+    let s = UdpSocket::bind("[::1]:0")
+        .await
+        .map_err(|e| Error::new_unknown(Origin::Unknown, e))?;
+    s.local_addr()
+        .map_err(|e| Error::new_unknown(Origin::Unknown, e))
+}
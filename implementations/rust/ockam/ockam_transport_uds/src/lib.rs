@@ -8,9 +8,11 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate core;
 
+mod options;
 mod router;
 mod transport;
 mod workers;
+pub use options::*;
 use tokio::net::unix::SocketAddr as TokioSocketAddr;
 use tracing::error;
 pub use transport::*;
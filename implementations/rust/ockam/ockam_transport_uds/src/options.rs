@@ -0,0 +1,132 @@
+use ockam_core::compat::sync::Arc;
+use ockam_core::flow_control::{FlowControlId, FlowControlOutgoingAccessControl, FlowControls};
+use ockam_core::{Address, OutgoingAccessControl};
+use serde::{Deserialize, Serialize};
+
+/// Trust Options for a UDS connection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UdsConnectionOptions {
+    pub(crate) consumer: Vec<FlowControlId>,
+    pub(crate) flow_control_id: FlowControlId,
+}
+
+impl UdsConnectionOptions {
+    #[allow(clippy::new_without_default)]
+    /// Mark this Uds Receiver as a Producer with a random [`FlowControlId`]
+    pub fn new() -> Self {
+        Self {
+            consumer: vec![],
+            flow_control_id: FlowControls::generate_flow_control_id(),
+        }
+    }
+
+    /// Mark that this Connection is a Consumer for to the given [`FlowControlId`]
+    pub fn as_consumer(mut self, id: &FlowControlId) -> Self {
+        self.consumer.push(id.clone());
+
+        self
+    }
+
+    /// Getter for freshly generated [`FlowControlId`]
+    pub fn flow_control_id(&self) -> FlowControlId {
+        self.flow_control_id.clone()
+    }
+}
+
+impl UdsConnectionOptions {
+    /// The sender addresses are the address of the send worker and the UDS addresses
+    /// of the peer, which are routed to the send worker by the UDS router
+    pub(crate) fn setup_flow_control(
+        &self,
+        flow_controls: &FlowControls,
+        receiver_address: &Address,
+        sender_addresses: &[Address],
+    ) {
+        flow_controls.add_producer(
+            receiver_address.clone(),
+            &self.flow_control_id,
+            None,
+            sender_addresses.to_vec(),
+        );
+
+        for id in &self.consumer {
+            for address in sender_addresses {
+                flow_controls.add_consumer(address.clone(), id);
+            }
+        }
+    }
+
+    pub(crate) fn create_receiver_access_control(
+        &self,
+        flow_controls: &FlowControls,
+    ) -> Arc<dyn OutgoingAccessControl> {
+        Arc::new(FlowControlOutgoingAccessControl::new(
+            flow_controls,
+            self.flow_control_id.clone(),
+            None,
+        ))
+    }
+}
+
+/// Trust Options for a UDS listener
+#[derive(Debug)]
+pub struct UdsListenerOptions {
+    pub(crate) flow_control_id: FlowControlId,
+}
+
+impl UdsListenerOptions {
+    /// Mark this Uds Listener as a Spawner with given [`FlowControlId`].
+    /// NOTE: Spawned connections get fresh random [`FlowControlId`], however they are still marked
+    /// with Spawner's [`FlowControlId`]
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            flow_control_id: FlowControls::generate_flow_control_id(),
+        }
+    }
+
+    /// Getter for freshly generated [`FlowControlId`]
+    pub fn spawner_flow_control_id(&self) -> FlowControlId {
+        self.flow_control_id.clone()
+    }
+}
+
+impl UdsListenerOptions {
+    pub(crate) fn setup_flow_control_for_listener(
+        &self,
+        flow_controls: &FlowControls,
+        address: &Address,
+    ) {
+        flow_controls.add_spawner(address.clone(), &self.flow_control_id);
+    }
+
+    pub(crate) fn setup_flow_control_for_connection(
+        &self,
+        flow_controls: &FlowControls,
+        receiver_address: &Address,
+        sender_addresses: &[Address],
+    ) -> FlowControlId {
+        let flow_control_id = FlowControls::generate_flow_control_id();
+
+        flow_controls.add_producer(
+            receiver_address.clone(),
+            &flow_control_id,
+            Some(&self.flow_control_id),
+            sender_addresses.to_vec(),
+        );
+
+        flow_control_id
+    }
+
+    pub(crate) fn create_receiver_access_control(
+        &self,
+        flow_controls: &FlowControls,
+        flow_control_id: FlowControlId,
+    ) -> Arc<dyn OutgoingAccessControl> {
+        Arc::new(FlowControlOutgoingAccessControl::new(
+            flow_controls,
+            flow_control_id,
+            Some(self.flow_control_id.clone()),
+        ))
+    }
+}
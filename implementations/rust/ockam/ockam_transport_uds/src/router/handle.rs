@@ -9,7 +9,7 @@ use ockam_transport_core::TransportError;
 use crate::{
     address_from_socket_addr, parse_socket_addr,
     workers::{UdsListenProcessor, WorkerPair},
-    UDS,
+    UdsConnectionOptions, UdsListenerOptions, UDS,
 };
 
 use super::{UdsRouterRequest, UdsRouterResponse};
@@ -67,19 +67,34 @@ impl UdsRouterHandle {
 
 impl UdsRouterHandle {
     /// Bind an incoming connection listener for this router
-    pub async fn bind(&self, addr: impl Into<SocketAddr>) -> Result<SocketAddr> {
+    pub async fn bind(
+        &self,
+        addr: impl Into<SocketAddr>,
+        options: UdsListenerOptions,
+    ) -> Result<SocketAddr> {
         let socket_addr = addr.into();
-        UdsListenProcessor::start(&self.ctx, self.async_try_clone().await?, socket_addr).await
+        UdsListenProcessor::start(
+            &self.ctx,
+            self.async_try_clone().await?,
+            socket_addr,
+            options,
+        )
+        .await
     }
 
     /// Establish an outgoing UDS connection on an existing transport
-    pub async fn connect<S: AsRef<str>>(&self, peer: S) -> Result<Address> {
+    pub async fn connect<S: AsRef<str>>(
+        &self,
+        peer: S,
+        options: UdsConnectionOptions,
+    ) -> Result<Address> {
         let response = self
             .ctx
             .send_and_receive(
                 self.api_addr.clone(),
                 UdsRouterRequest::Connect {
                     peer: peer.as_ref().to_string(),
+                    options,
                 },
             )
             .await?;
@@ -1,3 +1,4 @@
+use crate::UdsConnectionOptions;
 use ockam_core::{Address, Message, Result};
 use serde::{Deserialize, Serialize};
 
@@ -11,7 +12,10 @@ pub enum UdsRouterRequest {
         self_addr: Address,
     },
     /// Connect to a UDS Peer
-    Connect { peer: String },
+    Connect {
+        peer: String,
+        options: UdsConnectionOptions,
+    },
     /// Disconnect from a UDS Peer
     Disconnect { peer: String },
     /// Unregister (usually, after disconnection)
@@ -1,7 +1,7 @@
 use core::ops::Deref;
 use ockam_core::{
-    async_trait, compat::sync::Arc, Address, AllowAll, Any, Decodable, LocalMessage, Mailbox,
-    Mailboxes, Result, Routed, Worker,
+    async_trait, compat::sync::Arc, flow_control::FlowControlId, Address, AllowAll, Any, Decodable,
+    LocalMessage, Mailbox, Mailboxes, Result, Routed, Worker,
 };
 use ockam_node::{Context, WorkerBuilder};
 use ockam_transport_core::TransportError;
@@ -9,7 +9,7 @@ use std::collections::BTreeMap;
 use tracing::{debug, error, trace};
 
 use super::{UdsRouterHandle, UdsRouterRequest, UdsRouterResponse};
-use crate::{address_from_socket_addr, workers::UdsSendWorker, UDS};
+use crate::{address_from_socket_addr, workers::UdsSendWorker, UdsConnectionOptions, UDS};
 
 /// A UDS address router and connection listener
 ///
@@ -18,11 +18,17 @@ use crate::{address_from_socket_addr, workers::UdsSendWorker, UDS};
 /// facilitates this.
 ///
 /// Optionally you can also start listening for incoming connections
+///
+/// Connections created automatically when routing a message use default
+/// [`UdsConnectionOptions`]. Since their receivers can only answer the workers
+/// that sent messages through them, the router marks these workers as Consumers
+/// of the connection.
 pub(crate) struct UdsRouter {
     ctx: Context,
     main_addr: Address,
     api_addr: Address,
     map: BTreeMap<Address, Address>,
+    auto_connections: BTreeMap<Address, FlowControlId>,
     allow_auto_connection: bool,
 }
 
@@ -47,6 +53,7 @@ impl UdsRouter {
             main_addr: main_addr.clone(),
             api_addr: api_addr.clone(),
             map: BTreeMap::new(),
+            auto_connections: BTreeMap::new(),
             allow_auto_connection: true,
         };
 
@@ -85,13 +92,23 @@ impl UdsRouter {
 impl UdsRouter {
     /// Handles any [`UdsRouterRequest::Connect`] messages received by
     /// this node's worker
-    async fn handle_connect(&mut self, peer: String) -> Result<Address> {
+    async fn handle_connect(
+        &mut self,
+        peer: String,
+        options: UdsConnectionOptions,
+    ) -> Result<Address> {
         let (peer_addr, pathnames) = UdsRouterHandle::resolve_peer(peer)?;
 
         let router_handle = self.create_self_handle().await?;
-        let pair =
-            UdsSendWorker::start_pair(&self.ctx, router_handle, None, peer_addr, pathnames.clone())
-                .await?;
+        let pair = UdsSendWorker::start_pair(
+            &self.ctx,
+            router_handle,
+            None,
+            peer_addr,
+            pathnames.clone(),
+            options,
+        )
+        .await?;
 
         let path = match pair.peer().as_pathname() {
             Some(p) => p,
@@ -173,6 +190,7 @@ impl UdsRouter {
         trace!("UDS unregistration request: {}", &self_addr);
 
         self.map.retain(|_, v| v != &self_addr);
+        self.auto_connections.remove(&self_addr);
 
         Ok(())
     }
@@ -191,6 +209,14 @@ impl UdsRouter {
         // Resolve route to the connection worker responsible for the next hop
         let next = self.resolve_route(onward).await?;
 
+        // Allow the automatically created connection to answer the sender
+        if let Some(flow_control_id) = self.auto_connections.get(&next) {
+            if let Ok(sender) = msg.transport().return_route.next() {
+                ctx.flow_controls()
+                    .add_consumer(sender.clone(), flow_control_id);
+            }
+        }
+
         // Modify the transport message route
         let _ = msg.transport_mut().onward_route.step()?;
         msg.transport_mut()
@@ -243,7 +269,11 @@ impl UdsRouter {
         }
 
         if self.allow_auto_connection {
-            self.handle_connect(peer).await
+            let options = UdsConnectionOptions::new();
+            let flow_control_id = options.flow_control_id();
+            let next = self.handle_connect(peer, options).await?;
+            self.auto_connections.insert(next.clone(), flow_control_id);
+            Ok(next)
         } else {
             error!(
                 "Failed to resolve route, no existing connection to peer: {}",
@@ -279,8 +309,8 @@ impl Worker for UdsRouter {
                     ctx.send(return_route, UdsRouterResponse::Register(res))
                         .await?;
                 }
-                UdsRouterRequest::Connect { peer } => {
-                    let res = self.handle_connect(peer).await;
+                UdsRouterRequest::Connect { peer, options } => {
+                    let res = self.handle_connect(peer, options).await;
 
                     ctx.send(return_route, UdsRouterResponse::Connect(res))
                         .await?;
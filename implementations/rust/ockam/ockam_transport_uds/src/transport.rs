@@ -6,6 +6,7 @@ use ockam_node::{Context, HasContext};
 use crate::{
     parse_socket_addr,
     router::{UdsRouter, UdsRouterHandle},
+    UdsConnectionOptions, UdsListenerOptions,
 };
 
 /// High level management interface for UDS transports
@@ -23,27 +24,31 @@ use crate::{
 /// This step is optional because the underlying UdsRouter is capable of lazily
 /// establishing a connection upon arrival of an initial message.
 ///
+/// Messages received from a connection can only be delivered to the workers which
+/// were marked as Consumers of its [`FlowControlId`](ockam_core::flow_control::FlowControlId)
+/// with the [`UdsListenerOptions`] and [`UdsConnectionOptions`].
+///
 /// ```rust
-/// use ockam_transport_uds::UdsTransport;
+/// use ockam_transport_uds::{UdsConnectionOptions, UdsListenerOptions, UdsTransport};
 /// # use ockam_node::Context;
 /// # use ockam_core::Result;
 /// # async fn test(ctx: Context) -> Result<()> {
 /// let uds = UdsTransport::create(&ctx).await?;
-/// uds.listen("/tmp/example-socket").await?; // Listen on socket `/tmp/example-socket`
-/// uds.connect("/tmp/other-socket").await?; // And connect to `/tmp/other-socket`
+/// uds.listen("/tmp/example-socket", UdsListenerOptions::new()).await?; // Listen on socket `/tmp/example-socket`
+/// uds.connect("/tmp/other-socket", UdsConnectionOptions::new()).await?; // And connect to `/tmp/other-socket`
 /// # Ok(()) }
 /// ```
 ///
 /// The same `UdsTransport` can also bind to multiple sockets.
 ///
 /// ```rust
-/// use ockam_transport_uds::UdsTransport;
+/// use ockam_transport_uds::{UdsListenerOptions, UdsTransport};
 /// # use ockam_node::Context;
 /// # use ockam_core::Result;
 /// # async fn test(ctx: Context) -> Result<()> {
 /// let uds = UdsTransport::create(&ctx).await?;
-/// uds.listen("/tmp/socket-one").await?; // Listen on `/tmp/socket-one`
-/// uds.listen("/tmp/socket-two").await?; // Listen on `/tmp/socket-two`
+/// uds.listen("/tmp/socket-one", UdsListenerOptions::new()).await?; // Listen on `/tmp/socket-one`
+/// uds.listen("/tmp/socket-two", UdsListenerOptions::new()).await?; // Listen on `/tmp/socket-two`
 /// # Ok(()) }
 /// ```
 #[derive(AsyncTryClone)]
@@ -65,27 +70,31 @@ impl UdsTransport {
     /// Connects the [`UdsTransport`] to the given socket peer.
     ///
     /// ```rust
-    /// use ockam_transport_uds::UdsTransport;
+    /// use ockam_transport_uds::{UdsConnectionOptions, UdsTransport};
     /// # use ockam_node::Context;
     /// # use ockam_core::Result;
     /// # async fn test(ctx: Context) -> Result<()> {
     /// let uds = UdsTransport::create(&ctx).await?;
-    /// uds.connect("/tmp/socket-name").await?;
+    /// uds.connect("/tmp/socket-name", UdsConnectionOptions::new()).await?;
     /// # Ok(()) }
     /// ```
-    pub async fn connect<S: AsRef<str>>(&self, peer: S) -> Result<Address> {
-        self.router_handle.connect(peer.as_ref()).await
+    pub async fn connect<S: AsRef<str>>(
+        &self,
+        peer: S,
+        options: UdsConnectionOptions,
+    ) -> Result<Address> {
+        self.router_handle.connect(peer.as_ref(), options).await
     }
 
     /// Disconnects the [`UdsTransport`] from the given socket peer.
     ///
     /// ```rust
-    /// use ockam_transport_uds::UdsTransport;
+    /// use ockam_transport_uds::{UdsConnectionOptions, UdsTransport};
     /// # use ockam_node::Context;
     /// # use ockam_core::Result;
     /// # async fn test(ctx: Context) -> Result<()> {
     /// let uds = UdsTransport::create(&ctx).await?;
-    /// uds.connect("/tmp/socket-name").await?;
+    /// uds.connect("/tmp/socket-name", UdsConnectionOptions::new()).await?;
     ///
     /// uds.disconnect("/tmp/socket-name").await?;
     /// # Ok(()) }
@@ -97,17 +106,21 @@ impl UdsTransport {
     /// Binds the [`UdsTransport`] to listen and accept incoming connection requests to the given socket.
     ///
     /// ```rust
-    /// use ockam_transport_uds::UdsTransport;
+    /// use ockam_transport_uds::{UdsListenerOptions, UdsTransport};
     /// # use ockam_node::Context;
     /// # use ockam_core::Result;
     /// # async fn test(ctx: Context) -> Result<()> {
     /// let uds = UdsTransport::create(&ctx).await?;
-    /// uds.listen("/tmp/socket-name").await?;
+    /// uds.listen("/tmp/socket-name", UdsListenerOptions::new()).await?;
     /// # Ok(()) }
     /// ```
-    pub async fn listen<S: AsRef<str>>(&self, bind_addr: S) -> Result<SocketAddr> {
+    pub async fn listen<S: AsRef<str>>(
+        &self,
+        bind_addr: S,
+        options: UdsListenerOptions,
+    ) -> Result<SocketAddr> {
         let sock_addr = parse_socket_addr(bind_addr.as_ref())?;
-        self.router_handle.bind(sock_addr).await
+        self.router_handle.bind(sock_addr, options).await
     }
 }
 
@@ -10,7 +10,9 @@ use ockam_transport_core::TransportError;
 use tokio::net::UnixListener;
 use tracing::{debug, error, trace};
 
-use crate::{router::UdsRouterHandle, std_socket_addr_from_tokio, workers::UdsSendWorker};
+use crate::{
+    router::UdsRouterHandle, std_socket_addr_from_tokio, workers::UdsSendWorker, UdsListenerOptions,
+};
 
 /// A UDS Listener Processor
 ///
@@ -19,6 +21,7 @@ use crate::{router::UdsRouterHandle, std_socket_addr_from_tokio, workers::UdsSen
 pub(crate) struct UdsListenProcessor {
     inner: UnixListener,
     router_handle: UdsRouterHandle,
+    options: UdsListenerOptions,
 }
 
 impl UdsListenProcessor {
@@ -29,6 +32,7 @@ impl UdsListenProcessor {
         ctx: &Context,
         router_handle: UdsRouterHandle,
         addr: SocketAddr,
+        options: UdsListenerOptions,
     ) -> Result<SocketAddr> {
         let path = match addr.as_pathname() {
             Some(p) => p,
@@ -44,13 +48,16 @@ impl UdsListenProcessor {
 
         let std_sock_addr = std_socket_addr_from_tokio(&tokio_sock_addr)?;
 
+        let address = Address::random_tagged("UdsListenProcessor");
+        options.setup_flow_control_for_listener(ctx.flow_controls(), &address);
+
         let processor = Self {
             inner,
             router_handle,
+            options,
         };
 
-        ctx.start_processor(Address::random_tagged("UdsListenProcessor"), processor)
-            .await?;
+        ctx.start_processor(address, processor).await?;
 
         Ok(std_sock_addr)
     }
@@ -81,6 +88,16 @@ impl Processor for UdsListenProcessor {
         let (send_worker, pair) =
             UdsSendWorker::new_pair(handle_clone, Some(stream), std_sock_addr, vec![]).await?;
 
+        let receiver_flow_control_id = self.options.setup_flow_control_for_connection(
+            ctx.flow_controls(),
+            pair.rx_addr(),
+            &pair.sender_addresses()?,
+        );
+        let send_worker = send_worker.with_receiver_outgoing_access_control(
+            self.options
+                .create_receiver_access_control(ctx.flow_controls(), receiver_flow_control_id),
+        );
+
         self.router_handle.register(&pair).await?;
         debug!("UDS connection registered");
 
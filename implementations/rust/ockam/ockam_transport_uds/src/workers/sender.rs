@@ -1,10 +1,10 @@
 use std::os::unix::net::SocketAddr;
 
 use ockam_core::{
-    async_trait, compat::sync::Arc, Address, AllowAll, Any, Decodable, DenyAll, Encodable,
-    LocalMessage, Mailbox, Mailboxes, Message, Result, Routed, TransportMessage, Worker,
+    async_trait, compat::sync::Arc, Address, Any, Decodable, DenyAll, Encodable, LocalMessage,
+    Mailbox, Mailboxes, Message, OutgoingAccessControl, Result, Routed, TransportMessage, Worker,
 };
-use ockam_node::{Context, WorkerBuilder};
+use ockam_node::{Context, ProcessorBuilder, WorkerBuilder};
 use ockam_transport_core::TransportError;
 use serde::{Deserialize, Serialize};
 use socket2::SockRef;
@@ -18,6 +18,7 @@ use tokio::{
 use tracing::{debug, error, trace, warn};
 
 use crate::router::UdsRouterHandle;
+use crate::{address_from_socket_addr, UdsConnectionOptions, UDS};
 
 use super::UdsRecvProcessor;
 
@@ -27,6 +28,7 @@ pub(crate) struct WorkerPair {
     paths: Vec<String>,
     peer: SocketAddr,
     tx_addr: Address,
+    rx_addr: Address,
 }
 
 impl WorkerPair {
@@ -44,6 +46,19 @@ impl WorkerPair {
     pub fn tx_addr(&self) -> Address {
         self.tx_addr.clone()
     }
+
+    /// Return a reference to the [`Receiver Process Address`](ockam_core::Address)
+    pub fn rx_addr(&self) -> &Address {
+        &self.rx_addr
+    }
+
+    /// Addresses through which messages are sent to the peer: the transmit address,
+    /// and the UDS addresses that the router maps to it
+    pub fn sender_addresses(&self) -> Result<Vec<Address>> {
+        let mut addresses = vec![self.tx_addr(), address_from_socket_addr(&self.peer)?];
+        addresses.extend(self.paths.iter().map(|p| Address::new(UDS, p)));
+        Ok(addresses)
+    }
 }
 
 #[derive(Serialize, Deserialize, Message, Clone)]
@@ -59,6 +74,7 @@ pub(crate) struct UdsSendWorker {
     internal_addr: Address,
     rx_addr: Address,
     rx_should_be_stopped: bool,
+    receiver_outgoing_access_control: Arc<dyn OutgoingAccessControl>,
 }
 
 impl UdsSendWorker {
@@ -86,9 +102,20 @@ impl UdsSendWorker {
             internal_addr,
             rx_addr,
             rx_should_be_stopped: true,
+            receiver_outgoing_access_control: Arc::new(DenyAll),
         }
     }
 
+    /// Set the [`OutgoingAccessControl`] of the receiver, which restricts where
+    /// the messages received from the peer can be sent
+    pub(crate) fn with_receiver_outgoing_access_control(
+        mut self,
+        access_control: Arc<dyn OutgoingAccessControl>,
+    ) -> Self {
+        self.receiver_outgoing_access_control = access_control;
+        self
+    }
+
     pub(crate) fn internal_addr(&self) -> &Address {
         &self.internal_addr
     }
@@ -114,13 +141,20 @@ impl UdsSendWorker {
         let tx_addr = Address::random_tagged(&format!("UdsSendWorker_tx_addr_{role_str}"));
         let int_addr = Address::random_tagged(&format!("UdsSendWorker_int_addr_{role_str}"));
         let rx_addr = Address::random_tagged(&format!("UdsRecvProcessor_{role_str}"));
-        let sender = UdsSendWorker::new(router_handle, stream, peer.clone(), int_addr, rx_addr);
+        let sender = UdsSendWorker::new(
+            router_handle,
+            stream,
+            peer.clone(),
+            int_addr,
+            rx_addr.clone(),
+        );
         Ok((
             sender,
             WorkerPair {
                 paths: pathnames,
                 peer,
                 tx_addr,
+                rx_addr,
             },
         ))
     }
@@ -132,12 +166,22 @@ impl UdsSendWorker {
         stream: Option<UnixStream>,
         peer: SocketAddr,
         hostnames: Vec<String>,
+        options: UdsConnectionOptions,
     ) -> Result<WorkerPair> {
         let udsrouter_main_addr = router_handle.main_addr().clone();
 
         trace!("Creating new UDS worker pair");
         let (worker, pair) = Self::new_pair(router_handle, stream, peer, hostnames).await?;
 
+        options.setup_flow_control(
+            ctx.flow_controls(),
+            pair.rx_addr(),
+            &pair.sender_addresses()?,
+        );
+        let worker = worker.with_receiver_outgoing_access_control(
+            options.create_receiver_access_control(ctx.flow_controls()),
+        );
+
         let tx_mailbox = Mailbox::new(
             pair.tx_addr(),
             Arc::new(ockam_core::AllowSourceAddress(udsrouter_main_addr)),
@@ -228,7 +272,11 @@ impl Worker for UdsSendWorker {
             self.internal_addr.clone(),
         );
 
-        ctx.start_processor_with_access_control(self.rx_addr.clone(), receiver, DenyAll, AllowAll)
+        ProcessorBuilder::new(receiver)
+            .with_address(self.rx_addr.clone())
+            .with_incoming_access_control(DenyAll)
+            .with_outgoing_access_control_arc(self.receiver_outgoing_access_control.clone())
+            .start(ctx)
             .await?;
 
         Ok(())
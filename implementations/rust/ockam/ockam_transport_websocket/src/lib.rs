@@ -23,14 +23,19 @@
 //!
 //! // Now we can write the main function that will run the previous worker. In this case, our worker will be listening for new connections on port 8000 until the process is manually killed.
 //!
-//! use ockam_transport_websocket::WebSocketTransport;
+//! use ockam_transport_websocket::{WebSocketListenerOptions, WebSocketTransport};
 //! use ockam_node::NodeBuilder;
 //! use ockam_macros::node;
 //!
 //! #[ockam_macros::node(crate = "ockam_node")]
 //! async fn main(mut ctx: Context) -> Result<()> {//!
 //!     let ws = WebSocketTransport::create(&ctx).await?;
-//!     ws.listen("localhost:8000").await?; // Listen on port 8000
+//!     let listener_options = WebSocketListenerOptions::new();
+//!
+//!     // Allow the messages received over the connections of the listener to reach "my_worker"
+//!     ctx.flow_controls()
+//!         .add_consumer("my_worker", &listener_options.spawner_flow_control_id());
+//!     ws.listen("localhost:8000", listener_options).await?; // Listen on port 8000
 //!
 //!     // Start a worker, of type MyWorker, at address "my_worker"
 //!     ctx.start_worker("my_worker", MyWorker).await?;
@@ -89,12 +94,17 @@ use std::net::SocketAddr;
 
 use ockam_core::{Result, TransportType};
 use ockam_transport_core::TransportError;
+pub use options::*;
+pub use tls::*;
 pub use transport::*;
 
 use crate::router::{WebSocketRouter, WebSocketRouterHandle};
 
 mod error;
+mod options;
+mod proxy;
 mod router;
+mod tls;
 mod transport;
 mod workers;
 
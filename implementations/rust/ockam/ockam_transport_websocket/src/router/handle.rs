@@ -1,4 +1,3 @@
-use core::str::FromStr;
 use std::net::{SocketAddr, ToSocketAddrs};
 
 use ockam_core::{async_trait, Address, AsyncTryClone, DenyAll, Result};
@@ -7,7 +6,10 @@ use ockam_transport_core::TransportError;
 
 use crate::router::{WebSocketRouterRequest, WebSocketRouterResponse};
 use crate::workers::{WebSocketListenProcessor, WorkerPair};
-use crate::{parse_socket_addr, WebSocketAddress};
+use crate::{
+    parse_socket_addr, WebSocketClientTlsOptions, WebSocketConnectionOptions,
+    WebSocketListenerOptions,
+};
 
 /// A handle to connect to a WebSocketRouter.
 ///
@@ -39,14 +41,7 @@ impl WebSocketRouterHandle {
 
     /// Register a new connection worker with this router.
     pub(crate) async fn register(&self, pair: &WorkerPair) -> Result<()> {
-        let mut accepts = vec![pair.peer()];
-
-        accepts.extend(
-            pair.hostnames()
-                .iter()
-                .filter_map(|x| WebSocketAddress::from_str(x).ok())
-                .map(|addr| addr.into()),
-        );
+        let accepts = pair.accepts();
         let self_addr = pair.tx_addr();
         let response = self
             .ctx
@@ -56,15 +51,44 @@ impl WebSocketRouterHandle {
             )
             .await?;
 
-        let WebSocketRouterResponse::Register(res) = response;
+        if let WebSocketRouterResponse::Register(res) = response {
+            res
+        } else {
+            Err(TransportError::InvalidRouterResponseType.into())
+        }
+    }
+
+    /// Disconnect a connection, given the address of its sender worker.
+    pub(crate) async fn disconnect(&self, self_addr: Address) -> Result<()> {
+        let response = self
+            .ctx
+            .send_and_receive(
+                self.api_addr.clone(),
+                WebSocketRouterRequest::Disconnect { self_addr },
+            )
+            .await?;
 
-        res
+        if let WebSocketRouterResponse::Disconnect(res) = response {
+            res
+        } else {
+            Err(TransportError::InvalidRouterResponseType.into())
+        }
     }
 
     /// Bind an incoming connection listener for this router.
-    pub(crate) async fn bind(&self, addr: impl Into<SocketAddr>) -> Result<SocketAddr> {
+    pub(crate) async fn bind(
+        &self,
+        addr: impl Into<SocketAddr>,
+        options: WebSocketListenerOptions,
+    ) -> Result<SocketAddr> {
         let socket_addr = addr.into();
-        WebSocketListenProcessor::start(&self.ctx, self.async_try_clone().await?, socket_addr).await
+        WebSocketListenProcessor::start(
+            &self.ctx,
+            self.async_try_clone().await?,
+            socket_addr,
+            options,
+        )
+        .await
     }
 
     /// Return the peer's `SocketAddr` and `hostnames` given a plain `String` address.
@@ -96,15 +120,31 @@ impl WebSocketRouterHandle {
     }
 
     /// Establish an outgoing WS connection on an existing transport.
-    pub(crate) async fn connect<S: AsRef<str>>(&self, peer: S) -> Result<()> {
-        // Get peer address and connect to it.
-        let (peer_addr, hostnames) = Self::resolve_peer(peer.as_ref())?;
+    pub(crate) async fn connect<S: AsRef<str>>(
+        &self,
+        peer: S,
+        options: WebSocketConnectionOptions,
+    ) -> Result<Address> {
+        // A `wss://` URL enables TLS, with the default TLS options if none were given.
+        let (peer, options) = match peer.as_ref().split_once("://") {
+            Some(("ws", peer)) => (peer, options),
+            Some(("wss", peer)) if options.tls.is_none() => {
+                (peer, options.with_tls(WebSocketClientTlsOptions::new()))
+            }
+            Some(("wss", peer)) => (peer, options),
+            Some(_) => return Err(TransportError::InvalidAddress.into()),
+            None => (peer.as_ref(), options),
+        };
+        let peer = peer.trim_end_matches('/');
 
         // Create a new `WorkerPair` for the given peer, initializing a new pair
-        // of sender worker and receiver processor.
-        let pair = WorkerPair::from_client(&self.ctx, peer_addr, hostnames).await?;
+        // of sender worker and receiver processor. The peer is resolved, unless
+        // the connection goes through a proxy.
+        let pair = WorkerPair::from_client(&self.ctx, peer, options).await?;
 
         // Handle node's register request.
-        self.register(&pair).await
+        self.register(&pair).await?;
+
+        Ok(pair.tx_addr())
     }
 }
@@ -1,18 +1,17 @@
-use core::str::FromStr;
 use std::collections::BTreeMap;
 use std::ops::Deref;
 use std::sync::Arc;
 
 pub(crate) use handle::WebSocketRouterHandle;
 use ockam_core::{
-    async_trait, Address, AllowAll, Any, Decodable, LocalMessage, Mailbox, Mailboxes, Message,
-    Result, Routed, Worker,
+    async_trait, flow_control::FlowControlId, Address, AllowAll, Any, Decodable, LocalMessage,
+    Mailbox, Mailboxes, Message, Result, Routed, Worker,
 };
 use ockam_node::{Context, WorkerBuilder};
 use ockam_transport_core::TransportError;
 
 use crate::workers::WorkerPair;
-use crate::{WebSocketAddress, WS};
+use crate::{WebSocketConnectionOptions, WS};
 use serde::{Deserialize, Serialize};
 
 mod handle;
@@ -26,11 +25,17 @@ pub enum WebSocketRouterRequest {
         /// The clients own worker bus address.
         self_addr: Address,
     },
+    /// Disconnect a client and stop its worker.
+    Disconnect {
+        /// The clients own worker bus address.
+        self_addr: Address,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug, Message)]
 pub enum WebSocketRouterResponse {
     Register(Result<()>),
+    Disconnect(Result<()>),
 }
 
 /// A WebSocket address router and connection listener.
@@ -41,11 +46,16 @@ pub enum WebSocketRouterResponse {
 ///
 /// Optionally you can also start listening for incoming connections
 /// if the local node is part of a server architecture.
+///
+/// Connections created automatically when routing a message use default
+/// [`WebSocketConnectionOptions`]. The workers sending messages through them
+/// are marked as Consumers of the connection, so that they can receive the replies.
 pub(crate) struct WebSocketRouter {
     ctx: Context,
     main_addr: Address,
     api_addr: Address,
     map: BTreeMap<Address, Address>,
+    auto_connections: BTreeMap<Address, FlowControlId>,
     allow_auto_connection: bool,
 }
 
@@ -73,6 +83,7 @@ impl WebSocketRouter {
             main_addr: main_addr.clone(),
             api_addr: api_addr.clone(),
             map: BTreeMap::new(),
+            auto_connections: BTreeMap::new(),
             allow_auto_connection: true,
         };
 
@@ -141,6 +152,17 @@ impl Worker for WebSocketRouter {
                     )
                     .await?;
                 }
+                WebSocketRouterRequest::Disconnect { self_addr } => {
+                    trace!("handle_message disconnect: {:?}", self_addr);
+                    let res = self.handle_disconnect(ctx, self_addr).await;
+
+                    ctx.send_from_address(
+                        return_route,
+                        WebSocketRouterResponse::Disconnect(res),
+                        self.api_addr.clone(),
+                    )
+                    .await?;
+                }
             };
         } else {
             return Err(TransportError::InvalidAddress.into());
@@ -174,12 +196,23 @@ impl WebSocketRouter {
 
             // TODO: Check if this is the hostname and we have existing/pending connection to this IP
             if self.allow_auto_connection {
-                next = self.connect(peer_str).await?;
+                let options = WebSocketConnectionOptions::new();
+                let flow_control_id = options.flow_control_id();
+                next = self.connect(peer_str, options).await?;
+                self.auto_connections.insert(next.clone(), flow_control_id);
             } else {
                 return Err(TransportError::UnknownRoute.into());
             }
         }
 
+        // Allow the automatically created connection to answer the sender
+        if let Some(flow_control_id) = self.auto_connections.get(&next) {
+            if let Ok(sender) = msg.transport().return_route.next() {
+                ctx.flow_controls()
+                    .add_consumer(sender.clone(), flow_control_id);
+            }
+        }
+
         let _ = msg.transport_mut().onward_route.step()?;
         // Modify the transport message route
         msg.transport_mut()
@@ -219,22 +252,30 @@ impl WebSocketRouter {
         Ok(())
     }
 
-    async fn connect(&mut self, peer: String) -> Result<Address> {
-        // Get peer address and connect to it.
-        let (peer_addr, hostnames) = WebSocketRouterHandle::resolve_peer(peer)?;
+    async fn handle_disconnect(&mut self, ctx: &Context, self_addr: Address) -> Result<()> {
+        let registered = self.map.len();
+        self.map.retain(|_, addr| addr != &self_addr);
+        if self.map.len() == registered {
+            error!("Failed to disconnect, client not found: {}", self_addr);
+            return Err(TransportError::PeerNotFound.into());
+        }
+        self.auto_connections.remove(&self_addr);
+
+        ctx.stop_worker(self_addr).await
+    }
 
+    async fn connect(
+        &mut self,
+        peer: String,
+        options: WebSocketConnectionOptions,
+    ) -> Result<Address> {
         // Create a new `WorkerPair` for the given peer, initializing a new pair
-        // of sender worker and receiver processor.
-        let pair = WorkerPair::from_client(&self.ctx, peer_addr, hostnames).await?;
+        // of sender worker and receiver processor. The peer is resolved, unless
+        // the connection goes through a proxy.
+        let pair = WorkerPair::from_client(&self.ctx, &peer, options).await?;
 
         // Handle node's register request.
-        let mut accepts = vec![pair.peer()];
-        accepts.extend(
-            pair.hostnames()
-                .iter()
-                .filter_map(|x| WebSocketAddress::from_str(x).ok())
-                .map(|addr| addr.into()),
-        );
+        let accepts = pair.accepts();
         let self_addr = pair.tx_addr();
         self.handle_register(accepts, self_addr.clone()).await?;
 
@@ -0,0 +1,117 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use ockam_core::Result;
+use ockam_transport_core::tls::{
+    native_root_certificates, read_certificates, read_private_key, read_root_certificates,
+};
+use rustls::server::AllowAnyAuthenticatedClient;
+use rustls::{ClientConfig, ServerConfig};
+use serde::{Deserialize, Serialize};
+use tokio_rustls::TlsAcceptor;
+
+use crate::error::WebSocketError;
+
+/// TLS options of an outgoing `wss://` connection
+///
+/// By default, the server certificate is verified with the root certificates of the platform.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WebSocketClientTlsOptions {
+    ca_bundle: Option<PathBuf>,
+    client_certificate: Option<(PathBuf, PathBuf)>,
+}
+
+impl WebSocketClientTlsOptions {
+    /// Verify the server with the root certificates of the platform
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Verify the server with the certificates of the given PEM file instead of
+    /// the root certificates of the platform
+    pub fn with_ca_bundle(mut self, path: impl Into<PathBuf>) -> Self {
+        self.ca_bundle = Some(path.into());
+        self
+    }
+
+    /// Authenticate to the server with the given PEM certificate chain and private key
+    pub fn with_client_certificate(
+        mut self,
+        certificate_chain: impl Into<PathBuf>,
+        private_key: impl Into<PathBuf>,
+    ) -> Self {
+        self.client_certificate = Some((certificate_chain.into(), private_key.into()));
+        self
+    }
+
+    pub(crate) fn client_config(&self) -> Result<Arc<ClientConfig>> {
+        let roots = match &self.ca_bundle {
+            Some(path) => read_root_certificates(path)?,
+            None => native_root_certificates()?,
+        };
+
+        let builder = ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(roots);
+        let config = match &self.client_certificate {
+            Some((certificate_chain, private_key)) => builder
+                .with_client_auth_cert(
+                    read_certificates(certificate_chain)?,
+                    read_private_key(private_key)?,
+                )
+                .map_err(tls_error)?,
+            None => builder.with_no_client_auth(),
+        };
+
+        Ok(Arc::new(config))
+    }
+}
+
+/// TLS options of a `wss://` listener
+#[derive(Debug, Clone)]
+pub struct WebSocketServerTlsOptions {
+    certificate_chain: PathBuf,
+    private_key: PathBuf,
+    client_ca_bundle: Option<PathBuf>,
+}
+
+impl WebSocketServerTlsOptions {
+    /// Present the given PEM certificate chain, signed with the given PEM private key
+    pub fn new(certificate_chain: impl Into<PathBuf>, private_key: impl Into<PathBuf>) -> Self {
+        Self {
+            certificate_chain: certificate_chain.into(),
+            private_key: private_key.into(),
+            client_ca_bundle: None,
+        }
+    }
+
+    /// Only accept the clients presenting a certificate signed by one of the
+    /// certificates of the given PEM file
+    pub fn with_client_ca_bundle(mut self, path: impl Into<PathBuf>) -> Self {
+        self.client_ca_bundle = Some(path.into());
+        self
+    }
+
+    pub(crate) fn acceptor(&self) -> Result<TlsAcceptor> {
+        let builder = ServerConfig::builder().with_safe_defaults();
+        let builder = match &self.client_ca_bundle {
+            Some(path) => builder.with_client_cert_verifier(
+                AllowAnyAuthenticatedClient::new(read_root_certificates(path)?).boxed(),
+            ),
+            None => builder.with_no_client_auth(),
+        };
+        let config = builder
+            .with_single_cert(
+                read_certificates(&self.certificate_chain)?,
+                read_private_key(&self.private_key)?,
+            )
+            .map_err(tls_error)?;
+
+        Ok(TlsAcceptor::from(Arc::new(config)))
+    }
+}
+
+fn tls_error(e: rustls::Error) -> WebSocketError {
+    warn!("Invalid TLS configuration: {}", e);
+    WebSocketError::Tls
+}
@@ -5,7 +5,10 @@ use std::str::FromStr;
 use ockam_core::{async_trait, Address, Result};
 use ockam_node::{Context, HasContext};
 
-use crate::{parse_socket_addr, WebSocketRouter, WebSocketRouterHandle, WS};
+use crate::{
+    parse_socket_addr, WebSocketConnectionOptions, WebSocketListenerOptions, WebSocketRouter,
+    WebSocketRouterHandle, WS,
+};
 
 /// High level management interface for WebSocket transports.
 ///
@@ -22,27 +25,55 @@ use crate::{parse_socket_addr, WebSocketRouter, WebSocketRouterHandle, WS};
 /// This step is optional because the underlying WebSocketRouter is capable of lazily
 /// establishing a connection upon arrival of an initial message.
 ///
+/// Messages received from a connection can only be delivered to the workers which
+/// were marked as Consumers of its [`FlowControlId`](ockam_core::flow_control::FlowControlId)
+/// with the [`WebSocketListenerOptions`] and [`WebSocketConnectionOptions`].
+///
 /// ```rust
-/// use ockam_transport_websocket::WebSocketTransport;
+/// use ockam_transport_websocket::{WebSocketConnectionOptions, WebSocketListenerOptions, WebSocketTransport};
 /// # use ockam_core::Result;
 /// # use ockam_node::Context;
 /// # async fn test(ctx: Context) -> Result<()> {
 /// let ws = WebSocketTransport::create(&ctx).await?;
-/// ws.listen("127.0.0.1:8000").await?; // Listen on port 8000
-/// ws.connect("127.0.0.1:5000").await?; // And connect to port 5000
+/// ws.listen("127.0.0.1:8000", WebSocketListenerOptions::new()).await?; // Listen on port 8000
+/// ws.connect("127.0.0.1:5000", WebSocketConnectionOptions::new()).await?; // And connect to port 5000
+/// # Ok(()) }
+/// ```
+///
+/// Connections and listeners can be secured with TLS (`wss://`). Outgoing connections
+/// go through the HTTP proxy set in the `HTTPS_PROXY` or `HTTP_PROXY` environment
+/// variables, or through the proxy given in the [`WebSocketConnectionOptions`].
+///
+/// ```rust
+/// use ockam_transport_websocket::{
+///     WebSocketClientTlsOptions, WebSocketConnectionOptions, WebSocketListenerOptions,
+///     WebSocketServerTlsOptions, WebSocketTransport,
+/// };
+/// # use ockam_core::Result;
+/// # use ockam_node::Context;
+/// # async fn test(ctx: Context) -> Result<()> {
+/// let ws = WebSocketTransport::create(&ctx).await?;
+/// let tls = WebSocketServerTlsOptions::new("cert.pem", "key.pem");
+/// ws.listen("0.0.0.0:8443", WebSocketListenerOptions::new().with_tls(tls)).await?;
+///
+/// let tls = WebSocketClientTlsOptions::new().with_ca_bundle("ca.pem");
+/// let options = WebSocketConnectionOptions::new()
+///     .with_tls(tls)
+///     .with_proxy("http://proxy.local:3128");
+/// ws.connect("relay.example.com:443", options).await?;
 /// # Ok(()) }
 /// ```
 ///
 /// The same `WebSocketTransport` can also bind to multiple ports.
 ///
 /// ```rust
-/// use ockam_transport_websocket::WebSocketTransport;
+/// use ockam_transport_websocket::{WebSocketListenerOptions, WebSocketTransport};
 /// # use ockam_core::{Address, Result};
 /// # use ockam_node::Context;
 /// # async fn test(ctx: Context) -> Result<()> {
 /// let ws = WebSocketTransport::create(&ctx).await?;
-/// ws.listen("127.0.0.1:8000").await?; // Listen on port 8000
-/// ws.listen("127.0.0.1:9000").await?; // Listen on port 9000
+/// ws.listen("127.0.0.1:8000", WebSocketListenerOptions::new()).await?; // Listen on port 8000
+/// ws.listen("127.0.0.1:9000", WebSocketListenerOptions::new()).await?; // Listen on port 9000
 /// # Ok(()) }
 /// ```
 pub struct WebSocketTransport {
@@ -67,18 +98,33 @@ impl WebSocketTransport {
 
     /// Establish an outgoing WebSocket connection on an existing transport.
     ///
+    /// The peer is a `host:port` address, optionally prefixed with `ws://` or `wss://`.
+    /// A `wss://` address uses the TLS options of `options`, or the default ones.
+    ///
+    /// Returns the address of the worker sending the messages of the connection.
+    ///
     /// ```rust
-    /// use ockam_transport_websocket::WebSocketTransport;
+    /// use ockam_transport_websocket::{WebSocketConnectionOptions, WebSocketListenerOptions, WebSocketTransport};
     /// # use ockam_node::Context;
     /// # use ockam_core::Result;
     /// # async fn test(ctx: Context) -> Result<()> {
     /// let ws = WebSocketTransport::create(&ctx).await?;
-    /// ws.listen("127.0.0.1:8000").await?; // Listen on port 8000
-    /// ws.connect("127.0.0.1:5000").await?; // and connect to port 5000
+    /// ws.listen("127.0.0.1:8000", WebSocketListenerOptions::new()).await?; // Listen on port 8000
+    /// ws.connect("127.0.0.1:5000", WebSocketConnectionOptions::new()).await?; // and connect to port 5000
     /// # Ok(()) }
     /// ```
-    pub async fn connect<S: AsRef<str>>(&self, peer: S) -> Result<()> {
-        self.router_handle.connect(peer).await
+    pub async fn connect<S: AsRef<str>>(
+        &self,
+        peer: S,
+        options: WebSocketConnectionOptions,
+    ) -> Result<Address> {
+        self.router_handle.connect(peer, options).await
+    }
+
+    /// Disconnect an outgoing connection, given the address returned by
+    /// [`WebSocketTransport::connect`].
+    pub async fn disconnect(&self, address: impl Into<Address>) -> Result<()> {
+        self.router_handle.disconnect(address.into()).await
     }
 
     /// Start listening to incoming connections on an existing transport.
@@ -89,16 +135,20 @@ impl WebSocketTransport {
     /// which port was actually bound.
     ///
     /// ```rust
-    /// use ockam_transport_websocket::WebSocketTransport;
+    /// use ockam_transport_websocket::{WebSocketListenerOptions, WebSocketTransport};
     /// # use ockam_node::Context;
     /// # use ockam_core::Result;
     /// # async fn test(ctx: Context) -> Result<()> {
     /// let ws = WebSocketTransport::create(&ctx).await?;
-    /// ws.listen("127.0.0.1:8000").await?;
+    /// ws.listen("127.0.0.1:8000", WebSocketListenerOptions::new()).await?;
     /// # Ok(()) }
-    pub async fn listen<S: AsRef<str>>(&self, bind_addr: S) -> Result<SocketAddr> {
+    pub async fn listen<S: AsRef<str>>(
+        &self,
+        bind_addr: S,
+        options: WebSocketListenerOptions,
+    ) -> Result<SocketAddr> {
         let bind_addr = parse_socket_addr(bind_addr)?;
-        self.router_handle.bind(bind_addr).await
+        self.router_handle.bind(bind_addr, options).await
     }
 }
 
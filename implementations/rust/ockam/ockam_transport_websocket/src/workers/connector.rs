@@ -0,0 +1,144 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use ockam_core::{Address, Result};
+use ockam_transport_core::TransportError;
+use rustls::ClientConfig;
+use tokio::net::TcpStream;
+use tokio_tungstenite::Connector;
+
+use crate::error::WebSocketError;
+use crate::proxy::HttpProxy;
+use crate::router::WebSocketRouterHandle;
+use crate::workers::{TcpClientStream, TcpServerStream, WebSocketStream};
+use crate::{parse_socket_addr, WebSocketAddress, WebSocketConnectionOptions, WS};
+
+/// Opens the WebSocket stream of an outgoing connection, possibly with TLS
+/// and through an HTTP proxy.
+pub(crate) struct WebSocketConnector {
+    /// WebSocket address of the peer: its socket address, or its `host:port` when
+    /// the hostname is only resolved by the proxy
+    peer: Address,
+    /// Hostnames the peer was resolved from
+    hostnames: Vec<String>,
+    /// `host:port` used in the URL, to verify the server certificate and to ask the proxy
+    authority: String,
+    tls: Option<Arc<ClientConfig>>,
+    target: ConnectionTarget,
+}
+
+/// Where the TCP connection of a WebSocket connection is opened
+enum ConnectionTarget {
+    Direct(SocketAddr),
+    Proxy(HttpProxy),
+}
+
+impl WebSocketConnector {
+    /// The TLS configuration and the proxy are loaded here, so that invalid options
+    /// are reported when the connection is created.
+    ///
+    /// The proxy is chosen from the `host:port` peer before any DNS resolution: when a
+    /// proxy is used, the hostname is sent unresolved to the proxy, which may be the only
+    /// one able to resolve it. The peer is only resolved locally when connecting directly.
+    pub(crate) fn new(peer: &str, options: &WebSocketConnectionOptions) -> Result<Self> {
+        let tls = match &options.tls {
+            Some(tls) => Some(tls.client_config()?),
+            None => None,
+        };
+        let host = peer
+            .rsplit_once(':')
+            .map_or(peer, |(host, _)| host)
+            .trim_start_matches('[')
+            .trim_end_matches(']');
+
+        match options.proxy.resolve(host, tls.is_some())? {
+            Some(proxy) => {
+                let peer_address = match parse_socket_addr(peer) {
+                    Ok(socket_addr) => WebSocketAddress::from(socket_addr).into(),
+                    Err(_) => Address::from((WS, peer)),
+                };
+                Ok(Self {
+                    peer: peer_address,
+                    hostnames: vec![],
+                    authority: peer.to_string(),
+                    tls,
+                    target: ConnectionTarget::Proxy(proxy),
+                })
+            }
+            None => {
+                let (socket_addr, hostnames) = WebSocketRouterHandle::resolve_peer(peer)?;
+                let authority = hostnames
+                    .first()
+                    .cloned()
+                    .unwrap_or_else(|| socket_addr.to_string());
+                Ok(Self {
+                    peer: WebSocketAddress::from(socket_addr).into(),
+                    hostnames,
+                    authority,
+                    tls,
+                    target: ConnectionTarget::Direct(socket_addr),
+                })
+            }
+        }
+    }
+
+    pub(crate) fn peer(&self) -> Address {
+        self.peer.clone()
+    }
+
+    pub(crate) fn hostnames(&self) -> &[String] {
+        &self.hostnames
+    }
+
+    pub(crate) async fn connect(&self) -> Result<WebSocketStream<TcpClientStream>> {
+        let stream: TcpServerStream = match &self.target {
+            ConnectionTarget::Proxy(proxy) => Box::new(proxy.connect(&self.authority).await?),
+            ConnectionTarget::Direct(socket_addr) => Box::new(
+                TcpStream::connect(socket_addr)
+                    .await
+                    .map_err(TransportError::from)?,
+            ),
+        };
+
+        let (url, connector) = match &self.tls {
+            Some(config) => (
+                format!("wss://{}", self.authority),
+                Connector::Rustls(config.clone()),
+            ),
+            None => (format!("ws://{}", self.authority), Connector::Plain),
+        };
+        let (stream, _) =
+            tokio_tungstenite::client_async_tls_with_config(url, stream, None, Some(connector))
+                .await
+                .map_err(WebSocketError::from)?;
+
+        Ok(stream)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hostname_is_only_resolved_when_connecting_directly() {
+        let peer = "relay.unresolvable.invalid:4000";
+
+        // The proxy resolves the hostname
+        let options = WebSocketConnectionOptions::new().with_proxy("http://127.0.0.1:3128");
+        let connector = WebSocketConnector::new(peer, &options).unwrap();
+        assert_eq!(connector.peer(), Address::from((WS, peer)));
+        assert_eq!(connector.authority, peer);
+        assert!(matches!(connector.target, ConnectionTarget::Proxy(_)));
+
+        let options = WebSocketConnectionOptions::new().without_proxy();
+        assert!(WebSocketConnector::new(peer, &options).is_err());
+
+        let options = WebSocketConnectionOptions::new().with_proxy("http://127.0.0.1:3128");
+        let connector = WebSocketConnector::new("127.0.0.1:4000", &options).unwrap();
+        assert_eq!(
+            connector.peer(),
+            WebSocketAddress::from(parse_socket_addr("127.0.0.1:4000").unwrap()).into()
+        );
+    }
+}
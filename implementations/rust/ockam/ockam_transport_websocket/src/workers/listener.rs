@@ -1,21 +1,32 @@
 use std::net::SocketAddr;
+use std::time::Duration;
 
-use tokio::net::TcpListener;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinSet;
+use tokio_rustls::TlsAcceptor;
 
 use ockam_core::{async_trait, Address, AllowAll, Processor, Result};
 use ockam_node::Context;
 use ockam_transport_core::TransportError;
 
-use crate::{error::WebSocketError, workers::WorkerPair, WebSocketRouterHandle};
+use crate::workers::{TcpServerStream, WebSocketStream, WorkerPair};
+use crate::{WebSocketListenerOptions, WebSocketRouterHandle};
+
+/// Maximum duration of the TLS and WebSocket handshakes of an incoming connection
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
 
 /// A worker that runs in the background as a `Processor` waiting for incoming
 /// clients' connections.
 ///
-/// When a new connection is established, a new `WorkerPair` is spawned and
-/// registered by the router.
+/// The handshakes of a new connection run in a separate task, so that a slow
+/// client doesn't block the other ones. Once they succeed, a new `WorkerPair`
+/// is spawned and registered by the router.
 pub(crate) struct WebSocketListenProcessor {
     inner: TcpListener,
+    tls_acceptor: Option<TlsAcceptor>,
+    handshakes: JoinSet<Option<(WebSocketStream<TcpServerStream>, SocketAddr)>>,
     router_handle: WebSocketRouterHandle,
+    options: WebSocketListenerOptions,
 }
 
 impl WebSocketListenProcessor {
@@ -24,17 +35,26 @@ impl WebSocketListenProcessor {
         ctx: &Context,
         router_handle: WebSocketRouterHandle,
         addr: SocketAddr,
+        options: WebSocketListenerOptions,
     ) -> Result<SocketAddr> {
         debug!("Binding WebSocketListener to {}", addr);
+        let tls_acceptor = match &options.tls {
+            Some(tls) => Some(tls.acceptor()?),
+            None => None,
+        };
         let inner = TcpListener::bind(addr)
             .await
             .map_err(TransportError::from)?;
         let saddr = inner.local_addr().map_err(TransportError::from)?;
+        let waddr = Address::random_tagged("WebSocketListenProcessor");
+        options.setup_flow_control_for_listener(ctx.flow_controls(), &waddr);
         let processor = Self {
             inner,
+            tls_acceptor,
+            handshakes: JoinSet::new(),
             router_handle,
+            options,
         };
-        let waddr = Address::random_tagged("WebSocketListenProcessor");
         ctx.start_processor_with_access_control(
             waddr, processor, AllowAll, // FIXME: @ac
             AllowAll, // FIXME: @ac
@@ -55,15 +75,25 @@ impl Processor for WebSocketListenProcessor {
     async fn process(&mut self, ctx: &mut Self::Context) -> Result<bool> {
         debug!("Waiting for incoming TCP connection...");
 
-        // Wait for an incoming connection
-        let (tcp_stream, peer) = self.inner.accept().await.map_err(TransportError::from)?;
-        let ws_stream = tokio_tungstenite::accept_async(tcp_stream)
-            .await
-            .map_err(WebSocketError::from)?;
-        debug!("TCP connection accepted");
+        let (ws_stream, peer) = tokio::select! {
+            accepted = self.inner.accept() => {
+                // Wait for an incoming connection
+                let (tcp_stream, peer) = accepted.map_err(TransportError::from)?;
+                debug!("TCP connection accepted");
+                self.handshakes
+                    .spawn(handshake(self.tls_acceptor.clone(), tcp_stream, peer));
+                return Ok(true);
+            }
+            Some(handshake) = self.handshakes.join_next(), if !self.handshakes.is_empty() => {
+                match handshake {
+                    Ok(Some(handshake)) => handshake,
+                    _ => return Ok(true),
+                }
+            }
+        };
 
         // Spawn a connection worker for it
-        let pair = WorkerPair::from_server(ctx, ws_stream, peer, vec![]).await?;
+        let pair = WorkerPair::from_server(ctx, ws_stream, peer, vec![], &self.options).await?;
 
         // Register the connection with the local TcpRouter
         self.router_handle.register(&pair).await?;
@@ -72,3 +102,39 @@ impl Processor for WebSocketListenProcessor {
         Ok(true)
     }
 }
+
+/// Run the TLS and WebSocket handshakes of an incoming connection. A failed
+/// handshake only concerns this client, and is logged
+async fn handshake(
+    tls_acceptor: Option<TlsAcceptor>,
+    tcp_stream: TcpStream,
+    peer: SocketAddr,
+) -> Option<(WebSocketStream<TcpServerStream>, SocketAddr)> {
+    let handshake = async {
+        let stream: TcpServerStream = match tls_acceptor {
+            Some(tls_acceptor) => match tls_acceptor.accept(tcp_stream).await {
+                Ok(tls_stream) => Box::new(tls_stream),
+                Err(e) => {
+                    warn!("TLS handshake with {} failed: {}", peer, e);
+                    return None;
+                }
+            },
+            None => Box::new(tcp_stream),
+        };
+        match tokio_tungstenite::accept_async(stream).await {
+            Ok(ws_stream) => Some((ws_stream, peer)),
+            Err(e) => {
+                warn!("WebSocket handshake with {} failed: {}", peer, e);
+                None
+            }
+        }
+    };
+
+    match tokio::time::timeout(HANDSHAKE_TIMEOUT, handshake).await {
+        Ok(handshake) => handshake,
+        Err(_) => {
+            warn!("The handshakes with {} timed out", peer);
+            None
+        }
+    }
+}
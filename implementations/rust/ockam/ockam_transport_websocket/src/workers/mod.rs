@@ -1,8 +1,10 @@
+pub(crate) use connector::*;
 pub(crate) use listener::*;
 pub(crate) use receiver::*;
 pub(crate) use sender::*;
 pub(crate) use stream::*;
 
+mod connector;
 mod listener;
 mod receiver;
 mod sender;
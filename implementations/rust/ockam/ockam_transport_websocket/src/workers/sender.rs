@@ -6,18 +6,18 @@ use futures_util::stream::{SplitSink, SplitStream};
 use futures_util::{SinkExt, StreamExt};
 use tokio_tungstenite::tungstenite::protocol::Message as WebSocketMessage;
 
-use crate::error::WebSocketError;
 use ockam_core::{
-    async_trait, route, Address, AllowAll, Any, Decodable, Encodable, LocalMessage, Mailbox,
-    Mailboxes, Result, Routed, TransportMessage, Worker,
+    async_trait, route, Address, AllowAll, Any, Decodable, DenyAll, Encodable, LocalMessage,
+    Mailbox, Mailboxes, OutgoingAccessControl, Result, Routed, TransportMessage, Worker,
 };
-use ockam_node::{Context, DelayedEvent, WorkerBuilder};
+use ockam_node::{Context, DelayedEvent, ProcessorBuilder, WorkerBuilder};
 use ockam_transport_core::TransportError;
 
 use crate::workers::{
-    AsyncStream, TcpClientStream, TcpServerStream, WebSocketRecvProcessor, WebSocketStream,
+    AsyncStream, TcpClientStream, TcpServerStream, WebSocketConnector, WebSocketRecvProcessor,
+    WebSocketStream,
 };
-use crate::WebSocketAddress;
+use crate::{WebSocketAddress, WebSocketConnectionOptions, WebSocketListenerOptions, WS};
 
 /// Transmit and receive peers of a WebSocket connection.
 #[derive(Debug)]
@@ -28,9 +28,6 @@ pub(crate) struct WorkerPair {
 }
 
 impl WorkerPair {
-    pub(crate) fn hostnames(&self) -> &[String] {
-        &self.hostnames
-    }
     pub(crate) fn peer(&self) -> Address {
         self.peer.clone()
     }
@@ -38,26 +35,58 @@ impl WorkerPair {
         self.tx_addr.clone()
     }
 
+    /// WebSocket addresses that the router maps to the transmit address: the peer
+    /// socket address and the hostnames it was resolved from
+    pub(crate) fn accepts(&self) -> Vec<Address> {
+        let mut accepts = vec![self.peer()];
+        accepts.extend(self.hostnames.iter().map(|x| Address::from((WS, x))));
+        accepts
+    }
+
+    /// Addresses through which messages are sent to the peer: the transmit address,
+    /// and the WebSocket addresses that the router maps to it
+    fn sender_addresses(tx_addr: &Address, peer: &Address, hostnames: &[String]) -> Vec<Address> {
+        let mut addresses = vec![tx_addr.clone(), peer.clone()];
+        addresses.extend(hostnames.iter().map(|x| Address::from((WS, x))));
+        addresses
+    }
+
     /// Spawn instances of `WebSocketSendWorker` and `WebSocketRecvProcessor` and
     /// returns a `WorkerPair` instance that will be registered by the `WebSocketRouter`.
     ///
     /// The WebSocket stream is created when the `WebSocketSendWorker` is initialized.
     pub(crate) async fn from_client(
         ctx: &Context,
-        peer: SocketAddr,
-        hostnames: Vec<String>,
+        peer: &str,
+        options: WebSocketConnectionOptions,
     ) -> Result<WorkerPair> {
         trace!("Creating new WS worker pair");
 
+        let connector = WebSocketConnector::new(peer, &options)?;
+        let peer = connector.peer();
+        let hostnames = connector.hostnames().to_vec();
+
+        let tx_addr = Address::random_tagged("WebSocketSender.tx_addr.from_client");
+        let rx_addr = Address::random_tagged("WebSocketRecvProcessor.from_client");
+
+        options.setup_flow_control(
+            ctx.flow_controls(),
+            &rx_addr,
+            &Self::sender_addresses(&tx_addr, &peer, &hostnames),
+        );
+        let receiver_outgoing_access_control =
+            options.create_receiver_access_control(ctx.flow_controls());
+
         let internal_addr = Address::random_tagged("WebSocketSender.internal.from_client");
         let sender = WebSocketSendWorker::<TcpClientStream>::new(
-            peer,
+            peer.clone(),
+            connector,
             internal_addr.clone(),
+            rx_addr,
+            receiver_outgoing_access_control,
             DelayedEvent::create(ctx, internal_addr.clone(), vec![]).await?,
         );
 
-        let tx_addr = Address::random_tagged("WebSocketSender.tx_addr.from_client");
-
         let mailboxes = Mailboxes::new(
             Mailbox::new(
                 tx_addr.clone(),
@@ -78,7 +107,7 @@ impl WorkerPair {
         // Return a handle to the worker pair
         Ok(WorkerPair {
             hostnames,
-            peer: WebSocketAddress::from(peer).into(),
+            peer,
             tx_addr,
         })
     }
@@ -90,18 +119,31 @@ impl WorkerPair {
         stream: WebSocketStream<TcpServerStream>,
         peer: SocketAddr,
         hostnames: Vec<String>,
+        options: &WebSocketListenerOptions,
     ) -> Result<WorkerPair> {
         trace!("Creating new WS worker pair");
 
+        let peer: Address = WebSocketAddress::from(peer).into();
+        let tx_addr = Address::random_tagged("WebSocketSender.tx_addr.from_server");
+        let rx_addr = Address::random_tagged("WebSocketRecvProcessor.from_server");
+
+        let receiver_flow_control_id = options.setup_flow_control_for_connection(
+            ctx.flow_controls(),
+            &rx_addr,
+            &Self::sender_addresses(&tx_addr, &peer, &hostnames),
+        );
+        let receiver_outgoing_access_control =
+            options.create_receiver_access_control(ctx.flow_controls(), receiver_flow_control_id);
+
         let internal_addr = Address::random_tagged("WebSocketSender.internal.from_server");
         let sender = WebSocketSendWorker::<TcpServerStream>::new(
             stream,
-            peer,
+            peer.clone(),
             internal_addr.clone(),
+            rx_addr,
+            receiver_outgoing_access_control,
             DelayedEvent::create(ctx, internal_addr.clone(), vec![]).await?,
         );
-
-        let tx_addr = Address::random_tagged("WebSocketSender.tx_addr.from_server");
         let mailboxes = Mailboxes::new(
             Mailbox::new(
                 tx_addr.clone(),
@@ -122,7 +164,7 @@ impl WorkerPair {
         // Return a handle to the worker pair
         Ok(WorkerPair {
             hostnames,
-            peer: WebSocketAddress::from(peer).into(),
+            peer,
             tx_addr,
         })
     }
@@ -139,8 +181,11 @@ where
 {
     ws_stream: Option<SplitStream<WebSocketStream<S>>>,
     ws_sink: Option<SplitSink<WebSocketStream<S>, WebSocketMessage>>,
-    peer: SocketAddr,
+    peer: Address,
+    connector: Option<WebSocketConnector>,
     internal_addr: Address,
+    rx_addr: Address,
+    receiver_outgoing_access_control: Arc<dyn OutgoingAccessControl>,
     heartbeat: DelayedEvent<Vec<u8>>,
     heartbeat_interval: Option<Duration>,
 }
@@ -151,15 +196,13 @@ where
 {
     async fn handle_initialize(&mut self, ctx: &mut Context) -> Result<()> {
         if let Some(ws_stream) = self.ws_stream.take() {
-            let rx_addr = Address::random_tagged("WebSocketSendWorker.rx_addr");
-            let receiver = WebSocketRecvProcessor::new(ws_stream, self.peer);
-            ctx.start_processor_with_access_control(
-                rx_addr.clone(),
-                receiver,
-                AllowAll, // FIXME: @ac
-                AllowAll, // FIXME: @ac
-            )
-            .await?;
+            let receiver = WebSocketRecvProcessor::new(ws_stream, self.peer.clone());
+            ProcessorBuilder::new(receiver)
+                .with_address(self.rx_addr.clone())
+                .with_incoming_access_control(DenyAll)
+                .with_outgoing_access_control_arc(self.receiver_outgoing_access_control.clone())
+                .start(ctx)
+                .await?;
         } else {
             return Err(TransportError::GenericIo.into());
         }
@@ -169,6 +212,17 @@ where
         Ok(())
     }
 
+    /// Close the connection and stop its receiver
+    async fn handle_shutdown(&mut self, ctx: &mut Context) -> Result<()> {
+        self.heartbeat.cancel();
+        if let Some(ws_sink) = &mut self.ws_sink {
+            let _ = ws_sink.close().await;
+        }
+        // The receiver may already have stopped if the connection was closed by the peer
+        let _ = ctx.stop_processor(self.rx_addr.clone()).await;
+        Ok(())
+    }
+
     async fn schedule_heartbeat(&mut self) -> Result<()> {
         let heartbeat_interval = match &self.heartbeat_interval {
             Some(hi) => *hi,
@@ -229,8 +283,10 @@ where
 impl WebSocketSendWorker<TcpServerStream> {
     fn new(
         stream: WebSocketStream<TcpServerStream>,
-        peer: SocketAddr,
+        peer: Address,
         internal_addr: Address,
+        rx_addr: Address,
+        receiver_outgoing_access_control: Arc<dyn OutgoingAccessControl>,
         heartbeat: DelayedEvent<Vec<u8>>,
     ) -> Self {
         let (ws_sink, ws_stream) = stream.split();
@@ -238,7 +294,10 @@ impl WebSocketSendWorker<TcpServerStream> {
             ws_sink: Some(ws_sink),
             ws_stream: Some(ws_stream),
             peer,
+            connector: None,
             internal_addr,
+            rx_addr,
+            receiver_outgoing_access_control,
             heartbeat,
             heartbeat_interval: None,
         }
@@ -246,12 +305,22 @@ impl WebSocketSendWorker<TcpServerStream> {
 }
 
 impl WebSocketSendWorker<TcpClientStream> {
-    fn new(peer: SocketAddr, internal_addr: Address, heartbeat: DelayedEvent<Vec<u8>>) -> Self {
+    fn new(
+        peer: Address,
+        connector: WebSocketConnector,
+        internal_addr: Address,
+        rx_addr: Address,
+        receiver_outgoing_access_control: Arc<dyn OutgoingAccessControl>,
+        heartbeat: DelayedEvent<Vec<u8>>,
+    ) -> Self {
         Self {
             ws_stream: None,
             ws_sink: None,
             peer,
+            connector: Some(connector),
             internal_addr,
+            rx_addr,
+            receiver_outgoing_access_control,
             heartbeat,
             heartbeat_interval: None,
         }
@@ -259,10 +328,11 @@ impl WebSocketSendWorker<TcpClientStream> {
 
     async fn initialize_stream(&mut self) -> Result<()> {
         if self.ws_stream.is_none() {
-            let peer = WebSocketAddress::from(self.peer).to_string();
-            let (stream, _) = tokio_tungstenite::connect_async(peer)
-                .await
-                .map_err(WebSocketError::from)?;
+            let connector = self
+                .connector
+                .as_ref()
+                .ok_or(TransportError::PeerNotFound)?;
+            let stream = connector.connect().await?;
             let (ws_sink, ws_stream) = stream.split();
             self.ws_sink = Some(ws_sink);
             self.ws_stream = Some(ws_stream);
@@ -281,6 +351,10 @@ impl Worker for WebSocketSendWorker<TcpServerStream> {
         Ok(())
     }
 
+    async fn shutdown(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        self.handle_shutdown(ctx).await
+    }
+
     async fn handle_message(
         &mut self,
         ctx: &mut Context,
@@ -301,6 +375,10 @@ impl Worker for WebSocketSendWorker<TcpClientStream> {
         Ok(())
     }
 
+    async fn shutdown(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        self.handle_shutdown(ctx).await
+    }
+
     async fn handle_message(
         &mut self,
         ctx: &mut Context,
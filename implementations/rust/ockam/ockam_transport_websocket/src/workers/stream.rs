@@ -1,8 +1,9 @@
 /// Type alias for `tokio_tungstenite::WebSocketStream`.
 pub(crate) type WebSocketStream<S> = tokio_tungstenite::WebSocketStream<S>;
 
-/// Stream created when a server accepts a new connection.
-pub(crate) type TcpServerStream = tokio::net::TcpStream;
+/// Stream created when a server accepts a new connection. It is wrapped
+/// in TLS when the listener has TLS options.
+pub(crate) type TcpServerStream = Box<dyn ServerStream>;
 
 /// Stream created when a client connects to a server.
 pub(crate) type TcpClientStream = tokio_tungstenite::MaybeTlsStream<TcpServerStream>;
@@ -20,3 +21,14 @@ pub(crate) trait AsyncStream:
 impl AsyncStream for TcpClientStream {}
 
 impl AsyncStream for TcpServerStream {}
+
+/// Plain TCP or TLS stream accepted by a server.
+pub(crate) trait ServerStream:
+    tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send + 'static
+{
+}
+
+impl<S> ServerStream for S where
+    S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send + 'static
+{
+}
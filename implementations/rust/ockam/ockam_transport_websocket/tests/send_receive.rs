@@ -1,13 +1,20 @@
 use ockam_core::compat::rand::{self, Rng};
 use ockam_core::{route, Result, Routed, Worker};
 use ockam_node::Context;
-use ockam_transport_websocket::{WebSocketTransport, WS};
+use ockam_transport_websocket::{
+    WebSocketConnectionOptions, WebSocketListenerOptions, WebSocketTransport, WS,
+};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
 
 #[ignore]
 #[ockam_macros::test]
 async fn send_receive(ctx: &mut Context) -> Result<()> {
     let transport = WebSocketTransport::create(ctx).await?;
-    let listener_address = transport.listen("127.0.0.1:0").await?;
+    let listener_options = WebSocketListenerOptions::new();
+    ctx.flow_controls()
+        .add_consumer("echoer", &listener_options.spawner_flow_control_id());
+    let listener_address = transport.listen("127.0.0.1:0", listener_options).await?;
     ctx.start_worker("echoer", Echoer).await?;
 
     // Sender
@@ -30,6 +37,168 @@ async fn send_receive(ctx: &mut Context) -> Result<()> {
     Ok(())
 }
 
+#[ignore]
+#[ockam_macros::test]
+async fn send_receive_through_proxy(ctx: &mut Context) -> Result<()> {
+    let transport = WebSocketTransport::create(ctx).await?;
+    let listener_options = WebSocketListenerOptions::new();
+    ctx.flow_controls()
+        .add_consumer("echoer", &listener_options.spawner_flow_control_id());
+    let listener_address = transport.listen("127.0.0.1:0", listener_options).await?;
+    ctx.start_worker("echoer", Echoer).await?;
+
+    let proxy = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let proxy_address = proxy.local_addr().unwrap();
+    tokio::spawn(async move {
+        let (stream, _) = proxy.accept().await.unwrap();
+        let mut stream = BufReader::new(stream);
+
+        // Read the CONNECT request and its headers
+        let mut request = String::new();
+        stream.read_line(&mut request).await.unwrap();
+        let target = request.split_whitespace().nth(1).unwrap().to_string();
+        loop {
+            let mut header = String::new();
+            stream.read_line(&mut header).await.unwrap();
+            if header == "\r\n" {
+                break;
+            }
+        }
+
+        let mut target = TcpStream::connect(target).await.unwrap();
+        let mut stream = stream.into_inner();
+        stream
+            .write_all(b"HTTP/1.1 200 Connection established\r\n\r\n")
+            .await
+            .unwrap();
+        let _ = tokio::io::copy_bidirectional(&mut stream, &mut target).await;
+    });
+
+    let options = WebSocketConnectionOptions::new().with_proxy(format!("http://{proxy_address}"));
+    ctx.flow_controls()
+        .add_consumer(ctx.address(), &options.flow_control_id());
+    transport
+        .connect(listener_address.to_string(), options)
+        .await?;
+
+    let r = route![(WS, listener_address.to_string()), "echoer"];
+    let reply = ctx
+        .send_and_receive::<String>(r, "Hello through the proxy".to_string())
+        .await?;
+    assert_eq!(reply, "Hello through the proxy");
+
+    if let Err(e) = ctx.stop().await {
+        println!("Unclean stop: {}", e)
+    }
+
+    Ok(())
+}
+
+#[ockam_macros::test]
+async fn send_receive_through_proxy_to_unresolvable_host(ctx: &mut Context) -> Result<()> {
+    let transport = WebSocketTransport::create(ctx).await?;
+    let listener_options = WebSocketListenerOptions::new();
+    ctx.flow_controls()
+        .add_consumer("echoer", &listener_options.spawner_flow_control_id());
+    let listener_address = transport.listen("127.0.0.1:0", listener_options).await?;
+    ctx.start_worker("echoer", Echoer).await?;
+
+    // Only the proxy knows the address of this host
+    let peer = format!("relay.unresolvable.invalid:{}", listener_address.port());
+
+    let proxy = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let proxy_address = proxy.local_addr().unwrap();
+    let expected_target = peer.clone();
+    tokio::spawn(async move {
+        let (stream, _) = proxy.accept().await.unwrap();
+        let mut stream = BufReader::new(stream);
+
+        // The CONNECT request targets the unresolved hostname
+        let mut request = String::new();
+        stream.read_line(&mut request).await.unwrap();
+        let target = request.split_whitespace().nth(1).unwrap().to_string();
+        assert_eq!(target, expected_target);
+        loop {
+            let mut header = String::new();
+            stream.read_line(&mut header).await.unwrap();
+            if header == "\r\n" {
+                break;
+            }
+        }
+
+        let mut target = TcpStream::connect(listener_address).await.unwrap();
+        let mut stream = stream.into_inner();
+        stream
+            .write_all(b"HTTP/1.1 200 Connection established\r\n\r\n")
+            .await
+            .unwrap();
+        let _ = tokio::io::copy_bidirectional(&mut stream, &mut target).await;
+    });
+
+    let options = WebSocketConnectionOptions::new().with_proxy(format!("http://{proxy_address}"));
+    ctx.flow_controls()
+        .add_consumer(ctx.address(), &options.flow_control_id());
+    transport.connect(peer.clone(), options).await?;
+
+    let r = route![(WS, peer), "echoer"];
+    let reply = ctx
+        .send_and_receive::<String>(r, "Hello through the proxy".to_string())
+        .await?;
+    assert_eq!(reply, "Hello through the proxy");
+
+    if let Err(e) = ctx.stop().await {
+        println!("Unclean stop: {}", e)
+    }
+
+    Ok(())
+}
+
+#[ockam_macros::test]
+async fn reconnect_after_disconnect(ctx: &mut Context) -> Result<()> {
+    let transport = WebSocketTransport::create(ctx).await?;
+    let listener_options = WebSocketListenerOptions::new();
+    ctx.flow_controls()
+        .add_consumer("echoer", &listener_options.spawner_flow_control_id());
+    let listener_address = transport.listen("127.0.0.1:0", listener_options).await?;
+    ctx.start_worker("echoer", Echoer).await?;
+
+    let sender = transport
+        .connect(
+            listener_address.to_string(),
+            WebSocketConnectionOptions::new(),
+        )
+        .await?;
+    // The peer is already connected
+    assert!(transport
+        .connect(
+            listener_address.to_string(),
+            WebSocketConnectionOptions::new()
+        )
+        .await
+        .is_err());
+
+    transport.disconnect(sender.clone()).await?;
+    assert!(transport.disconnect(sender).await.is_err());
+
+    let options = WebSocketConnectionOptions::new();
+    ctx.flow_controls()
+        .add_consumer(ctx.address(), &options.flow_control_id());
+    transport
+        .connect(listener_address.to_string(), options)
+        .await?;
+    let r = route![(WS, listener_address.to_string()), "echoer"];
+    let reply = ctx
+        .send_and_receive::<String>(r, "Hello again".to_string())
+        .await?;
+    assert_eq!(reply, "Hello again");
+
+    if let Err(e) = ctx.stop().await {
+        println!("Unclean stop: {}", e)
+    }
+
+    Ok(())
+}
+
 pub struct Echoer;
 
 #[ockam_core::worker]
@@ -32,6 +32,20 @@ pub enum VaultError {
     InvalidSha256Len,
     /// Invalid Signature Size
     InvalidSignatureSize,
+    /// KEM encapsulation failed
+    KemEncapsulate,
+    /// KEM decapsulation failed
+    KemDecapsulate,
+    /// KEM operations are not supported by this vault
+    KemNotSupported,
+    /// Invalid key or passphrase for an encrypted storage
+    InvalidStorageKey,
+    /// The storage could not be decrypted
+    StorageDecryption,
+    /// The storage is encrypted and no key was provided
+    EncryptedStorage,
+    /// A key was provided but the storage is not encrypted
+    UnencryptedStorage,
 }
 
 impl ockam_core::compat::error::Error for VaultError {}
@@ -54,6 +68,13 @@ impl core::fmt::Display for VaultError {
             Self::KeyNotFound => write!(f, "key not found"),
             Self::InvalidSha256Len => write!(f, "invalid sha256 len"),
             Self::InvalidSignatureSize => write!(f, "invalid signature len"),
+            Self::KemEncapsulate => write!(f, "kem encapsulation failed"),
+            Self::KemDecapsulate => write!(f, "kem decapsulation failed"),
+            Self::KemNotSupported => write!(f, "kem operations are not supported"),
+            Self::InvalidStorageKey => write!(f, "invalid storage key"),
+            Self::StorageDecryption => write!(f, "storage decryption failed"),
+            Self::EncryptedStorage => write!(f, "storage is encrypted, a key is required"),
+            Self::UnencryptedStorage => write!(f, "storage is not encrypted, it must be migrated"),
         }
     }
 }
@@ -65,6 +86,7 @@ impl From<VaultError> for Error {
         let kind = match err {
             InvalidPublicKey | InvalidKeyType | InvalidHkdfOutputType => Kind::Misuse,
             UnknownEcdhKeyType => Kind::NotFound,
+            KemNotSupported => Kind::Unsupported,
             _ => Kind::Invalid,
         };
 
@@ -1,7 +1,9 @@
 use super::aes::make_aes;
 
 use crate::constants::{
-    X25519_PUBLIC_LENGTH_USIZE, X25519_SECRET_LENGTH_U32, X25519_SECRET_LENGTH_USIZE,
+    KYBER768_CIPHERTEXT_LENGTH_USIZE, KYBER768_PUBLIC_LENGTH_USIZE, KYBER768_SECRET_LENGTH_USIZE,
+    KYBER768_SHARED_SECRET_LENGTH_U32, X25519_PUBLIC_LENGTH_USIZE, X25519_SECRET_LENGTH_U32,
+    X25519_SECRET_LENGTH_USIZE,
 };
 use crate::{
     Buffer, KeyId, PublicKey, Secret, SecretAttributes, SecretType, SecureChannelVault,
@@ -62,6 +64,14 @@ impl SoftwareSecureChannelVault {
                 let pk = x25519_dalek::PublicKey::from(&key);
                 Ok(PublicKey::new(pk.to_bytes().to_vec(), SecretType::X25519))
             }
+            SecretType::Kyber768 => {
+                let key = Self::check_kyber768_secret_key(key)?;
+                // A Kyber secret key contains: the IND-CPA secret key, the public key,
+                // the hash of the public key and a random value
+                let start = KYBER768_SECRET_LENGTH_USIZE - KYBER768_PUBLIC_LENGTH_USIZE - 64;
+                let pk = &key[start..(start + KYBER768_PUBLIC_LENGTH_USIZE)];
+                Ok(PublicKey::new(pk.to_vec(), SecretType::Kyber768))
+            }
             SecretType::NistP256 | SecretType::Ed25519 | SecretType::Buffer | SecretType::Aes => {
                 Err(VaultError::InvalidKeyType.into())
             }
@@ -101,11 +111,37 @@ impl SoftwareSecureChannelVault {
         )))
     }
 
+    fn check_kyber768_secret_key(key: &Secret) -> Result<&[u8]> {
+        if key.as_ref().len() != KYBER768_SECRET_LENGTH_USIZE {
+            return Err(VaultError::InvalidSecretLength(
+                SecretType::Kyber768,
+                key.as_ref().len(),
+                KYBER768_SECRET_LENGTH_USIZE as u32,
+            )
+            .into());
+        }
+
+        Ok(key.as_ref())
+    }
+
+    fn import_kyber768_public_key(public_key: &PublicKey) -> Result<&[u8]> {
+        if public_key.stype() != SecretType::Kyber768 {
+            return Err(VaultError::InvalidKeyType.into());
+        }
+
+        if public_key.data().len() != KYBER768_PUBLIC_LENGTH_USIZE {
+            return Err(VaultError::InvalidPublicLength.into());
+        }
+
+        Ok(public_key.data())
+    }
+
     /// Compute key id from secret and attributes
     pub(crate) fn compute_key_id(secret: &Secret, attributes: &SecretAttributes) -> Result<KeyId> {
         Ok(match attributes.secret_type() {
-            SecretType::X25519 => {
-                let public_key = Self::compute_public_key_from_secret(secret, SecretType::X25519)?;
+            SecretType::X25519 | SecretType::Kyber768 => {
+                let public_key =
+                    Self::compute_public_key_from_secret(secret, attributes.secret_type())?;
                 Self::compute_key_id_for_public_key(&public_key)?
             }
             SecretType::Buffer | SecretType::Aes => {
@@ -136,7 +172,7 @@ impl SoftwareSecureChannelVault {
                 let dh = secret_key.diffie_hellman(&peer_public_key);
                 Ok(dh.as_bytes().to_vec())
             }
-            SecretType::Buffer | SecretType::Aes | SecretType::Ed25519 => {
+            SecretType::Buffer | SecretType::Aes | SecretType::Ed25519 | SecretType::Kyber768 => {
                 Err(VaultError::UnknownEcdhKeyType.into())
             }
             SecretType::NistP256 => Err(VaultError::UnknownEcdhKeyType.into()),
@@ -161,6 +197,11 @@ impl SoftwareSecureChannelVault {
 
                 Ok(Secret::new(secret_key))
             }
+            SecretType::Kyber768 => {
+                let key_pair = pqc_kyber::keypair(&mut thread_rng())
+                    .map_err(|_| VaultError::InvalidKeyType)?;
+                Ok(Secret::new(key_pair.secret.to_vec()))
+            }
             SecretType::Buffer | SecretType::Aes => {
                 let bytes = {
                     let mut rng = thread_rng();
@@ -300,6 +341,37 @@ impl SecureChannelVault for SoftwareSecureChannelVault {
             .await
     }
 
+    async fn kem_encapsulate(&self, peer_public_key: &PublicKey) -> Result<(Buffer<u8>, KeyId)> {
+        let peer_public_key = Self::import_kyber768_public_key(peer_public_key)?;
+        let (ciphertext, shared_secret) =
+            pqc_kyber::encapsulate(peer_public_key, &mut thread_rng())
+                .map_err(|_| VaultError::KemEncapsulate)?;
+
+        let attributes = SecretAttributes::Buffer(KYBER768_SHARED_SECRET_LENGTH_U32);
+        let key_id = self
+            .import_ephemeral_secret(Secret::new(shared_secret.to_vec()), attributes)
+            .await?;
+        Ok((ciphertext.to_vec(), key_id))
+    }
+
+    async fn kem_decapsulate(&self, secret: &KeyId, ciphertext: &[u8]) -> Result<KeyId> {
+        let stored_secret = self.get_secret(secret).await?;
+        if stored_secret.attributes().secret_type() != SecretType::Kyber768 {
+            return Err(VaultError::InvalidKeyType.into());
+        }
+        if ciphertext.len() != KYBER768_CIPHERTEXT_LENGTH_USIZE {
+            return Err(VaultError::KemDecapsulate.into());
+        }
+
+        let secret_key = Self::check_kyber768_secret_key(stored_secret.secret())?;
+        let shared_secret = pqc_kyber::decapsulate(ciphertext, secret_key)
+            .map_err(|_| VaultError::KemDecapsulate)?;
+
+        let attributes = SecretAttributes::Buffer(KYBER768_SHARED_SECRET_LENGTH_U32);
+        self.import_ephemeral_secret(Secret::new(shared_secret.to_vec()), attributes)
+            .await
+    }
+
     async fn hkdf_sha256(
         &self,
         salt: &KeyId,
@@ -316,9 +388,10 @@ impl SecureChannelVault for SoftwareSecureChannelVault {
 
             match attributes.secret_type() {
                 SecretType::Buffer | SecretType::Aes => {}
-                SecretType::X25519 | SecretType::Ed25519 | SecretType::NistP256 => {
-                    return Err(VaultError::InvalidHkdfOutputType.into())
-                }
+                SecretType::X25519
+                | SecretType::Ed25519
+                | SecretType::NistP256
+                | SecretType::Kyber768 => return Err(VaultError::InvalidHkdfOutputType.into()),
             }
         }
 
@@ -398,3 +471,31 @@ impl SecureChannelVault for SoftwareSecureChannelVault {
         aes.decrypt_message(cipher_text, nonce, aad)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_kem_encapsulate_decapsulate() -> Result<()> {
+        let vault = SoftwareSecureChannelVault::create();
+        let secret = vault
+            .generate_ephemeral_secret(SecretAttributes::Kyber768)
+            .await?;
+        let public_key = vault.get_public_key(&secret).await?;
+        assert_eq!(public_key.stype(), SecretType::Kyber768);
+        assert_eq!(public_key.data().len(), KYBER768_PUBLIC_LENGTH_USIZE);
+
+        let (ciphertext, shared_secret1) = vault.kem_encapsulate(&public_key).await?;
+        assert_eq!(ciphertext.len(), KYBER768_CIPHERTEXT_LENGTH_USIZE);
+        let shared_secret2 = vault.kem_decapsulate(&secret, &ciphertext).await?;
+
+        let shared_secret1 = vault.get_ephemeral_secret(&shared_secret1)?;
+        let shared_secret2 = vault.get_ephemeral_secret(&shared_secret2)?;
+        assert_eq!(shared_secret1.secret(), shared_secret2.secret());
+
+        // the KEM keys cannot be used for a Diffie-Hellman
+        assert!(vault.ec_diffie_hellman(&secret, &public_key).await.is_err());
+        Ok(())
+    }
+}
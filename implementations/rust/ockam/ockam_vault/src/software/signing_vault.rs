@@ -120,7 +120,7 @@ impl SoftwareSigningVault {
 
                 Ok(PublicKey::new(verifying_key, SecretType::NistP256))
             }
-            SecretType::X25519 | SecretType::Buffer | SecretType::Aes => {
+            SecretType::X25519 | SecretType::Buffer | SecretType::Aes | SecretType::Kyber768 => {
                 Err(VaultError::InvalidKeyType.into())
             }
         }
@@ -177,7 +177,7 @@ impl SigningVault for SoftwareSigningVault {
 
                 Secret::new(signing_key)
             }
-            SecretType::Buffer | SecretType::Aes | SecretType::X25519 => {
+            SecretType::Buffer | SecretType::Aes | SecretType::X25519 | SecretType::Kyber768 => {
                 return Err(VaultError::InvalidKeyType.into());
             }
         };
@@ -228,7 +228,7 @@ impl SigningVault for SoftwareSigningVault {
 
                 Ok(Signature::new(signature))
             }
-            SecretType::Buffer | SecretType::Aes | SecretType::X25519 => {
+            SecretType::Buffer | SecretType::Aes | SecretType::X25519 | SecretType::Kyber768 => {
                 Err(VaultError::InvalidKeyType.into())
             }
         }
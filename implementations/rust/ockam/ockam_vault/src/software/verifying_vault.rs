@@ -109,7 +109,7 @@ impl SoftwareVerifyingVault {
                 use p256::ecdsa::signature::Verifier;
                 Ok(public_key.verify(data, &signature).is_ok())
             }
-            SecretType::Buffer | SecretType::Aes | SecretType::X25519 => {
+            SecretType::Buffer | SecretType::Aes | SecretType::X25519 | SecretType::Kyber768 => {
                 Err(VaultError::InvalidPublicKey.into())
             }
         }
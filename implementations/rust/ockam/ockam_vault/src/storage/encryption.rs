@@ -0,0 +1,190 @@
+use crate::constants::{AES256_SECRET_LENGTH_USIZE, AES_NONCE_LENGTH_USIZE};
+use crate::{Secret, VaultError};
+
+use aes_gcm::aead::{Aead, NewAead, Payload};
+use aes_gcm::Aes256Gcm;
+use argon2::{Algorithm, Argon2, Params, Version};
+use ockam_core::compat::rand::{thread_rng, RngCore};
+use ockam_core::errcode::{Kind, Origin};
+use ockam_core::{hex_encoding, Error, Result};
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Length of the salt used to derive a key from a passphrase
+const SALT_LENGTH: usize = 16;
+
+/// Key used to encrypt the secrets of a [`super::PersistentStorage`] at rest
+#[derive(Clone)]
+pub enum StorageKey {
+    /// Passphrase from which the encryption key is derived with Argon2id
+    Passphrase(String),
+    /// 32 bytes encryption key, for example read from a key file
+    Key(Secret),
+}
+
+impl StorageKey {
+    /// Read a 32 bytes key from a file, either hex-encoded or raw
+    pub fn from_key_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read(path).map_err(|e| Error::new(Origin::Vault, Kind::Io, e))?;
+        Self::from_bytes(&content)
+    }
+
+    /// Create a key from 32 bytes, either hex-encoded or raw
+    pub fn from_bytes(content: &[u8]) -> Result<Self> {
+        let decoded = core::str::from_utf8(content)
+            .ok()
+            .and_then(|s| hex::decode(s.trim()).ok());
+        let key = match decoded {
+            Some(key) if key.len() == AES256_SECRET_LENGTH_USIZE => key,
+            _ if content.len() == AES256_SECRET_LENGTH_USIZE => content.to_vec(),
+            _ => return Err(VaultError::InvalidStorageKey.into()),
+        };
+        Ok(StorageKey::Key(Secret::new(key)))
+    }
+}
+
+/// Parameters used to derive an encryption key from a passphrase
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub(super) struct KdfParameters {
+    #[serde(with = "hex_encoding")]
+    salt: Vec<u8>,
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+}
+
+impl KdfParameters {
+    /// Default Argon2id parameters with a fresh salt
+    fn generate() -> Self {
+        let mut salt = vec![0u8; SALT_LENGTH];
+        thread_rng().fill_bytes(&mut salt);
+        Self {
+            salt,
+            m_cost: Params::DEFAULT_M_COST,
+            t_cost: Params::DEFAULT_T_COST,
+            p_cost: Params::DEFAULT_P_COST,
+        }
+    }
+
+    fn derive_key(&self, passphrase: &str) -> Result<Secret> {
+        let params = Params::new(
+            self.m_cost,
+            self.t_cost,
+            self.p_cost,
+            Some(AES256_SECRET_LENGTH_USIZE),
+        )
+        .map_err(|_| VaultError::InvalidStorageKey)?;
+        let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, params);
+
+        let mut key = vec![0u8; AES256_SECRET_LENGTH_USIZE];
+        argon2
+            .hash_password_into(passphrase.as_bytes(), &self.salt, &mut key)
+            .map_err(|_| VaultError::InvalidStorageKey)?;
+        Ok(Secret::new(key))
+    }
+}
+
+/// Encrypted content of a vault file
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub(super) struct EncryptedData {
+    /// Present if the key is derived from a passphrase
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    kdf: Option<KdfParameters>,
+    #[serde(with = "hex_encoding")]
+    nonce: Vec<u8>,
+    #[serde(with = "hex_encoding")]
+    ciphertext: Vec<u8>,
+}
+
+impl EncryptedData {
+    /// Return the key derivation parameters used to encrypt this data
+    pub(super) fn kdf(&self) -> Option<&KdfParameters> {
+        self.kdf.as_ref()
+    }
+}
+
+/// Encrypt and decrypt the content of a vault file with AES-256-GCM
+#[derive(Clone)]
+pub(super) struct StorageEncryption {
+    key: Secret,
+    kdf: Option<KdfParameters>,
+}
+
+impl StorageEncryption {
+    /// Create the encryption key. The key derivation parameters of an existing encrypted file
+    /// are reused so that the file can be decrypted, otherwise new parameters are generated
+    pub(super) fn create(key: &StorageKey, existing: Option<&KdfParameters>) -> Result<Self> {
+        match key {
+            StorageKey::Passphrase(passphrase) => {
+                let kdf = existing.cloned().unwrap_or_else(KdfParameters::generate);
+                Ok(Self {
+                    key: kdf.derive_key(passphrase)?,
+                    kdf: Some(kdf),
+                })
+            }
+            StorageKey::Key(key) => {
+                if key.length() != AES256_SECRET_LENGTH_USIZE {
+                    return Err(VaultError::InvalidStorageKey.into());
+                }
+                Ok(Self {
+                    key: key.clone(),
+                    kdf: None,
+                })
+            }
+        }
+    }
+
+    pub(super) fn encrypt(&self, plaintext: &[u8]) -> Result<EncryptedData> {
+        let mut nonce = vec![0u8; AES_NONCE_LENGTH_USIZE];
+        thread_rng().fill_bytes(&mut nonce);
+
+        let aad = self.aad();
+        let ciphertext = self
+            .cipher()
+            .encrypt(
+                nonce.as_slice().into(),
+                Payload {
+                    msg: plaintext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| VaultError::AeadAesGcmEncrypt)?;
+
+        Ok(EncryptedData {
+            kdf: self.kdf.clone(),
+            nonce,
+            ciphertext,
+        })
+    }
+
+    pub(super) fn decrypt(&self, data: &EncryptedData) -> Result<Vec<u8>> {
+        // the file was encrypted with another key, for example after a key rotation
+        if data.kdf != self.kdf || data.nonce.len() != AES_NONCE_LENGTH_USIZE {
+            return Err(VaultError::StorageDecryption.into());
+        }
+
+        let aad = self.aad();
+        self.cipher()
+            .decrypt(
+                data.nonce.as_slice().into(),
+                Payload {
+                    msg: &data.ciphertext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| VaultError::StorageDecryption.into())
+    }
+
+    fn cipher(&self) -> Aes256Gcm {
+        Aes256Gcm::new(self.key.as_ref().into())
+    }
+
+    /// The key derivation parameters are authenticated with the ciphertext
+    fn aad(&self) -> Vec<u8> {
+        match &self.kdf {
+            Some(kdf) => kdf.salt.clone(),
+            None => vec![],
+        }
+    }
+}
@@ -1,8 +1,10 @@
-use crate::{KeyId, Secret, SecretAttributes, StoredSecret};
+use super::encryption::{EncryptedData, StorageEncryption, StorageKey};
+use crate::{KeyId, Secret, SecretAttributes, StoredSecret, VaultError};
 
 use ockam_core::compat::collections::BTreeMap;
 use ockam_core::compat::sync::Arc;
-use ockam_core::{async_trait, Result};
+use ockam_core::errcode::{Kind, Origin};
+use ockam_core::{async_trait, Error, Result};
 use ockam_node::{FileValueStorage, InMemoryKeyValueStorage, KeyValueStorage, ValueStorage};
 
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
@@ -14,17 +16,132 @@ use std::path::Path;
 /// WARNING: This implementation provides limited consistency if the same file is reused from
 /// multiple instances and/or processes. For example, if one process deletes a value, the other
 /// process will still have it in its cache and return it on a Get query.
+///
+/// The secrets can be encrypted at rest with a [`StorageKey`]
 pub struct PersistentStorage {
-    storage: Arc<FileValueStorage<StoredSecrets>>,
+    storage: Arc<FileValueStorage<VaultFile>>,
     cache: InMemoryKeyValueStorage<KeyId, StoredSecret>,
+    encryption: Option<StorageEncryption>,
 }
 
 impl PersistentStorage {
     /// Create a new file storage for a Vault
     pub async fn create(path: &Path) -> Result<Arc<dyn KeyValueStorage<KeyId, StoredSecret>>> {
-        let storage = Arc::new(FileValueStorage::create(path).await?);
+        Ok(Arc::new(Self::open(path, None).await?))
+    }
+
+    /// Create a new file storage for a Vault where secrets are encrypted with the given key.
+    /// An existing plaintext file containing secrets is rejected, it must be migrated
+    /// with [`PersistentStorage::rotate_key`]
+    pub async fn create_encrypted(
+        path: &Path,
+        key: StorageKey,
+    ) -> Result<Arc<dyn KeyValueStorage<KeyId, StoredSecret>>> {
+        Ok(Arc::new(Self::open(path, Some(&key)).await?))
+    }
+
+    /// Encrypt the file with a new key, or decrypt it if no new key is given.
+    /// The current key is required if the file is already encrypted
+    pub async fn rotate_key(
+        path: &Path,
+        current_key: Option<StorageKey>,
+        new_key: Option<StorageKey>,
+    ) -> Result<()> {
+        let storage = Self::open(path, current_key.as_ref()).await?;
+        let current_encryption = storage.encryption.clone();
+        let new_encryption = match new_key {
+            Some(key) => Some(StorageEncryption::create(&key, None)?),
+            None => None,
+        };
+
+        let t = move |v: VaultFile| {
+            let secrets = v.open(current_encryption.as_ref())?;
+            VaultFile::seal(&secrets, new_encryption.as_ref())
+        };
+        storage.storage.update_value(t).await
+    }
+
+    /// Return true if the file at the given path contains encrypted secrets
+    pub async fn is_encrypted(path: &Path) -> Result<bool> {
+        let storage = FileValueStorage::<VaultFile>::create(path).await?;
+        storage
+            .read_value(|v: VaultFile| Ok(matches!(v, VaultFile::Encrypted(_))))
+            .await
+    }
+
+    async fn open(path: &Path, key: Option<&StorageKey>) -> Result<PersistentStorage> {
+        let storage = Arc::new(FileValueStorage::<VaultFile>::create(path).await?);
+        let encryption = match key {
+            Some(key) => {
+                let kdf = storage
+                    .read_value(|v: VaultFile| match v {
+                        VaultFile::Encrypted(data) => Ok(data.kdf().cloned()),
+                        VaultFile::Plain(_) => Ok(None),
+                    })
+                    .await?;
+                Some(StorageEncryption::create(key, kdf.as_ref())?)
+            }
+            None => None,
+        };
         let cache = InMemoryKeyValueStorage::new();
-        Ok(Arc::new(PersistentStorage { storage, cache }))
+        let persistent_storage = PersistentStorage {
+            storage,
+            cache,
+            encryption,
+        };
+
+        // fail early if the file cannot be decrypted
+        let encryption = persistent_storage.encryption.clone();
+        persistent_storage
+            .storage
+            .read_value(move |v: VaultFile| v.open(encryption.as_ref()).map(|_| ()))
+            .await?;
+        Ok(persistent_storage)
+    }
+}
+
+/// Content of a vault file: either the plaintext secrets or their encryption
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum VaultFile {
+    Plain(StoredSecrets),
+    Encrypted(EncryptedData),
+}
+
+impl Default for VaultFile {
+    fn default() -> Self {
+        VaultFile::Plain(StoredSecrets::default())
+    }
+}
+
+impl VaultFile {
+    /// Return the stored secrets, decrypted if necessary
+    fn open(self, encryption: Option<&StorageEncryption>) -> Result<StoredSecrets> {
+        match (self, encryption) {
+            (VaultFile::Plain(secrets), None) => Ok(secrets),
+            // a new storage is created as an empty plaintext file. Other plaintext files are
+            // refused so that an encrypted file can't be replaced with attacker-chosen secrets
+            (VaultFile::Plain(secrets), Some(_)) if secrets.secrets.is_empty() => Ok(secrets),
+            (VaultFile::Plain(_), Some(_)) => Err(VaultError::UnencryptedStorage.into()),
+            (VaultFile::Encrypted(data), Some(encryption)) => {
+                let plaintext = encryption.decrypt(&data)?;
+                serde_cbor::from_slice(&plaintext)
+                    .map_err(|e| Error::new(Origin::Vault, Kind::Serialization, e))
+            }
+            (VaultFile::Encrypted(_), None) => Err(VaultError::EncryptedStorage.into()),
+        }
+    }
+
+    /// Return the content to store, encrypted if necessary
+    fn seal(secrets: &StoredSecrets, encryption: Option<&StorageEncryption>) -> Result<Self> {
+        match encryption {
+            Some(encryption) => {
+                let plaintext = serde_cbor::to_vec(secrets)
+                    .map_err(|e| Error::new(Origin::Vault, Kind::Serialization, e))?;
+                Ok(VaultFile::Encrypted(encryption.encrypt(&plaintext)?))
+            }
+            None => Ok(VaultFile::Plain(secrets.clone())),
+        }
     }
 }
 
@@ -118,9 +235,11 @@ impl KeyValueStorage<KeyId, StoredSecret> for PersistentStorage {
             .put(key_id.clone(), stored_secret.clone())
             .await?;
 
-        let t = move |mut v: StoredSecrets| {
-            v.add_stored_secret(key_id.clone(), stored_secret.clone());
-            Ok(v)
+        let encryption = self.encryption.clone();
+        let t = move |v: VaultFile| {
+            let mut secrets = v.open(encryption.as_ref())?;
+            secrets.add_stored_secret(key_id.clone(), stored_secret.clone());
+            VaultFile::seal(&secrets, encryption.as_ref())
         };
         self.storage.update_value(t).await
     }
@@ -130,17 +249,21 @@ impl KeyValueStorage<KeyId, StoredSecret> for PersistentStorage {
             return Ok(Some(s));
         }
         let k = key_id.clone();
-        let t =
-            move |v: StoredSecrets| -> Result<Option<StoredSecret>> { Ok(v.get_stored_secret(&k)) };
+        let encryption = self.encryption.clone();
+        let t = move |v: VaultFile| -> Result<Option<StoredSecret>> {
+            Ok(v.open(encryption.as_ref())?.get_stored_secret(&k))
+        };
         self.storage.read_value(t).await
     }
 
     async fn delete(&self, key_id: &KeyId) -> Result<Option<StoredSecret>> {
         self.cache.delete(key_id).await?;
         let k = key_id.clone();
-        let t = move |mut v: StoredSecrets| -> Result<(StoredSecrets, Option<StoredSecret>)> {
-            let r = v.delete_stored_secret(&k);
-            Ok((v, r))
+        let encryption = self.encryption.clone();
+        let t = move |v: VaultFile| -> Result<(VaultFile, Option<StoredSecret>)> {
+            let mut secrets = v.open(encryption.as_ref())?;
+            let r = secrets.delete_stored_secret(&k);
+            Ok((VaultFile::seal(&secrets, encryption.as_ref())?, r))
         };
         self.storage.modify_value(t).await
     }
@@ -186,4 +309,85 @@ pub(crate) mod tests {
         assert_eq!(actual, Some(stored_secret));
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_encrypted_persistent_storage() -> Result<()> {
+        let temp_file = NamedTempFile::new().unwrap();
+        let key = StorageKey::Key(Secret::new(vec![2; 32]));
+        let storage = PersistentStorage::create_encrypted(temp_file.path(), key.clone()).await?;
+
+        let secret = Secret::new(vec![1; 32]);
+        let key_id: KeyId = "key-id".into();
+        let stored_secret = StoredSecret::new(secret, SecretAttributes::Ed25519);
+        storage.put(key_id.clone(), stored_secret.clone()).await?;
+
+        // the secret is not stored in plaintext
+        let file_contents = std::fs::read_to_string(temp_file.path()).unwrap();
+        assert!(!file_contents.contains("0101010101"));
+        assert!(PersistentStorage::is_encrypted(temp_file.path()).await?);
+
+        // the secrets can be read again with the same key
+        let storage = PersistentStorage::create_encrypted(temp_file.path(), key).await?;
+        assert_eq!(storage.get(&key_id).await?, Some(stored_secret));
+
+        // but not without a key, or with a different key
+        assert!(PersistentStorage::create(temp_file.path()).await.is_err());
+        let other_key = StorageKey::Key(Secret::new(vec![3; 32]));
+        assert!(
+            PersistentStorage::create_encrypted(temp_file.path(), other_key)
+                .await
+                .is_err()
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rotate_storage_key() -> Result<()> {
+        let temp_file = NamedTempFile::new().unwrap();
+        let storage = PersistentStorage::create(temp_file.path()).await?;
+
+        let key_id: KeyId = "key-id".into();
+        let stored_secret = StoredSecret::new(Secret::new(vec![1; 32]), SecretAttributes::Ed25519);
+        storage.put(key_id.clone(), stored_secret.clone()).await?;
+        assert!(!PersistentStorage::is_encrypted(temp_file.path()).await?);
+
+        // migrate a plaintext file to a file encrypted with a key derived from a passphrase
+        let passphrase = StorageKey::Passphrase("passphrase".to_string());
+        PersistentStorage::rotate_key(temp_file.path(), None, Some(passphrase.clone())).await?;
+        assert!(PersistentStorage::is_encrypted(temp_file.path()).await?);
+        let storage =
+            PersistentStorage::create_encrypted(temp_file.path(), passphrase.clone()).await?;
+        assert_eq!(storage.get(&key_id).await?, Some(stored_secret.clone()));
+
+        // rotate the key
+        let key = StorageKey::from_bytes(hex::encode([2; 32]).as_bytes())?;
+        PersistentStorage::rotate_key(
+            temp_file.path(),
+            Some(passphrase.clone()),
+            Some(key.clone()),
+        )
+        .await?;
+        assert!(
+            PersistentStorage::create_encrypted(temp_file.path(), passphrase)
+                .await
+                .is_err()
+        );
+        let storage = PersistentStorage::create_encrypted(temp_file.path(), key).await?;
+        assert_eq!(storage.get(&key_id).await?, Some(stored_secret));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_plaintext_storage_is_refused_with_a_key() -> Result<()> {
+        let temp_file = NamedTempFile::new().unwrap();
+        let storage = PersistentStorage::create(temp_file.path()).await?;
+        let stored_secret = StoredSecret::new(Secret::new(vec![1; 32]), SecretAttributes::Ed25519);
+        storage.put("key-id".into(), stored_secret).await?;
+
+        let key = StorageKey::Key(Secret::new(vec![2; 32]));
+        assert!(PersistentStorage::create_encrypted(temp_file.path(), key)
+            .await
+            .is_err());
+        Ok(())
+    }
 }
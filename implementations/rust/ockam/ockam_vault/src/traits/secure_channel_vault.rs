@@ -1,4 +1,4 @@
-use crate::{Buffer, KeyId, PublicKey, Secret, SecretAttributes, SmallBuffer};
+use crate::{Buffer, KeyId, PublicKey, Secret, SecretAttributes, SmallBuffer, VaultError};
 use ockam_core::{async_trait, compat::boxed::Box, Result};
 
 /// Vault used for Secure Channel
@@ -40,6 +40,20 @@ pub trait SecureChannelVault: Send + Sync + 'static {
     async fn ec_diffie_hellman(&self, secret: &KeyId, peer_public_key: &PublicKey)
         -> Result<KeyId>;
 
+    /// Encapsulate a fresh shared secret for the peer's KEM [`PublicKey`].
+    /// Return the ciphertext to send to the peer and the [`KeyId`] of the shared secret.
+    /// Vaults which don't support KEM operations return an unsupported operation error
+    async fn kem_encapsulate(&self, _peer_public_key: &PublicKey) -> Result<(Buffer<u8>, KeyId)> {
+        Err(VaultError::KemNotSupported.into())
+    }
+
+    /// Decapsulate the shared secret contained in a ciphertext with a KEM secret
+    /// and return the [`KeyId`] of the shared secret.
+    /// Vaults which don't support KEM operations return an unsupported operation error
+    async fn kem_decapsulate(&self, _secret: &KeyId, _ciphertext: &[u8]) -> Result<KeyId> {
+        Err(VaultError::KemNotSupported.into())
+    }
+
     /// Derive multiple output [`super::Secret`]s with given attributes using
     /// the HKDF-SHA256 given the specified salt, info and input key
     /// material
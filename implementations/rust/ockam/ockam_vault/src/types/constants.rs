@@ -37,6 +37,22 @@ pub const NIST_P256_PUBLIC_LENGTH_USIZE: usize = 65;
 /// NIST P256 signature length.
 pub const NIST_P256_SIGNATURE_LENGTH_USIZE: usize = 64;
 
+/// Kyber768 private key length.
+pub const KYBER768_SECRET_LENGTH_U32: u32 = 2400;
+/// Kyber768 private key length.
+pub const KYBER768_SECRET_LENGTH_USIZE: usize = 2400;
+
+/// Kyber768 public key length.
+pub const KYBER768_PUBLIC_LENGTH_U32: u32 = 1184;
+/// Kyber768 public key length.
+pub const KYBER768_PUBLIC_LENGTH_USIZE: usize = 1184;
+
+/// Kyber768 ciphertext length.
+pub const KYBER768_CIPHERTEXT_LENGTH_USIZE: usize = 1088;
+
+/// Kyber768 shared secret length.
+pub const KYBER768_SHARED_SECRET_LENGTH_U32: u32 = 32;
+
 /// AES-GCM nonce length
 pub const AES_NONCE_LENGTH_USIZE: usize = 12;
 
@@ -70,5 +86,29 @@ const_assert_eq!(
     NIST_P256_PUBLIC_LENGTH_U32,
     NIST_P256_PUBLIC_LENGTH_USIZE as u32
 );
+const_assert_eq!(
+    KYBER768_SECRET_LENGTH_U32,
+    KYBER768_SECRET_LENGTH_USIZE as u32
+);
+const_assert_eq!(
+    KYBER768_PUBLIC_LENGTH_U32,
+    KYBER768_PUBLIC_LENGTH_USIZE as u32
+);
+const_assert_eq!(
+    KYBER768_SECRET_LENGTH_USIZE,
+    pqc_kyber::KYBER_SECRETKEYBYTES
+);
+const_assert_eq!(
+    KYBER768_PUBLIC_LENGTH_USIZE,
+    pqc_kyber::KYBER_PUBLICKEYBYTES
+);
+const_assert_eq!(
+    KYBER768_CIPHERTEXT_LENGTH_USIZE,
+    pqc_kyber::KYBER_CIPHERTEXTBYTES
+);
+const_assert_eq!(
+    KYBER768_SHARED_SECRET_LENGTH_U32,
+    pqc_kyber::KYBER_SSBYTES as u32
+);
 const_assert_eq!(AES256_SECRET_LENGTH_U32, AES256_SECRET_LENGTH_USIZE as u32);
 const_assert_eq!(AES128_SECRET_LENGTH_U32, AES128_SECRET_LENGTH_USIZE as u32);
@@ -1,6 +1,7 @@
 use crate::constants::{AES128_SECRET_LENGTH_U32, AES256_SECRET_LENGTH_U32};
 use crate::constants::{
-    ED25519_SECRET_LENGTH_U32, NIST_P256_SECRET_LENGTH_U32, X25519_SECRET_LENGTH_U32,
+    ED25519_SECRET_LENGTH_U32, KYBER768_SECRET_LENGTH_U32, NIST_P256_SECRET_LENGTH_U32,
+    X25519_SECRET_LENGTH_U32,
 };
 use crate::VaultError;
 use core::fmt;
@@ -29,6 +30,8 @@ pub enum SecretAttributes {
     X25519,
     /// NistP256 secret with length 32
     NistP256,
+    /// Kyber768 KEM secret with length 2400
+    Kyber768,
 }
 
 impl From<SecretAttributes> for SecretType {
@@ -40,6 +43,7 @@ impl From<SecretAttributes> for SecretType {
             SecretAttributes::Ed25519 => SecretType::Ed25519,
             SecretAttributes::X25519 => SecretType::X25519,
             SecretAttributes::NistP256 => SecretType::NistP256,
+            SecretAttributes::Kyber768 => SecretType::Kyber768,
         }
     }
 }
@@ -53,6 +57,7 @@ impl TryFrom<SecretType> for SecretAttributes {
             SecretType::X25519 => Ok(SecretAttributes::X25519),
             SecretType::Ed25519 => Ok(SecretAttributes::Ed25519),
             SecretType::NistP256 => Ok(SecretAttributes::NistP256),
+            SecretType::Kyber768 => Ok(SecretAttributes::Kyber768),
         }
     }
 }
@@ -72,6 +77,7 @@ impl SecretAttributes {
             SecretAttributes::Ed25519 => ED25519_SECRET_LENGTH_U32,
             SecretAttributes::X25519 => X25519_SECRET_LENGTH_U32,
             SecretAttributes::NistP256 => NIST_P256_SECRET_LENGTH_U32,
+            SecretAttributes::Kyber768 => KYBER768_SECRET_LENGTH_U32,
         }
     }
 }
@@ -90,7 +96,9 @@ pub enum SecretType {
     /// Ed 22519 key
     #[n(4)] Ed25519,
     /// NIST P-256 key
-    #[n(5)] NistP256
+    #[n(5)] NistP256,
+    /// Kyber768 KEM key
+    #[n(6)] Kyber768,
 }
 
 impl Display for SecretType {
@@ -101,6 +109,7 @@ impl Display for SecretType {
             SecretType::X25519 => write!(f, "X25519"),
             SecretType::Ed25519 => write!(f, "Ed25519"),
             SecretType::NistP256 => write!(f, "NistP256"),
+            SecretType::Kyber768 => write!(f, "Kyber768"),
         }
     }
 }
@@ -124,6 +133,7 @@ mod tests {
             (SecretAttributes::Aes128, r#""Aes128""#),
             (SecretAttributes::Aes256, r#""Aes256""#),
             (SecretAttributes::NistP256, r#""NistP256""#),
+            (SecretAttributes::Kyber768, r#""Kyber768""#),
         ] {
             let actual_json = serde_json::to_string(&attributes).unwrap();
             assert_eq!(actual_json, expected_json);
@@ -142,6 +152,7 @@ mod tests {
             (SecretAttributes::Ed25519, r#"03"#),
             (SecretAttributes::X25519, r#"04"#),
             (SecretAttributes::NistP256, r#"05"#),
+            (SecretAttributes::Kyber768, r#"06"#),
         ] {
             let actual_bare = hex::encode(serde_bare::to_vec(&attributes).unwrap());
             assert_eq!(actual_bare, expected_bare);